@@ -2,31 +2,69 @@
 
 use anyhow::Result;
 use colored::*;
+use std::path::Path;
 use super::CommandUtils;
+use crate::utils::{FileUtils, ProcessUtils, ProgressBar, TextUtils};
+
+/// Version constants written by `--git-version`
+pub(crate) const VERSION_FILE: &str = "src/generated/version.rs";
+
+/// Tag reported when the repository has no tags
+const UNTAGGED: &str = "untagged";
+
+/// Crates listed by the size report
+const SIZE_REPORT_CRATES: usize = 10;
+
+/// Directory the WebAssembly package is copied to
+const WASM_PUBLIC_DIR: &str = "public/wasm";
+
+/// Manifest mapping bundled asset names to their versioned file names
+const ASSET_MANIFEST: &str = "public/mix-manifest.json";
+
+/// Compilation target of the build command
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum BuildTarget {
+    /// Binary for the host, built with cargo
+    #[default]
+    Native,
+    /// WebAssembly package for the browser, built with wasm-pack
+    Wasm32,
+}
+
+/// Flags of the build command
+#[derive(Debug, Default)]
+pub struct BuildOptions {
+    pub optimize: bool,
+    pub output: Option<String>,
+    pub git_version: bool,
+    pub size_report: bool,
+    pub target: BuildTarget,
+    pub no_minify: bool,
+}
 
 /// Handle build command
-pub async fn handle(env: String, optimize: bool, output: Option<String>) -> Result<()> {
+pub async fn handle(env: String, options: BuildOptions) -> Result<()> {
     CommandUtils::ensure_rustisan_project()?;
 
     CommandUtils::info(&format!("Building application for {} environment", env));
 
-    if optimize {
+    if options.optimize {
         CommandUtils::info("Optimizations enabled");
     }
 
-    if let Some(ref output_dir) = output {
+    if options.target == BuildTarget::Wasm32 {
+        CommandUtils::info("Target: wasm32");
+    }
+
+    if let Some(ref output_dir) = options.output {
         CommandUtils::info(&format!("Output directory: {}", output_dir));
     }
 
-    build_application(&env, optimize, output).await
+    build_application(&env, options).await
 }
 
-async fn build_application(env: &str, optimize: bool, output: Option<String>) -> Result<()> {
-    // Set environment variables
-    unsafe {
-        std::env::set_var("RUSTISAN_ENV", env);
-        std::env::set_var("APP_ENV", env);
-    }
+async fn build_application(env: &str, options: BuildOptions) -> Result<()> {
+    let BuildOptions { optimize, output, git_version, size_report, target, no_minify } = options;
 
     // Determine build profile
     let profile = if optimize || env == "production" {
@@ -47,13 +85,43 @@ async fn build_application(env: &str, optimize: bool, output: Option<String>) ->
     CommandUtils::info("Caching configuration...");
     cache_configuration().await?;
 
+    // Embed the Git commit and tag
+    if git_version {
+        CommandUtils::info("Writing Git version...");
+        write_version_file(Path::new("."))?;
+    }
+
+    if target == BuildTarget::Wasm32 {
+        ensure_wasm_pack_installed()?;
+        if !has_cdylib(&CommandUtils::read_file("Cargo.toml")?) {
+            CommandUtils::warning("Cargo.toml has no [lib] crate-type = [\"cdylib\"], wasm-pack needs it to build a package");
+        }
+    }
+
     // Build the application
     CommandUtils::info("Compiling application...");
-    compile_application(profile).await?;
+    compile_application(env, profile, target).await?;
+
+    if target == BuildTarget::Wasm32 {
+        CommandUtils::info(&format!("Copying WebAssembly package to {}...", WASM_PUBLIC_DIR));
+        copy_directory(Path::new("pkg"), Path::new(WASM_PUBLIC_DIR))?;
+        optimize_wasm(Path::new(WASM_PUBLIC_DIR))?;
+    }
+
+    // Report what contributes to the binary size
+    if size_report {
+        if target == BuildTarget::Wasm32 {
+            CommandUtils::warning("The size report is only available for native builds");
+        } else if profile == "release" {
+            print_size_analysis(profile);
+        } else {
+            CommandUtils::warning("The size report needs a release build, use --optimize");
+        }
+    }
 
     // Copy assets and resources
     CommandUtils::info("Processing assets...");
-    process_assets().await?;
+    process_assets(!no_minify).await?;
 
     // Generate optimized autoloads
     CommandUtils::info("Generating autoloads...");
@@ -126,31 +194,105 @@ async fn cache_configuration() -> Result<()> {
     Ok(())
 }
 
-async fn compile_application(profile: &str) -> Result<()> {
-    let mut args = vec!["build"];
+/// Command compiling the application for a profile and target
+///
+/// The target environment reaches the compiler and build scripts as
+/// `RUSTISAN_ENV` and `APP_ENV`.
+fn compile_command(env: &str, profile: &str, target: BuildTarget) -> std::process::Command {
+    let (program, args) = match target {
+        BuildTarget::Native => {
+            let mut args = vec!["build"];
+            if profile == "release" {
+                args.push("--release");
+            }
+            ("cargo", args)
+        }
+        BuildTarget::Wasm32 => {
+            let mode = if profile == "release" { "--release" } else { "--dev" };
+            ("wasm-pack", vec!["build", "--target", "web", mode])
+        }
+    };
+
+    let mut command = std::process::Command::new(program);
+    command.args(args).env("RUSTISAN_ENV", env).env("APP_ENV", env);
+    command
+}
+
+async fn compile_application(env: &str, profile: &str, target: BuildTarget) -> Result<()> {
+    let mut command = compile_command(env, profile, target);
+
+    // Stream the compiler's output above a spinner so progress is visible while compiling
+    let progress = std::cell::RefCell::new(ProgressBar::new("Compiling", 0));
+    let print_line = |line: &str| {
+        let mut progress = progress.borrow_mut();
+        progress.println_above(line);
+        progress.increment();
+    };
+
+    let success = ProcessUtils::execute_command_streaming(&mut command, print_line, print_line)?;
 
-    if profile == "release" {
-        args.push("--release");
+    progress.borrow().finish_and_clear();
+    if !success {
+        anyhow::bail!("Compilation failed");
     }
 
-    let output = std::process::Command::new("cargo")
-        .args(&args)
-        .output()?;
+    CommandUtils::success("Compilation finished");
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("Compilation failed: {}", stderr);
+    Ok(())
+}
+
+/// Make sure wasm-pack is available, installing it with cargo otherwise
+fn ensure_wasm_pack_installed() -> Result<()> {
+    if ProcessUtils::command_exists("wasm-pack") {
+        return Ok(());
     }
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    if !stdout.is_empty() {
-        println!("{}", stdout);
+    CommandUtils::info("Installing wasm-pack...");
+    if !ProcessUtils::execute("cargo", &["install", "wasm-pack"])? {
+        anyhow::bail!("Failed to install wasm-pack, install it with: cargo install wasm-pack");
     }
+    CommandUtils::success("wasm-pack installed");
 
     Ok(())
 }
 
-async fn process_assets() -> Result<()> {
+/// Whether a manifest builds its library as a `cdylib`, as wasm-pack requires
+fn has_cdylib(manifest: &str) -> bool {
+    let Ok(manifest) = manifest.parse::<toml::Value>() else {
+        return false;
+    };
+
+    manifest
+        .get("lib")
+        .and_then(|lib| lib.get("crate-type"))
+        .and_then(|types| types.as_array())
+        .is_some_and(|types| types.iter().any(|t| t.as_str() == Some("cdylib")))
+}
+
+/// Run `wasm-opt -O3` on the WebAssembly files of a directory when wasm-opt is installed
+fn optimize_wasm(dir: &Path) -> Result<()> {
+    if !ProcessUtils::command_exists("wasm-opt") {
+        CommandUtils::info("Install binaryen to optimize the package with wasm-opt");
+        return Ok(());
+    }
+
+    for entry in walkdir::WalkDir::new(dir) {
+        let path = entry?.into_path();
+        if path.extension().is_none_or(|ext| ext != "wasm") {
+            continue;
+        }
+
+        let file = path.to_string_lossy();
+        CommandUtils::info(&format!("Optimizing {}...", file));
+        if !ProcessUtils::execute("wasm-opt", &["-O3", &file, "-o", &file])? {
+            CommandUtils::warning(&format!("wasm-opt failed on {}", file));
+        }
+    }
+
+    Ok(())
+}
+
+async fn process_assets(minify: bool) -> Result<()> {
     let assets_dir = std::path::Path::new("assets");
     let public_dir = std::path::Path::new("public");
 
@@ -163,28 +305,215 @@ async fn process_assets() -> Result<()> {
     }
 
     // Process CSS and JavaScript files
-    process_css_files().await?;
-    process_js_files().await?;
+    let mut manifest = std::collections::BTreeMap::new();
+    manifest.extend(process_css_files(minify).await?);
+    manifest.extend(process_js_files(minify).await?);
+
+    if !manifest.is_empty() {
+        CommandUtils::write_file(ASSET_MANIFEST, &serde_json::to_string_pretty(&manifest)?)?;
+    }
 
     Ok(())
 }
 
-async fn process_css_files() -> Result<()> {
-    // TODO: Implement CSS processing (minification, bundling, etc.)
-    Ok(())
+/// Bundle `resources/assets/css` into `public/css/app.css`, returning its manifest entry
+async fn process_css_files(minify: bool) -> Result<Option<(String, String)>> {
+    let minifier = if minify { Some(minify_css as fn(&str) -> String) } else { None };
+    bundle_assets(Path::new("resources/assets/css"), Path::new("public/css"), "css", minifier)
+}
+
+/// Bundle `resources/assets/js` into `public/js/app.js`, returning its manifest entry
+async fn process_js_files(minify: bool) -> Result<Option<(String, String)>> {
+    let minifier = if minify { Some(minify_js as fn(&str) -> String) } else { None };
+    bundle_assets(Path::new("resources/assets/js"), Path::new("public/js"), "js", minifier)
+}
+
+/// Concatenate the files with an extension under `source_dir` in alphabetical order
+///
+/// Writes `app.{extension}` and a copy versioned with its content hash to
+/// `output_dir`, and returns the manifest entry mapping one to the other.
+fn bundle_assets(
+    source_dir: &Path,
+    output_dir: &Path,
+    extension: &str,
+    minifier: Option<fn(&str) -> String>,
+) -> Result<Option<(String, String)>> {
+    let mut files: Vec<_> = walkdir::WalkDir::new(source_dir)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.into_path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == extension))
+        .collect();
+    if files.is_empty() {
+        return Ok(None);
+    }
+    files.sort();
+
+    let sources = files.iter().map(CommandUtils::read_file).collect::<Result<Vec<_>>>()?;
+    let bundle = sources.join("\n");
+    let bundle = match minifier {
+        Some(minify) => minify(&bundle),
+        None => bundle,
+    };
+
+    let name = format!("app.{}", extension);
+    let versioned = versioned_name(&name, &bundle);
+    CommandUtils::ensure_directory(output_dir)?;
+    CommandUtils::write_file(output_dir.join(&name), &bundle)?;
+    CommandUtils::write_file(output_dir.join(&versioned), &bundle)?;
+
+    CommandUtils::info(&format!("Bundled {} {} file(s) into {}", files.len(), extension, output_dir.join(&name).display()));
+
+    Ok(Some((name, versioned)))
+}
+
+/// File name with the first 8 characters of the content's MD5, e.g. `app.1a2b3c4d.css`
+fn versioned_name(name: &str, content: &str) -> String {
+    use md5::{Digest, Md5};
+
+    let hash: String = Md5::digest(content.as_bytes()).iter().map(|byte| format!("{:02x}", byte)).collect();
+    match name.rsplit_once('.') {
+        Some((stem, extension)) => format!("{}.{}.{}", stem, &hash[..8], extension),
+        None => format!("{}.{}", name, &hash[..8]),
+    }
+}
+
+/// Strip comments and collapse whitespace in CSS
+fn minify_css(source: &str) -> String {
+    let comments = regex::Regex::new(r"(?s)/\*.*?\*/").unwrap();
+    let whitespace = regex::Regex::new(r"\s+").unwrap();
+    let around_punctuation = regex::Regex::new(r"\s*([{};,>])\s*").unwrap();
+    // Only after colons, a space before one is a descendant selector as in `.nav :hover`
+    let after_colon = regex::Regex::new(r":\s+").unwrap();
+
+    let source = comments.replace_all(source, "");
+    let source = whitespace.replace_all(&source, " ");
+    let source = around_punctuation.replace_all(&source, "$1");
+    let source = after_colon.replace_all(&source, ":");
+    source.replace(";}", "}").trim().to_string()
+}
+
+/// Strip comments and indentation in JavaScript, keeping line breaks for automatic semicolon insertion
+fn minify_js(source: &str) -> String {
+    let block_comments = regex::Regex::new(r"(?s)/\*.*?\*/").unwrap();
+    let line_comments = regex::Regex::new(r"(?m)^\s*//.*$").unwrap();
+    let whitespace = regex::Regex::new(r"[ \t]+").unwrap();
+
+    let source = block_comments.replace_all(source, "");
+    let source = line_comments.replace_all(&source, "");
+    source
+        .lines()
+        .map(|line| whitespace.replace_all(line.trim(), " ").into_owned())
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Run git in a directory, returning its trimmed output when it succeeds with some
+fn git_output(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = std::process::Command::new("git").args(args).current_dir(dir).output().ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !stdout.is_empty()).then_some(stdout)
+}
+
+/// Short hash of the commit checked out in a directory
+pub(crate) fn git_commit(dir: &Path) -> Option<String> {
+    git_output(dir, &["rev-parse", "--short", "HEAD"])
 }
 
-async fn process_js_files() -> Result<()> {
-    // TODO: Implement JavaScript processing (minification, bundling, etc.)
+/// Latest tag reachable from HEAD, or "untagged"
+pub(crate) fn git_tag(dir: &Path) -> String {
+    git_output(dir, &["describe", "--tags", "--abbrev=0"]).unwrap_or_else(|| UNTAGGED.to_string())
+}
+
+/// Source of the generated version module
+fn version_source(commit: &str, tag: &str) -> String {
+    format!(
+        r#"//! Generated by `rustisan build --git-version`, do not edit
+
+pub const GIT_COMMIT: &str = {commit:?};
+pub const GIT_TAG: &str = {tag:?};
+
+/// Tag and commit the binary was built from, e.g. "v1.2.0 (abc1234)"
+pub fn version_info() -> String {{
+    format!("{{}} ({{}})", GIT_TAG, GIT_COMMIT)
+}}
+"#
+    )
+}
+
+/// Write `src/generated/version.rs` for the repository in `dir` and keep it out of Git
+fn write_version_file(dir: &Path) -> Result<()> {
+    let commit = git_commit(dir).ok_or_else(|| anyhow::anyhow!("Failed to read the Git commit, is this a Git repository?"))?;
+    let tag = git_tag(dir);
+
+    FileUtils::atomic_write(dir.join(VERSION_FILE), &version_source(&commit, &tag))?;
+
+    let mod_file = dir.join("src/generated/mod.rs");
+    if !mod_file.exists() {
+        FileUtils::atomic_write(&mod_file, "//! Generated modules\npub mod version;\n")?;
+    }
+
+    match crate_root(dir) {
+        Some(root) => {
+            let source = std::fs::read_to_string(&root)?;
+            if let Some(updated) = declare_generated_module(&source) {
+                FileUtils::atomic_write(&root, &updated)?;
+                CommandUtils::info(&format!("Declared mod generated in {}", root.display()));
+            }
+        }
+        None => CommandUtils::warning("No src/main.rs or src/lib.rs found, declare `mod generated;` in your crate root"),
+    }
+
+    let gitignore = dir.join(".gitignore");
+    let content = std::fs::read_to_string(&gitignore).unwrap_or_default();
+    let entry = format!("/{}", VERSION_FILE);
+    if !content.lines().any(|line| line.trim() == entry || line.trim() == VERSION_FILE) {
+        let separator = if content.is_empty() || content.ends_with('\n') { "" } else { "\n" };
+        FileUtils::atomic_write(&gitignore, &format!("{}{}\n# Git version\n{}\n", content, separator, entry))?;
+    }
+
+    CommandUtils::success(&format!("Git version {} ({}) written to {}", tag, commit, VERSION_FILE));
+
     Ok(())
 }
 
-async fn generate_autoloads() -> Result<()> {
+/// The library root if the crate has one, otherwise the binary root
+fn crate_root(dir: &Path) -> Option<std::path::PathBuf> {
+    ["src/lib.rs", "src/main.rs"].into_iter().map(|file| dir.join(file)).find(|path| path.exists())
+}
+
+/// Add `mod generated;` after the module declarations of a crate root, unless already declared
+fn declare_generated_module(source: &str) -> Option<String> {
+    let declared = regex::Regex::new(r"(?m)^\s*(pub(\([^)]*\))?\s+)?mod\s+generated\s*;").unwrap();
+    if declared.is_match(source) {
+        return None;
+    }
+
+    let lines: Vec<&str> = source.lines().collect();
+    let is_declaration = |line: &str| {
+        let line = line.trim_start_matches("pub ").trim_start_matches("pub(crate) ");
+        line.starts_with("mod ") && line.trim_end().ends_with(';')
+    };
+    // Below the last top-level `mod` line, or else below the crate docs and attributes
+    let position = lines
+        .iter()
+        .rposition(|line| is_declaration(line))
+        .or_else(|| lines.iter().rposition(|line| line.starts_with("//!") || line.starts_with("#![")))
+        .map_or(0, |index| index + 1);
+
+    let mut updated: Vec<&str> = lines[..position].to_vec();
+    updated.push("mod generated;");
+    updated.extend(&lines[position..]);
+    Some(format!("{}\n", updated.join("\n")))
+}
+
+pub(crate) async fn generate_autoloads() -> Result<()> {
     // TODO: Implement autoload generation for optimized class loading
     Ok(())
 }
 
-async fn copy_to_output(output_dir: &str, profile: &str) -> Result<()> {
+pub(crate) async fn copy_to_output(output_dir: &str, profile: &str) -> Result<()> {
     let output_path = std::path::Path::new(output_dir);
     CommandUtils::ensure_directory(output_path)?;
 
@@ -256,10 +585,26 @@ fn get_target_info() -> String {
     std::env::var("TARGET").unwrap_or_else(|_| "unknown".to_string())
 }
 
-fn get_binary_size(profile: &str) -> String {
-    let binary_path = format!("target/{}/rustisan", profile);
+/// Path of the application binary built with `profile`
+pub(crate) fn binary_path(profile: &str) -> String {
+    let manifest = std::fs::read_to_string(CommandUtils::app_dir().join("Cargo.toml")).unwrap_or_default();
+    format!("target/{}/{}", profile, binary_name(&manifest))
+}
+
+/// Name of the first binary target in a Cargo.toml, falling back to the package name
+fn binary_name(manifest: &str) -> String {
+    let manifest: toml::Value = manifest.parse().unwrap_or_else(|_| toml::Value::Table(Default::default()));
+    manifest
+        .get("bin")
+        .and_then(|bins| bins.as_array())
+        .and_then(|bins| bins.iter().find_map(|bin| bin.get("name")?.as_str()))
+        .or_else(|| manifest.get("package")?.get("name")?.as_str())
+        .unwrap_or("app")
+        .to_string()
+}
 
-    if let Ok(metadata) = std::fs::metadata(&binary_path) {
+fn get_binary_size(profile: &str) -> String {
+    if let Ok(metadata) = std::fs::metadata(binary_path(profile)) {
         let size = metadata.len();
         format_size(size)
     } else {
@@ -267,7 +612,93 @@ fn get_binary_size(profile: &str) -> String {
     }
 }
 
-fn format_size(size: u64) -> String {
+/// Print the biggest crates with cargo-bloat, or the debug info share of the binary without it
+fn print_size_analysis(profile: &str) {
+    const WIDTHS: &[usize] = &[30, 12, 10, 10];
+
+    println!("\n{}", "Size Analysis:".bold());
+
+    if ProcessUtils::command_exists("cargo-bloat") {
+        match ProcessUtils::execute_with_output("cargo", &["bloat", "--release", "--crates"]) {
+            Ok((true, stdout, _)) => {
+                let rows: Vec<Vec<String>> = parse_bloat_crates(&stdout)
+                    .into_iter()
+                    .take(SIZE_REPORT_CRATES)
+                    .map(|krate| vec![krate.name, krate.size, krate.file_percent, krate.text_percent])
+                    .collect();
+                println!("{}", TextUtils::table_format(&["Crate", "Size", "File", ".text"], &rows, WIDTHS));
+                return;
+            }
+            Ok((false, _, stderr)) => CommandUtils::warning(&format!("cargo bloat failed: {}", stderr.trim())),
+            Err(e) => CommandUtils::warning(&format!("Failed to run cargo bloat: {}", e)),
+        }
+    }
+
+    let path = binary_path(profile);
+    let Ok(total) = std::fs::metadata(&path).map(|metadata| metadata.len()) else {
+        CommandUtils::warning(&format!("Binary not found: {}", path));
+        return;
+    };
+
+    let mut rows = vec![vec!["Binary".to_string(), format_size(total)]];
+    if let Some(stripped) = stripped_size(Path::new(&path)) {
+        rows.push(vec!["Debug info".to_string(), format_size(total.saturating_sub(stripped))]);
+        rows.push(vec!["Stripped".to_string(), format_size(stripped)]);
+    }
+    println!("{}", TextUtils::table_format(&["Section", "Size"], &rows, &WIDTHS[..2]));
+
+    if !ProcessUtils::command_exists("cargo-bloat") {
+        CommandUtils::info("Install cargo-bloat for a per-crate breakdown: cargo install cargo-bloat");
+    }
+}
+
+/// Size of a binary after `strip --strip-debug`, measured on a temporary copy
+fn stripped_size(binary: &Path) -> Option<u64> {
+    let copy = std::env::temp_dir().join(format!("rustisan-strip-{}", std::process::id()));
+    std::fs::copy(binary, &copy).ok()?;
+
+    let status = std::process::Command::new("strip").arg("--strip-debug").arg(&copy).status();
+    let size = match status {
+        Ok(status) if status.success() => std::fs::metadata(&copy).ok().map(|metadata| metadata.len()),
+        _ => None,
+    };
+
+    let _ = std::fs::remove_file(&copy);
+    size
+}
+
+/// A crate row of `cargo bloat --crates`
+#[derive(Debug, PartialEq)]
+struct BloatCrate {
+    file_percent: String,
+    text_percent: String,
+    size: String,
+    name: String,
+}
+
+/// Crate rows of `cargo bloat --crates` output, biggest first, without the header and summary lines
+fn parse_bloat_crates(output: &str) -> Vec<BloatCrate> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut columns = line.split_whitespace();
+            let file_percent = columns.next()?;
+            let text_percent = columns.next()?;
+            let size = columns.next()?;
+            let name = columns.collect::<Vec<_>>().join(" ");
+
+            let is_crate = file_percent.ends_with('%') && !name.is_empty() && !name.contains(' ');
+            is_crate.then(|| BloatCrate {
+                file_percent: file_percent.to_string(),
+                text_percent: text_percent.to_string(),
+                size: size.to_string(),
+                name,
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn format_size(size: u64) -> String {
     const UNITS: &[&str] = &["B", "KB", "MB", "GB"];
     let mut size = size as f64;
     let mut unit_index = 0;
@@ -282,12 +713,12 @@ fn format_size(size: u64) -> String {
 
 /// Build for production with optimizations
 pub async fn build_production() -> Result<()> {
-    handle("production".to_string(), true, None).await
+    handle("production".to_string(), BuildOptions { optimize: true, ..Default::default() }).await
 }
 
 /// Build for development
 pub async fn build_development() -> Result<()> {
-    handle("development".to_string(), false, None).await
+    handle("development".to_string(), BuildOptions::default()).await
 }
 
 /// Build with specific target
@@ -307,3 +738,170 @@ pub async fn build_target(target: &str) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0.00 B");
+        assert_eq!(format_size(512), "512.00 B");
+        assert_eq!(format_size(1536), "1.50 KB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.00 MB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.00 GB");
+        assert_eq!(format_size(2048 * 1024 * 1024 * 1024), "2048.00 GB");
+    }
+
+    /// Program and arguments of a command, for comparing in assertions
+    fn command_line(command: &std::process::Command) -> Vec<&std::ffi::OsStr> {
+        std::iter::once(command.get_program()).chain(command.get_args()).collect()
+    }
+
+    #[test]
+    fn test_compile_command() {
+        assert_eq!(command_line(&compile_command("production", "release", BuildTarget::Native)), ["cargo", "build", "--release"]);
+        assert_eq!(command_line(&compile_command("production", "debug", BuildTarget::Native)), ["cargo", "build"]);
+        assert_eq!(
+            command_line(&compile_command("production", "release", BuildTarget::Wasm32)),
+            ["wasm-pack", "build", "--target", "web", "--release"]
+        );
+        assert_eq!(
+            command_line(&compile_command("production", "debug", BuildTarget::Wasm32)),
+            ["wasm-pack", "build", "--target", "web", "--dev"]
+        );
+    }
+
+    #[test]
+    fn test_compile_command_passes_build_environment() {
+        let command = compile_command("staging", "debug", BuildTarget::Native);
+        let envs: Vec<_> = command.get_envs().collect();
+
+        assert!(envs.contains(&("RUSTISAN_ENV".as_ref(), Some("staging".as_ref()))));
+        assert!(envs.contains(&("APP_ENV".as_ref(), Some("staging".as_ref()))));
+    }
+
+    #[test]
+    fn test_has_cdylib() {
+        assert!(has_cdylib("[package]\nname = \"app\"\n\n[lib]\ncrate-type = [\"cdylib\", \"rlib\"]\n"));
+        assert!(!has_cdylib("[package]\nname = \"app\"\n\n[lib]\npath = \"src/lib.rs\"\n"));
+        assert!(!has_cdylib("[package]\nname = \"app\"\n"));
+    }
+
+    #[test]
+    fn test_binary_name() {
+        assert_eq!(binary_name("[package]\nname = \"shop\"\n"), "shop");
+        assert_eq!(binary_name("[package]\nname = \"shop\"\n\n[[bin]]\nname = \"server\"\npath = \"src/main.rs\"\n"), "server");
+        assert_eq!(binary_name(""), "app");
+    }
+
+    #[test]
+    fn test_minify_css() {
+        let source = include_str!("../../tests/fixtures/assets/css/app.css");
+
+        assert_eq!(
+            minify_css(source),
+            "body{margin:0;font-family:sans-serif}.nav>a,.nav>span{color:#333}.button:hover{color:red}"
+        );
+        assert_eq!(minify_css(".nav :hover { color: red; }"), ".nav :hover{color:red}");
+        assert_eq!(minify_js("// greet\nfunction greet() {\n    /* say hi */\n    return   'hi';\n}\n"), "function greet() {\nreturn 'hi';\n}");
+    }
+
+    #[test]
+    fn test_bundle_assets() {
+        let dir = tempfile::tempdir().unwrap();
+        let source_dir = dir.path().join("css");
+        std::fs::create_dir_all(&source_dir).unwrap();
+        std::fs::write(source_dir.join("b.css"), "b {}").unwrap();
+        std::fs::write(source_dir.join("a.css"), "a {}").unwrap();
+        std::fs::write(source_dir.join("notes.txt"), "skipped").unwrap();
+
+        let output_dir = dir.path().join("public");
+        let (name, versioned) = bundle_assets(&source_dir, &output_dir, "css", None).unwrap().unwrap();
+        assert_eq!(name, "app.css");
+        assert_eq!(versioned, versioned_name("app.css", "a {}\nb {}"));
+        assert_eq!(std::fs::read_to_string(output_dir.join("app.css")).unwrap(), "a {}\nb {}");
+        assert!(output_dir.join(&versioned).exists());
+
+        assert!(bundle_assets(&dir.path().join("js"), &output_dir, "js", None).unwrap().is_none());
+        assert_eq!(versioned_name("app.css", ""), "app.d41d8cd9.css");
+    }
+
+    #[test]
+    fn test_parse_bloat_crates() {
+        let output = " File  .text     Size Crate
+ 5.3%  23.9%  77.3KiB std
+ 2.1%   9.4%  30.5KiB clap_builder
+ 0.4%   1.8%   5.8KiB [Unknown]
+ 4.0%  18.0%  58.4KiB And 12 more crates. Use -n N to show more.
+21.9% 100.0% 324.0KiB .text section size, the file size is 1.4MiB
+";
+
+        let crates = parse_bloat_crates(output);
+        let names: Vec<&str> = crates.iter().map(|krate| krate.name.as_str()).collect();
+        assert_eq!(names, ["std", "clap_builder", "[Unknown]"]);
+        assert_eq!(
+            crates[0],
+            BloatCrate {
+                file_percent: "5.3%".to_string(),
+                text_percent: "23.9%".to_string(),
+                size: "77.3KiB".to_string(),
+                name: "std".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_git_commit_of_cli_repository() {
+        let commit = git_commit(Path::new(env!("CARGO_MANIFEST_DIR")));
+
+        assert!(commit.is_some_and(|commit| !commit.is_empty()));
+    }
+
+    #[test]
+    fn test_declare_generated_module() {
+        assert_eq!(
+            declare_generated_module("//! App\n\nuse std::env;\n\nfn main() {}\n").unwrap(),
+            "//! App\nmod generated;\n\nuse std::env;\n\nfn main() {}\n"
+        );
+        assert_eq!(declare_generated_module("fn main() {}").unwrap(), "mod generated;\nfn main() {}\n");
+        assert!(declare_generated_module("pub mod generated;\nfn main() {}\n").is_none());
+    }
+
+    #[test]
+    fn test_version_source_escapes_values() {
+        assert!(version_source("abc1234", "v1.0-\"rc\"").contains(r#"pub const GIT_TAG: &str = "v1.0-\"rc\"";"#));
+    }
+
+    #[test]
+    fn test_write_version_file() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(write_version_file(dir.path()).is_err());
+
+        let git = |args: &[&str]| {
+            std::process::Command::new("git").args(args).current_dir(dir.path()).output().unwrap()
+        };
+        git(&["init", "-q"]);
+        git(&["-c", "user.name=test", "-c", "user.email=test@example.com", "commit", "-q", "--allow-empty", "-m", "init"]);
+        std::fs::write(dir.path().join(".gitignore"), "/target/").unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/main.rs"), "mod routes;\n\nfn main() {}\n").unwrap();
+
+        write_version_file(dir.path()).unwrap();
+        write_version_file(dir.path()).unwrap();
+
+        let source = std::fs::read_to_string(dir.path().join(VERSION_FILE)).unwrap();
+        let commit = git_commit(dir.path()).unwrap();
+        assert!(source.contains(&format!("pub const GIT_COMMIT: &str = \"{}\";", commit)));
+        assert!(source.contains("pub const GIT_TAG: &str = \"untagged\";"));
+        assert!(dir.path().join("src/generated/mod.rs").exists());
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("src/main.rs")).unwrap(),
+            "mod routes;\nmod generated;\n\nfn main() {}\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join(".gitignore")).unwrap(),
+            "/target/\n\n# Git version\n/src/generated/version.rs\n"
+        );
+    }
+}
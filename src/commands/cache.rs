@@ -2,8 +2,28 @@
 
 use anyhow::Result;
 use colored::*;
+use redis::aio::MultiplexedConnection;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
 use crate::CacheCommands;
+use crate::utils::{ProcessUtils, TextUtils};
 use super::CommandUtils;
+use super::build::format_size;
+use super::db::{get_config_value, load_config};
+use super::optimize::{timed, OptimizeReport};
+use super::queue::{connect, DEFAULT_REDIS_URL};
+
+/// Directory of the file cache store
+const CACHE_DIR: &str = "storage/cache";
+
+/// Redis counters the application increments on cache hits and misses
+const HITS_KEY: &str = "cache:stats:hits";
+const MISSES_KEY: &str = "cache:stats:misses";
+
+/// Keys fetched per `SCAN` round trip
+const SCAN_COUNT: usize = 100;
 
 /// Handle cache command
 pub async fn handle(operation: CacheCommands) -> Result<()> {
@@ -13,9 +33,257 @@ pub async fn handle(operation: CacheCommands) -> Result<()> {
         CacheCommands::Clear => clear_all_cache().await,
         CacheCommands::Forget { key } => forget_cache_key(key).await,
         CacheCommands::Config => cache_config().await,
+        CacheCommands::Routes => super::route::cache_routes().await,
+        CacheCommands::List { prefix } => list_cache_keys(prefix).await,
+        CacheCommands::Stats => cache_stats().await,
+        CacheCommands::Warm { config, routes, all, timeout } => {
+            warm_cache(config, routes, all, Duration::from_secs(timeout)).await
+        }
+    }
+}
+
+/// Cache store selected by `[cache] default`
+enum CacheStore {
+    File(FileStore),
+    Redis(String),
+}
+
+impl CacheStore {
+    /// Read the active store from rustisan.toml, `memory` being the file store
+    fn from_config() -> Result<Self> {
+        let config = load_config()?;
+        let driver = get_config_value(&config, "cache.default").unwrap_or_else(|| "memory".to_string());
+
+        match driver.as_str() {
+            "memory" | "file" => Ok(Self::File(FileStore::new(CACHE_DIR))),
+            "redis" => Ok(Self::Redis(redis_url(&config))),
+            driver => anyhow::bail!("Unsupported cache store: {} (expected memory, file or redis)", driver),
+        }
+    }
+}
+
+/// Redis URL from `[redis] url`, or built from `host`, `port`, `password` and `database`
+fn redis_url(config: &toml::Value) -> String {
+    if let Some(url) = get_config_value(config, "redis.url") {
+        return url;
+    }
+    if config.get("redis").is_none() {
+        return DEFAULT_REDIS_URL.to_string();
+    }
+
+    let host = get_config_value(config, "redis.host").unwrap_or_else(|| "127.0.0.1".to_string());
+    let port = get_config_value(config, "redis.port").unwrap_or_else(|| "6379".to_string());
+    let database = get_config_value(config, "redis.database").unwrap_or_else(|| "0".to_string());
+    match get_config_value(config, "redis.password").filter(|password| !password.is_empty()) {
+        Some(password) => format!("redis://:{}@{}:{}/{}", password, host, port, database),
+        None => format!("redis://{}:{}/{}", host, port, database),
     }
 }
 
+/// A cache entry as stored on disk
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    key: String,
+    value: serde_json::Value,
+    /// Unix timestamp after which the entry is stale
+    expires_at: Option<i64>,
+}
+
+/// Cache entries stored as `{md5(key)}.cache` files
+struct FileStore {
+    dir: PathBuf,
+}
+
+impl FileStore {
+    fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        use md5::{Digest, Md5};
+
+        let hash: String = Md5::digest(key.as_bytes()).iter().map(|byte| format!("{:02x}", byte)).collect();
+        self.dir.join(format!("{}.cache", hash))
+    }
+
+    /// Store a value the way the framework does, expiring after `ttl` seconds when given
+    #[cfg(test)]
+    fn put(&self, key: &str, value: serde_json::Value, ttl: Option<i64>) -> Result<()> {
+        let entry = CacheEntry {
+            key: key.to_string(),
+            value,
+            expires_at: ttl.map(|ttl| chrono::Utc::now().timestamp() + ttl),
+        };
+        CommandUtils::ensure_directory(&self.dir)?;
+        std::fs::write(self.path(key), serde_json::to_string(&entry)?)?;
+        Ok(())
+    }
+
+    /// Delete a key, returning whether it existed
+    fn forget(&self, key: &str) -> Result<bool> {
+        match std::fs::remove_file(self.path(key)) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Cache files with the key stored in each, or the file name when unreadable
+    fn entries(&self) -> Result<Vec<(String, PathBuf)>> {
+        if !self.dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut entries = Vec::new();
+        for entry in std::fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|extension| extension.to_str()) != Some("cache") {
+                continue;
+            }
+            let key = std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| serde_json::from_str::<CacheEntry>(&content).ok())
+                .map(|entry| entry.key)
+                .unwrap_or_else(|| file_stem(&path));
+            entries.push((key, path));
+        }
+        entries.sort();
+        Ok(entries)
+    }
+
+    fn keys(&self, prefix: Option<&str>) -> Result<Vec<String>> {
+        Ok(self
+            .entries()?
+            .into_iter()
+            .map(|(key, _)| key)
+            .filter(|key| prefix.is_none_or(|prefix| key.starts_with(prefix)))
+            .collect())
+    }
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default()
+}
+
+/// Keys matching `{prefix}*`, walked with `SCAN` so large databases are not blocked
+async fn scan_keys(redis: &mut MultiplexedConnection, prefix: Option<&str>) -> Result<Vec<String>> {
+    let pattern = format!("{}*", prefix.unwrap_or_default());
+    let mut cursor = 0u64;
+    let mut keys = Vec::new();
+
+    loop {
+        let (next, batch): (u64, Vec<String>) = redis::cmd("SCAN")
+            .arg(cursor)
+            .arg("MATCH")
+            .arg(&pattern)
+            .arg("COUNT")
+            .arg(SCAN_COUNT)
+            .query_async(redis)
+            .await?;
+        keys.extend(batch.into_iter().filter(|key| key != HITS_KEY && key != MISSES_KEY));
+
+        if next == 0 {
+            break;
+        }
+        cursor = next;
+    }
+
+    keys.sort();
+    Ok(keys)
+}
+
+async fn list_cache_keys(prefix: Option<String>) -> Result<()> {
+    let keys = match CacheStore::from_config()? {
+        CacheStore::File(store) => store.keys(prefix.as_deref())?,
+        CacheStore::Redis(url) => scan_keys(&mut connect(&url).await?, prefix.as_deref()).await?,
+    };
+
+    if CommandUtils::is_json_output() {
+        println!("{}", serde_json::json!({ "keys": keys }));
+    } else if keys.is_empty() {
+        CommandUtils::info("No cache keys found");
+    } else {
+        for key in &keys {
+            println!("  {}", key);
+        }
+        CommandUtils::info(&format!("{} key(s)", keys.len()));
+    }
+
+    Ok(())
+}
+
+/// Cache counters shown by `cache stats`
+#[derive(Debug, Default, PartialEq, Serialize)]
+struct CacheStats {
+    keys: usize,
+    hits: Option<u64>,
+    misses: Option<u64>,
+    memory: u64,
+}
+
+impl CacheStats {
+    /// Share of lookups that were hits, when any were recorded
+    fn hit_ratio(&self) -> Option<f64> {
+        let (hits, misses) = (self.hits?, self.misses?);
+        (hits + misses > 0).then(|| hits as f64 / (hits + misses) as f64)
+    }
+
+    fn rows(&self) -> Vec<Vec<String>> {
+        let count = |value: Option<u64>| value.map_or_else(|| "N/A".to_string(), |value| value.to_string());
+        vec![
+            vec!["Keys".to_string(), self.keys.to_string()],
+            vec!["Hits".to_string(), count(self.hits)],
+            vec!["Misses".to_string(), count(self.misses)],
+            vec![
+                "Hit ratio".to_string(),
+                self.hit_ratio().map_or_else(|| "N/A".to_string(), |ratio| format!("{:.1}%", ratio * 100.0)),
+            ],
+            vec!["Memory usage".to_string(), format_size(self.memory)],
+        ]
+    }
+}
+
+async fn cache_stats() -> Result<()> {
+    let stats = match CacheStore::from_config()? {
+        CacheStore::File(store) => {
+            let entries = store.entries()?;
+            let memory = entries
+                .iter()
+                .filter_map(|(_, path)| std::fs::metadata(path).ok())
+                .map(|metadata| metadata.len())
+                .sum();
+            CacheStats { keys: entries.len(), memory, ..Default::default() }
+        }
+        CacheStore::Redis(url) => {
+            let mut redis = connect(&url).await?;
+            let keys = scan_keys(&mut redis, None).await?;
+            let (hits, misses): (Option<u64>, Option<u64>) =
+                redis::cmd("MGET").arg(HITS_KEY).arg(MISSES_KEY).query_async(&mut redis).await?;
+
+            let mut memory = 0;
+            for key in &keys {
+                let usage: Option<u64> = redis::cmd("MEMORY").arg("USAGE").arg(key).query_async(&mut redis).await?;
+                memory += usage.unwrap_or_default();
+            }
+
+            CacheStats {
+                keys: keys.len(),
+                hits: Some(hits.unwrap_or_default()),
+                misses: Some(misses.unwrap_or_default()),
+                memory,
+            }
+        }
+    };
+
+    if CommandUtils::is_json_output() {
+        println!("{}", serde_json::json!({ "stats": stats, "hit_ratio": stats.hit_ratio() }));
+    } else {
+        println!("{}", TextUtils::table_format(&["Metric", "Value"], &stats.rows(), &[16, 16]));
+    }
+
+    Ok(())
+}
+
 async fn clear_all_cache() -> Result<()> {
     CommandUtils::info("Clearing all cache...");
 
@@ -44,6 +312,7 @@ async fn clear_all_cache() -> Result<()> {
     // Clear specific cache files
     let cache_files = [
         "bootstrap/cache/routes.json",
+        "bootstrap/cache/routes.bin",
         "bootstrap/cache/config.json",
         "bootstrap/cache/services.json",
     ];
@@ -72,16 +341,113 @@ async fn clear_all_cache() -> Result<()> {
 async fn forget_cache_key(key: String) -> Result<()> {
     CommandUtils::info(&format!("Forgetting cache key: {}", key));
 
-    // TODO: Implement specific cache key removal logic
-    // This would typically involve connecting to the cache store (Redis, Memcached, etc.)
-    // and removing the specific key
+    let forgotten = match CacheStore::from_config()? {
+        CacheStore::File(store) => store.forget(&key)?,
+        CacheStore::Redis(url) => {
+            let mut redis = connect(&url).await?;
+            let deleted: u64 = redis::cmd("DEL").arg(&key).query_async(&mut redis).await?;
+            deleted > 0
+        }
+    };
+
+    if forgotten {
+        CommandUtils::success(&format!("Cache key '{}' has been forgotten", key));
+    } else {
+        CommandUtils::warning(&format!("Cache key '{}' was not found", key));
+    }
+
+    Ok(())
+}
+
+/// A step of `cache warm`
+#[derive(Debug, Clone, PartialEq)]
+enum WarmStep {
+    Config,
+    Routes,
+    /// A shell command from `[cache.warm_up] commands`
+    Command(String),
+}
+
+/// Steps selected by the `cache warm` flags, `--all` being the default
+fn warm_steps(config: bool, routes: bool, all: bool, commands: &[String]) -> Vec<WarmStep> {
+    let all = all || !(config || routes);
+    let mut steps = Vec::new();
+
+    if config || all {
+        steps.push(WarmStep::Config);
+    }
+    if routes || all {
+        steps.push(WarmStep::Routes);
+    }
+    if all {
+        steps.extend(commands.iter().cloned().map(WarmStep::Command));
+    }
+
+    steps
+}
+
+/// Commands listed under `[cache.warm_up] commands` in rustisan.toml
+fn warm_up_commands() -> Vec<String> {
+    load_config()
+        .ok()
+        .and_then(|config| {
+            let commands = config.get("cache")?.get("warm_up")?.get("commands")?.as_array()?.clone();
+            Some(commands.iter().filter_map(|command| command.as_str().map(str::to_string)).collect())
+        })
+        .unwrap_or_default()
+}
+
+/// Run a warm-up command through the shell, killing it after `timeout`
+async fn run_warm_up_command(command: &str, timeout: Duration) -> Result<()> {
+    let (shell, flag) = ProcessUtils::get_shell_command();
+    let status = tokio::process::Command::new(shell).args([flag, command]).kill_on_drop(true).status();
+
+    let status = tokio::time::timeout(timeout, status)
+        .await
+        .map_err(|_| anyhow::anyhow!("'{}' timed out after {}s", command, timeout.as_secs()))??;
+    if !status.success() {
+        anyhow::bail!("'{}' exited with {}", command, status);
+    }
+    Ok(())
+}
+
+/// Warm the caches, reporting each failed step and continuing with the next
+async fn warm_cache(config: bool, routes: bool, all: bool, timeout: Duration) -> Result<()> {
+    CommandUtils::info("Warming cache...");
 
-    CommandUtils::success(&format!("Cache key '{}' has been forgotten", key));
+    run_warm_steps(warm_steps(config, routes, all, &warm_up_commands()), timeout).await
+}
 
+/// Run every step, failing once they have all run if any of them failed
+async fn run_warm_steps(steps: Vec<WarmStep>, timeout: Duration) -> Result<()> {
+    let mut report = OptimizeReport::default();
+
+    for step in steps {
+        match step {
+            WarmStep::Config => {
+                let (elapsed, result) = timed(cache_config()).await;
+                report.record("Config cached", elapsed, result);
+            }
+            WarmStep::Routes => {
+                let (elapsed, result) = timed(super::route::cache_routes()).await;
+                report.record("Routes cached", elapsed, result);
+            }
+            WarmStep::Command(command) => {
+                let (elapsed, result) = timed(run_warm_up_command(&command, timeout)).await;
+                report.record(&format!("Ran '{}'", command), elapsed, result);
+            }
+        }
+    }
+
+    if !report.all_succeeded() {
+        return Err(anyhow::anyhow!("Cache warming failed: {}", report.summary()));
+    }
+
+    CommandUtils::success(&report.summary());
     Ok(())
 }
 
-async fn cache_config() -> Result<()> {
+pub(crate) async fn cache_config() -> Result<()> {
     CommandUtils::info("Caching configuration...");
 
     // Load configuration files
@@ -147,3 +513,79 @@ fn clear_directory(dir: &std::path::Path) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_store_put_and_forget() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileStore::new(dir.path());
+        let path = store.path("users:1");
+
+        store.put("users:1", serde_json::json!({ "name": "Ada" }), Some(60)).unwrap();
+        assert!(path.exists());
+        assert_eq!(path.file_name().unwrap().len(), 32 + ".cache".len());
+
+        assert!(store.forget("users:1").unwrap());
+        assert!(!path.exists());
+        assert!(!store.forget("users:1").unwrap());
+    }
+
+    #[test]
+    fn test_file_store_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileStore::new(dir.path());
+        store.put("users:1", serde_json::json!(1), None).unwrap();
+        store.put("users:2", serde_json::json!(2), None).unwrap();
+        store.put("posts:1", serde_json::json!(3), None).unwrap();
+
+        assert_eq!(store.keys(Some("users:")).unwrap(), ["users:1", "users:2"]);
+        assert_eq!(store.keys(None).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_redis_url_from_config() {
+        let config: toml::Value = toml::from_str("[redis]\nhost = \"cache\"\nport = 6380\npassword = \"secret\"\ndatabase = 2\n").unwrap();
+        assert_eq!(redis_url(&config), "redis://:secret@cache:6380/2");
+        assert_eq!(redis_url(&toml::Value::Table(Default::default())), DEFAULT_REDIS_URL);
+    }
+
+    #[test]
+    fn test_warm_all_runs_config_and_routes() {
+        let commands = vec!["cargo run -- custom:warm".to_string()];
+
+        assert_eq!(
+            warm_steps(false, false, true, &commands),
+            [WarmStep::Config, WarmStep::Routes, WarmStep::Command(commands[0].clone())]
+        );
+        assert_eq!(warm_steps(false, false, false, &commands).len(), 3);
+        assert_eq!(warm_steps(false, true, false, &commands), [WarmStep::Routes]);
+    }
+
+    #[tokio::test]
+    async fn test_failed_warm_step_fails_the_command() {
+        let steps = vec![WarmStep::Command("exit 1".to_string()), WarmStep::Command("exit 0".to_string())];
+        let error = run_warm_steps(steps, Duration::from_secs(5)).await.unwrap_err();
+        assert!(error.to_string().contains("1 of 2 steps succeeded"));
+
+        assert!(run_warm_steps(vec![WarmStep::Command("exit 0".to_string())], Duration::from_secs(5)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_warm_up_command_timeout() {
+        let error = run_warm_up_command("sleep 5", Duration::from_millis(100)).await.unwrap_err();
+        assert_eq!(error.to_string(), "'sleep 5' timed out after 0s");
+        assert!(run_warm_up_command("exit 0", Duration::from_secs(5)).await.is_ok());
+        assert!(run_warm_up_command("exit 1", Duration::from_secs(5)).await.is_err());
+    }
+
+    #[test]
+    fn test_hit_ratio() {
+        let stats = CacheStats { hits: Some(3), misses: Some(1), ..Default::default() };
+        assert_eq!(stats.hit_ratio(), Some(0.75));
+        assert_eq!(stats.rows()[3][1], "75.0%");
+        assert_eq!(CacheStats::default().hit_ratio(), None);
+    }
+}
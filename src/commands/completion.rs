@@ -0,0 +1,119 @@
+//! Shell completion command for the Rustisan CLI
+//!
+//! This module generates completion scripts for bash, zsh, fish and PowerShell.
+
+use anyhow::Result;
+use clap::CommandFactory;
+use clap_complete::{generate, Shell};
+use colored::*;
+use std::io::Write;
+use std::path::PathBuf;
+
+use super::CommandUtils;
+use crate::Cli;
+
+/// Handle the completion command
+pub async fn handle(shell: Option<String>, install: bool) -> Result<()> {
+    let shell = match shell {
+        Some(name) => parse_shell(&name)?,
+        None if install => detect_shell()?,
+        None => anyhow::bail!("Please specify a shell: bash, zsh, fish or powershell"),
+    };
+
+    if install {
+        install_completion(shell)
+    } else {
+        let mut stdout = std::io::stdout();
+        stdout.write_all(&generate_completion(shell))?;
+        stdout.flush()?;
+        Ok(())
+    }
+}
+
+/// Parse a shell name
+fn parse_shell(name: &str) -> Result<Shell> {
+    match name.to_lowercase().as_str() {
+        "bash" => Ok(Shell::Bash),
+        "zsh" => Ok(Shell::Zsh),
+        "fish" => Ok(Shell::Fish),
+        "powershell" | "pwsh" => Ok(Shell::PowerShell),
+        _ => Err(anyhow::anyhow!(
+            "Unsupported shell: {} (expected bash, zsh, fish or powershell)",
+            name
+        )),
+    }
+}
+
+/// Detect the user's shell from the environment
+fn detect_shell() -> Result<Shell> {
+    if let Ok(shell_path) = std::env::var("SHELL") {
+        let name = shell_path.rsplit('/').next().unwrap_or_default();
+        if let Ok(shell) = parse_shell(name) {
+            return Ok(shell);
+        }
+    }
+
+    if std::env::var("PSModulePath").is_ok() {
+        return Ok(Shell::PowerShell);
+    }
+
+    Err(anyhow::anyhow!("Could not detect your shell; pass it explicitly, e.g. 'rustisan completion bash --install'"))
+}
+
+/// Generate the completion script for a shell
+fn generate_completion(shell: Shell) -> Vec<u8> {
+    let mut command = Cli::command();
+    let mut buffer = Vec::new();
+    generate(shell, &mut command, "rustisan", &mut buffer);
+    buffer
+}
+
+/// Write the completion script to the shell's completion directory
+fn install_completion(shell: Shell) -> Result<()> {
+    let home = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .map(PathBuf::from)
+        .map_err(|_| anyhow::anyhow!("Could not determine your home directory"))?;
+
+    let path = match shell {
+        Shell::Bash => home.join(".local/share/bash-completion/completions/rustisan"),
+        Shell::Zsh => home.join(".zfunc/_rustisan"),
+        Shell::Fish => home.join(".config/fish/completions/rustisan.fish"),
+        Shell::PowerShell => home.join(".config/powershell/rustisan.ps1"),
+        _ => anyhow::bail!("Installing completions for {} is not supported", shell),
+    };
+
+    let script = String::from_utf8(generate_completion(shell))?;
+    CommandUtils::write_file(&path, &script)?;
+
+    CommandUtils::success(&format!(
+        "Installed {} completions to {}",
+        shell,
+        path.display().to_string().cyan().bold()
+    ));
+
+    match shell {
+        Shell::Zsh => CommandUtils::info("Make sure ~/.zfunc is in your fpath: add 'fpath=(~/.zfunc $fpath)' before 'compinit' in ~/.zshrc"),
+        Shell::PowerShell => CommandUtils::info(&format!("Add '. {}' to your PowerShell profile", path.display())),
+        _ => CommandUtils::info("Restart your shell to enable completions"),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_completion() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish, Shell::PowerShell] {
+            let script = String::from_utf8(generate_completion(shell)).unwrap();
+
+            assert!(!script.is_empty());
+            for subcommand in ["new", "make", "serve", "migrate"] {
+                assert!(script.contains(subcommand), "{} completion is missing '{}'", shell, subcommand);
+            }
+        }
+    }
+}
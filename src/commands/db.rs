@@ -3,30 +3,63 @@
 //! This module provides commands for managing database operations,
 //! similar to Laravel's database commands.
 
+mod backup;
+mod metrics;
+pub(crate) mod schema;
+pub(crate) mod schema_diff;
+
 use anyhow::Result;
 use colored::*;
 use std::process::Command;
 use std::fs;
+use std::path::{Path, PathBuf};
 use toml::Value;
 
 use super::CommandUtils;
+use super::build::format_size;
 use crate::DbCommands;
+use crate::utils::TextUtils;
+use crate::utils::env::expand_env_vars;
+use backup::SystemRunner;
+use metrics::PoolMetrics;
+
+pub use schema::SchemaFormat;
 
 /// Handle database commands
 pub async fn handle(operation: DbCommands) -> Result<()> {
     match operation {
-        DbCommands::Status => show_status().await,
+        DbCommands::Status { interval } => show_status(interval).await,
         DbCommands::Create => create_database().await,
         DbCommands::Drop { force } => drop_database(force).await,
         DbCommands::Reset { force } => reset_database(force).await,
         DbCommands::Seed => seed_database().await,
+        DbCommands::Backup { output, gzip } => backup_database(output, gzip).await,
+        DbCommands::Restore { file, force } => restore_database(file, force).await,
+        DbCommands::Schema { table, format } => show_schema(table, format),
     }
 }
 
-/// Show database status
-async fn show_status() -> Result<()> {
+/// Show database status, refreshing every `interval` seconds when given
+async fn show_status(interval: Option<u64>) -> Result<()> {
     CommandUtils::ensure_rustisan_project()?;
 
+    let Some(seconds) = interval else {
+        return print_status().await;
+    };
+
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(seconds));
+    loop {
+        interval.tick().await;
+
+        // Clear the screen so the status refreshes in place
+        print!("\x1b[2J\x1b[H");
+        println!("{}", format!("Refreshing every {}s, press Ctrl+C to stop", seconds).dimmed());
+        print_status().await?;
+    }
+}
+
+/// Print a snapshot of the database status
+async fn print_status() -> Result<()> {
     CommandUtils::info("Database Status:");
     println!();
 
@@ -41,18 +74,26 @@ async fn show_status() -> Result<()> {
                 .unwrap_or_else(|| "unknown".to_string());
 
             println!("  {} {}", "Driver:".cyan().bold(), db_driver);
-            println!("  {} {}:{}", "Host:".cyan().bold(), db_host, db_port);
-            println!("  {} {}", "Database:".cyan().bold(), db_name);
+            if db_driver == "sqlite" {
+                println!("  {} {}", "Path:".cyan().bold(), db_name);
+            } else {
+                println!("  {} {}:{}", "Host:".cyan().bold(), db_host, db_port);
+                println!("  {} {}", "Database:".cyan().bold(), db_name);
+            }
 
             // Test connection
-            match test_connection(&db_driver, &db_host, &db_port, &db_name).await {
+            let connected = match test_connection(&db_driver, &db_host, &db_port, &db_name).await {
                 Ok(_) => {
                     CommandUtils::success("Database connection: OK");
+                    true
                 }
                 Err(e) => {
                     CommandUtils::error(&format!("Database connection failed: {}", e));
+                    false
                 }
-            }
+            };
+
+            print_pool_metrics(&config, connected);
         } else {
             CommandUtils::warning("No database configuration found in rustisan.toml");
         }
@@ -68,6 +109,107 @@ async fn show_status() -> Result<()> {
     Ok(())
 }
 
+/// Print the connection pool metrics table
+///
+/// Live counts are only queried when the connection test succeeded; fields
+/// the server refuses to report are shown as N/A.
+fn print_pool_metrics(config: &Value, connected: bool) {
+    let mut metrics = PoolMetrics {
+        pool_min: get_config_value(config, "database.connections.default.pool_min"),
+        pool_max: get_config_value(config, "database.connections.default.pool_max"),
+        ..Default::default()
+    };
+
+    if connected && let Ok(connection) = Connection::from_config() {
+        for warning in metrics.introspect(&connection.driver, |sql| execute_sql(&connection, sql)) {
+            CommandUtils::warning(&warning);
+        }
+    }
+
+    println!();
+    println!("{}", TextUtils::table_format(&["Metric", "Value"], &metrics.rows(), &[28, 20]));
+}
+
+/// Show the tables of the database, or the columns and indexes of one table
+fn show_schema(table: Option<String>, format: SchemaFormat) -> Result<()> {
+    CommandUtils::ensure_rustisan_project()?;
+
+    let connection = Connection::from_config()?;
+    let query = |sql: &str| execute_sql(&connection, sql);
+    let (driver, database) = (connection.driver.as_str(), connection.database.as_str());
+
+    let tables = match table {
+        Some(table) => vec![table],
+        None if format == SchemaFormat::Sql => {
+            schema::tables(driver, database, query)?.into_iter().map(|table| table.name).collect()
+        }
+        None => {
+            let tables = schema::tables(driver, database, query)?;
+            if tables.is_empty() {
+                CommandUtils::warning(&format!("No tables found in '{}'", database));
+                return Ok(());
+            }
+
+            let rows: Vec<Vec<String>> = tables
+                .iter()
+                .map(|table| {
+                    vec![
+                        table.name.clone(),
+                        table.rows.map_or_else(|| "N/A".to_string(), |rows| rows.to_string()),
+                        table.size.map_or_else(|| "N/A".to_string(), format_size),
+                    ]
+                })
+                .collect();
+
+            CommandUtils::info(&format!("Tables in '{}':", database.cyan().bold()));
+            println!("{}", TextUtils::table_format(&["Table", "Rows", "Size"], &rows, &[30, 12, 12]));
+            return Ok(());
+        }
+    };
+
+    if format == SchemaFormat::Sql {
+        for table in &tables {
+            println!("{}\n", schema::create_table(driver, database, table, query)?);
+        }
+        return Ok(());
+    }
+
+    let table = &tables[0];
+    let columns: Vec<Vec<String>> = schema::columns(driver, database, table, query)?
+        .into_iter()
+        .map(|column| {
+            vec![
+                column.name,
+                column.data_type,
+                if column.nullable { "YES" } else { "NO" }.to_string(),
+                column.default.unwrap_or_else(|| "NULL".to_string()),
+                column.key,
+            ]
+        })
+        .collect();
+
+    CommandUtils::info(&format!("Columns of '{}':", table.cyan().bold()));
+    println!(
+        "{}",
+        TextUtils::table_format(&["Column", "Type", "Nullable", "Default", "Key"], &columns, &[24, 20, 8, 20, 8])
+    );
+
+    let indexes: Vec<Vec<String>> = schema::indexes(driver, database, table, query)?
+        .into_iter()
+        .map(|index| vec![index.name, index.columns, if index.unique { "YES" } else { "NO" }.to_string()])
+        .collect();
+
+    println!();
+    if indexes.is_empty() {
+        CommandUtils::info(&format!("No indexes on '{}'", table));
+    } else {
+        CommandUtils::info(&format!("Indexes of '{}':", table.cyan().bold()));
+        println!("{}", TextUtils::table_format(&["Index", "Columns", "Unique"], &indexes, &[30, 30, 8]));
+    }
+
+    Ok(())
+}
+
 /// Create database
 async fn create_database() -> Result<()> {
     CommandUtils::ensure_rustisan_project()?;
@@ -83,6 +225,7 @@ async fn create_database() -> Result<()> {
     match db_driver.as_str() {
         "mysql" => create_mysql_database(&db_name).await?,
         "postgres" => create_postgres_database(&db_name).await?,
+        "sqlite" => create_sqlite_database(&db_name).await?,
         _ => {
             return Err(anyhow::anyhow!("Unsupported database driver: {}", db_driver));
         }
@@ -103,19 +246,9 @@ async fn drop_database(force: bool) -> Result<()> {
     let db_name = get_config_value(&config, "database.connections.default.database")
         .ok_or_else(|| anyhow::anyhow!("Database name not configured in rustisan.toml"))?;
 
-    if !force {
-        CommandUtils::warning(&format!("This will permanently delete database '{}'", db_name));
-        print!("Are you sure? (yes/no): ");
-        use std::io::{self, Write};
-        io::stdout().flush()?;
-
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
-
-        if input.trim().to_lowercase() != "yes" {
-            CommandUtils::info("Operation cancelled");
-            return Ok(());
-        }
+    if !confirm_destructive(&format!("This will permanently delete database '{}'", db_name), force)? {
+        CommandUtils::info("Operation cancelled");
+        return Ok(());
     }
 
     CommandUtils::info(&format!("Dropping database '{}'...", db_name.cyan().bold()));
@@ -123,6 +256,7 @@ async fn drop_database(force: bool) -> Result<()> {
     match db_driver.as_str() {
         "mysql" => drop_mysql_database(&db_name).await?,
         "postgres" => drop_postgres_database(&db_name).await?,
+        "sqlite" => drop_sqlite_database(&db_name).await?,
         _ => {
             return Err(anyhow::anyhow!("Unsupported database driver: {}", db_driver));
         }
@@ -150,6 +284,69 @@ async fn reset_database(force: bool) -> Result<()> {
     Ok(())
 }
 
+/// Back up the database to a SQL dump
+async fn backup_database(output: Option<String>, gzip: bool) -> Result<()> {
+    CommandUtils::ensure_rustisan_project()?;
+
+    let config = load_config()?;
+    let connection = Connection::from_config()?;
+    let path = match output {
+        Some(output) => PathBuf::from(output),
+        None => {
+            let app_name = get_config_value(&config, "app.name").unwrap_or_else(|| "rustisan".to_string());
+            backup::default_backup_path(&app_name, &chrono::Local::now(), gzip)
+        }
+    };
+
+    CommandUtils::info(&format!("Backing up database '{}'...", connection.database.cyan().bold()));
+
+    let size = backup::backup(&SystemRunner, &connection, &path, gzip)?;
+
+    CommandUtils::success(&format!("Database backed up to {} ({})", path.display(), format_size(size)));
+
+    Ok(())
+}
+
+/// Restore the database from a SQL dump
+async fn restore_database(file: String, force: bool) -> Result<()> {
+    CommandUtils::ensure_rustisan_project()?;
+
+    let connection = Connection::from_config()?;
+    let path = Path::new(&file);
+    backup::validate_backup_file(path)?;
+
+    let warning = format!("This will overwrite database '{}' with the contents of '{}'", connection.database, file);
+    if !confirm_destructive(&warning, force)? {
+        CommandUtils::info("Operation cancelled");
+        return Ok(());
+    }
+
+    CommandUtils::info(&format!("Restoring database '{}'...", connection.database.cyan().bold()));
+
+    backup::restore(&SystemRunner, &connection, path)?;
+
+    CommandUtils::success(&format!("Database '{}' restored from {}", connection.database.cyan().bold(), file));
+
+    Ok(())
+}
+
+/// Ask the user to confirm a destructive operation unless `force` is set
+fn confirm_destructive(warning: &str, force: bool) -> Result<bool> {
+    if force {
+        return Ok(true);
+    }
+
+    CommandUtils::warning(warning);
+    print!("Are you sure? (yes/no): ");
+    use std::io::{self, Write};
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+
+    Ok(input.trim().to_lowercase() == "yes")
+}
+
 /// Seed database
 async fn seed_database() -> Result<()> {
     CommandUtils::ensure_rustisan_project()?;
@@ -173,41 +370,24 @@ async fn seed_database() -> Result<()> {
 }
 
 /// Test database connection
-async fn test_connection(driver: &str, host: &str, port: &str, database: &str) -> Result<()> {
+pub(crate) async fn test_connection(driver: &str, host: &str, port: &str, database: &str) -> Result<()> {
     match driver {
         "mysql" => test_mysql_connection(host, port, database).await,
         "postgres" => test_postgres_connection(host, port, database).await,
+        "sqlite" => test_sqlite_connection(database).await,
         _ => Err(anyhow::anyhow!("Unsupported database driver: {}", driver)),
     }
 }
 
 /// Create MySQL database
 async fn create_mysql_database(db_name: &str) -> Result<()> {
-    let config = load_config()?;
-    let host = get_config_value(&config, "database.connections.default.host")
-        .unwrap_or_else(|| "localhost".to_string());
-    let port = get_config_value(&config, "database.connections.default.port")
-        .unwrap_or_else(|| "3306".to_string());
-    let username = get_config_value(&config, "database.connections.default.username")
-        .unwrap_or_else(|| "root".to_string());
-    let password = get_config_value(&config, "database.connections.default.password")
-        .unwrap_or_default();
-
-    let mut args = vec![
-        format!("-h{}", host),
-        format!("-P{}", port),
-        format!("-u{}", username),
-    ];
-
-    if !password.is_empty() {
-        args.push(format!("-p{}", password));
-    }
-
-    args.push("-e".to_string());
-    args.push(format!("CREATE DATABASE IF NOT EXISTS `{}`", db_name));
+    let connection = Connection::from_config()?;
 
     let output = Command::new("mysql")
-        .args(&args)
+        .envs(connection.password_env())
+        .args(connection.mysql_args())
+        .arg("-e")
+        .arg(format!("CREATE DATABASE IF NOT EXISTS `{}`", db_name))
         .output()?;
 
     if !output.status.success() {
@@ -220,31 +400,13 @@ async fn create_mysql_database(db_name: &str) -> Result<()> {
 
 /// Drop MySQL database
 async fn drop_mysql_database(db_name: &str) -> Result<()> {
-    let config = load_config()?;
-    let host = get_config_value(&config, "database.connections.default.host")
-        .unwrap_or_else(|| "localhost".to_string());
-    let port = get_config_value(&config, "database.connections.default.port")
-        .unwrap_or_else(|| "3306".to_string());
-    let username = get_config_value(&config, "database.connections.default.username")
-        .unwrap_or_else(|| "root".to_string());
-    let password = get_config_value(&config, "database.connections.default.password")
-        .unwrap_or_default();
-
-    let mut args = vec![
-        format!("-h{}", host),
-        format!("-P{}", port),
-        format!("-u{}", username),
-    ];
-
-    if !password.is_empty() {
-        args.push(format!("-p{}", password));
-    }
-
-    args.push("-e".to_string());
-    args.push(format!("DROP DATABASE IF EXISTS `{}`", db_name));
+    let connection = Connection::from_config()?;
 
     let output = Command::new("mysql")
-        .args(&args)
+        .envs(connection.password_env())
+        .args(connection.mysql_args())
+        .arg("-e")
+        .arg(format!("DROP DATABASE IF EXISTS `{}`", db_name))
         .output()?;
 
     if !output.status.success() {
@@ -257,28 +419,17 @@ async fn drop_mysql_database(db_name: &str) -> Result<()> {
 
 /// Test MySQL connection
 async fn test_mysql_connection(host: &str, port: &str, database: &str) -> Result<()> {
-    let config = load_config()?;
-    let username = get_config_value(&config, "database.connections.default.username")
-        .unwrap_or_else(|| "root".to_string());
-    let password = get_config_value(&config, "database.connections.default.password")
-        .unwrap_or_default();
-
-    let mut args = vec![
-        format!("-h{}", host),
-        format!("-P{}", port),
-        format!("-u{}", username),
-    ];
-
-    if !password.is_empty() {
-        args.push(format!("-p{}", password));
-    }
-
-    args.push(database.to_string());
-    args.push("-e".to_string());
-    args.push("SELECT 1".to_string());
+    let connection = Connection {
+        host: host.to_string(),
+        port: port.to_string(),
+        database: database.to_string(),
+        ..Connection::from_config()?
+    };
 
     let output = Command::new("mysql")
-        .args(&args)
+        .envs(connection.password_env())
+        .args(connection.mysql_args())
+        .args([connection.database.as_str(), "-e", "SELECT 1"])
         .output()?;
 
     if !output.status.success() {
@@ -310,8 +461,146 @@ async fn test_postgres_connection(host: &str, port: &str, database: &str) -> Res
     Ok(())
 }
 
+/// Create SQLite database file
+async fn create_sqlite_database(db_path: &str) -> Result<()> {
+    let path = Path::new(db_path);
+
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+
+    if !path.exists() {
+        fs::File::create(path)
+            .map_err(|e| anyhow::anyhow!("SQLite error: could not create '{}': {}", db_path, e))?;
+    }
+
+    Ok(())
+}
+
+/// Drop SQLite database file
+async fn drop_sqlite_database(db_path: &str) -> Result<()> {
+    let path = Path::new(db_path);
+
+    if path.exists() {
+        fs::remove_file(path)
+            .map_err(|e| anyhow::anyhow!("SQLite error: could not delete '{}': {}", db_path, e))?;
+    }
+
+    Ok(())
+}
+
+/// Test SQLite connection by checking the database file
+async fn test_sqlite_connection(db_path: &str) -> Result<()> {
+    let path = Path::new(db_path);
+
+    let metadata = fs::metadata(path)
+        .map_err(|_| anyhow::anyhow!("Connection failed: database file '{}' not found", db_path))?;
+
+    if !metadata.is_file() {
+        return Err(anyhow::anyhow!("Connection failed: '{}' is not a file", db_path));
+    }
+
+    // An empty file is a valid, not yet initialized SQLite database
+    if metadata.len() > 0 {
+        use std::io::Read;
+        let mut header = [0u8; 16];
+        let mut file = fs::File::open(path)?;
+        let read = file.read(&mut header)?;
+        if read < header.len() || header != *SQLITE_HEADER {
+            return Err(anyhow::anyhow!("Connection failed: '{}' is not a SQLite database", db_path));
+        }
+    }
+
+    Ok(())
+}
+
+/// Magic header string at the start of every SQLite 3 database file
+const SQLITE_HEADER: &[u8; 16] = b"SQLite format 3\0";
+
+/// Connection settings for the default database connection
+pub(crate) struct Connection {
+    pub driver: String,
+    pub host: String,
+    pub port: String,
+    pub database: String,
+    pub username: String,
+    pub password: String,
+}
+
+impl Connection {
+    /// Load the default connection from rustisan.toml
+    pub(crate) fn from_config() -> Result<Self> {
+        let config = load_config()?;
+        let driver = get_config_value(&config, "database.connections.default.driver")
+            .ok_or_else(|| anyhow::anyhow!("Database driver not configured in rustisan.toml"))?;
+        let database = get_config_value(&config, "database.connections.default.database")
+            .ok_or_else(|| anyhow::anyhow!("Database name not configured in rustisan.toml"))?;
+        let default_port = if driver == "postgres" { "5432" } else { "3306" };
+        let default_username = if driver == "postgres" { "postgres" } else { "root" };
+
+        Ok(Self {
+            host: get_config_value(&config, "database.connections.default.host")
+                .unwrap_or_else(|| "localhost".to_string()),
+            port: get_config_value(&config, "database.connections.default.port")
+                .unwrap_or_else(|| default_port.to_string()),
+            username: get_config_value(&config, "database.connections.default.username")
+                .unwrap_or_else(|| default_username.to_string()),
+            password: get_config_value(&config, "database.connections.default.password")
+                .unwrap_or_default(),
+            driver,
+            database,
+        })
+    }
+
+    /// Host, port and user arguments of the `mysql` client tools
+    pub(crate) fn mysql_args(&self) -> Vec<String> {
+        vec![format!("-h{}", self.host), format!("-P{}", self.port), format!("-u{}", self.username)]
+    }
+
+    /// Environment passing the password to the client tools, which keeps it off the command line
+    pub(crate) fn password_env(&self) -> Vec<(&'static str, String)> {
+        match self.driver.as_str() {
+            "mysql" if !self.password.is_empty() => vec![("MYSQL_PWD", self.password.clone())],
+            "postgres" => vec![("PGPASSWORD", self.password.clone())],
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Execute a SQL statement through the driver's command-line client
+///
+/// Returns the raw output with one row per line and columns separated by tabs.
+pub(crate) fn execute_sql(connection: &Connection, sql: &str) -> Result<String> {
+    let output = match connection.driver.as_str() {
+        "mysql" => Command::new("mysql")
+            .envs(connection.password_env())
+            .args(connection.mysql_args())
+            .args(["-N", "-B", &connection.database, "-e", sql])
+            .output()?,
+        "postgres" => Command::new("psql")
+            .envs(connection.password_env())
+            .args(["-h", &connection.host, "-p", &connection.port])
+            .args(["-U", &connection.username, "-d", &connection.database])
+            .args(["-t", "-A", "-F", "\t", "-v", "ON_ERROR_STOP=1", "-c", sql])
+            .output()?,
+        "sqlite" => Command::new("sqlite3")
+            .args(["-batch", "-separator", "\t", &connection.database, sql])
+            .output()?,
+        driver => {
+            return Err(anyhow::anyhow!("Unsupported database driver: {}", driver));
+        }
+    };
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow::anyhow!("{} error: {}", connection.driver, stderr.trim()));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
 /// Load configuration from rustisan.toml
-fn load_config() -> Result<Value> {
+pub(crate) fn load_config() -> Result<Value> {
     let config_content = fs::read_to_string("rustisan.toml")
         .map_err(|_| anyhow::anyhow!("rustisan.toml not found"))?;
     let config: Value = toml::from_str(&config_content)
@@ -319,8 +608,8 @@ fn load_config() -> Result<Value> {
     Ok(config)
 }
 
-/// Get nested value from TOML configuration
-fn get_config_value(config: &Value, key: &str) -> Option<String> {
+/// Get nested value from TOML configuration, expanding `${ENV_VAR}` placeholders
+pub(crate) fn get_config_value(config: &Value, key: &str) -> Option<String> {
     let parts: Vec<&str> = key.split('.').collect();
     let mut current = config;
 
@@ -334,7 +623,7 @@ fn get_config_value(config: &Value, key: &str) -> Option<String> {
     }
 
     match current {
-        Value::String(s) => Some(s.clone()),
+        Value::String(s) => Some(expand_env_vars(s)),
         Value::Integer(i) => Some(i.to_string()),
         Value::Float(f) => Some(f.to_string()),
         Value::Boolean(b) => Some(b.to_string()),
@@ -0,0 +1,315 @@
+//! Database backup and restore
+//!
+//! Dumps are produced by the driver's own client tools (`mysqldump`, `pg_dump`
+//! or `sqlite3 .dump`) and restored by piping the file back into `mysql`,
+//! `psql` or `sqlite3`. Backups can be gzip-compressed; restore detects this
+//! from the `.gz` extension.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, TimeZone};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use super::Connection;
+use crate::commands::CommandUtils;
+
+/// Directory backups are written to when no output path is given
+pub const BACKUP_DIR: &str = "storage/backups";
+
+/// Invocation of a database client program
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClientCommand {
+    pub program: &'static str,
+    pub args: Vec<String>,
+    pub env: Vec<(&'static str, String)>,
+}
+
+/// Runs client programs, so tests can substitute a mock
+pub trait CommandRunner {
+    /// Run `command`, feeding it `stdin`, and return its stdout
+    fn run(&self, command: &ClientCommand, stdin: Option<&[u8]>) -> Result<Vec<u8>>;
+}
+
+/// Runner backed by `std::process::Command`
+pub struct SystemRunner;
+
+impl CommandRunner for SystemRunner {
+    fn run(&self, command: &ClientCommand, stdin: Option<&[u8]>) -> Result<Vec<u8>> {
+        let mut child = Command::new(command.program)
+            .args(&command.args)
+            .envs(command.env.iter().map(|(key, value)| (key, value)))
+            .stdin(if stdin.is_some() { Stdio::piped() } else { Stdio::null() })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Failed to run {}", command.program))?;
+
+        // Feed stdin from a separate thread so a full stdout pipe cannot deadlock us
+        let output = std::thread::scope(|scope| {
+            if let (Some(input), Some(mut pipe)) = (stdin, child.stdin.take()) {
+                scope.spawn(move || pipe.write_all(input));
+            }
+            child.wait_with_output()
+        })?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(anyhow::anyhow!("{} error: {}", command.program, stderr.trim()));
+        }
+
+        Ok(output.stdout)
+    }
+}
+
+/// Default backup location: `storage/backups/{app_name}_{timestamp}.sql[.gz]`
+pub fn default_backup_path<Tz: TimeZone>(app_name: &str, now: &DateTime<Tz>, gzip: bool) -> PathBuf
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let extension = if gzip { "sql.gz" } else { "sql" };
+    Path::new(BACKUP_DIR).join(format!(
+        "{}_{}.{}",
+        CommandUtils::to_snake_case(app_name),
+        now.format("%Y%m%d_%H%M%S"),
+        extension
+    ))
+}
+
+/// Dump the database to `path`, returning the size of the written file
+pub fn backup(runner: &dyn CommandRunner, connection: &Connection, path: &Path, gzip: bool) -> Result<u64> {
+    let dump = runner.run(&dump_command(connection)?, None)?;
+
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+
+    let file = fs::File::create(path)
+        .with_context(|| format!("Could not create backup file '{}'", path.display()))?;
+    if gzip {
+        let mut encoder = GzEncoder::new(file, Compression::default());
+        encoder.write_all(&dump)?;
+        encoder.finish()?;
+    } else {
+        let mut file = file;
+        file.write_all(&dump)?;
+    }
+
+    Ok(fs::metadata(path)?.len())
+}
+
+/// Check that `path` is a non-empty file
+pub fn validate_backup_file(path: &Path) -> Result<()> {
+    let metadata = fs::metadata(path)
+        .map_err(|_| anyhow::anyhow!("Backup file '{}' not found", path.display()))?;
+
+    if !metadata.is_file() {
+        return Err(anyhow::anyhow!("'{}' is not a file", path.display()));
+    }
+    if metadata.len() == 0 {
+        return Err(anyhow::anyhow!("Backup file '{}' is empty", path.display()));
+    }
+
+    Ok(())
+}
+
+/// Pipe the dump at `path` into the database client
+pub fn restore(runner: &dyn CommandRunner, connection: &Connection, path: &Path) -> Result<()> {
+    validate_backup_file(path)?;
+
+    let sql = read_backup(path)?;
+    runner.run(&restore_command(connection)?, Some(&sql))?;
+
+    Ok(())
+}
+
+/// Read a backup file, decompressing it when it ends in `.gz`
+fn read_backup(path: &Path) -> Result<Vec<u8>> {
+    let content = fs::read(path)?;
+
+    if path.extension().is_some_and(|ext| ext == "gz") {
+        let mut sql = Vec::new();
+        GzDecoder::new(content.as_slice())
+            .read_to_end(&mut sql)
+            .with_context(|| format!("Could not decompress '{}'", path.display()))?;
+        return Ok(sql);
+    }
+
+    Ok(content)
+}
+
+fn dump_command(connection: &Connection) -> Result<ClientCommand> {
+    match connection.driver.as_str() {
+        "mysql" => {
+            let mut args = connection.mysql_args();
+            args.extend([
+                "--single-transaction".to_string(),
+                "--routines".to_string(),
+                "--triggers".to_string(),
+                connection.database.clone(),
+            ]);
+            Ok(ClientCommand { program: "mysqldump", args, env: connection.password_env() })
+        }
+        "postgres" => {
+            let mut args = postgres_args(connection);
+            args.extend(["--clean".to_string(), "--if-exists".to_string(), "--no-owner".to_string()]);
+            Ok(ClientCommand { program: "pg_dump", args, env: connection.password_env() })
+        }
+        "sqlite" => Ok(ClientCommand {
+            program: "sqlite3",
+            args: vec![connection.database.clone(), ".dump".to_string()],
+            env: Vec::new(),
+        }),
+        driver => Err(anyhow::anyhow!("Unsupported database driver: {}", driver)),
+    }
+}
+
+fn restore_command(connection: &Connection) -> Result<ClientCommand> {
+    match connection.driver.as_str() {
+        "mysql" => {
+            let mut args = connection.mysql_args();
+            args.push(connection.database.clone());
+            Ok(ClientCommand { program: "mysql", args, env: connection.password_env() })
+        }
+        "postgres" => {
+            let mut args = postgres_args(connection);
+            args.extend(["-q".to_string(), "-v".to_string(), "ON_ERROR_STOP=1".to_string()]);
+            Ok(ClientCommand { program: "psql", args, env: connection.password_env() })
+        }
+        "sqlite" => Ok(ClientCommand {
+            program: "sqlite3",
+            args: vec!["-batch".to_string(), connection.database.clone()],
+            env: Vec::new(),
+        }),
+        driver => Err(anyhow::anyhow!("Unsupported database driver: {}", driver)),
+    }
+}
+
+fn postgres_args(connection: &Connection) -> Vec<String> {
+    vec![
+        "-h".to_string(),
+        connection.host.clone(),
+        "-p".to_string(),
+        connection.port.clone(),
+        "-U".to_string(),
+        connection.username.clone(),
+        "-d".to_string(),
+        connection.database.clone(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/backup.sql");
+
+    /// Records invocations and answers with canned stdout
+    struct MockRunner {
+        stdout: Vec<u8>,
+        calls: RefCell<Vec<(ClientCommand, Option<Vec<u8>>)>>,
+    }
+
+    impl MockRunner {
+        fn new(stdout: &[u8]) -> Self {
+            Self { stdout: stdout.to_vec(), calls: RefCell::new(Vec::new()) }
+        }
+    }
+
+    impl CommandRunner for MockRunner {
+        fn run(&self, command: &ClientCommand, stdin: Option<&[u8]>) -> Result<Vec<u8>> {
+            self.calls.borrow_mut().push((command.clone(), stdin.map(<[u8]>::to_vec)));
+            Ok(self.stdout.clone())
+        }
+    }
+
+    fn connection(driver: &str) -> Connection {
+        Connection {
+            driver: driver.to_string(),
+            host: "db.internal".to_string(),
+            port: "3306".to_string(),
+            database: "shop".to_string(),
+            username: "app".to_string(),
+            password: "secret".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_backup_writes_dump() {
+        let dir = tempfile::tempdir().unwrap();
+        let sql = fs::read(FIXTURE).unwrap();
+        let runner = MockRunner::new(&sql);
+
+        let plain = dir.path().join("backups/shop.sql");
+        let size = backup(&runner, &connection("mysql"), &plain, false).unwrap();
+        assert_eq!(fs::read(&plain).unwrap(), sql);
+        assert_eq!(size, sql.len() as u64);
+
+        let gzipped = dir.path().join("shop.sql.gz");
+        let size = backup(&runner, &connection("mysql"), &gzipped, true).unwrap();
+        assert_eq!(size, fs::metadata(&gzipped).unwrap().len());
+        assert_eq!(read_backup(&gzipped).unwrap(), sql);
+
+        let calls = runner.calls.borrow();
+        let (command, stdin) = &calls[0];
+        assert_eq!(command.program, "mysqldump");
+        assert_eq!(command.args[..3], ["-hdb.internal", "-P3306", "-uapp"]);
+        assert!(!command.args.iter().any(|arg| arg.contains("secret")));
+        assert_eq!(command.env, vec![("MYSQL_PWD", "secret".to_string())]);
+        assert_eq!(command.args.last().unwrap(), "shop");
+        assert!(stdin.is_none());
+    }
+
+    #[test]
+    fn test_restore_pipes_file_into_client() {
+        let dir = tempfile::tempdir().unwrap();
+        let sql = fs::read(FIXTURE).unwrap();
+        let runner = MockRunner::new(b"");
+
+        restore(&runner, &connection("postgres"), Path::new(FIXTURE)).unwrap();
+
+        let gzipped = dir.path().join("shop.sql.gz");
+        backup(&MockRunner::new(&sql), &connection("postgres"), &gzipped, true).unwrap();
+        restore(&runner, &connection("postgres"), &gzipped).unwrap();
+
+        let calls = runner.calls.borrow();
+        assert_eq!(calls.len(), 2);
+        for (command, stdin) in calls.iter() {
+            assert_eq!(command.program, "psql");
+            assert!(command.args.windows(2).any(|pair| pair == ["-d", "shop"]));
+            assert_eq!(command.env, vec![("PGPASSWORD", "secret".to_string())]);
+            assert_eq!(stdin.as_deref(), Some(sql.as_slice()));
+        }
+    }
+
+    #[test]
+    fn test_restore_rejects_missing_or_empty_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let empty = dir.path().join("empty.sql");
+        fs::write(&empty, "").unwrap();
+        let runner = MockRunner::new(b"");
+
+        assert!(restore(&runner, &connection("mysql"), &dir.path().join("missing.sql")).is_err());
+        assert!(restore(&runner, &connection("mysql"), &empty).is_err());
+        assert!(runner.calls.borrow().is_empty());
+        assert!(dump_command(&connection("oracle")).is_err());
+    }
+
+    #[test]
+    fn test_default_backup_path() {
+        use chrono::Utc;
+
+        let now = Utc.with_ymd_and_hms(2024, 5, 17, 10, 42, 31).unwrap();
+        assert_eq!(
+            default_backup_path("ShopApp", &now, false),
+            Path::new("storage/backups/shop_app_20240517_104231.sql")
+        );
+        assert_eq!(
+            default_backup_path("ShopApp", &now, true),
+            Path::new("storage/backups/shop_app_20240517_104231.sql.gz")
+        );
+    }
+}
@@ -0,0 +1,156 @@
+//! Connection pool metrics for `db status`
+//!
+//! Pool bounds come from `pool_min`/`pool_max` in rustisan.toml. Live counts
+//! are read from the server: `SHOW GLOBAL STATUS` on MySQL, `pg_stat_activity`
+//! and `pg_stat_database` on PostgreSQL. SQLite has no server to introspect.
+
+use anyhow::Result;
+use std::collections::HashMap;
+
+/// Connection pool metrics shown by `db status`
+#[derive(Debug, Default, PartialEq)]
+pub struct PoolMetrics {
+    pub pool_min: Option<String>,
+    pub pool_max: Option<String>,
+    pub open: Option<u64>,
+    pub active: Option<u64>,
+    pub idle: Option<u64>,
+    pub queries: Option<u64>,
+}
+
+const MYSQL_STATUS_QUERY: &str =
+    "SHOW GLOBAL STATUS WHERE Variable_name IN ('Threads_connected', 'Threads_running', 'Questions')";
+
+const POSTGRES_ACTIVITY_QUERY: &str = "SELECT count(*), \
+     count(*) FILTER (WHERE state = 'active'), \
+     count(*) FILTER (WHERE state LIKE 'idle%') \
+     FROM pg_stat_activity WHERE datname = current_database()";
+
+const POSTGRES_QUERIES_QUERY: &str =
+    "SELECT xact_commit + xact_rollback FROM pg_stat_database WHERE datname = current_database()";
+
+impl PoolMetrics {
+    /// Read live connection counts, running introspection SQL through `query`
+    ///
+    /// Fields whose query fails stay `None`; the failures are returned as
+    /// warnings so the caller can report them without aborting.
+    pub fn introspect<F>(&mut self, driver: &str, query: F) -> Vec<String>
+    where
+        F: Fn(&str) -> Result<String>,
+    {
+        let mut warnings = Vec::new();
+
+        match driver {
+            "mysql" => match query(MYSQL_STATUS_QUERY) {
+                Ok(output) => {
+                    let status = parse_mysql_status(&output);
+                    self.open = status.get("Threads_connected").copied();
+                    self.active = status.get("Threads_running").copied();
+                    self.idle = self.open.zip(self.active).map(|(open, active)| open.saturating_sub(active));
+                    self.queries = status.get("Questions").copied();
+                }
+                Err(e) => warnings.push(format!("Could not read connection metrics: {}", e)),
+            },
+            "postgres" => {
+                match query(POSTGRES_ACTIVITY_QUERY) {
+                    Ok(output) => {
+                        let counts = parse_numbers(&output);
+                        self.open = counts.first().copied();
+                        self.active = counts.get(1).copied();
+                        self.idle = counts.get(2).copied();
+                    }
+                    Err(e) => warnings.push(format!("Could not read pg_stat_activity: {}", e)),
+                }
+                match query(POSTGRES_QUERIES_QUERY) {
+                    Ok(output) => self.queries = parse_numbers(&output).first().copied(),
+                    Err(e) => warnings.push(format!("Could not read pg_stat_database: {}", e)),
+                }
+            }
+            _ => {}
+        }
+
+        warnings
+    }
+
+    /// Rows for the two-column metrics table
+    pub fn rows(&self) -> Vec<Vec<String>> {
+        let value = |value: &Option<String>| value.clone().unwrap_or_else(|| "N/A".to_string());
+        let count = |count: Option<u64>| count.map(|c| c.to_string()).unwrap_or_else(|| "N/A".to_string());
+
+        vec![
+            vec!["Pool min".to_string(), value(&self.pool_min)],
+            vec!["Pool max".to_string(), value(&self.pool_max)],
+            vec!["Open connections".to_string(), count(self.open)],
+            vec!["Active connections".to_string(), count(self.active)],
+            vec!["Idle connections".to_string(), count(self.idle)],
+            vec!["Queries since stats reset".to_string(), count(self.queries)],
+        ]
+    }
+}
+
+/// Parse `Variable_name<TAB>Value` lines from `mysql -N -B`
+fn parse_mysql_status(output: &str) -> HashMap<String, u64> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (name, value) = line.split_once('\t')?;
+            Some((name.trim().to_string(), value.trim().parse().ok()?))
+        })
+        .collect()
+}
+
+/// Parse the tab-separated numbers of the first row
+fn parse_numbers(output: &str) -> Vec<u64> {
+    output
+        .lines()
+        .next()
+        .map(|row| row.split('\t').filter_map(|value| value.trim().parse().ok()).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mysql_introspection() {
+        let mut metrics = PoolMetrics { pool_min: Some("1".to_string()), ..Default::default() };
+        let warnings = metrics.introspect("mysql", |sql| {
+            assert_eq!(sql, MYSQL_STATUS_QUERY);
+            Ok("Questions\t1523\nThreads_connected\t7\nThreads_running\t2\n".to_string())
+        });
+
+        assert!(warnings.is_empty());
+        assert_eq!(metrics.open, Some(7));
+        assert_eq!(metrics.active, Some(2));
+        assert_eq!(metrics.idle, Some(5));
+        assert_eq!(metrics.queries, Some(1523));
+        assert_eq!(metrics.rows()[0], ["Pool min", "1"]);
+        assert_eq!(metrics.rows()[1], ["Pool max", "N/A"]);
+    }
+
+    #[test]
+    fn test_postgres_partial_failure() {
+        let mut metrics = PoolMetrics::default();
+        let warnings = metrics.introspect("postgres", |sql| {
+            if sql == POSTGRES_QUERIES_QUERY {
+                Err(anyhow::anyhow!("permission denied"))
+            } else {
+                Ok("4\t1\t3\n".to_string())
+            }
+        });
+
+        assert_eq!(warnings, ["Could not read pg_stat_database: permission denied"]);
+        assert_eq!((metrics.open, metrics.active, metrics.idle), (Some(4), Some(1), Some(3)));
+        assert_eq!(metrics.rows()[5], ["Queries since stats reset", "N/A"]);
+    }
+
+    #[test]
+    fn test_sqlite_has_no_introspection() {
+        let mut metrics = PoolMetrics::default();
+        let warnings = metrics.introspect("sqlite", |_| panic!("sqlite should not be queried"));
+
+        assert!(warnings.is_empty());
+        assert_eq!(metrics, PoolMetrics::default());
+    }
+}
@@ -0,0 +1,338 @@
+//! Read-only schema introspection for `db schema`
+//!
+//! Tables come from `INFORMATION_SCHEMA` on MySQL, `pg_tables` and
+//! `pg_stat_user_tables` on PostgreSQL and `sqlite_master` on SQLite. Every
+//! query is a `SELECT` or `SHOW`, nothing is written to the database.
+
+use anyhow::Result;
+
+/// Output of `db schema`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum SchemaFormat {
+    /// Tables of tables, columns and indexes
+    #[default]
+    Table,
+    /// `CREATE TABLE` statements
+    Sql,
+}
+
+/// A table with its approximate row count and size in bytes
+#[derive(Debug, PartialEq)]
+pub struct TableInfo {
+    pub name: String,
+    pub rows: Option<u64>,
+    pub size: Option<u64>,
+}
+
+/// A column as shown by `db schema --table`
+#[derive(Debug, PartialEq)]
+pub struct ColumnInfo {
+    pub name: String,
+    pub data_type: String,
+    pub nullable: bool,
+    pub default: Option<String>,
+    pub key: String,
+}
+
+impl ColumnInfo {
+    /// SQL following the column name, e.g. `varchar(255) NOT NULL DEFAULT 'guest'`
+    pub fn definition(&self) -> String {
+        let mut definition = self.data_type.clone();
+        if !self.nullable {
+            definition.push_str(" NOT NULL");
+        }
+        if let Some(ref default) = self.default {
+            definition.push_str(&format!(" DEFAULT {}", default));
+        }
+        definition
+    }
+}
+
+/// An index with its columns in order
+#[derive(Debug, PartialEq)]
+pub struct IndexInfo {
+    pub name: String,
+    pub columns: String,
+    pub unique: bool,
+}
+
+/// Reject table names that would need quoting, so they can be embedded in queries
+pub fn validate_table_name(table: &str) -> Result<()> {
+    let valid = !table.is_empty() && table.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if !valid {
+        anyhow::bail!("Invalid table name: {}", table);
+    }
+    Ok(())
+}
+
+/// List the tables of the database, running SQL through `query`
+pub fn tables<F>(driver: &str, database: &str, query: F) -> Result<Vec<TableInfo>>
+where
+    F: Fn(&str) -> Result<String>,
+{
+    let sql = match driver {
+        "mysql" => format!(
+            "SELECT TABLE_NAME, TABLE_ROWS, DATA_LENGTH + INDEX_LENGTH FROM INFORMATION_SCHEMA.TABLES \
+             WHERE TABLE_SCHEMA = '{}' AND TABLE_TYPE = 'BASE TABLE' ORDER BY TABLE_NAME",
+            escape(database)
+        ),
+        "postgres" => "SELECT t.tablename, s.n_live_tup, \
+             pg_total_relation_size(quote_ident(t.schemaname) || '.' || quote_ident(t.tablename)) \
+             FROM pg_tables t LEFT JOIN pg_stat_user_tables s \
+             ON s.schemaname = t.schemaname AND s.relname = t.tablename \
+             WHERE t.schemaname = 'public' ORDER BY t.tablename"
+            .to_string(),
+        "sqlite" => "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name"
+            .to_string(),
+        driver => anyhow::bail!("Unsupported database driver: {}", driver),
+    };
+
+    let mut tables: Vec<TableInfo> = rows(&query(&sql)?)
+        .map(|columns| TableInfo {
+            name: columns[0].to_string(),
+            rows: columns.get(1).and_then(|rows| rows.parse().ok()),
+            size: columns.get(2).and_then(|size| size.parse().ok()),
+        })
+        .collect();
+
+    // SQLite keeps no statistics, count the rows instead
+    if driver == "sqlite" {
+        for table in &mut tables {
+            validate_table_name(&table.name)?;
+            let count = query(&format!("SELECT COUNT(*) FROM \"{}\"", table.name))?;
+            table.rows = count.trim().parse().ok();
+        }
+    }
+
+    Ok(tables)
+}
+
+/// Columns of a table in declaration order
+pub fn columns<F>(driver: &str, database: &str, table: &str, query: F) -> Result<Vec<ColumnInfo>>
+where
+    F: Fn(&str) -> Result<String>,
+{
+    validate_table_name(table)?;
+
+    let sql = match driver {
+        "mysql" => format!(
+            "SELECT COLUMN_NAME, COLUMN_TYPE, IS_NULLABLE, COALESCE(COLUMN_DEFAULT, ''), COLUMN_KEY \
+             FROM INFORMATION_SCHEMA.COLUMNS WHERE TABLE_SCHEMA = '{}' AND TABLE_NAME = '{}' ORDER BY ORDINAL_POSITION",
+            escape(database),
+            table
+        ),
+        "postgres" => format!(
+            "SELECT c.column_name, c.data_type, c.is_nullable, COALESCE(c.column_default, ''), \
+             COALESCE((SELECT string_agg(tc.constraint_type, ',') FROM information_schema.key_column_usage k \
+             JOIN information_schema.table_constraints tc \
+             ON tc.constraint_name = k.constraint_name AND tc.table_schema = k.table_schema \
+             WHERE k.table_schema = c.table_schema AND k.table_name = c.table_name AND k.column_name = c.column_name), '') \
+             FROM information_schema.columns c WHERE c.table_schema = 'public' AND c.table_name = '{}' \
+             ORDER BY c.ordinal_position",
+            table
+        ),
+        "sqlite" => format!(
+            "SELECT name, type, CASE WHEN \"notnull\" THEN 'NO' ELSE 'YES' END, COALESCE(dflt_value, ''), \
+             CASE WHEN pk THEN 'PRI' ELSE '' END FROM pragma_table_info('{}')",
+            table
+        ),
+        driver => anyhow::bail!("Unsupported database driver: {}", driver),
+    };
+
+    let columns: Vec<ColumnInfo> = rows(&query(&sql)?)
+        .map(|columns| ColumnInfo {
+            name: columns[0].to_string(),
+            data_type: columns.get(1).unwrap_or(&"").to_string(),
+            nullable: columns.get(2) == Some(&"YES"),
+            default: columns.get(3).filter(|value| !value.is_empty()).map(|value| value.to_string()),
+            key: columns.get(4).unwrap_or(&"").to_string(),
+        })
+        .collect();
+
+    if columns.is_empty() {
+        anyhow::bail!("Table '{}' not found", table);
+    }
+
+    Ok(columns)
+}
+
+/// Indexes of a table
+pub fn indexes<F>(driver: &str, database: &str, table: &str, query: F) -> Result<Vec<IndexInfo>>
+where
+    F: Fn(&str) -> Result<String>,
+{
+    validate_table_name(table)?;
+
+    let sql = match driver {
+        "mysql" => format!(
+            "SELECT INDEX_NAME, GROUP_CONCAT(COLUMN_NAME ORDER BY SEQ_IN_INDEX), IF(NON_UNIQUE = 0, 'YES', 'NO') \
+             FROM INFORMATION_SCHEMA.STATISTICS WHERE TABLE_SCHEMA = '{}' AND TABLE_NAME = '{}' \
+             GROUP BY INDEX_NAME, NON_UNIQUE ORDER BY INDEX_NAME",
+            escape(database),
+            table
+        ),
+        "postgres" => format!(
+            "SELECT i.relname, string_agg(a.attname, ',' ORDER BY array_position(ix.indkey::int2[], a.attnum)), \
+             CASE WHEN ix.indisunique THEN 'YES' ELSE 'NO' END \
+             FROM pg_index ix JOIN pg_class t ON t.oid = ix.indrelid JOIN pg_class i ON i.oid = ix.indexrelid \
+             JOIN pg_namespace n ON n.oid = t.relnamespace \
+             JOIN pg_attribute a ON a.attrelid = t.oid AND a.attnum = ANY(ix.indkey) \
+             WHERE n.nspname = 'public' AND t.relname = '{}' GROUP BY i.relname, ix.indisunique ORDER BY i.relname",
+            table
+        ),
+        "sqlite" => format!(
+            "SELECT il.name, group_concat(ii.name, ','), CASE WHEN il.\"unique\" THEN 'YES' ELSE 'NO' END \
+             FROM pragma_index_list('{}') il, pragma_index_info(il.name) ii GROUP BY il.name ORDER BY il.name",
+            table
+        ),
+        driver => anyhow::bail!("Unsupported database driver: {}", driver),
+    };
+
+    Ok(rows(&query(&sql)?)
+        .map(|columns| IndexInfo {
+            name: columns[0].to_string(),
+            columns: columns.get(1).unwrap_or(&"").to_string(),
+            unique: columns.get(2) == Some(&"YES"),
+        })
+        .collect())
+}
+
+/// `CREATE TABLE` statement of a table
+///
+/// MySQL and SQLite report the statement they store, PostgreSQL has no
+/// equivalent so it is rebuilt from the columns.
+pub fn create_table<F>(driver: &str, database: &str, table: &str, query: F) -> Result<String>
+where
+    F: Fn(&str) -> Result<String>,
+{
+    validate_table_name(table)?;
+
+    match driver {
+        "mysql" => {
+            let output = query(&format!("SHOW CREATE TABLE `{}`", table))?;
+            let (_, statement) = output
+                .trim_end()
+                .split_once('\t')
+                .ok_or_else(|| anyhow::anyhow!("Table '{}' not found", table))?;
+            // Batch mode escapes the newlines of the statement
+            Ok(format!("{};", statement.replace("\\n", "\n")))
+        }
+        "sqlite" => {
+            let output = query(&format!("SELECT sql FROM sqlite_master WHERE type = 'table' AND name = '{}'", table))?;
+            if output.trim().is_empty() {
+                anyhow::bail!("Table '{}' not found", table);
+            }
+            Ok(format!("{};", output.trim()))
+        }
+        _ => {
+            let columns = columns(driver, database, table, query)?;
+            Ok(columns_to_ddl(table, &columns))
+        }
+    }
+}
+
+/// Rebuild a `CREATE TABLE` statement from introspected columns
+fn columns_to_ddl(table: &str, columns: &[ColumnInfo]) -> String {
+    let mut definitions: Vec<String> = columns
+        .iter()
+        .map(|column| format!("    {} {}", column.name, column.definition()))
+        .collect();
+
+    let primary: Vec<&str> = columns
+        .iter()
+        .filter(|column| column.key.split(',').any(|key| key == "PRIMARY KEY" || key == "PRI"))
+        .map(|column| column.name.as_str())
+        .collect();
+    if !primary.is_empty() {
+        definitions.push(format!("    PRIMARY KEY ({})", primary.join(", ")));
+    }
+
+    format!("CREATE TABLE {} (\n{}\n);", table, definitions.join(",\n"))
+}
+
+/// Non-empty tab-separated rows of a client's output
+fn rows(output: &str) -> impl Iterator<Item = Vec<&str>> {
+    output
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.split('\t').map(str::trim).collect())
+}
+
+/// Escape a string for use inside a single-quoted SQL literal
+fn escape(value: &str) -> String {
+    value.replace('\'', "''")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mysql_tables() {
+        let query = |sql: &str| {
+            assert!(sql.starts_with("SELECT TABLE_NAME") && sql.contains("TABLE_SCHEMA = 'shop'"));
+            Ok("posts\t120\t65536\nusers\tNULL\tNULL\n".to_string())
+        };
+
+        assert_eq!(
+            tables("mysql", "shop", query).unwrap(),
+            [
+                TableInfo { name: "posts".to_string(), rows: Some(120), size: Some(65536) },
+                TableInfo { name: "users".to_string(), rows: None, size: None },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sqlite_tables_are_counted() {
+        let query = |sql: &str| match sql {
+            "SELECT COUNT(*) FROM \"users\"" => Ok("3\n".to_string()),
+            sql if sql.contains("sqlite_master") => Ok("users\n".to_string()),
+            sql => panic!("unexpected query: {}", sql),
+        };
+
+        assert_eq!(tables("sqlite", "db.sqlite", query).unwrap(), [TableInfo { name: "users".to_string(), rows: Some(3), size: None }]);
+    }
+
+    #[test]
+    fn test_columns_and_postgres_ddl() {
+        let query = |_: &str| Ok("id\tbigint\tNO\tnextval('users_id_seq'::regclass)\tPRIMARY KEY\nemail\tcharacter varying\tYES\t\t\n".to_string());
+
+        let users = columns("postgres", "shop", "users", query).unwrap();
+        assert_eq!(
+            users[1],
+            ColumnInfo {
+                name: "email".to_string(),
+                data_type: "character varying".to_string(),
+                nullable: true,
+                default: None,
+                key: String::new(),
+            }
+        );
+        assert_eq!(
+            create_table("postgres", "shop", "users", query).unwrap(),
+            "CREATE TABLE users (\n    id bigint NOT NULL DEFAULT nextval('users_id_seq'::regclass),\n    email character varying,\n    PRIMARY KEY (id)\n);"
+        );
+        assert!(columns("postgres", "shop", "missing", |_: &str| Ok(String::new())).is_err());
+    }
+
+    #[test]
+    fn test_mysql_create_table_and_indexes() {
+        let ddl = create_table("mysql", "shop", "users", |sql: &str| {
+            assert_eq!(sql, "SHOW CREATE TABLE `users`");
+            Ok("users\tCREATE TABLE `users` (\\n  `id` int NOT NULL\\n)\n".to_string())
+        })
+        .unwrap();
+        assert_eq!(ddl, "CREATE TABLE `users` (\n  `id` int NOT NULL\n);");
+
+        let indexes = indexes("mysql", "shop", "users", |_: &str| Ok("PRIMARY\tid\tYES\nidx_name\tlast,first\tNO\n".to_string())).unwrap();
+        assert_eq!(indexes[1], IndexInfo { name: "idx_name".to_string(), columns: "last,first".to_string(), unique: false });
+    }
+
+    #[test]
+    fn test_validate_table_name() {
+        assert!(validate_table_name("user_roles").is_ok());
+        assert!(validate_table_name("users; DROP TABLE users").is_err());
+        assert!(validate_table_name("").is_err());
+    }
+}
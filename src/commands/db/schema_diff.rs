@@ -0,0 +1,333 @@
+//! Diff a desired schema file against the current database schema
+//!
+//! The schema file is read with a small regex-based lexer that understands
+//! `CREATE TABLE` and `CREATE INDEX` statements. Anything else, such as
+//! foreign keys or views, is carried into the migration as a TODO comment
+//! instead of being guessed at.
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+use super::schema::{ColumnInfo, IndexInfo};
+
+static LINE_COMMENT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"--[^\n]*").unwrap());
+static BLOCK_COMMENT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?s)/\*.*?\*/").unwrap());
+static CREATE_TABLE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?is)^CREATE\s+TABLE\s+(?:IF\s+NOT\s+EXISTS\s+)?([`"\w.]+)\s*\((.*)\)[^)]*$"#).unwrap()
+});
+static CREATE_INDEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?is)^CREATE\s+(UNIQUE\s+)?INDEX\s+(?:IF\s+NOT\s+EXISTS\s+)?([`"\w]+)\s+ON\s+([`"\w.]+)\s*\((.*)\)$"#)
+        .unwrap()
+});
+static TABLE_INDEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?is)^(UNIQUE\s+)?(?:KEY|INDEX)\s+([`"\w]+)\s*\((.*)\)$"#).unwrap());
+static PRIMARY_KEY: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)^PRIMARY\s+KEY\b").unwrap());
+static COLUMN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"(?s)^[`"]?(\w+)[`"]?\s+(.+)$"#).unwrap());
+
+/// A column and the SQL following its name, e.g. `VARCHAR(255) NOT NULL`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnDefinition {
+    pub name: String,
+    pub definition: String,
+}
+
+impl From<ColumnInfo> for ColumnDefinition {
+    fn from(column: ColumnInfo) -> Self {
+        Self { definition: column.definition(), name: column.name }
+    }
+}
+
+/// Columns and indexes of a table, either parsed or introspected
+#[derive(Debug, Default, PartialEq)]
+pub struct TableDefinition {
+    pub name: String,
+    pub columns: Vec<ColumnDefinition>,
+    pub indexes: Vec<IndexInfo>,
+}
+
+/// Tables declared by a schema file and the statements it could not read
+#[derive(Debug, Default)]
+pub struct SchemaFile {
+    /// Each table with the `CREATE TABLE` statement declaring it
+    pub tables: Vec<(TableDefinition, String)>,
+    /// Unsupported SQL and the table it belongs to, if any
+    pub unsupported: Vec<(Option<String>, String)>,
+}
+
+/// Parse the `CREATE TABLE` and `CREATE INDEX` statements of a schema file
+pub fn parse_schema_file(sql: &str) -> SchemaFile {
+    let sql = BLOCK_COMMENT.replace_all(sql, "");
+    let sql = LINE_COMMENT.replace_all(&sql, "");
+    let mut schema = SchemaFile::default();
+
+    for statement in split_top_level(&sql, ';') {
+        if let Some(captures) = CREATE_TABLE.captures(&statement) {
+            let name = unquote(&captures[1]);
+            let mut table = TableDefinition { name: name.clone(), ..Default::default() };
+
+            for item in split_top_level(&captures[2], ',') {
+                if PRIMARY_KEY.is_match(&item) {
+                    continue;
+                }
+                if let Some(index) = TABLE_INDEX.captures(&item) {
+                    table.indexes.push(IndexInfo {
+                        name: unquote(&index[2]),
+                        columns: index_columns(&index[3]),
+                        unique: index.get(1).is_some(),
+                    });
+                } else if let Some(column) = COLUMN.captures(&item).filter(|_| !is_constraint(&item)) {
+                    table.columns.push(ColumnDefinition {
+                        name: column[1].to_string(),
+                        definition: column[2].split_whitespace().collect::<Vec<_>>().join(" "),
+                    });
+                } else {
+                    schema.unsupported.push((Some(name.clone()), item));
+                }
+            }
+
+            schema.tables.push((table, statement));
+        } else if let Some(captures) = CREATE_INDEX.captures(&statement) {
+            let index = IndexInfo {
+                name: unquote(&captures[2]),
+                columns: index_columns(&captures[4]),
+                unique: captures.get(1).is_some(),
+            };
+            let table = unquote(&captures[3]);
+            match schema.tables.iter_mut().find(|(existing, _)| existing.name == table) {
+                Some((existing, _)) => existing.indexes.push(index),
+                None => schema.unsupported.push((Some(table), statement)),
+            }
+        } else {
+            schema.unsupported.push((None, statement));
+        }
+    }
+
+    schema
+}
+
+/// Statements turning the current schema into the desired one, and reverting it
+#[derive(Debug, Default, PartialEq)]
+pub struct SchemaDiff {
+    pub up: Vec<String>,
+    pub down: Vec<String>,
+}
+
+impl SchemaDiff {
+    /// Body of the migration's `up()`
+    pub fn up_body(&self) -> String {
+        Self::body(&self.up)
+    }
+
+    /// Body of the migration's `down()`, undoing `up()` in reverse order
+    pub fn down_body(&self) -> String {
+        Self::body(&self.down.iter().rev().cloned().collect::<Vec<_>>())
+    }
+
+    fn body(lines: &[String]) -> String {
+        let lines: String = lines.iter().map(|line| format!("        {}\n", line)).collect();
+        format!("{}        Ok(())", lines)
+    }
+
+    fn statement(sql: &str) -> String {
+        format!("schema.statement({:?})?;", sql)
+    }
+
+    fn push(&mut self, up: &str, down: &str) {
+        self.up.push(Self::statement(up));
+        self.down.push(Self::statement(down));
+    }
+}
+
+/// Compare the desired schema with the current tables
+///
+/// Tables missing from the database are created, columns are added and
+/// dropped and missing indexes are added. Tables absent from the schema file
+/// are left alone so a file may describe only part of the database.
+pub fn diff_schema(driver: &str, desired: &SchemaFile, current: &[TableDefinition]) -> SchemaDiff {
+    let mut diff = SchemaDiff::default();
+
+    for (table, statement) in &desired.tables {
+        let Some(existing) = current.iter().find(|existing| existing.name.eq_ignore_ascii_case(&table.name)) else {
+            diff.push(statement, &format!("DROP TABLE IF EXISTS {}", table.name));
+            continue;
+        };
+
+        for column in &table.columns {
+            if !existing.columns.iter().any(|existing| existing.name.eq_ignore_ascii_case(&column.name)) {
+                diff.push(
+                    &format!("ALTER TABLE {} ADD COLUMN {} {}", table.name, column.name, column.definition),
+                    &format!("ALTER TABLE {} DROP COLUMN {}", table.name, column.name),
+                );
+            }
+        }
+
+        for index in &table.indexes {
+            if !existing.indexes.iter().any(|existing| existing.name.eq_ignore_ascii_case(&index.name)) {
+                diff.push(&add_index(driver, &table.name, index), &drop_index(driver, &table.name, &index.name));
+            }
+        }
+
+        for column in &existing.columns {
+            if !table.columns.iter().any(|desired| desired.name.eq_ignore_ascii_case(&column.name)) {
+                diff.push(
+                    &format!("ALTER TABLE {} DROP COLUMN {}", table.name, column.name),
+                    &format!("ALTER TABLE {} ADD COLUMN {} {}", table.name, column.name, column.definition),
+                );
+            }
+        }
+
+        for (_, sql) in desired.unsupported.iter().filter(|(name, _)| name.as_ref() == Some(&table.name)) {
+            diff.up.push(todo(sql));
+        }
+    }
+
+    for (table, sql) in &desired.unsupported {
+        let known = table.as_ref().is_some_and(|table| desired.tables.iter().any(|(desired, _)| &desired.name == table));
+        if !known {
+            diff.up.push(todo(sql));
+        }
+    }
+
+    diff
+}
+
+fn add_index(driver: &str, table: &str, index: &IndexInfo) -> String {
+    let unique = if index.unique { "UNIQUE " } else { "" };
+    let columns = index.columns.replace(',', ", ");
+    match driver {
+        "mysql" => format!("ALTER TABLE {} ADD {}INDEX {} ({})", table, unique, index.name, columns),
+        _ => format!("CREATE {}INDEX {} ON {} ({})", unique, index.name, table, columns),
+    }
+}
+
+fn drop_index(driver: &str, table: &str, index: &str) -> String {
+    match driver {
+        "mysql" => format!("ALTER TABLE {} DROP INDEX {}", table, index),
+        _ => format!("DROP INDEX {}", index),
+    }
+}
+
+fn todo(sql: &str) -> String {
+    format!("// TODO: manually implement: {}", sql.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+/// Table items that look like columns but declare constraints
+fn is_constraint(item: &str) -> bool {
+    let keyword = item.split_whitespace().next().unwrap_or_default().to_ascii_uppercase();
+    matches!(keyword.as_str(), "CONSTRAINT" | "FOREIGN" | "CHECK" | "UNIQUE" | "FULLTEXT" | "SPATIAL")
+}
+
+/// Index columns as `a,b`, without quotes, lengths or sort order
+fn index_columns(columns: &str) -> String {
+    split_top_level(columns, ',')
+        .iter()
+        .filter_map(|column| column.split(['(', ' ']).next().map(unquote))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn unquote(identifier: &str) -> String {
+    identifier.trim().trim_matches(['`', '"']).to_string()
+}
+
+/// Split on `separator` outside parentheses and quotes, dropping empty parts
+fn split_top_level(sql: &str, separator: char) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+
+    for ch in sql.chars() {
+        match (quote, ch) {
+            (Some(open), ch) if ch == open => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"' | '`') => quote = Some(ch),
+            (None, '(') => depth += 1,
+            (None, ')') => depth = depth.saturating_sub(1),
+            (None, ch) if ch == separator && depth == 0 => {
+                parts.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(ch);
+    }
+    parts.push(current);
+
+    parts.into_iter().map(|part| part.trim().to_string()).filter(|part| !part.is_empty()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn baseline() -> Vec<TableDefinition> {
+        vec![TableDefinition {
+            name: "users".to_string(),
+            columns: vec![
+                ColumnDefinition { name: "id".to_string(), definition: "bigint NOT NULL".to_string() },
+                ColumnDefinition { name: "email".to_string(), definition: "varchar(255) NOT NULL".to_string() },
+                ColumnDefinition { name: "legacy_token".to_string(), definition: "varchar(64)".to_string() },
+            ],
+            indexes: vec![IndexInfo { name: "PRIMARY".to_string(), columns: "id".to_string(), unique: true }],
+        }]
+    }
+
+    #[test]
+    fn test_parse_schema_file() {
+        let schema = parse_schema_file(include_str!("../../../tests/fixtures/schema/desired.sql"));
+
+        let (users, _) = &schema.tables[0];
+        assert_eq!(users.name, "users");
+        assert_eq!(
+            users.columns.iter().map(|column| column.name.as_str()).collect::<Vec<_>>(),
+            ["id", "email", "name", "avatar"]
+        );
+        assert_eq!(users.columns[3].definition, "VARCHAR(255) NULL DEFAULT 'default.png'");
+        assert_eq!(
+            users.indexes,
+            [
+                IndexInfo { name: "users_email_unique".to_string(), columns: "email".to_string(), unique: true },
+                IndexInfo { name: "users_name_index".to_string(), columns: "name".to_string(), unique: false },
+            ]
+        );
+        assert_eq!(schema.tables[1].0.name, "posts");
+        assert_eq!(schema.unsupported.len(), 2);
+    }
+
+    #[test]
+    fn test_diff_schema_against_baseline() {
+        let schema = parse_schema_file(include_str!("../../../tests/fixtures/schema/desired.sql"));
+        let diff = diff_schema("mysql", &schema, &baseline());
+        let up = diff.up_body();
+        let down = diff.down_body();
+
+        assert!(up.contains(r#"schema.statement("ALTER TABLE users ADD COLUMN name VARCHAR(100) NOT NULL")?;"#));
+        assert!(up.contains(r#"schema.statement("ALTER TABLE users ADD COLUMN avatar VARCHAR(255) NULL DEFAULT 'default.png'")?;"#));
+        assert!(up.contains(r#"schema.statement("ALTER TABLE users ADD UNIQUE INDEX users_email_unique (email)")?;"#));
+        assert!(up.contains(r#"schema.statement("ALTER TABLE users DROP COLUMN legacy_token")?;"#));
+        assert!(up.contains("schema.statement(\"CREATE TABLE posts ("));
+        // Constraints of new tables are created with the table itself
+        assert!(!up.contains("// TODO: manually implement: CONSTRAINT"));
+        assert!(up.contains("// TODO: manually implement: CREATE VIEW active_users AS SELECT * FROM users"));
+
+        assert!(down.starts_with(r#"        schema.statement("DROP TABLE IF EXISTS posts")?;"#));
+        assert!(down.contains(r#"schema.statement("ALTER TABLE users ADD COLUMN legacy_token varchar(64)")?;"#));
+        assert!(down.contains(r#"schema.statement("ALTER TABLE users DROP INDEX users_email_unique")?;"#));
+        assert!(down.ends_with("        Ok(())"));
+    }
+
+    #[test]
+    fn test_unchanged_schema_has_empty_diff() {
+        let schema = parse_schema_file("CREATE TABLE users (id bigint NOT NULL, email varchar(255) NOT NULL, legacy_token varchar(64));");
+        assert_eq!(diff_schema("mysql", &schema, &baseline()), SchemaDiff::default());
+    }
+
+    #[test]
+    fn test_split_top_level_ignores_nested_separators() {
+        assert_eq!(
+            split_top_level("a DECIMAL(8,2), b VARCHAR(10) DEFAULT 'x,y', c INT", ','),
+            ["a DECIMAL(8,2)", "b VARCHAR(10) DEFAULT 'x,y'", "c INT"]
+        );
+    }
+}
@@ -1,29 +1,72 @@
 //! Deploy command implementations for the Rustisan CLI
 
+mod history;
+mod kubernetes;
+mod ssh;
+
 use anyhow::Result;
 use colored::*;
+use std::path::Path;
+use std::time::Duration;
 use super::CommandUtils;
+use crate::utils::{ProcessUtils, TextUtils};
+use history::DeploymentRecord;
+use kubernetes::ManifestOptions;
+use ssh::{shell_command, SshTarget};
+
+pub use ssh::FingerprintCheck;
+
+/// Default timeout for the pre-deployment test run
+const TEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default timeout for the post-deployment health check
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Number of history records listed before a rollback
+const HISTORY_DISPLAY_LIMIT: usize = 10;
+
+/// Directory the files synced to deployment servers are staged in
+const DEPLOY_OUTPUT_DIR: &str = "target/deploy";
+
+/// Flags of the deploy command
+#[derive(Debug, Default)]
+pub struct DeployOptions {
+    pub skip_build: bool,
+    pub dry_run: bool,
+    pub timeout: Option<u64>,
+    pub rollback: bool,
+    pub version: Option<String>,
+    pub force: bool,
+    pub ssh_fingerprint_check: FingerprintCheck,
+}
 
 /// Handle deploy command
-pub async fn handle(target: Option<String>, skip_build: bool, dry_run: bool) -> Result<()> {
+pub async fn handle(target: Option<String>, options: DeployOptions) -> Result<()> {
     CommandUtils::ensure_rustisan_project()?;
 
     let deployment_target = target.unwrap_or_else(|| "production".to_string());
 
+    if options.rollback {
+        return rollback_deployment(&deployment_target, &options).await;
+    }
+
     CommandUtils::info(&format!("Deploying to: {}", deployment_target));
 
-    if dry_run {
+    if options.dry_run {
         CommandUtils::info("Dry run mode - no actual deployment will occur");
     }
 
-    if skip_build {
+    if options.skip_build {
         CommandUtils::info("Skipping build step");
     }
 
-    deploy_application(&deployment_target, skip_build, dry_run).await
+    deploy_application(&deployment_target, &options).await
 }
 
-async fn deploy_application(target: &str, skip_build: bool, dry_run: bool) -> Result<()> {
+async fn deploy_application(target: &str, options: &DeployOptions) -> Result<()> {
+    let dry_run = options.dry_run;
+    let timeout = options.timeout.map(Duration::from_secs);
+
     // Load deployment configuration
     let deploy_config = load_deployment_config(target)?;
 
@@ -32,20 +75,22 @@ async fn deploy_application(target: &str, skip_build: bool, dry_run: bool) -> Re
     run_pre_deployment_checks(&deploy_config).await?;
 
     // Build application if not skipped
-    if !skip_build {
+    if !options.skip_build {
         CommandUtils::info("Building application for deployment...");
         build_for_deployment().await?;
     }
 
     // Run tests before deployment
     CommandUtils::info("Running tests...");
-    run_deployment_tests().await?;
+    run_deployment_tests(timeout.unwrap_or(TEST_TIMEOUT)).await?;
+
+    let version = deployment_version();
 
     // Deploy based on target type
     match deploy_config.deployment_type.as_str() {
-        "docker" => deploy_docker(&deploy_config, dry_run).await?,
+        "docker" => deploy_docker(&deploy_config, &version, dry_run).await?,
         "kubernetes" => deploy_kubernetes(&deploy_config, dry_run).await?,
-        "server" => deploy_server(&deploy_config, dry_run).await?,
+        "server" => deploy_server(&deploy_config, options.ssh_fingerprint_check, dry_run).await?,
         "cloud" => deploy_cloud(&deploy_config, dry_run).await?,
         _ => {
             CommandUtils::error(&format!("Unknown deployment type: {}", deploy_config.deployment_type));
@@ -56,7 +101,7 @@ async fn deploy_application(target: &str, skip_build: bool, dry_run: bool) -> Re
     // Post-deployment tasks
     if !dry_run {
         CommandUtils::info("Running post-deployment tasks...");
-        run_post_deployment_tasks(&deploy_config).await?;
+        run_post_deployment_tasks(&deploy_config, target, &version, timeout.unwrap_or(HEALTH_CHECK_TIMEOUT)).await?;
     }
 
     CommandUtils::success("Deployment completed successfully");
@@ -71,12 +116,23 @@ struct DeploymentConfig {
     port: Option<u16>,
     user: Option<String>,
     path: Option<String>,
+    ssh_key_path: Option<String>,
+    ssh_known_hosts: Option<String>,
+    ssh_port: Option<u16>,
     docker_image: Option<String>,
     kubernetes_namespace: Option<String>,
     cloud_provider: Option<String>,
+    aws_region: Option<String>,
+    aws_account_id: Option<String>,
+    aws_cluster: Option<String>,
+    gcp_project: Option<String>,
+    gcp_region: Option<String>,
+    azure_resource_group: Option<String>,
+    azure_app: Option<String>,
     environment_variables: Option<std::collections::HashMap<String, String>>,
     pre_deploy_commands: Option<Vec<String>>,
     post_deploy_commands: Option<Vec<String>>,
+    health_check_url: Option<String>,
 }
 
 impl Default for DeploymentConfig {
@@ -87,12 +143,23 @@ impl Default for DeploymentConfig {
             port: Some(22),
             user: None,
             path: None,
+            ssh_key_path: None,
+            ssh_known_hosts: None,
+            ssh_port: None,
             docker_image: None,
             kubernetes_namespace: None,
             cloud_provider: None,
+            aws_region: None,
+            aws_account_id: None,
+            aws_cluster: None,
+            gcp_project: None,
+            gcp_region: None,
+            azure_resource_group: None,
+            azure_app: None,
             environment_variables: None,
             pre_deploy_commands: None,
             post_deploy_commands: None,
+            health_check_url: None,
         }
     }
 }
@@ -158,20 +225,12 @@ async fn build_for_deployment() -> Result<()> {
     Ok(())
 }
 
-async fn run_deployment_tests() -> Result<()> {
-    let output = std::process::Command::new("cargo")
-        .args(&["test", "--release"])
-        .output()?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        anyhow::bail!("Tests failed: {}", stderr);
-    }
-
-    Ok(())
+async fn run_deployment_tests(timeout: Duration) -> Result<()> {
+    ProcessUtils::execute_with_timeout("cargo", &["test", "--release"], timeout)
+        .map_err(|error| anyhow::anyhow!("Tests failed: {}", error))
 }
 
-async fn deploy_docker(config: &DeploymentConfig, dry_run: bool) -> Result<()> {
+async fn deploy_docker(config: &DeploymentConfig, version: &str, dry_run: bool) -> Result<()> {
     CommandUtils::info("Deploying via Docker...");
 
     let image_name = config.docker_image.as_ref()
@@ -185,6 +244,14 @@ async fn deploy_docker(config: &DeploymentConfig, dry_run: bool) -> Result<()> {
         run_shell_command(&build_cmd).await?;
     }
 
+    // Keep a tag per version so the deployment can be rolled back
+    let version_tag_cmd = format!("docker tag {} {}", image_name, versioned_image(image_name, version));
+    CommandUtils::info(&format!("Tagging version: {}", version_tag_cmd));
+
+    if !dry_run {
+        run_shell_command(&version_tag_cmd).await?;
+    }
+
     // Push to registry (if configured)
     if let Some(registry) = std::env::var("DOCKER_REGISTRY").ok() {
         let tag_cmd = format!("docker tag {} {}/{}", image_name, registry, image_name);
@@ -211,8 +278,15 @@ async fn deploy_kubernetes(config: &DeploymentConfig, dry_run: bool) -> Result<(
     let namespace = config.kubernetes_namespace.as_ref()
         .unwrap_or(&default_namespace);
 
+    let manifest_dir = Path::new(kubernetes::MANIFEST_DIR);
+    if !manifest_dir.exists() {
+        CommandUtils::info("No k8s/ directory found, generating manifests...");
+        let options = ManifestOptions::from_config("rustisan", 2, config.docker_image.clone(), &load_app_config()?);
+        kubernetes::write(manifest_dir, &options, false)?;
+    }
+
     // Apply Kubernetes manifests
-    let apply_cmd = format!("kubectl apply -f k8s/ -n {}", namespace);
+    let apply_cmd = format!("kubectl apply -f {}/ -n {}", kubernetes::MANIFEST_DIR, namespace);
     CommandUtils::info(&format!("Applying manifests: {}", apply_cmd));
 
     if !dry_run {
@@ -220,7 +294,11 @@ async fn deploy_kubernetes(config: &DeploymentConfig, dry_run: bool) -> Result<(
     }
 
     // Check deployment status
-    let status_cmd = format!("kubectl rollout status deployment/rustisan -n {}", namespace);
+    let status_cmd = format!(
+        "kubectl rollout status deployment/{} -n {}",
+        kubernetes::deployment_name(manifest_dir),
+        namespace
+    );
     CommandUtils::info(&format!("Checking status: {}", status_cmd));
 
     if !dry_run {
@@ -232,38 +310,130 @@ async fn deploy_kubernetes(config: &DeploymentConfig, dry_run: bool) -> Result<(
     Ok(())
 }
 
-async fn deploy_server(config: &DeploymentConfig, dry_run: bool) -> Result<()> {
+/// Generate Kubernetes manifests in k8s/
+pub async fn make_k8s(name: String, replicas: u32, image: Option<String>, force: bool) -> Result<()> {
+    CommandUtils::ensure_rustisan_project()?;
+
+    // Fall back to the production image so `deploy` applies what it builds
+    let image = image.or_else(|| {
+        let path = Path::new("deploy/production.toml");
+        let content = std::fs::read_to_string(path).ok()?;
+        toml::from_str::<DeploymentConfig>(&content).ok()?.docker_image
+    });
+
+    let options = ManifestOptions::from_config(&name, replicas, image, &load_app_config()?);
+    let (written, skipped) = kubernetes::write(Path::new(kubernetes::MANIFEST_DIR), &options, force)?;
+
+    for path in &skipped {
+        CommandUtils::warning(&format!("{} already exists, skipping", path.display()));
+    }
+    for path in &written {
+        CommandUtils::success(&format!("Created {}", path.display()));
+    }
+
+    if !written.is_empty() {
+        CommandUtils::info(&format!("Apply with: kubectl apply -f {}/", kubernetes::MANIFEST_DIR));
+    }
+
+    Ok(())
+}
+
+/// Read rustisan.toml, or an empty table when it cannot be parsed
+fn load_app_config() -> Result<toml::Value> {
+    let content = std::fs::read_to_string("rustisan.toml")?;
+    Ok(toml::from_str(&content).unwrap_or_else(|_| toml::Value::Table(Default::default())))
+}
+
+async fn deploy_server(config: &DeploymentConfig, fingerprint_check: FingerprintCheck, dry_run: bool) -> Result<()> {
     CommandUtils::info("Deploying to server...");
 
-    let host = config.host.as_ref()
-        .ok_or_else(|| anyhow::anyhow!("Server host not specified"))?;
-    let user = config.user.as_ref()
-        .ok_or_else(|| anyhow::anyhow!("Server user not specified"))?;
+    let ssh = SshTarget::from_config(config, fingerprint_check)?;
     let path = config.path.as_ref()
         .ok_or_else(|| anyhow::anyhow!("Server path not specified"))?;
 
-    // Copy binary to server
-    let binary_path = "target/release/rustisan";
-    let scp_cmd = format!("scp {} {}@{}:{}/", binary_path, user, host, path);
-    CommandUtils::info(&format!("Copying binary: {}", scp_cmd));
+    // Fail early if the server cannot be reached without a password prompt
+    let preflight_cmd = shell_command("ssh", &ssh.preflight_args());
+    CommandUtils::info(&format!("Checking SSH access: {}", preflight_cmd));
+
+    if !dry_run && run_shell_command(&preflight_cmd).await.is_err() {
+        anyhow::bail!(
+            "Could not authenticate to {}@{}:{}. Check that ssh_key_path points to a key the server accepts \
+             and that the host key matches (see --ssh-fingerprint-check)",
+            ssh.user,
+            ssh.host,
+            ssh.port
+        );
+    }
 
+    // Keep the running binary so the deployment can be rolled back
+    let backup_cmd = shell_command(
+        "ssh",
+        &ssh.ssh_args(&format!("[ ! -f {}/rustisan ] || cp {}/rustisan {}/rustisan.prev", path, path, path)),
+    );
+    CommandUtils::info(&format!("Keeping previous binary: {}", backup_cmd));
+
+    if !dry_run {
+        run_shell_command(&backup_cmd).await?;
+    }
+
+    // Copy the binary, config cache and public assets to the server
     if !dry_run {
-        run_shell_command(&scp_cmd).await?;
+        super::build::copy_to_output(DEPLOY_OUTPUT_DIR, "release").await?;
     }
+    sync_deployment_files(&ssh, DEPLOY_OUTPUT_DIR, path, dry_run).await?;
 
     // Restart service
-    let restart_cmd = format!("ssh {}@{} 'sudo systemctl restart rustisan'", user, host);
+    let restart_cmd = shell_command("ssh", &ssh.ssh_args("sudo systemctl restart rustisan"));
     CommandUtils::info(&format!("Restarting service: {}", restart_cmd));
 
     if !dry_run {
         run_shell_command(&restart_cmd).await?;
     }
 
+    // Run post-deployment commands on the server
+    if let Some(ref commands) = config.post_deploy_commands {
+        for command in commands {
+            let remote_cmd = shell_command("ssh", &ssh.ssh_args(&format!("cd {} && {}", path, command)));
+            CommandUtils::info(&format!("Running post-deploy command: {}", remote_cmd));
+
+            if !dry_run {
+                run_shell_command(&remote_cmd).await?;
+            }
+        }
+    }
+
     CommandUtils::success("Server deployment completed");
 
     Ok(())
 }
 
+/// Copy the contents of `local_dir` to `remote_dir`, using rsync when available
+async fn sync_deployment_files(ssh: &SshTarget, local_dir: &str, remote_dir: &str, dry_run: bool) -> Result<()> {
+    let sync_cmd = if ProcessUtils::command_exists("rsync") {
+        shell_command("rsync", &ssh.rsync_args(local_dir, remote_dir))
+    } else {
+        CommandUtils::warning("rsync not found, falling back to scp");
+
+        let mut sources: Vec<String> = match std::fs::read_dir(local_dir) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path().display().to_string())
+                .collect(),
+            Err(_) => vec![format!("{}/rustisan", local_dir)],
+        };
+        sources.sort();
+        shell_command("scp", &ssh.scp_args(&sources, remote_dir))
+    };
+
+    CommandUtils::info(&format!("Syncing files: {}", sync_cmd));
+
+    if !dry_run {
+        run_shell_command(&sync_cmd).await?;
+    }
+
+    Ok(())
+}
+
 async fn deploy_cloud(config: &DeploymentConfig, dry_run: bool) -> Result<()> {
     CommandUtils::info("Deploying to cloud...");
 
@@ -287,56 +457,134 @@ async fn deploy_cloud(config: &DeploymentConfig, dry_run: bool) -> Result<()> {
 
 async fn deploy_aws(config: &DeploymentConfig, dry_run: bool) -> Result<()> {
     CommandUtils::info("Deploying to AWS...");
-
-    // TODO: Implement AWS deployment logic
-    // This could involve:
-    // - Building and pushing to ECR
-    // - Updating ECS service
-    // - Deploying to Lambda
-    // - Updating Elastic Beanstalk
-
-    if !dry_run {
-        CommandUtils::info("AWS deployment would be executed here");
-    }
-
-    Ok(())
+    run_deployment_steps(&aws_commands(config)?, dry_run).await
 }
 
 async fn deploy_gcp(config: &DeploymentConfig, dry_run: bool) -> Result<()> {
     CommandUtils::info("Deploying to Google Cloud Platform...");
+    run_deployment_steps(&gcp_commands(config)?, dry_run).await
+}
 
-    // TODO: Implement GCP deployment logic
-    // This could involve:
-    // - Building and pushing to GCR
-    // - Updating Cloud Run service
-    // - Deploying to App Engine
-    // - Updating GKE deployment
+async fn deploy_azure(config: &DeploymentConfig, dry_run: bool) -> Result<()> {
+    CommandUtils::info("Deploying to Microsoft Azure...");
+    run_deployment_steps(&azure_commands(config)?, dry_run).await
+}
 
-    if !dry_run {
-        CommandUtils::info("GCP deployment would be executed here");
+/// A described shell command run as part of a deployment
+type DeploymentStep = (&'static str, String);
+
+/// Run each step in order, or only print it in dry run mode
+async fn run_deployment_steps(steps: &[DeploymentStep], dry_run: bool) -> Result<()> {
+    for (description, command) in steps {
+        if dry_run {
+            CommandUtils::info(&format!("{} (dry run): {}", description, command));
+        } else {
+            CommandUtils::info(&format!("{}: {}", description, command));
+            run_shell_command(command).await?;
+        }
     }
 
     Ok(())
 }
 
-async fn deploy_azure(config: &DeploymentConfig, dry_run: bool) -> Result<()> {
-    CommandUtils::info("Deploying to Microsoft Azure...");
-
-    // TODO: Implement Azure deployment logic
-    // This could involve:
-    // - Building and pushing to ACR
-    // - Updating Container Instances
-    // - Deploying to App Service
-    // - Updating AKS deployment
+fn required<'a>(value: &'a Option<String>, key: &str) -> Result<&'a str> {
+    value
+        .as_deref()
+        .ok_or_else(|| anyhow::anyhow!("'{}' not specified in deployment config", key))
+}
 
-    if !dry_run {
-        CommandUtils::info("Azure deployment would be executed here");
+/// Image name without its tag, e.g. `app` for `app:1.2`
+fn image_repository(image: &str) -> &str {
+    let name_start = image.rfind('/').map_or(0, |slash| slash + 1);
+    match image[name_start..].find(':') {
+        Some(colon) => &image[..name_start + colon],
+        None => image,
     }
+}
 
-    Ok(())
+/// Build, push to ECR and roll the ECS service named after the image
+fn aws_commands(config: &DeploymentConfig) -> Result<Vec<DeploymentStep>> {
+    let image = required(&config.docker_image, "docker_image")?;
+    let region = required(&config.aws_region, "aws_region")?;
+    let account = required(&config.aws_account_id, "aws_account_id")?;
+    let cluster = required(&config.aws_cluster, "aws_cluster")?;
+
+    let registry = format!("{}.dkr.ecr.{}.amazonaws.com", account, region);
+    let remote_image = format!("{}/{}", registry, image);
+    let service = image_repository(image);
+
+    Ok(vec![
+        (
+            "Logging in to ECR",
+            format!(
+                "aws ecr get-login-password --region {} | docker login --username AWS --password-stdin {}",
+                region, registry
+            ),
+        ),
+        ("Building Docker image", format!("docker build -t {} .", remote_image)),
+        ("Pushing image to ECR", format!("docker push {}", remote_image)),
+        (
+            "Updating ECS service",
+            format!(
+                "aws ecs update-service --cluster {} --service {} --force-new-deployment --region {}",
+                cluster, service, region
+            ),
+        ),
+    ])
+}
+
+/// Build, push to Container Registry and deploy to Cloud Run
+fn gcp_commands(config: &DeploymentConfig) -> Result<Vec<DeploymentStep>> {
+    let image = required(&config.docker_image, "docker_image")?;
+    let project = required(&config.gcp_project, "gcp_project")?;
+    let region = required(&config.gcp_region, "gcp_region")?;
+
+    // Bare image names are pushed to the project's registry
+    let remote_image = if image.contains('/') {
+        image.to_string()
+    } else {
+        format!("gcr.io/{}/{}", project, image)
+    };
+    let service = image_repository(image).rsplit('/').next().unwrap_or(image);
+
+    Ok(vec![
+        ("Building Docker image", format!("docker build -t {} .", remote_image)),
+        ("Pushing image", format!("docker push {}", remote_image)),
+        (
+            "Deploying to Cloud Run",
+            format!(
+                "gcloud run deploy {} --image {} --region {} --project {} --quiet",
+                service, remote_image, region, project
+            ),
+        ),
+    ])
+}
+
+/// Build, push and point the Container App at the new image
+fn azure_commands(config: &DeploymentConfig) -> Result<Vec<DeploymentStep>> {
+    let image = required(&config.docker_image, "docker_image")?;
+    let resource_group = required(&config.azure_resource_group, "azure_resource_group")?;
+    let app = required(&config.azure_app, "azure_app")?;
+
+    Ok(vec![
+        ("Building Docker image", format!("docker build -t {} .", image)),
+        ("Pushing image", format!("docker push {}", image)),
+        (
+            "Updating Container App",
+            format!(
+                "az containerapp update --name {} --resource-group {} --image {}",
+                app, resource_group, image
+            ),
+        ),
+    ])
 }
 
-async fn run_post_deployment_tasks(config: &DeploymentConfig) -> Result<()> {
+async fn run_post_deployment_tasks(
+    config: &DeploymentConfig,
+    target: &str,
+    version: &str,
+    health_check_timeout: Duration,
+) -> Result<()> {
     // Run database migrations
     CommandUtils::info("Running database migrations...");
     run_shell_command("cargo run -- migrate").await?;
@@ -345,8 +593,10 @@ async fn run_post_deployment_tasks(config: &DeploymentConfig) -> Result<()> {
     CommandUtils::info("Clearing caches...");
     run_shell_command("cargo run -- cache:clear").await?;
 
-    // Run custom post-deployment commands
-    if let Some(ref commands) = config.post_deploy_commands {
+    // Run custom post-deployment commands; servers already ran them over SSH
+    if config.deployment_type != "server"
+        && let Some(ref commands) = config.post_deploy_commands
+    {
         for command in commands {
             CommandUtils::info(&format!("Running post-deploy command: {}", command));
             run_shell_command(command).await?;
@@ -355,13 +605,167 @@ async fn run_post_deployment_tasks(config: &DeploymentConfig) -> Result<()> {
 
     // Health check
     CommandUtils::info("Running health check...");
-    run_health_check().await?;
+    run_health_check(config, health_check_timeout).await?;
+
+    // Record the deployment for rollbacks
+    history::append(Path::new(history::HISTORY_FILE), DeploymentRecord {
+        timestamp: chrono::Utc::now().to_rfc3339(),
+        version: version.to_string(),
+        target: target.to_string(),
+        deployment_type: config.deployment_type.clone(),
+        status: history::STATUS_SUCCESS.to_string(),
+        image: deployed_image(config, version),
+    })?;
 
     CommandUtils::success("Post-deployment tasks completed");
 
     Ok(())
 }
 
+/// Undo a deployment by restoring the one before it
+async fn rollback_deployment(target: &str, options: &DeployOptions) -> Result<()> {
+    let dry_run = options.dry_run;
+    let history_path = Path::new(history::HISTORY_FILE);
+    let mut records = history::load(history_path)?;
+
+    let recent: Vec<Vec<String>> = records
+        .iter()
+        .rev()
+        .filter(|record| record.target == target)
+        .take(HISTORY_DISPLAY_LIMIT)
+        .map(|record| vec![record.timestamp.clone(), record.version.clone(), record.target.clone(), record.status.clone()])
+        .collect();
+
+    if recent.is_empty() {
+        return Err(anyhow::anyhow!("No deployments to '{}' found in {}", target, history::HISTORY_FILE));
+    }
+
+    println!("{}", "Recent deployments:".bold());
+    println!("{}", TextUtils::table_format(&["Timestamp", "Version", "Target", "Status"], &recent, &[26, 12, 14, 12]));
+
+    let (current, previous) = history::select_rollback(&records, target, options.version.as_deref())?;
+    let latest = records.iter().rposition(|record| record.target == target && record.status == history::STATUS_SUCCESS);
+    let config = load_deployment_config(target)?;
+    let steps = rollback_commands(&config, &records[previous], latest == Some(current), options.ssh_fingerprint_check)?;
+
+    CommandUtils::info(&format!(
+        "Rolling back '{}' from version {} to {}",
+        target, records[current].version, records[previous].version
+    ));
+
+    if !options.force && !dry_run {
+        CommandUtils::warning("This will replace the running deployment");
+        print!("Are you sure? (yes/no): ");
+        use std::io::{self, Write};
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input)?;
+
+        if input.trim().to_lowercase() != "yes" {
+            CommandUtils::info("Rollback cancelled");
+            return Ok(());
+        }
+    }
+
+    run_deployment_steps(&steps, dry_run).await?;
+
+    if !dry_run {
+        records[current].status = history::STATUS_ROLLED_BACK.to_string();
+        history::save(history_path, &records)?;
+    }
+
+    CommandUtils::success(&format!("Rolled back to version {}", records[previous].version));
+
+    Ok(())
+}
+
+/// Commands that restore `previous`
+///
+/// Servers only keep one previous binary and Kubernetes deployments are undone
+/// to the previous rollout revision, so both can only roll back the latest
+/// deployment.
+fn rollback_commands(
+    config: &DeploymentConfig,
+    previous: &DeploymentRecord,
+    is_latest: bool,
+    fingerprint_check: FingerprintCheck,
+) -> Result<Vec<DeploymentStep>> {
+    let previous_image = || {
+        previous
+            .image
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("No image recorded for version {}", previous.version))
+    };
+
+    match config.deployment_type.as_str() {
+        "docker" => {
+            let image = required(&config.docker_image, "docker_image")?;
+            let mut steps = vec![("Restoring image tag", format!("docker tag {} {}", previous_image()?, image))];
+
+            if let Ok(registry) = std::env::var("DOCKER_REGISTRY") {
+                steps.push(("Tagging", format!("docker tag {} {}/{}", image, registry, image)));
+                steps.push(("Pushing", format!("docker push {}/{}", registry, image)));
+            }
+
+            Ok(steps)
+        }
+        "kubernetes" => {
+            if !is_latest {
+                anyhow::bail!("Kubernetes deployments can only roll back the latest deployment");
+            }
+
+            // Manifests are applied with an unversioned image, the rollout history holds the previous pod template
+            let namespace = config.kubernetes_namespace.as_deref().unwrap_or("default");
+            let name = kubernetes::deployment_name(Path::new(kubernetes::MANIFEST_DIR));
+            Ok(vec![
+                ("Undoing rollout", format!("kubectl rollout undo deployment/{} -n {}", name, namespace)),
+                ("Checking status", format!("kubectl rollout status deployment/{} -n {}", name, namespace)),
+            ])
+        }
+        "server" => {
+            if !is_latest {
+                anyhow::bail!("Server deployments can only roll back the latest deployment");
+            }
+
+            let ssh = SshTarget::from_config(config, fingerprint_check)?;
+            let path = required(&config.path, "path")?;
+            Ok(vec![(
+                "Restoring previous binary",
+                shell_command(
+                    "ssh",
+                    &ssh.ssh_args(&format!("mv {}/rustisan.prev {}/rustisan && sudo systemctl restart rustisan", path, path)),
+                ),
+            )])
+        }
+        other => Err(anyhow::anyhow!("Rollback is not supported for '{}' deployments", other)),
+    }
+}
+
+/// Version recorded for a deployment: the git commit, or a timestamp outside git
+fn deployment_version() -> String {
+    match ProcessUtils::execute_with_output("git", &["rev-parse", "--short", "HEAD"]) {
+        Ok((true, stdout, _)) if !stdout.trim().is_empty() => stdout.trim().to_string(),
+        _ => chrono::Utc::now().format("%Y%m%d%H%M%S").to_string(),
+    }
+}
+
+/// `image` retagged with the deployment version, e.g. `shop:a1b2c3d`
+fn versioned_image(image: &str, version: &str) -> String {
+    format!("{}:{}", image_repository(image), version)
+}
+
+/// Image to record in the history for this deployment
+fn deployed_image(config: &DeploymentConfig, version: &str) -> Option<String> {
+    let image = config.docker_image.as_deref()?;
+    match config.deployment_type.as_str() {
+        "docker" => Some(versioned_image(image, version)),
+        // Rolled back through the rollout history, the unversioned image would restore nothing
+        "kubernetes" => None,
+        _ => Some(image.to_string()),
+    }
+}
+
 async fn run_shell_command(command: &str) -> Result<()> {
     let output = std::process::Command::new("sh")
         .arg("-c")
@@ -376,12 +780,15 @@ async fn run_shell_command(command: &str) -> Result<()> {
     Ok(())
 }
 
-async fn run_health_check() -> Result<()> {
-    // TODO: Implement health check logic
-    // This could involve:
-    // - HTTP health endpoint check
-    // - Database connectivity check
-    // - Service dependency checks
+/// Request the configured health endpoint, which reports on the application's own dependencies
+async fn run_health_check(config: &DeploymentConfig, timeout: Duration) -> Result<()> {
+    let Some(ref url) = config.health_check_url else {
+        CommandUtils::warning("Health check skipped, no health_check_url is configured");
+        return Ok(());
+    };
+
+    ProcessUtils::execute_with_timeout("curl", &["--fail", "--silent", "--show-error", url], timeout)
+        .map_err(|error| anyhow::anyhow!("Health check failed for {}: {}", url, error))?;
 
     CommandUtils::success("Health check passed");
 
@@ -410,6 +817,9 @@ host = "your-server.com"
 port = 22
 user = "deploy"
 path = "/opt/rustisan"
+# ssh_port = 22                           # Overrides port for SSH connections
+# ssh_key_path = "~/.ssh/id_ed25519"      # Passed to ssh/scp/rsync as -i
+# ssh_known_hosts = "deploy/known_hosts"  # Used instead of ~/.ssh/known_hosts
 
 # Docker settings (if deployment_type = "docker")
 docker_image = "rustisan-app"
@@ -420,6 +830,22 @@ kubernetes_namespace = "default"
 # Cloud settings (if deployment_type = "cloud")
 cloud_provider = "aws"  # Options: aws, gcp, azure
 
+# AWS: pushes docker_image to ECR and updates the ECS service of the same name
+# aws_region = "us-east-1"
+# aws_account_id = "123456789012"
+# aws_cluster = "rustisan"
+
+# GCP: deploys docker_image to the Cloud Run service of the same name
+# gcp_project = "my-project"
+# gcp_region = "us-central1"
+
+# Azure: updates a Container App to docker_image (include the registry, e.g. myacr.azurecr.io/app)
+# azure_resource_group = "rustisan-rg"
+# azure_app = "rustisan-app"
+
+# URL checked after deployment
+# health_check_url = "https://your-server.com/health"
+
 # Environment variables to set
 [environment_variables]
 APP_ENV = "{}"
@@ -444,3 +870,136 @@ post_deploy_commands = [
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cloud_config(image: &str) -> DeploymentConfig {
+        DeploymentConfig {
+            deployment_type: "cloud".to_string(),
+            docker_image: Some(image.to_string()),
+            aws_region: Some("eu-west-1".to_string()),
+            aws_account_id: Some("123456789012".to_string()),
+            aws_cluster: Some("prod".to_string()),
+            gcp_project: Some("acme".to_string()),
+            gcp_region: Some("europe-west1".to_string()),
+            azure_resource_group: Some("acme-rg".to_string()),
+            azure_app: Some("shop".to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn commands(steps: Vec<DeploymentStep>) -> Vec<String> {
+        steps.into_iter().map(|(_, command)| command).collect()
+    }
+
+    #[test]
+    fn test_aws_commands() {
+        let commands = commands(aws_commands(&cloud_config("shop:1.4")).unwrap());
+        assert_eq!(
+            commands,
+            [
+                "aws ecr get-login-password --region eu-west-1 | docker login --username AWS --password-stdin 123456789012.dkr.ecr.eu-west-1.amazonaws.com",
+                "docker build -t 123456789012.dkr.ecr.eu-west-1.amazonaws.com/shop:1.4 .",
+                "docker push 123456789012.dkr.ecr.eu-west-1.amazonaws.com/shop:1.4",
+                "aws ecs update-service --cluster prod --service shop --force-new-deployment --region eu-west-1",
+            ]
+        );
+
+        let missing = DeploymentConfig { aws_cluster: None, ..cloud_config("shop") };
+        let error = aws_commands(&missing).unwrap_err().to_string();
+        assert_eq!(error, "'aws_cluster' not specified in deployment config");
+    }
+
+    #[test]
+    fn test_gcp_commands() {
+        let commands = commands(gcp_commands(&cloud_config("shop")).unwrap());
+        assert_eq!(
+            commands,
+            [
+                "docker build -t gcr.io/acme/shop .",
+                "docker push gcr.io/acme/shop",
+                "gcloud run deploy shop --image gcr.io/acme/shop --region europe-west1 --project acme --quiet",
+            ]
+        );
+
+        let registry_image = gcp_commands(&cloud_config("europe-docker.pkg.dev/acme/apps/shop:2")).unwrap();
+        assert_eq!(
+            registry_image[2].1,
+            "gcloud run deploy shop --image europe-docker.pkg.dev/acme/apps/shop:2 --region europe-west1 --project acme --quiet"
+        );
+    }
+
+    #[test]
+    fn test_azure_commands() {
+        let commands = commands(azure_commands(&cloud_config("acme.azurecr.io/shop:3")).unwrap());
+        assert_eq!(
+            commands,
+            [
+                "docker build -t acme.azurecr.io/shop:3 .",
+                "docker push acme.azurecr.io/shop:3",
+                "az containerapp update --name shop --resource-group acme-rg --image acme.azurecr.io/shop:3",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rollback_commands() {
+        let previous = DeploymentRecord {
+            timestamp: "2024-05-04T10:00:00Z".to_string(),
+            version: "0a1b2c3".to_string(),
+            target: "production".to_string(),
+            deployment_type: "docker".to_string(),
+            status: history::STATUS_SUCCESS.to_string(),
+            image: Some("shop:0a1b2c3".to_string()),
+        };
+
+        let docker = DeploymentConfig { deployment_type: "docker".to_string(), ..cloud_config("shop") };
+        assert_eq!(commands(rollback_commands(&docker, &previous, true, FingerprintCheck::AcceptNew).unwrap()), ["docker tag shop:0a1b2c3 shop"]);
+
+        let kubernetes = DeploymentConfig {
+            deployment_type: "kubernetes".to_string(),
+            kubernetes_namespace: Some("web".to_string()),
+            ..cloud_config("shop")
+        };
+        let recorded = DeploymentRecord {
+            deployment_type: "kubernetes".to_string(),
+            image: deployed_image(&kubernetes, "0a1b2c3"),
+            ..previous.clone()
+        };
+        assert_eq!(recorded.image, None);
+        assert_eq!(
+            commands(rollback_commands(&kubernetes, &recorded, true, FingerprintCheck::AcceptNew).unwrap()),
+            [
+                "kubectl rollout undo deployment/rustisan -n web",
+                "kubectl rollout status deployment/rustisan -n web",
+            ]
+        );
+        assert!(rollback_commands(&kubernetes, &recorded, false, FingerprintCheck::AcceptNew).is_err());
+
+        let server = DeploymentConfig {
+            host: Some("app.example.com".to_string()),
+            user: Some("deploy".to_string()),
+            path: Some("/opt/shop".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            commands(rollback_commands(&server, &previous, true, FingerprintCheck::Strict).unwrap()),
+            [
+                "ssh -p 22 -o StrictHostKeyChecking=yes deploy@app.example.com \
+                 'mv /opt/shop/rustisan.prev /opt/shop/rustisan && sudo systemctl restart rustisan'"
+            ]
+        );
+        assert!(rollback_commands(&server, &previous, false, FingerprintCheck::Strict).is_err());
+        assert!(rollback_commands(&cloud_config("shop"), &previous, true, FingerprintCheck::Strict).is_err());
+    }
+
+    #[test]
+    fn test_image_repository() {
+        assert_eq!(image_repository("shop"), "shop");
+        assert_eq!(image_repository("shop:1.4"), "shop");
+        assert_eq!(image_repository("localhost:5000/shop:1.4"), "localhost:5000/shop");
+        assert_eq!(image_repository("localhost:5000/shop"), "localhost:5000/shop");
+    }
+}
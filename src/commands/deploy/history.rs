@@ -0,0 +1,144 @@
+//! Deployment history used by `deploy --rollback`
+//!
+//! Every successful deployment appends a record to
+//! `storage/deployments/history.json`. Rolling back undoes a record by
+//! redeploying the successful record that preceded it for the same target.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// Location of the history file, relative to the project root
+pub const HISTORY_FILE: &str = "storage/deployments/history.json";
+
+pub const STATUS_SUCCESS: &str = "success";
+pub const STATUS_ROLLED_BACK: &str = "rolled_back";
+
+/// A single deployment
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct DeploymentRecord {
+    pub timestamp: String,
+    pub version: String,
+    pub target: String,
+    pub deployment_type: String,
+    pub status: String,
+    /// Image that was deployed, for docker and kubernetes targets
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+}
+
+/// Read the history, oldest first; a missing file is an empty history
+pub fn load(path: &Path) -> Result<Vec<DeploymentRecord>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(path)?;
+    serde_json::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+pub fn save(path: &Path, history: &[DeploymentRecord]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(history)?)?;
+    Ok(())
+}
+
+/// Append a record to the history file
+pub fn append(path: &Path, record: DeploymentRecord) -> Result<()> {
+    let mut history = load(path)?;
+    history.push(record);
+    save(path, &history)
+}
+
+/// Pick the deployment to undo and the one to restore
+///
+/// The deployment to undo is the latest successful one for `target`, or the
+/// one matching `version`. Returns the indices of both records.
+pub fn select_rollback(history: &[DeploymentRecord], target: &str, version: Option<&str>) -> Result<(usize, usize)> {
+    let candidates: Vec<usize> = history
+        .iter()
+        .enumerate()
+        .filter(|(_, record)| record.target == target && record.status == STATUS_SUCCESS)
+        .map(|(index, _)| index)
+        .collect();
+
+    let position = match version {
+        Some(version) => candidates
+            .iter()
+            .rposition(|&index| history[index].version == version)
+            .ok_or_else(|| anyhow::anyhow!("No successful deployment of version '{}' to '{}'", version, target))?,
+        None => candidates
+            .len()
+            .checked_sub(1)
+            .ok_or_else(|| anyhow::anyhow!("No successful deployments to '{}' to roll back", target))?,
+    };
+
+    let current = candidates[position];
+    let previous = position
+        .checked_sub(1)
+        .map(|previous| candidates[previous])
+        .ok_or_else(|| {
+            anyhow::anyhow!("Version '{}' is the first deployment to '{}'; nothing to roll back to", history[current].version, target)
+        })?;
+
+    Ok((current, previous))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = r#"[
+  { "timestamp": "2024-05-01T10:00:00Z", "version": "a1b2c3d", "target": "production", "deployment_type": "docker", "status": "success", "image": "shop:a1b2c3d" },
+  { "timestamp": "2024-05-02T10:00:00Z", "version": "e4f5a6b", "target": "staging", "deployment_type": "docker", "status": "success", "image": "shop:e4f5a6b" },
+  { "timestamp": "2024-05-03T10:00:00Z", "version": "c7d8e9f", "target": "production", "deployment_type": "docker", "status": "rolled_back", "image": "shop:c7d8e9f" },
+  { "timestamp": "2024-05-04T10:00:00Z", "version": "0a1b2c3", "target": "production", "deployment_type": "docker", "status": "success", "image": "shop:0a1b2c3" },
+  { "timestamp": "2024-05-05T10:00:00Z", "version": "9f8e7d6", "target": "production", "deployment_type": "docker", "status": "success", "image": "shop:9f8e7d6" }
+]"#;
+
+    #[test]
+    fn test_select_rollback() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("deployments/history.json");
+        save(&path, &serde_json::from_str::<Vec<DeploymentRecord>>(FIXTURE).unwrap()).unwrap();
+        let history = load(&path).unwrap();
+
+        // Latest production deployment rolls back to the one before it
+        let (current, previous) = select_rollback(&history, "production", None).unwrap();
+        assert_eq!(history[current].version, "9f8e7d6");
+        assert_eq!(history[previous].version, "0a1b2c3");
+
+        // Rolled back and other targets' deployments are skipped
+        let (current, previous) = select_rollback(&history, "production", Some("0a1b2c3")).unwrap();
+        assert_eq!(history[current].version, "0a1b2c3");
+        assert_eq!(history[previous].version, "a1b2c3d");
+        assert_eq!(history[previous].image.as_deref(), Some("shop:a1b2c3d"));
+
+        assert!(select_rollback(&history, "production", Some("a1b2c3d")).is_err());
+        assert!(select_rollback(&history, "production", Some("c7d8e9f")).is_err());
+        assert!(select_rollback(&history, "staging", None).is_err());
+        assert!(select_rollback(&history, "qa", None).is_err());
+    }
+
+    #[test]
+    fn test_append_creates_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("storage/deployments/history.json");
+        assert!(load(&path).unwrap().is_empty());
+
+        let record = DeploymentRecord {
+            timestamp: "2024-05-01T10:00:00Z".to_string(),
+            version: "a1b2c3d".to_string(),
+            target: "production".to_string(),
+            deployment_type: "server".to_string(),
+            status: STATUS_SUCCESS.to_string(),
+            image: None,
+        };
+        append(&path, record.clone()).unwrap();
+        append(&path, record.clone()).unwrap();
+
+        assert_eq!(load(&path).unwrap(), vec![record.clone(), record]);
+        assert!(!std::fs::read_to_string(&path).unwrap().contains("\"image\""));
+    }
+}
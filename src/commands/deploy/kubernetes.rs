@@ -0,0 +1,293 @@
+//! Kubernetes manifests for `make k8s` and kubernetes deployments
+//!
+//! Manifests are rendered from templates embedded in the binary. Ports, the
+//! ingress host and the ConfigMap come from `rustisan.toml`; container
+//! resources can be set in a `[kubernetes.resources]` table:
+//!
+//! ```toml
+//! [kubernetes.resources]
+//! cpu_request = "100m"
+//! cpu_limit = "500m"
+//! memory_request = "128Mi"
+//! memory_limit = "512Mi"
+//! ```
+
+use anyhow::Result;
+use handlebars::Handlebars;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use toml::Value;
+
+use crate::commands::CommandUtils;
+use crate::commands::config::{config_key_to_env, format_value, get_nested_value, is_sensitive_key};
+
+/// Directory the manifests are written to and applied from
+pub const MANIFEST_DIR: &str = "k8s";
+
+const TEMPLATES: &[(&str, &str)] = &[
+    ("deployment.yaml", include_str!("templates/deployment.yaml")),
+    ("service.yaml", include_str!("templates/service.yaml")),
+    ("ingress.yaml", include_str!("templates/ingress.yaml")),
+    ("configmap.yaml", include_str!("templates/configmap.yaml")),
+];
+
+/// Container resource requests and limits
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct Resources {
+    pub cpu_request: String,
+    pub cpu_limit: String,
+    pub memory_request: String,
+    pub memory_limit: String,
+}
+
+impl Default for Resources {
+    fn default() -> Self {
+        Self {
+            cpu_request: "100m".to_string(),
+            cpu_limit: "500m".to_string(),
+            memory_request: "128Mi".to_string(),
+            memory_limit: "512Mi".to_string(),
+        }
+    }
+}
+
+/// Values substituted into the manifest templates
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ManifestOptions {
+    pub name: String,
+    pub replicas: u32,
+    pub image: String,
+    pub port: u16,
+    pub host: String,
+    pub resources: Resources,
+    /// ConfigMap entries with values already quoted for YAML
+    pub env: BTreeMap<String, String>,
+}
+
+impl ManifestOptions {
+    /// Build the options from the application's `rustisan.toml`
+    pub fn from_config(name: &str, replicas: u32, image: Option<String>, config: &Value) -> Self {
+        let name = resource_name(name);
+        let string = |key: &str| get_nested_value(config, key).and_then(|value| value.as_str().map(str::to_string));
+
+        let defaults = Resources::default();
+        let resources = Resources {
+            cpu_request: string("kubernetes.resources.cpu_request").unwrap_or(defaults.cpu_request),
+            cpu_limit: string("kubernetes.resources.cpu_limit").unwrap_or(defaults.cpu_limit),
+            memory_request: string("kubernetes.resources.memory_request").unwrap_or(defaults.memory_request),
+            memory_limit: string("kubernetes.resources.memory_limit").unwrap_or(defaults.memory_limit),
+        };
+
+        let port = get_nested_value(config, "server.port")
+            .and_then(|port| port.as_integer())
+            .and_then(|port| u16::try_from(port).ok())
+            .unwrap_or(3000);
+
+        let mut env = BTreeMap::new();
+        collect_env(config, "", &mut env);
+
+        Self {
+            image: image.unwrap_or_else(|| format!("{}:latest", name)),
+            host: string("app.url").as_deref().map(url_host).unwrap_or_else(|| "localhost".to_string()),
+            name,
+            replicas,
+            port,
+            resources,
+            env,
+        }
+    }
+}
+
+/// Render every manifest as `(file name, content)` pairs
+pub fn render(options: &ManifestOptions) -> Result<Vec<(&'static str, String)>> {
+    let mut handlebars = Handlebars::new();
+    handlebars.register_escape_fn(handlebars::no_escape);
+    handlebars.set_strict_mode(true);
+
+    TEMPLATES
+        .iter()
+        .map(|(file, template)| Ok((*file, handlebars.render_template(template, options)?)))
+        .collect()
+}
+
+/// Write the manifests into `dir`, leaving existing files untouched unless `force` is set
+///
+/// Returns the files that were written and those that already existed.
+pub fn write(dir: &Path, options: &ManifestOptions, force: bool) -> Result<(Vec<PathBuf>, Vec<PathBuf>)> {
+    std::fs::create_dir_all(dir)?;
+
+    let mut written = Vec::new();
+    let mut skipped = Vec::new();
+    for (file, content) in render(options)? {
+        let path = dir.join(file);
+        if path.exists() && !force {
+            skipped.push(path);
+        } else {
+            if path.exists() {
+                CommandUtils::warning(&format!("Overwriting existing file: {}", path.display()));
+            }
+            std::fs::write(&path, content)?;
+            written.push(path);
+        }
+    }
+
+    Ok((written, skipped))
+}
+
+/// Name of the Deployment in `dir`, falling back to `rustisan`
+pub fn deployment_name(dir: &Path) -> String {
+    std::fs::read_to_string(dir.join("deployment.yaml"))
+        .ok()
+        .and_then(|content| serde_yaml::from_str::<serde_yaml::Value>(&content).ok())
+        .and_then(|manifest| manifest["metadata"]["name"].as_str().map(str::to_string))
+        .unwrap_or_else(|| "rustisan".to_string())
+}
+
+/// Flatten scalar configuration keys into ConfigMap entries, skipping secrets
+fn collect_env(value: &Value, prefix: &str, env: &mut BTreeMap<String, String>) {
+    match value {
+        Value::Table(table) => {
+            for (key, val) in table {
+                let full_key = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                collect_env(val, &full_key, env);
+            }
+        }
+        Value::Array(items) if items.iter().any(Value::is_table) => {}
+        _ if is_sensitive_key(prefix) => {}
+        _ => {
+            // JSON strings are valid YAML scalars and keep values such as `true` as strings
+            let quoted = serde_json::Value::String(format_value(value)).to_string();
+            env.insert(config_key_to_env(prefix), quoted);
+        }
+    }
+}
+
+/// Kubernetes resource names are lowercase DNS labels
+fn resource_name(name: &str) -> String {
+    let name: String = name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    name.trim_matches('-').to_string()
+}
+
+/// Host part of a URL such as `https://shop.example.com:8443/path`
+fn url_host(url: &str) -> String {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split('/').next().unwrap_or(rest);
+    authority.split(':').next().unwrap_or(authority).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"
+[app]
+name = "My Shop"
+url = "https://shop.example.com:8443/"
+debug = true
+key = "base64:secret"
+
+[server]
+port = 8080
+hosts = ["0.0.0.0"]
+
+[database.connections.default]
+driver = "postgres"
+password = "hunter2"
+
+[kubernetes.resources]
+cpu_limit = "1"
+memory_limit = "1Gi"
+
+[[schedule]]
+command = "cleanup"
+"#;
+
+    fn manifests(image: Option<String>) -> BTreeMap<&'static str, serde_yaml::Value> {
+        let config: Value = toml::from_str(CONFIG).unwrap();
+        let options = ManifestOptions::from_config("My_Shop", 3, image, &config);
+        render(&options)
+            .unwrap()
+            .into_iter()
+            .map(|(file, content)| (file, serde_yaml::from_str(&content).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_deployment_and_service() {
+        let manifests = manifests(Some("registry.example.com/shop:1.2.0".to_string()));
+
+        let deployment = &manifests["deployment.yaml"];
+        assert_eq!(deployment["kind"], "Deployment");
+        assert_eq!(deployment["metadata"]["name"], "my-shop");
+        assert_eq!(deployment["spec"]["replicas"], 3);
+
+        let container = &deployment["spec"]["template"]["spec"]["containers"][0];
+        assert_eq!(container["image"], "registry.example.com/shop:1.2.0");
+        assert_eq!(container["ports"][0]["containerPort"], 8080);
+        assert_eq!(container["envFrom"][0]["configMapRef"]["name"], "my-shop-config");
+        assert_eq!(container["resources"]["requests"]["cpu"], "100m");
+        assert_eq!(container["resources"]["limits"]["cpu"], "1");
+        assert_eq!(container["resources"]["limits"]["memory"], "1Gi");
+
+        let service = &manifests["service.yaml"];
+        assert_eq!(service["kind"], "Service");
+        assert_eq!(service["spec"]["selector"]["app"], "my-shop");
+        assert_eq!(service["spec"]["ports"][0]["targetPort"], 8080);
+    }
+
+    #[test]
+    fn test_ingress_and_configmap() {
+        let manifests = manifests(None);
+
+        let container = &manifests["deployment.yaml"]["spec"]["template"]["spec"]["containers"][0];
+        assert_eq!(container["image"], "my-shop:latest");
+
+        let ingress = &manifests["ingress.yaml"];
+        assert_eq!(ingress["kind"], "Ingress");
+        assert_eq!(ingress["spec"]["rules"][0]["host"], "shop.example.com");
+        assert_eq!(ingress["spec"]["rules"][0]["http"]["paths"][0]["backend"]["service"]["name"], "my-shop");
+
+        let data = &manifests["configmap.yaml"]["data"];
+        assert_eq!(data["APP__NAME"], "My Shop");
+        assert_eq!(data["APP__DEBUG"], "true");
+        assert_eq!(data["SERVER__PORT"], "8080");
+        assert_eq!(data["SERVER__HOSTS"], "[0.0.0.0]");
+        assert_eq!(data["DATABASE__CONNECTIONS__DEFAULT__DRIVER"], "postgres");
+        assert!(data.get("APP__KEY").is_none());
+        assert!(data.get("DATABASE__CONNECTIONS__DEFAULT__PASSWORD").is_none());
+        assert!(data.get("SCHEDULE").is_none());
+    }
+
+    #[test]
+    fn test_write_and_deployment_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let k8s = dir.path().join(MANIFEST_DIR);
+        assert_eq!(deployment_name(&k8s), "rustisan");
+
+        let config: Value = toml::from_str(CONFIG).unwrap();
+        let options = ManifestOptions::from_config("shop", 2, None, &config);
+        let (written, skipped) = write(&k8s, &options, false).unwrap();
+        assert_eq!(written.len(), 4);
+        assert!(skipped.is_empty());
+        assert_eq!(deployment_name(&k8s), "shop");
+
+        let (written, skipped) = write(&k8s, &options, false).unwrap();
+        assert!(written.is_empty());
+        assert_eq!(skipped.len(), 4);
+
+        let (written, skipped) = write(&k8s, &options, true).unwrap();
+        assert_eq!(written.len(), 4);
+        assert!(skipped.is_empty());
+    }
+
+    #[test]
+    fn test_url_host() {
+        assert_eq!(url_host("http://localhost:3000"), "localhost");
+        assert_eq!(url_host("https://shop.example.com/app"), "shop.example.com");
+        assert_eq!(url_host("shop.example.com"), "shop.example.com");
+    }
+}
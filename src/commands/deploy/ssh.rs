@@ -0,0 +1,234 @@
+//! SSH, scp and rsync invocations for server deployments
+//!
+//! Arguments are built as vectors so they can be checked without a server,
+//! then rendered with [`shell_command`] for `run_shell_command`.
+
+use anyhow::Result;
+
+use super::{required, DeploymentConfig};
+
+/// How ssh treats unknown or changed host keys
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum FingerprintCheck {
+    /// Only connect to hosts already in known_hosts
+    Strict,
+    /// Add unknown hosts, refuse changed keys
+    #[default]
+    AcceptNew,
+    /// Skip host key verification
+    No,
+}
+
+impl FingerprintCheck {
+    /// Value for ssh's `StrictHostKeyChecking` option
+    fn ssh_option(self) -> &'static str {
+        match self {
+            Self::Strict => "yes",
+            Self::AcceptNew => "accept-new",
+            Self::No => "no",
+        }
+    }
+}
+
+/// Connection settings for a deployment server
+#[derive(Debug, Clone, PartialEq)]
+pub struct SshTarget {
+    pub user: String,
+    pub host: String,
+    pub port: u16,
+    pub key_path: Option<String>,
+    pub known_hosts: Option<String>,
+    pub fingerprint_check: FingerprintCheck,
+}
+
+impl SshTarget {
+    /// Read the server settings, preferring `ssh_port` over `port`
+    pub fn from_config(config: &DeploymentConfig, fingerprint_check: FingerprintCheck) -> Result<Self> {
+        Ok(Self {
+            user: required(&config.user, "user")?.to_string(),
+            host: required(&config.host, "host")?.to_string(),
+            port: config.ssh_port.or(config.port).unwrap_or(22),
+            key_path: config.ssh_key_path.as_deref().map(expand_home),
+            known_hosts: config.ssh_known_hosts.as_deref().map(expand_home),
+            fingerprint_check,
+        })
+    }
+
+    fn destination(&self) -> String {
+        format!("{}@{}", self.user, self.host)
+    }
+
+    /// Options understood by both ssh and scp
+    fn common_options(&self) -> Vec<String> {
+        let mut args = vec![
+            "-o".to_string(),
+            format!("StrictHostKeyChecking={}", self.fingerprint_check.ssh_option()),
+        ];
+
+        if let Some(ref known_hosts) = self.known_hosts {
+            args.push("-o".to_string());
+            args.push(format!("UserKnownHostsFile={}", known_hosts));
+        }
+        if let Some(ref key_path) = self.key_path {
+            args.push("-i".to_string());
+            args.push(key_path.clone());
+        }
+
+        args
+    }
+
+    /// ssh arguments without the destination, as used for rsync's `-e`
+    fn ssh_options(&self) -> Vec<String> {
+        let mut args = vec!["-p".to_string(), self.port.to_string()];
+        args.extend(self.common_options());
+        args
+    }
+
+    /// Arguments to run `remote_command` on the server
+    pub fn ssh_args(&self, remote_command: &str) -> Vec<String> {
+        let mut args = self.ssh_options();
+        args.push(self.destination());
+        args.push(remote_command.to_string());
+        args
+    }
+
+    /// Arguments for a non-interactive connection test
+    pub fn preflight_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "-o".to_string(),
+            "ConnectTimeout=5".to_string(),
+            "-o".to_string(),
+            "BatchMode=yes".to_string(),
+        ];
+        args.extend(self.ssh_args("true"));
+        args
+    }
+
+    /// Arguments to copy `sources` into `remote_dir`
+    pub fn scp_args(&self, sources: &[String], remote_dir: &str) -> Vec<String> {
+        let mut args = vec!["-r".to_string(), "-P".to_string(), self.port.to_string()];
+        args.extend(self.common_options());
+        args.extend(sources.iter().cloned());
+        args.push(format!("{}:{}/", self.destination(), remote_dir));
+        args
+    }
+
+    /// Arguments to sync the contents of `local_dir` into `remote_dir`
+    pub fn rsync_args(&self, local_dir: &str, remote_dir: &str) -> Vec<String> {
+        vec![
+            "-az".to_string(),
+            "-e".to_string(),
+            shell_command("ssh", &self.ssh_options()),
+            format!("{}/", local_dir.trim_end_matches('/')),
+            format!("{}:{}/", self.destination(), remote_dir),
+        ]
+    }
+}
+
+/// Expand a leading `~/`, which would not survive shell quoting
+fn expand_home(path: &str) -> String {
+    match (path.strip_prefix("~/"), std::env::var("HOME")) {
+        (Some(rest), Ok(home)) => format!("{}/{}", home.trim_end_matches('/'), rest),
+        _ => path.to_string(),
+    }
+}
+
+/// Render a program and its arguments as a shell command line
+pub fn shell_command(program: &str, args: &[String]) -> String {
+    let mut command = program.to_string();
+    for arg in args {
+        command.push(' ');
+        command.push_str(&shell_quote(arg));
+    }
+    command
+}
+
+fn shell_quote(arg: &str) -> String {
+    let safe = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@,+%".contains(c));
+
+    if safe {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture() -> DeploymentConfig {
+        DeploymentConfig {
+            host: Some("app.example.com".to_string()),
+            user: Some("deploy".to_string()),
+            ssh_port: Some(2222),
+            ssh_key_path: Some("/home/ci/.ssh/deploy_ed25519".to_string()),
+            ssh_known_hosts: Some("deploy/known_hosts".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_ssh_and_scp_args() {
+        let target = SshTarget::from_config(&fixture(), FingerprintCheck::Strict).unwrap();
+
+        assert_eq!(
+            target.ssh_args("sudo systemctl restart rustisan"),
+            [
+                "-p", "2222",
+                "-o", "StrictHostKeyChecking=yes",
+                "-o", "UserKnownHostsFile=deploy/known_hosts",
+                "-i", "/home/ci/.ssh/deploy_ed25519",
+                "deploy@app.example.com",
+                "sudo systemctl restart rustisan",
+            ]
+        );
+
+        assert_eq!(
+            target.scp_args(&["target/deploy/rustisan".to_string(), "target/deploy/public".to_string()], "/opt/shop"),
+            [
+                "-r", "-P", "2222",
+                "-o", "StrictHostKeyChecking=yes",
+                "-o", "UserKnownHostsFile=deploy/known_hosts",
+                "-i", "/home/ci/.ssh/deploy_ed25519",
+                "target/deploy/rustisan", "target/deploy/public",
+                "deploy@app.example.com:/opt/shop/",
+            ]
+        );
+
+        let preflight = target.preflight_args();
+        assert_eq!(preflight[..4], ["-o", "ConnectTimeout=5", "-o", "BatchMode=yes"]);
+        assert_eq!(preflight.last().unwrap(), "true");
+    }
+
+    #[test]
+    fn test_rsync_args_and_defaults() {
+        let config = DeploymentConfig {
+            ssh_port: None,
+            ssh_key_path: None,
+            ssh_known_hosts: None,
+            ..fixture()
+        };
+        let target = SshTarget::from_config(&config, FingerprintCheck::default()).unwrap();
+
+        assert_eq!(target.port, 22);
+        assert_eq!(
+            target.rsync_args("target/deploy/", "/opt/shop"),
+            ["-az", "-e", "ssh -p 22 -o StrictHostKeyChecking=accept-new", "target/deploy/", "deploy@app.example.com:/opt/shop/"]
+        );
+
+        assert!(SshTarget::from_config(&DeploymentConfig::default(), FingerprintCheck::No).is_err());
+    }
+
+    #[test]
+    fn test_shell_command_quoting() {
+        let args = vec!["-p".to_string(), "22".to_string(), "deploy@host".to_string(), "cd /opt/shop && echo 'done'".to_string()];
+        assert_eq!(
+            shell_command("ssh", &args),
+            r"ssh -p 22 deploy@host 'cd /opt/shop && echo '\''done'\'''"
+        );
+    }
+}
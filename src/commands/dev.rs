@@ -2,8 +2,65 @@
 
 use anyhow::Result;
 use colored::*;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 use crate::DevCommands;
 use super::CommandUtils;
+use crate::utils::{FileUtils, ProcessUtils};
+
+/// Directory profiles are written to
+const PROFILES_DIR: &str = "storage/profiles";
+
+/// Directory benchmark summaries are written to
+const BENCHMARKS_DIR: &str = "storage/benchmarks";
+
+/// Benchmark file created by `dev benchmark --generate`
+const BENCHMARK_FILE: &str = "benches/benchmarks.rs";
+
+/// Relative change in median time reported as a regression or improvement
+const BENCHMARK_THRESHOLD: f64 = 0.10;
+
+/// Sampling frequency of the profiler in Hz
+#[cfg(unix)]
+const SAMPLING_FREQUENCY: i32 = 100;
+
+/// Lint groups enabled by `dev check --pedantic`
+const PEDANTIC_GROUPS: &[&str] = &["clippy::pedantic", "clippy::nursery", "clippy::cargo"];
+
+/// Pedantic lints that conflict with the patterns of Rustisan applications
+const PEDANTIC_ALLOWED: &[&str] = &[
+    "clippy::module_name_repetitions",
+    "clippy::missing_errors_doc",
+    "clippy::missing_panics_doc",
+    "clippy::multiple_crate_versions",
+    "clippy::cargo_common_metadata",
+];
+
+/// The `[clippy]` table of rustisan.toml
+///
+/// ```toml
+/// [clippy]
+/// allow = ["clippy::too_many_lines"]
+/// deny = ["clippy::unwrap_used"]
+/// ```
+#[derive(Debug, Default, serde::Deserialize)]
+struct ClippyConfig {
+    #[serde(default)]
+    allow: Vec<String>,
+    #[serde(default)]
+    deny: Vec<String>,
+}
+
+/// A diagnostic reported by clippy
+#[derive(Debug, PartialEq, serde::Serialize)]
+struct Diagnostic {
+    file: String,
+    line: u64,
+    column: u64,
+    level: String,
+    code: Option<String>,
+    message: String,
+}
 
 /// Handle dev command
 pub async fn handle(tool: DevCommands) -> Result<()> {
@@ -19,12 +76,21 @@ pub async fn handle(tool: DevCommands) -> Result<()> {
         DevCommands::Format => {
             format_code().await
         }
-        DevCommands::Check => {
-            check_code().await
+        DevCommands::Check { pedantic, fix } => {
+            check_code(pedantic, fix).await
         }
         DevCommands::Docs { open } => {
             generate_docs(open).await
         }
+        DevCommands::Profile { duration, output, flamegraph } => {
+            profile_app(duration, output, flamegraph).await
+        }
+        DevCommands::Benchmark { generate, run, compare } => {
+            benchmark(generate, run, compare).await
+        }
+        DevCommands::Setup { check_only } => {
+            setup_dev_environment(check_only).await
+        }
     }
 }
 
@@ -109,30 +175,124 @@ async fn format_code() -> Result<()> {
     Ok(())
 }
 
-async fn check_code() -> Result<()> {
+async fn check_code(pedantic: bool, fix: bool) -> Result<()> {
     CommandUtils::info("Checking code with clippy...");
 
+    let config = load_clippy_config();
+    let args = clippy_args(pedantic, fix, &config);
+
     // Run clippy
     let output = std::process::Command::new("cargo")
-        .args(&["clippy", "--all-targets", "--all-features", "--", "-D", "warnings"])
+        .args(&args)
         .output()?;
 
+    let diagnostics = parse_diagnostics(&String::from_utf8_lossy(&output.stdout));
+    print_diagnostics(&diagnostics);
+
+    let errors = diagnostics.iter().filter(|d| d.level == "error").count();
+    let warnings = diagnostics.iter().filter(|d| d.level == "warning").count();
+    let summary = format!("{} errors, {} warnings", errors, warnings);
+
     if output.status.success() {
-        CommandUtils::success("Code check passed");
+        CommandUtils::success(&format!("Code check passed: {}", summary));
+    } else {
+        if diagnostics.is_empty() {
+            // Failed before clippy could report anything, e.g. a manifest error
+            println!("{}", String::from_utf8_lossy(&output.stderr));
+        }
+        CommandUtils::error(&format!("Code check failed: {}", summary));
+        anyhow::bail!("Code check failed");
+    }
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        if !stdout.is_empty() {
-            println!("{}", stdout);
+    Ok(())
+}
+
+/// Read the `[clippy]` table of rustisan.toml, if any
+fn load_clippy_config() -> ClippyConfig {
+    std::fs::read_to_string("rustisan.toml")
+        .ok()
+        .and_then(|content| content.parse::<toml::Value>().ok())
+        .and_then(|config| config.get("clippy").cloned())
+        .and_then(|clippy| clippy.try_into().ok())
+        .unwrap_or_default()
+}
+
+/// Arguments of the cargo invocation running clippy
+fn clippy_args(pedantic: bool, fix: bool, config: &ClippyConfig) -> Vec<String> {
+    let mut args = vec!["clippy"];
+    if fix {
+        args.extend(["--fix", "--allow-dirty"]);
+    }
+    args.extend(["--all-targets", "--all-features", "--message-format=json", "--"]);
+    if !fix {
+        args.extend(["-D", "warnings"]);
+    }
+
+    let mut args: Vec<String> = args.into_iter().map(String::from).collect();
+    let mut lint = |flag: &str, name: &str| args.extend([flag.to_string(), name.to_string()]);
+
+    if pedantic {
+        PEDANTIC_GROUPS.iter().for_each(|group| lint("-W", group));
+        PEDANTIC_ALLOWED.iter().for_each(|allowed| lint("-A", allowed));
+    }
+    config.allow.iter().for_each(|allowed| lint("-A", allowed));
+    config.deny.iter().for_each(|denied| lint("-D", denied));
+
+    args
+}
+
+/// Diagnostics with a location from cargo's `--message-format=json` output
+fn parse_diagnostics(output: &str) -> Vec<Diagnostic> {
+    output
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|message| message["reason"] == "compiler-message")
+        .filter_map(|message| {
+            let message = &message["message"];
+            let span = message["spans"].as_array()?.iter().find(|span| span["is_primary"] == true)?;
+
+            Some(Diagnostic {
+                file: span["file_name"].as_str()?.to_string(),
+                line: span["line_start"].as_u64()?,
+                column: span["column_start"].as_u64()?,
+                level: message["level"].as_str()?.to_string(),
+                code: message["code"]["code"].as_str().map(String::from),
+                message: message["message"].as_str()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Print diagnostics grouped by file, or one JSON object per diagnostic
+fn print_diagnostics(diagnostics: &[Diagnostic]) {
+    if CommandUtils::is_json_output() {
+        for diagnostic in diagnostics {
+            println!("{}", serde_json::json!(diagnostic));
         }
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        CommandUtils::error(&format!("Code check failed: {}", stderr));
+        return;
+    }
 
-        // Still show the output as it contains useful information
-        println!("{}", stderr);
+    let mut files: std::collections::BTreeMap<&str, Vec<&Diagnostic>> = std::collections::BTreeMap::new();
+    for diagnostic in diagnostics {
+        files.entry(&diagnostic.file).or_default().push(diagnostic);
     }
 
-    Ok(())
+    for (file, diagnostics) in files {
+        let errors = diagnostics.iter().filter(|d| d.level == "error").count();
+        let warnings = diagnostics.iter().filter(|d| d.level == "warning").count();
+        println!("\n{} ({} errors, {} warnings)", file.bold(), errors, warnings);
+
+        for diagnostic in diagnostics {
+            let level = match diagnostic.level.as_str() {
+                "error" => diagnostic.level.red().bold(),
+                "warning" => diagnostic.level.yellow().bold(),
+                _ => diagnostic.level.normal(),
+            };
+            let code = diagnostic.code.as_ref().map(|code| format!("[{}]", code)).unwrap_or_default();
+            println!("  {}:{} {}{} {}", diagnostic.line, diagnostic.column, level, code.dimmed(), diagnostic.message);
+        }
+    }
+    println!();
 }
 
 async fn generate_docs(open: bool) -> Result<()> {
@@ -179,7 +339,7 @@ pub async fn run_all_checks() -> Result<()> {
 
     // Check code
     CommandUtils::info("Step 2/3: Checking code...");
-    check_code().await?;
+    check_code(false, false).await?;
 
     // Run tests
     CommandUtils::info("Step 3/3: Running tests...");
@@ -206,39 +366,222 @@ async fn run_tests() -> Result<()> {
 }
 
 /// Setup development environment
-pub async fn setup_dev_environment() -> Result<()> {
-    CommandUtils::info("Setting up development environment...");
+/// Check, and unless `check_only` bootstrap, everything needed to work on the project
+pub async fn setup_dev_environment(check_only: bool) -> Result<()> {
+    CommandUtils::info(if check_only {
+        "Checking development environment..."
+    } else {
+        "Setting up development environment..."
+    });
 
-    // Install useful development tools
-    let tools = [
-        ("cargo-watch", "File watcher for automatic rebuilds"),
-        ("cargo-edit", "Commands for editing Cargo.toml"),
-        ("cargo-outdated", "Check for outdated dependencies"),
-        ("cargo-audit", "Security audit for dependencies"),
-    ];
+    let mut checks = vec![check_rust_version(Path::new("Cargo.toml"))];
+    report_setup_check(&checks[0]);
 
-    for (tool, description) in &tools {
-        CommandUtils::info(&format!("Installing {}: {}", tool, description));
+    for tool in load_dev_tools() {
+        let check = check_dev_tool(&tool, check_only);
+        report_setup_check(&check);
+        checks.push(check);
+    }
 
-        let output = std::process::Command::new("cargo")
-            .args(&["install", tool])
-            .output()?;
+    for check in [check_build(), check_database().await] {
+        report_setup_check(&check);
+        checks.push(check);
+    }
 
-        if output.status.success() {
-            CommandUtils::success(&format!("{} installed successfully", tool));
-        } else {
-            CommandUtils::warning(&format!("Failed to install {}", tool));
-        }
+    for dir in STORAGE_DIRS {
+        let check = check_storage_dir(Path::new(dir), check_only);
+        report_setup_check(&check);
+        checks.push(check);
     }
 
-    // Create development configuration files
-    create_dev_config_files().await?;
+    if !check_only {
+        create_dev_config_files().await?;
+    }
 
-    CommandUtils::success("Development environment setup complete");
+    let passed = checks.iter().filter(|check| check.passed).count();
+    let summary = format!("{}/{} checks passed", passed, checks.len());
 
+    if passed < checks.len() {
+        anyhow::bail!(summary);
+    }
+
+    CommandUtils::success(&summary);
     Ok(())
 }
 
+/// Outcome of one `dev setup` check
+#[derive(Debug, PartialEq)]
+struct SetupCheck {
+    name: String,
+    passed: bool,
+    detail: String,
+}
+
+impl SetupCheck {
+    fn new(name: &str, passed: bool, detail: impl Into<String>) -> Self {
+        Self { name: name.to_string(), passed, detail: detail.into() }
+    }
+}
+
+fn report_setup_check(check: &SetupCheck) {
+    let mark = if check.passed { "✓".green() } else { "✗".red() };
+    println!("{} {}: {}", mark, check.name.bold(), check.detail);
+}
+
+/// A tool from `[[dev.tools]]` in rustisan.toml
+///
+/// ```toml
+/// [[dev.tools]]
+/// name = "cargo-watch"
+/// install = "cargo install cargo-watch"
+/// ```
+#[derive(Debug, Clone, PartialEq, serde::Deserialize)]
+struct DevTool {
+    name: String,
+    /// Shell command installing the tool, `cargo install <name>` by default
+    install: Option<String>,
+}
+
+impl DevTool {
+    fn install_command(&self) -> String {
+        self.install.clone().unwrap_or_else(|| format!("cargo install {}", self.name))
+    }
+}
+
+/// Tools checked when `[[dev.tools]]` is not configured
+const DEFAULT_DEV_TOOLS: &[&str] = &["cargo-watch", "cargo-outdated", "cargo-audit"];
+
+/// Directories under `storage/` the application writes to
+const STORAGE_DIRS: &[&str] = &["storage/logs", "storage/cache", "storage/sessions", "storage/uploads"];
+
+fn load_dev_tools() -> Vec<DevTool> {
+    std::fs::read_to_string("rustisan.toml")
+        .ok()
+        .and_then(|content| content.parse::<toml::Value>().ok())
+        .and_then(|config| config.get("dev")?.get("tools").cloned())
+        .and_then(|tools| tools.try_into().ok())
+        .unwrap_or_else(|| {
+            DEFAULT_DEV_TOOLS.iter().map(|name| DevTool { name: name.to_string(), install: None }).collect()
+        })
+}
+
+/// Compare `rustc --version` against the `rust-version` declared in `manifest`
+fn check_rust_version(manifest: &Path) -> SetupCheck {
+    let msrv = std::fs::read_to_string(manifest)
+        .ok()
+        .and_then(|content| content.parse::<toml::Value>().ok())
+        .and_then(|manifest| manifest.get("package")?.get("rust-version")?.as_str().map(str::to_string));
+
+    let installed = std::process::Command::new("rustc")
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8_lossy(&output.stdout).split_whitespace().nth(1).map(str::to_string));
+
+    match (installed, msrv) {
+        (None, _) => SetupCheck::new("Rust version", false, "rustc not found"),
+        (Some(installed), None) => SetupCheck::new("Rust version", true, format!("rustc {} (no rust-version declared)", installed)),
+        (Some(installed), Some(msrv)) => SetupCheck::new(
+            "Rust version",
+            version_at_least(&installed, &msrv),
+            format!("rustc {} (requires {})", installed, msrv),
+        ),
+    }
+}
+
+/// Whether dotted version `installed` is at least `required`, missing components counting as 0
+fn version_at_least(installed: &str, required: &str) -> bool {
+    let parts = |version: &str| -> Vec<u64> {
+        version
+            .split(['.', '-'])
+            .take(3)
+            .map(|part| part.parse().unwrap_or(0))
+            .chain(std::iter::repeat(0))
+            .take(3)
+            .collect()
+    };
+    parts(installed) >= parts(required)
+}
+
+/// Check a tool is on the PATH, installing it first unless `check_only`
+fn check_dev_tool(tool: &DevTool, check_only: bool) -> SetupCheck {
+    if CommandUtils::command_exists(&tool.name) {
+        return SetupCheck::new(&tool.name, true, "installed");
+    }
+    if check_only {
+        return SetupCheck::new(&tool.name, false, format!("not installed, run `{}`", tool.install_command()));
+    }
+
+    CommandUtils::info(&format!("Installing {}...", tool.name));
+    let (shell, flag) = ProcessUtils::get_shell_command();
+    let installed = std::process::Command::new(shell)
+        .args([flag, &tool.install_command()])
+        .status()
+        .is_ok_and(|status| status.success());
+
+    if installed {
+        SetupCheck::new(&tool.name, true, "installed")
+    } else {
+        SetupCheck::new(&tool.name, false, format!("`{}` failed", tool.install_command()))
+    }
+}
+
+/// Build the project, reporting the first compilation error
+fn check_build() -> SetupCheck {
+    match ProcessUtils::execute_with_output("cargo", &["build", "--quiet"]) {
+        Ok((true, _, _)) => SetupCheck::new("Build", true, "cargo build succeeded"),
+        Ok((false, _, stderr)) => {
+            let error = stderr.lines().find(|line| line.starts_with("error")).unwrap_or("cargo build failed");
+            SetupCheck::new("Build", false, error)
+        }
+        Err(e) => SetupCheck::new("Build", false, e.to_string()),
+    }
+}
+
+/// Connect to the default database connection
+async fn check_database() -> SetupCheck {
+    use super::db::{get_config_value, load_config};
+
+    let Some(config) = load_config().ok() else {
+        return SetupCheck::new("Database", false, "rustisan.toml not found");
+    };
+    let value = |key: &str, default: &str| {
+        get_config_value(&config, &format!("database.connections.default.{}", key)).unwrap_or_else(|| default.to_string())
+    };
+    let Some(driver) = get_config_value(&config, "database.connections.default.driver") else {
+        return SetupCheck::new("Database", true, "no database configured");
+    };
+
+    match super::db::test_connection(&driver, &value("host", "localhost"), &value("port", "3306"), &value("database", "")).await {
+        Ok(()) => SetupCheck::new("Database", true, format!("{} connection succeeded", driver)),
+        Err(e) => SetupCheck::new("Database", false, e.to_string()),
+    }
+}
+
+/// Check a storage directory exists and is writable, creating it first unless `check_only`
+fn check_storage_dir(dir: &Path, check_only: bool) -> SetupCheck {
+    let name = dir.display().to_string();
+
+    if !dir.is_dir() {
+        if check_only {
+            return SetupCheck::new(&name, false, "missing");
+        }
+        if let Err(e) = std::fs::create_dir_all(dir) {
+            return SetupCheck::new(&name, false, format!("cannot be created: {}", e));
+        }
+    }
+
+    let probe = dir.join(".rustisan-write-check");
+    match std::fs::write(&probe, b"") {
+        Ok(()) => {
+            let _ = std::fs::remove_file(&probe);
+            SetupCheck::new(&name, true, "writable")
+        }
+        Err(e) => SetupCheck::new(&name, false, format!("not writable: {}", e)),
+    }
+}
+
 async fn create_dev_config_files() -> Result<()> {
     // Create .rustfmt.toml if it doesn't exist
     if !std::path::Path::new(".rustfmt.toml").exists() {
@@ -299,62 +642,573 @@ l = "clippy"
     Ok(())
 }
 
-/// Profile the application
-pub async fn profile_app() -> Result<()> {
-    CommandUtils::info("Profiling application...");
+/// Format a profile is written in
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ProfileFormat {
+    /// pprof protobuf, readable by `go tool pprof`
+    Protobuf,
+    /// Flamegraph SVG
+    Flamegraph,
+}
+
+/// Profile the application server for `duration` seconds
+///
+/// The release binary runs in a background task while pprof samples this
+/// process, so the profile covers the work done in-process; frames of the
+/// server itself need pprof linked into the application.
+pub async fn profile_app(duration: u64, output: Option<String>, flamegraph: bool) -> Result<()> {
+    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S").to_string();
+    let (path, format) = profile_path(output, flamegraph, &timestamp)?;
+
+    let binary = super::build::binary_path("release");
+    if !Path::new(&binary).exists() {
+        CommandUtils::info("Building application in release mode...");
+        ProcessUtils::execute_or_fail("cargo", &["build", "--release"])?;
+    }
+
+    CommandUtils::info("Starting application server...");
+    let mut child = tokio::process::Command::new(&binary).kill_on_drop(true).spawn()?;
+    let server = tokio::spawn(async move { child.wait().await });
 
-    // Check if profiling tools are available
-    let tools = ["perf", "valgrind", "cargo-profdata"];
+    CommandUtils::info(&format!("Profiling for {} seconds...", duration));
+    let result = capture_profile(Duration::from_secs(duration), &path, format).await;
 
-    for tool in &tools {
-        let available = std::process::Command::new("which")
-            .arg(tool)
-            .output()
-            .map(|output| output.status.success())
-            .unwrap_or(false);
+    // The task owns the child, aborting it drops the child and kills the server
+    server.abort();
+    result?;
 
-        if available {
-            CommandUtils::info(&format!("{} is available", tool));
-        } else {
-            CommandUtils::warning(&format!("{} is not available", tool));
+    CommandUtils::success(&format!("Profile written to {}", path.display()));
+
+    Ok(())
+}
+
+/// Where to write a profile and in which format, from `--output` or `--flamegraph`
+fn profile_path(output: Option<String>, flamegraph: bool, timestamp: &str) -> Result<(PathBuf, ProfileFormat)> {
+    let path = match output {
+        Some(output) => PathBuf::from(output),
+        None => {
+            let extension = if flamegraph { "svg" } else { "pb" };
+            Path::new(PROFILES_DIR).join(format!("{}.{}", timestamp, extension))
         }
+    };
+
+    let format = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("pb") => ProfileFormat::Protobuf,
+        Some("svg") => ProfileFormat::Flamegraph,
+        _ => anyhow::bail!("Unsupported profile output {}, use a .pb or .svg file", path.display()),
+    };
+
+    Ok((path, format))
+}
+
+/// Sample this process for `duration` and write the report to `path`
+#[cfg(unix)]
+async fn capture_profile(duration: Duration, path: &Path, format: ProfileFormat) -> Result<()> {
+    use pprof::protos::Message;
+
+    let guard = pprof::ProfilerGuard::new(SAMPLING_FREQUENCY)?;
+    tokio::time::sleep(duration).await;
+    let report = guard.report().build()?;
+
+    let mut content = Vec::new();
+    match format {
+        ProfileFormat::Protobuf => report.pprof()?.write_to_vec(&mut content)?,
+        ProfileFormat::Flamegraph => report.flamegraph(&mut content)?,
     }
 
-    // Build with profiling symbols
-    CommandUtils::info("Building with profiling symbols...");
-    let output = std::process::Command::new("cargo")
-        .args(&["build", "--release", "--profile", "profiling"])
-        .output()?;
+    if let Some(parent) = path.parent() {
+        CommandUtils::ensure_directory(parent)?;
+    }
+    std::fs::write(path, content)?;
 
-    if output.status.success() {
-        CommandUtils::success("Profiling build complete");
-        CommandUtils::info("Run your application with profiling tools:");
-        CommandUtils::info("  perf record ./target/release/rustisan");
-        CommandUtils::info("  valgrind --tool=callgrind ./target/release/rustisan");
+    Ok(())
+}
+
+#[cfg(not(unix))]
+async fn capture_profile(_duration: Duration, _path: &Path, _format: ProfileFormat) -> Result<()> {
+    anyhow::bail!("Profiling is only supported on Unix")
+}
+
+/// Timing of one criterion benchmark, in nanoseconds
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+struct BenchmarkResult {
+    name: String,
+    lower: f64,
+    median: f64,
+    upper: f64,
+}
+
+/// A `storage/benchmarks/{timestamp}.json` file
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct BenchmarkSummary {
+    timestamp: String,
+    benchmarks: Vec<BenchmarkResult>,
+}
+
+/// Generate, run and compare criterion benchmarks
+async fn benchmark(generate: bool, run: bool, compare: Option<String>) -> Result<()> {
+    if generate {
+        generate_benchmarks()?;
+    }
+
+    let latest = if run || !(generate || compare.is_some()) {
+        Some(run_benchmarks()?)
     } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        CommandUtils::error(&format!("Profiling build failed: {}", stderr));
+        None
+    };
+
+    if let Some(old) = compare {
+        let new = match latest {
+            Some(path) => path,
+            None => latest_benchmark_summary()?,
+        };
+        compare_benchmarks(Path::new(&old), &new)?;
+    }
+
+    Ok(())
+}
+
+/// Write benches/benchmarks.rs and register it with criterion in Cargo.toml
+fn generate_benchmarks() -> Result<()> {
+    if Path::new(BENCHMARK_FILE).exists() {
+        CommandUtils::warning(&format!("{} already exists, leaving it untouched", BENCHMARK_FILE));
+        return Ok(());
+    }
+
+    let functions = service_functions(Path::new("src/services"))?;
+    if functions.is_empty() {
+        CommandUtils::warning("No public functions found in src/services/, generating an example benchmark");
+    }
+
+    CommandUtils::ensure_directory(Path::new("benches"))?;
+    FileUtils::atomic_write(BENCHMARK_FILE, &benchmark_source(&functions))?;
+    CommandUtils::success(&format!("Benchmarks created: {} ({} stub(s))", BENCHMARK_FILE, functions.len()));
+
+    let manifest_path = Path::new("Cargo.toml");
+    let mut manifest: toml_edit::DocumentMut = std::fs::read_to_string(manifest_path)?.parse()?;
+    if register_benchmark(&mut manifest) {
+        FileUtils::atomic_write(manifest_path, &manifest.to_string())?;
+        CommandUtils::info("Added criterion and the benchmarks target to Cargo.toml");
     }
 
     Ok(())
 }
 
-/// Benchmark the application
-pub async fn benchmark() -> Result<()> {
+/// Public functions of each service module, as `(module, function)`
+fn service_functions(dir: &Path) -> Result<Vec<(String, String)>> {
+    let function = regex::Regex::new(r"(?m)^\s*pub\s+(?:async\s+)?fn\s+(\w+)").unwrap();
+    let mut functions = Vec::new();
+
+    if !dir.exists() {
+        return Ok(functions);
+    }
+
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs") && !path.ends_with("mod.rs"))
+        .collect();
+    files.sort();
+
+    for path in files {
+        let module = path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        let source = std::fs::read_to_string(&path)?;
+        for captures in function.captures_iter(&source) {
+            let name = captures[1].to_string();
+            if !functions.contains(&(module.clone(), name.clone())) {
+                functions.push((module.clone(), name));
+            }
+        }
+    }
+
+    Ok(functions)
+}
+
+/// Source of benches/benchmarks.rs with one stub per service function
+fn benchmark_source(functions: &[(String, String)]) -> String {
+    let stubs: String = if functions.is_empty() {
+        "    group.bench_function(\"example\", |b| b.iter(|| black_box(2 + 2)));\n".to_string()
+    } else {
+        functions
+            .iter()
+            .map(|(module, function)| {
+                format!(
+                    "    group.bench_function(\"{module}::{function}\", |b| {{\n        b.iter(|| {{\n            // TODO: call services::{module}::{function} with representative input\n            black_box(())\n        }})\n    }});\n",
+                    module = module,
+                    function = function
+                )
+            })
+            .collect()
+    };
+
+    format!(
+        r#"//! Benchmarks generated by Rustisan CLI
+//!
+//! Run with `rustisan dev benchmark --run` or `cargo bench`.
+
+use criterion::{{black_box, criterion_group, criterion_main, Criterion}};
+
+fn services(c: &mut Criterion) {{
+    let mut group = c.benchmark_group("services");
+{stubs}    group.finish();
+}}
+
+criterion_group!(benches, services);
+criterion_main!(benches);
+"#,
+        stubs = stubs
+    )
+}
+
+/// Add criterion to `[dev-dependencies]` and a `[[bench]]` target without the default harness
+///
+/// Returns whether the manifest changed.
+fn register_benchmark(manifest: &mut toml_edit::DocumentMut) -> bool {
+    let mut changed = false;
+
+    let dev_dependencies = manifest
+        .entry("dev-dependencies")
+        .or_insert(toml_edit::Item::Table(toml_edit::Table::new()));
+    if let Some(dev_dependencies) = dev_dependencies.as_table_like_mut()
+        && !dev_dependencies.contains_key("criterion")
+    {
+        dev_dependencies.insert("criterion", toml_edit::value("0.5"));
+        changed = true;
+    }
+
+    let benches = manifest
+        .entry("bench")
+        .or_insert(toml_edit::Item::ArrayOfTables(toml_edit::ArrayOfTables::new()));
+    if let Some(benches) = benches.as_array_of_tables_mut()
+        && !benches.iter().any(|bench| bench.get("name").and_then(|item| item.as_str()) == Some("benchmarks"))
+    {
+        let mut bench = toml_edit::Table::new();
+        bench["name"] = toml_edit::value("benchmarks");
+        bench["harness"] = toml_edit::value(false);
+        benches.push(bench);
+        changed = true;
+    }
+
+    changed
+}
+
+/// Run `cargo bench` and save the parsed timings, returning the summary path
+fn run_benchmarks() -> Result<PathBuf> {
     CommandUtils::info("Running benchmarks...");
 
-    let output = std::process::Command::new("cargo")
-        .args(&["bench"])
-        .output()?;
+    let output = std::process::Command::new("cargo").arg("bench").output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    print!("{}", stdout);
 
-    if output.status.success() {
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        println!("{}", stdout);
-        CommandUtils::success("Benchmarks completed");
+    if !output.status.success() {
+        anyhow::bail!("Benchmarks failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+
+    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S").to_string();
+    let summary = BenchmarkSummary { timestamp: timestamp.clone(), benchmarks: parse_criterion_output(&stdout) };
+    if summary.benchmarks.is_empty() {
+        CommandUtils::warning("No criterion timings found in the output");
+    }
+
+    CommandUtils::ensure_directory(Path::new(BENCHMARKS_DIR))?;
+    let path = Path::new(BENCHMARKS_DIR).join(format!("{}.json", timestamp));
+    std::fs::write(&path, serde_json::to_string_pretty(&summary)?)?;
+
+    CommandUtils::success(&format!("Benchmarks completed, summary saved to {}", path.display()));
+    Ok(path)
+}
+
+/// Parse criterion's `time: [low median high]` lines
+///
+/// The benchmark name precedes `time:` on the same line, or sits on the line
+/// before when it is too long.
+fn parse_criterion_output(output: &str) -> Vec<BenchmarkResult> {
+    let mut results = Vec::new();
+    let mut previous = "";
+
+    for line in output.lines() {
+        let Some((name, timing)) = line.split_once("time:") else {
+            if !line.trim().is_empty() {
+                previous = line.trim();
+            }
+            continue;
+        };
+
+        let values: Vec<f64> = timing
+            .trim()
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .chunks(2)
+            .filter_map(|pair| Some(pair[0].parse::<f64>().ok()? * unit_nanoseconds(pair.get(1)?)?))
+            .collect();
+
+        if let [lower, median, upper] = values[..] {
+            let name = if name.trim().is_empty() { previous } else { name.trim() };
+            results.push(BenchmarkResult { name: name.to_string(), lower, median, upper });
+        }
+    }
+
+    results
+}
+
+/// Nanoseconds in one criterion time unit
+fn unit_nanoseconds(unit: &str) -> Option<f64> {
+    match unit {
+        "ps" => Some(0.001),
+        "ns" => Some(1.0),
+        "µs" | "us" => Some(1_000.0),
+        "ms" => Some(1_000_000.0),
+        "s" => Some(1_000_000_000.0),
+        _ => None,
+    }
+}
+
+/// Most recent summary in storage/benchmarks/
+fn latest_benchmark_summary() -> Result<PathBuf> {
+    let mut summaries: Vec<PathBuf> = std::fs::read_dir(BENCHMARKS_DIR)
+        .map_err(|_| anyhow::anyhow!("No benchmark summaries in {}, run with --run first", BENCHMARKS_DIR))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    summaries.sort();
+    summaries.pop().ok_or_else(|| anyhow::anyhow!("No benchmark summaries in {}, run with --run first", BENCHMARKS_DIR))
+}
+
+fn read_summary(path: &Path) -> Result<BenchmarkSummary> {
+    let content = std::fs::read_to_string(path).map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&content).map_err(|e| anyhow::anyhow!("Invalid benchmark summary {}: {}", path.display(), e))
+}
+
+/// Relative change of the median time of each benchmark present in both summaries
+fn benchmark_changes(old: &BenchmarkSummary, new: &BenchmarkSummary) -> Vec<(String, f64)> {
+    new.benchmarks
+        .iter()
+        .filter_map(|result| {
+            let previous = old.benchmarks.iter().find(|previous| previous.name == result.name)?;
+            (previous.median > 0.0).then(|| (result.name.clone(), (result.median - previous.median) / previous.median))
+        })
+        .collect()
+}
+
+/// Print each benchmark's change, regressions in red and improvements in green
+fn compare_benchmarks(old: &Path, new: &Path) -> Result<()> {
+    let changes = benchmark_changes(&read_summary(old)?, &read_summary(new)?);
+    CommandUtils::info(&format!("Comparing {} against {}", new.display(), old.display()));
+
+    if changes.is_empty() {
+        CommandUtils::warning("No benchmarks in common");
+        return Ok(());
+    }
+
+    let mut regressions = 0;
+    for (name, change) in &changes {
+        let line = format!("  {:<50} {:+.1}%", name, change * 100.0);
+        if *change > BENCHMARK_THRESHOLD {
+            regressions += 1;
+            println!("{}", line.red());
+        } else if *change < -BENCHMARK_THRESHOLD {
+            println!("{}", line.green());
+        } else {
+            println!("{}", line.dimmed());
+        }
+    }
+
+    if regressions > 0 {
+        CommandUtils::warning(&format!("{} benchmark(s) regressed by more than 10%", regressions));
     } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        CommandUtils::error(&format!("Benchmarks failed: {}", stderr));
+        CommandUtils::success("No regressions");
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_only_rust_version_has_no_side_effects() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let manifest = dir.path().join("Cargo.toml");
+        std::fs::write(&manifest, "[package]\nname = \"shop\"\nversion = \"0.1.0\"\nrust-version = \"1.56\"\n").unwrap();
+
+        let check = check_rust_version(&manifest);
+        assert!(check.passed, "{:?}", check);
+        assert!(check.detail.contains("requires 1.56"));
+
+        std::fs::write(&manifest, "[package]\nname = \"shop\"\nrust-version = \"99.0\"\n").unwrap();
+        assert!(!check_rust_version(&manifest).passed);
+
+        let missing = dir.path().join("storage/logs");
+        assert!(!check_storage_dir(&missing, true).passed);
+
+        let entries: Vec<_> = std::fs::read_dir(dir.path()).unwrap().map(|entry| entry.unwrap().file_name()).collect();
+        assert_eq!(entries, ["Cargo.toml"]);
+    }
+
+    #[test]
+    fn test_version_at_least() {
+        assert!(version_at_least("1.95.0", "1.70"));
+        assert!(version_at_least("1.70.0", "1.70.0"));
+        assert!(version_at_least("1.80.0-nightly", "1.80"));
+        assert!(!version_at_least("1.69.2", "1.70"));
+        assert!(!version_at_least("1.9.0", "1.10.0"));
+    }
+
+    #[test]
+    fn test_parse_criterion_output() {
+        let output = "\
+Benchmarking services/user_service::create_user: Analyzing
+services/user_service::create_user
+                        time:   [1.2034 µs 1.2156 µs 1.2290 µs]
+                        change: [-2.1% +0.4% +3.0%] (p = 0.71 > 0.05)
+fib 20                  time:   [26.029 ns 26.251 ns 26.505 ns]
+Found 3 outliers among 100 measurements (3.00%)
+";
+
+        let results = parse_criterion_output(output);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].name, "services/user_service::create_user");
+        assert!((results[0].median - 1215.6).abs() < 1e-6);
+        assert_eq!(results[1].name, "fib 20");
+        assert!((results[1].median - 26.251).abs() < 1e-9);
+        assert!(results[1].lower < results[1].median && results[1].median < results[1].upper);
+    }
+
+    #[test]
+    fn test_benchmark_changes() {
+        let summary = |median: f64| BenchmarkSummary {
+            timestamp: String::new(),
+            benchmarks: vec![BenchmarkResult { name: "fib".to_string(), lower: median, median, upper: median }],
+        };
+
+        let changes = benchmark_changes(&summary(100.0), &summary(125.0));
+        assert_eq!(changes, [("fib".to_string(), 0.25)]);
+    }
+
+    #[test]
+    fn test_generate_benchmarks_source() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("user_service.rs"),
+            "impl UserService {\n    pub async fn create_user(&self) {}\n    fn helper() {}\n    pub fn find(&self) {}\n}\n",
+        )
+        .unwrap();
+
+        let functions = service_functions(dir.path()).unwrap();
+        assert_eq!(
+            functions,
+            [
+                ("user_service".to_string(), "create_user".to_string()),
+                ("user_service".to_string(), "find".to_string())
+            ]
+        );
+        assert!(benchmark_source(&functions).contains("group.bench_function(\"user_service::find\""));
+
+        let mut manifest: toml_edit::DocumentMut = "[package]\nname = \"shop\"\n".parse().unwrap();
+        assert!(register_benchmark(&mut manifest));
+        assert!(!register_benchmark(&mut manifest));
+        assert_eq!(manifest["dev-dependencies"]["criterion"].as_str(), Some("0.5"));
+        assert_eq!(manifest["bench"][0]["harness"].as_bool(), Some(false));
+    }
+
+    #[test]
+    fn test_clippy_args() {
+        let args = clippy_args(false, false, &ClippyConfig::default());
+        assert_eq!(args, ["clippy", "--all-targets", "--all-features", "--message-format=json", "--", "-D", "warnings"]);
+
+        let config = ClippyConfig { allow: vec!["clippy::too_many_lines".to_string()], deny: vec!["clippy::unwrap_used".to_string()] };
+        let args = clippy_args(true, false, &config);
+        assert_eq!(
+            args[7..],
+            [
+                "-W",
+                "clippy::pedantic",
+                "-W",
+                "clippy::nursery",
+                "-W",
+                "clippy::cargo",
+                "-A",
+                "clippy::module_name_repetitions",
+                "-A",
+                "clippy::missing_errors_doc",
+                "-A",
+                "clippy::missing_panics_doc",
+                "-A",
+                "clippy::multiple_crate_versions",
+                "-A",
+                "clippy::cargo_common_metadata",
+                "-A",
+                "clippy::too_many_lines",
+                "-D",
+                "clippy::unwrap_used",
+            ]
+        );
+
+        let args = clippy_args(false, true, &ClippyConfig::default());
+        assert_eq!(args, ["clippy", "--fix", "--allow-dirty", "--all-targets", "--all-features", "--message-format=json", "--"]);
+    }
+
+    #[test]
+    fn test_parse_diagnostics() {
+        let output = concat!(
+            r#"{"reason":"compiler-artifact","target":{"name":"app"}}"#,
+            "\n",
+            r#"{"reason":"compiler-message","message":{"level":"warning","message":"unused variable: `x`","code":{"code":"unused_variables"},"spans":[{"file_name":"src/main.rs","line_start":3,"column_start":9,"is_primary":true}]}}"#,
+            "\n",
+            r#"{"reason":"compiler-message","message":{"level":"warning","message":"1 warning emitted","code":null,"spans":[]}}"#,
+            "\n",
+        );
+
+        assert_eq!(
+            parse_diagnostics(output),
+            [Diagnostic {
+                file: "src/main.rs".to_string(),
+                line: 3,
+                column: 9,
+                level: "warning".to_string(),
+                code: Some("unused_variables".to_string()),
+                message: "unused variable: `x`".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_profile_path() {
+        assert_eq!(
+            profile_path(None, false, "20250101_120000").unwrap(),
+            (PathBuf::from("storage/profiles/20250101_120000.pb"), ProfileFormat::Protobuf)
+        );
+        assert_eq!(
+            profile_path(None, true, "20250101_120000").unwrap(),
+            (PathBuf::from("storage/profiles/20250101_120000.svg"), ProfileFormat::Flamegraph)
+        );
+        assert_eq!(
+            profile_path(Some("cpu.svg".to_string()), false, "20250101_120000").unwrap(),
+            (PathBuf::from("cpu.svg"), ProfileFormat::Flamegraph)
+        );
+        assert!(profile_path(Some("cpu.txt".to_string()), false, "20250101_120000").is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_capture_profile() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("profiles/cpu.pb");
+
+        // Keep a thread busy so the profiler has samples to record
+        let busy = std::thread::spawn(|| {
+            let start = std::time::Instant::now();
+            let mut counter = 0u64;
+            while start.elapsed() < Duration::from_millis(1200) {
+                counter = std::hint::black_box(counter.wrapping_add(1));
+            }
+        });
+
+        capture_profile(Duration::from_secs(1), &path, ProfileFormat::Protobuf).await.unwrap();
+        busy.join().unwrap();
+
+        assert!(std::fs::metadata(&path).unwrap().len() > 0);
+    }
+}
@@ -1,15 +1,45 @@
 //! Info command implementations for the Rustisan CLI
 
+mod complexity;
+mod watch;
+
 use anyhow::Result;
 use colored::*;
-use super::CommandUtils;
+use std::path::{Path, PathBuf};
+use super::{CommandUtils, OutputFormat};
+use crate::utils::TextUtils;
+
+/// Directory project statistics are saved to by `info --detailed`
+const STATS_DIR: &str = "storage/stats";
+
+/// Number of files and functions listed as most complex
+const TOP_COMPLEX: usize = 5;
+
+/// Options of the info command
+#[derive(Debug, Default)]
+pub struct InfoOptions {
+    pub detailed: bool,
+    pub complexity_threshold: Option<usize>,
+    pub compare_last: bool,
+    /// Refresh interval in seconds of `--watch`
+    pub watch: Option<u64>,
+    pub no_clear: bool,
+}
 
 /// Handle info command
-pub async fn handle(detailed: bool) -> Result<()> {
+pub async fn handle(options: InfoOptions, format: OutputFormat) -> Result<()> {
     CommandUtils::ensure_rustisan_project()?;
 
-    if detailed {
-        show_detailed_info().await
+    if let Some(seconds) = options.watch {
+        return watch_info(std::time::Duration::from_secs(seconds), !options.no_clear).await;
+    }
+
+    if format == OutputFormat::Json {
+        return show_json_info(&options).await;
+    }
+
+    if options.detailed {
+        show_detailed_info(&options).await
     } else {
         show_basic_info().await
     }
@@ -27,21 +57,69 @@ async fn show_basic_info() -> Result<()> {
     Ok(())
 }
 
-async fn show_detailed_info() -> Result<()> {
+/// Re-render the basic info with live process, disk and server metrics every `interval`
+async fn watch_info(interval: std::time::Duration, clear: bool) -> Result<()> {
+    let app_info = gather_app_info()?;
+    let system_info = gather_system_info()?;
+    let basic_info = basic_info_text(&app_info, &system_info);
+    let mut monitor = watch::Monitor::new(Path::new("."));
+
+    watch::watch_loop(interval, clear, &mut std::io::stdout(), || {
+        Ok(format!("{}\n{}", basic_info, monitor.sample(interval).render()))
+    })
+    .await
+}
+
+async fn show_detailed_info(options: &InfoOptions) -> Result<()> {
     CommandUtils::info("Gathering detailed application information...");
 
     let app_info = gather_app_info()?;
     let system_info = gather_system_info()?;
     let dependencies = gather_dependencies()?;
     let environment = gather_environment_info()?;
+    let stats = gather_full_stats()?;
+    let previous = if options.compare_last { last_saved_stats()? } else { None };
 
     print_app_header(&app_info);
     print_detailed_info(&app_info, &system_info, &dependencies, &environment);
+    print_project_statistics(&stats, previous.as_ref());
+    warn_complex_files(&stats, options.complexity_threshold);
+
+    let path = save_stats(&stats)?;
+    log::debug!("Saved project statistics to {}", path.display());
+
+    Ok(())
+}
+
+async fn show_json_info(options: &InfoOptions) -> Result<()> {
+    let detailed = options.detailed;
+    let app_info = gather_app_info()?;
+    let system_info = gather_system_info()?;
+
+    let mut info = serde_json::json!({
+        "app": app_info,
+        "system": system_info,
+    });
+
+    if detailed {
+        info["dependencies"] = serde_json::to_value(gather_dependencies()?)?;
+        info["environment"] = serde_json::to_value(mask_environment(&gather_environment_info()?))?;
+        let stats = gather_full_stats()?;
+        if options.compare_last
+            && let Some(previous) = last_saved_stats()?
+        {
+            info["statistics_delta"] = serde_json::to_value(stats_deltas(&stats, &previous))?;
+        }
+        info["statistics"] = serde_json::to_value(&stats)?;
+        save_stats(&stats)?;
+    }
+
+    println!("{}", info);
 
     Ok(())
 }
 
-#[derive(Debug)]
+#[derive(Debug, serde::Serialize)]
 struct AppInfo {
     name: String,
     version: String,
@@ -50,9 +128,11 @@ struct AppInfo {
     edition: String,
     repository: Option<String>,
     license: Option<String>,
+    git_commit: Option<String>,
+    git_tag: Option<String>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, serde::Serialize)]
 struct SystemInfo {
     rustc_version: String,
     cargo_version: String,
@@ -61,7 +141,7 @@ struct SystemInfo {
     architecture: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, serde::Serialize)]
 struct DependencyInfo {
     name: String,
     version: String,
@@ -113,6 +193,8 @@ fn gather_app_info() -> Result<AppInfo> {
         .and_then(|v| v.as_str())
         .map(|s| s.to_string());
 
+    let (git_commit, git_tag) = gather_git_version();
+
     Ok(AppInfo {
         name,
         version,
@@ -121,9 +203,33 @@ fn gather_app_info() -> Result<AppInfo> {
         edition,
         repository,
         license,
+        git_commit,
+        git_tag,
     })
 }
 
+/// Commit and tag embedded by `build --git-version`, or read from Git when not built with it
+fn gather_git_version() -> (Option<String>, Option<String>) {
+    if let Ok(source) = std::fs::read_to_string(super::build::VERSION_FILE) {
+        return (version_const(&source, "GIT_COMMIT"), version_const(&source, "GIT_TAG"));
+    }
+
+    let dir = std::path::Path::new(".");
+    match super::build::git_commit(dir) {
+        Some(commit) => (Some(commit), Some(super::build::git_tag(dir))),
+        None => (None, None),
+    }
+}
+
+/// Value of a `pub const NAME: &str = "...";` line
+fn version_const(source: &str, name: &str) -> Option<String> {
+    let prefix = format!("pub const {}: &str = \"", name);
+    source
+        .lines()
+        .find_map(|line| line.strip_prefix(&prefix)?.strip_suffix("\";"))
+        .map(|value| value.to_string())
+}
+
 fn gather_system_info() -> Result<SystemInfo> {
     let rustc_version = get_rustc_version()?;
     let cargo_version = get_cargo_version()?;
@@ -259,21 +365,42 @@ fn print_app_header(app_info: &AppInfo) {
 }
 
 fn print_basic_info(app_info: &AppInfo, system_info: &SystemInfo) {
-    println!("{}", "Application Information:".bold());
-    println!("┌─────────────────────────────────────────────────────────────────────────────┐");
-    println!("│ {} │ {} │", "Name".bold(), app_info.name);
-    println!("│ {} │ {} │", "Version".bold(), app_info.version);
-    println!("│ {} │ {} │", "Edition".bold(), app_info.edition);
-    println!("└─────────────────────────────────────────────────────────────────────────────┘");
-
-    println!("\n{}", "System Information:".bold());
-    println!("┌─────────────────────────────────────────────────────────────────────────────┐");
-    println!("│ {} │ {} │", "Rust Compiler".bold(), system_info.rustc_version);
-    println!("│ {} │ {} │", "Cargo Version".bold(), system_info.cargo_version);
-    println!("│ {} │ {} │", "Operating System".bold(), system_info.os);
-    println!("│ {} │ {} │", "Architecture".bold(), system_info.architecture);
-    println!("│ {} │ {} │", "Target Triple".bold(), system_info.target_triple);
-    println!("└─────────────────────────────────────────────────────────────────────────────┘");
+    print!("{}", basic_info_text(app_info, system_info));
+}
+
+/// Application and system tables shown by `info` and each `info --watch` frame
+fn basic_info_text(app_info: &AppInfo, system_info: &SystemInfo) -> String {
+    const WIDTHS: &[usize] = &[18, 55];
+
+    let row = |label: &str, value: &str| vec![label.bold().to_string(), value.to_string()];
+
+    let mut app_rows = vec![
+        row("Name", &app_info.name),
+        row("Version", &app_info.version),
+        row("Edition", &app_info.edition),
+    ];
+    if let Some(ref commit) = app_info.git_commit {
+        app_rows.push(row("Git Commit", commit));
+    }
+    if let Some(ref tag) = app_info.git_tag {
+        app_rows.push(row("Git Tag", tag));
+    }
+
+    let system_rows = [
+        row("Rust Compiler", &system_info.rustc_version),
+        row("Cargo Version", &system_info.cargo_version),
+        row("Operating System", &system_info.os),
+        row("Architecture", &system_info.architecture),
+        row("Target Triple", &system_info.target_triple),
+    ];
+
+    format!(
+        "{}\n{}\n\n{}\n{}\n",
+        "Application Information:".bold(),
+        TextUtils::table_format(&["Property", "Value"], &app_rows, WIDTHS),
+        "System Information:".bold(),
+        TextUtils::table_format(&["Property", "Value"], &system_rows, WIDTHS),
+    )
 }
 
 fn print_detailed_info(
@@ -315,20 +442,7 @@ fn print_detailed_info(
         println!("│ {} │ {} │", "Variable".bold(), "Value".bold());
         println!("├─────────────────────────────────────────────────────────────────────────────┤");
 
-        let mut env_vars: Vec<_> = environment.iter().collect();
-        env_vars.sort_by(|a, b| a.0.cmp(b.0));
-
-        for (key, value) in env_vars {
-            // Mask sensitive values
-            let masked_value = if key.to_lowercase().contains("password") ||
-                                key.to_lowercase().contains("secret") ||
-                                key.to_lowercase().contains("token") ||
-                                key.to_lowercase().contains("key") {
-                "***".to_string()
-            } else {
-                value.clone()
-            };
-
+        for (key, masked_value) in mask_environment(environment) {
             println!("│ {} │ {} │",
                 format!("{:20}", key),
                 format!("{:50}", masked_value)
@@ -337,68 +451,290 @@ fn print_detailed_info(
 
         println!("└─────────────────────────────────────────────────────────────────────────────┘");
     }
+}
 
-    // Project Statistics
-    print_project_statistics();
+/// Sort environment variables by name and mask sensitive values
+fn mask_environment(environment: &std::collections::HashMap<String, String>) -> std::collections::BTreeMap<String, String> {
+    environment
+        .iter()
+        .map(|(key, value)| {
+            let lower = key.to_lowercase();
+            let masked_value = if lower.contains("password") ||
+                                lower.contains("secret") ||
+                                lower.contains("token") ||
+                                lower.contains("key") {
+                "***".to_string()
+            } else {
+                value.clone()
+            };
+            (key.clone(), masked_value)
+        })
+        .collect()
 }
 
-fn print_project_statistics() {
+fn print_project_statistics(stats: &ProjectStats, previous: Option<&ProjectStats>) {
+    let deltas = previous.map(|previous| stats_deltas(stats, previous));
+    let rows: Vec<Vec<String>> = stats
+        .summary()
+        .into_iter()
+        .enumerate()
+        .map(|(index, (label, value))| {
+            let mut row = vec![label.bold().to_string(), value.to_string()];
+            if let Some(ref deltas) = deltas {
+                row.push(format_delta(deltas[index].1));
+            }
+            row
+        })
+        .collect();
+
+    let headers: &[&str] = if deltas.is_some() { &["Metric", "Value", "Change"] } else { &["Metric", "Value"] };
     println!("\n{}", "Project Statistics:".bold());
-    println!("┌─────────────────────────────────────────────────────────────────────────────┐");
+    println!("{}", TextUtils::table_format(headers, &rows, &[18, 12, 10]));
+
+    if !stats.files.is_empty() {
+        let files: Vec<Vec<String>> = stats
+            .files
+            .iter()
+            .take(TOP_COMPLEX)
+            .map(|file| vec![file.path.clone(), file.complexity.to_string()])
+            .collect();
+        println!("\n{}", "Most Complex Files:".bold());
+        println!("{}", TextUtils::table_format(&["File", "Complexity"], &files, &[55, 10]));
+
+        let functions: Vec<Vec<String>> = stats
+            .most_complex_functions(TOP_COMPLEX)
+            .into_iter()
+            .map(|(path, function, complexity)| vec![format!("{}::{}", path, function), complexity.to_string()])
+            .collect();
+        println!("\n{}", "Most Complex Functions:".bold());
+        println!("{}", TextUtils::table_format(&["Function", "Complexity"], &functions, &[55, 10]));
+    }
+}
 
-    let stats = calculate_project_stats();
+fn format_delta(delta: i64) -> String {
+    match delta {
+        0 => "0".dimmed().to_string(),
+        delta if delta > 0 => format!("+{}", delta),
+        delta => delta.to_string(),
+    }
+}
 
-    println!("│ {} │ {} │", "Source Files".bold(), stats.source_files);
-    println!("│ {} │ {} │", "Test Files".bold(), stats.test_files);
-    println!("│ {} │ {} │", "Total Lines".bold(), stats.total_lines);
-    println!("│ {} │ {} │", "Code Lines".bold(), stats.code_lines);
-    println!("│ {} │ {} │", "Comment Lines".bold(), stats.comment_lines);
-    println!("│ {} │ {} │", "Blank Lines".bold(), stats.blank_lines);
+/// Warn about every file whose complexity exceeds `threshold`
+fn warn_complex_files(stats: &ProjectStats, threshold: Option<usize>) {
+    let Some(threshold) = threshold else { return };
 
-    println!("└─────────────────────────────────────────────────────────────────────────────┘");
+    for file in stats.files.iter().filter(|file| file.complexity > threshold) {
+        CommandUtils::warning(&format!(
+            "{} has a complexity of {} (threshold {})",
+            file.path, file.complexity, threshold
+        ));
+    }
 }
 
-#[derive(Debug, Default)]
-struct ProjectStats {
-    source_files: usize,
-    test_files: usize,
-    total_lines: usize,
-    code_lines: usize,
-    comment_lines: usize,
-    blank_lines: usize,
+/// Line counts, TODO comments and complexity of the project's sources
+#[derive(Debug, Default, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ProjectStats {
+    pub source_files: usize,
+    pub test_files: usize,
+    pub total_lines: usize,
+    pub code_lines: usize,
+    pub comment_lines: usize,
+    pub blank_lines: usize,
+    #[serde(default)]
+    pub todo_comments: usize,
+    #[serde(default)]
+    pub total_complexity: usize,
+    /// Files sorted from most to least complex
+    #[serde(default)]
+    pub files: Vec<FileComplexity>,
 }
 
-fn calculate_project_stats() -> ProjectStats {
-    let mut stats = ProjectStats::default();
+/// Complexity of a source file and its functions
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct FileComplexity {
+    pub path: String,
+    /// Sum of the complexity of the file's functions
+    pub complexity: usize,
+    pub functions: Vec<(String, usize)>,
+}
 
-    if let Ok(entries) = walkdir::WalkDir::new("src").into_iter().collect::<Result<Vec<_>, _>>() {
-        for entry in entries {
-            if let Some(path) = entry.path().to_str() {
-                if path.ends_with(".rs") {
-                    stats.source_files += 1;
-
-                    if path.contains("test") {
-                        stats.test_files += 1;
-                    }
-
-                    if let Ok(content) = std::fs::read_to_string(entry.path()) {
-                        for line in content.lines() {
-                            let trimmed = line.trim();
-                            stats.total_lines += 1;
-
-                            if trimmed.is_empty() {
-                                stats.blank_lines += 1;
-                            } else if trimmed.starts_with("//") || trimmed.starts_with("/*") {
-                                stats.comment_lines += 1;
-                            } else {
-                                stats.code_lines += 1;
-                            }
-                        }
-                    }
-                }
+impl FileComplexity {
+    fn analyze(path: &str, source: &str) -> Self {
+        let functions = complexity::function_complexities(source);
+        Self {
+            path: path.to_string(),
+            complexity: functions.iter().map(|(_, complexity)| complexity).sum(),
+            functions,
+        }
+    }
+}
+
+impl ProjectStats {
+    /// Labelled counters, in display order
+    fn summary(&self) -> Vec<(&'static str, usize)> {
+        vec![
+            ("Source Files", self.source_files),
+            ("Test Files", self.test_files),
+            ("Total Lines", self.total_lines),
+            ("Code Lines", self.code_lines),
+            ("Comment Lines", self.comment_lines),
+            ("Blank Lines", self.blank_lines),
+            ("TODO Comments", self.todo_comments),
+            ("Complexity", self.total_complexity),
+        ]
+    }
+
+    /// The `limit` most complex functions as `(file, function, complexity)`
+    fn most_complex_functions(&self, limit: usize) -> Vec<(&str, &str, usize)> {
+        let mut functions: Vec<(&str, &str, usize)> = self
+            .files
+            .iter()
+            .flat_map(|file| file.functions.iter().map(|(name, complexity)| (file.path.as_str(), name.as_str(), *complexity)))
+            .collect();
+        functions.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| (a.0, a.1).cmp(&(b.0, b.1))));
+        functions.truncate(limit);
+        functions
+    }
+
+    /// Count the lines of one source file and add its complexity
+    fn add_file(&mut self, path: &Path, content: &str) {
+        let display = path.display().to_string();
+        self.source_files += 1;
+        if display.contains("test") {
+            self.test_files += 1;
+        }
+
+        for line in content.lines() {
+            let trimmed = line.trim();
+            self.total_lines += 1;
+
+            if trimmed.is_empty() {
+                self.blank_lines += 1;
+            } else if trimmed.starts_with("//") || trimmed.starts_with("/*") {
+                self.comment_lines += 1;
+            } else {
+                self.code_lines += 1;
             }
         }
+
+        let file = FileComplexity::analyze(&display, content);
+        self.total_complexity += file.complexity;
+        self.files.push(file);
+    }
+}
+
+/// Gather line counts, TODO comments and complexity for every `.rs` file in src/
+pub fn gather_full_stats() -> Result<ProjectStats> {
+    let mut stats = ProjectStats::default();
+
+    for entry in walkdir::WalkDir::new("src") {
+        let entry = entry?;
+        let path = entry.path();
+        if !path.is_file() || path.extension().is_none_or(|ext| ext != "rs") {
+            continue;
+        }
+
+        let content = std::fs::read_to_string(path)?;
+        stats.add_file(path, &content);
+        stats.todo_comments += count_todo_comments(path);
     }
 
+    stats.files.sort_by(|a, b| b.complexity.cmp(&a.complexity).then_with(|| a.path.cmp(&b.path)));
+    Ok(stats)
+}
+
+/// Count `// TODO` and `// FIXME` comments in a file
+pub fn count_todo_comments(path: &Path) -> usize {
+    std::fs::read_to_string(path)
+        .map(|content| content.matches("// TODO").count() + content.matches("// FIXME").count())
+        .unwrap_or(0)
+}
+
+/// Change of each summary counter since `previous`
+fn stats_deltas(stats: &ProjectStats, previous: &ProjectStats) -> Vec<(&'static str, i64)> {
     stats
+        .summary()
+        .into_iter()
+        .zip(previous.summary())
+        .map(|((label, current), (_, previous))| (label, current as i64 - previous as i64))
+        .collect()
+}
+
+/// Save the statistics to storage/stats/{timestamp}.json
+fn save_stats(stats: &ProjectStats) -> Result<PathBuf> {
+    CommandUtils::ensure_directory(Path::new(STATS_DIR))?;
+    let path = Path::new(STATS_DIR).join(format!("{}.json", chrono::Local::now().format("%Y%m%d_%H%M%S")));
+    std::fs::write(&path, serde_json::to_string_pretty(stats)?)?;
+    Ok(path)
+}
+
+/// The most recently saved statistics, if any
+fn last_saved_stats() -> Result<Option<ProjectStats>> {
+    let Ok(entries) = std::fs::read_dir(STATS_DIR) else {
+        return Ok(None);
+    };
+
+    let latest = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .max();
+    match latest {
+        Some(path) => Ok(Some(serde_json::from_str(&std::fs::read_to_string(path)?)?)),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_info() {
+        let app_info = gather_app_info().unwrap();
+        let system_info = gather_system_info().unwrap();
+        let line = serde_json::json!({ "app": app_info, "system": system_info }).to_string();
+
+        let info: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(info["app"]["name"], env!("CARGO_PKG_NAME"));
+        assert_eq!(info["app"]["version"], env!("CARGO_PKG_VERSION"));
+        assert!(info["system"]["os"].is_string());
+        assert!(info["app"]["git_commit"].is_string());
+    }
+
+    #[test]
+    fn test_complexity_of_fixture() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/complexity/sample.rs");
+        let source = std::fs::read_to_string(&path).unwrap();
+        let file = FileComplexity::analyze("sample.rs", &source);
+
+        assert_eq!(
+            file.functions,
+            [("clone".to_string(), 1), ("shipping".to_string(), 5), ("total".to_string(), 5)]
+        );
+        assert_eq!(file.complexity, 11);
+        assert_eq!(count_todo_comments(&path), 2);
+    }
+
+    #[test]
+    fn test_stats_deltas() {
+        let mut stats = ProjectStats::default();
+        stats.add_file(Path::new("src/lib.rs"), "fn main() {\n    if true && false {}\n}\n\n// done\n");
+        assert_eq!((stats.total_lines, stats.code_lines, stats.blank_lines, stats.comment_lines), (5, 3, 1, 1));
+        assert_eq!(stats.total_complexity, 3);
+
+        let previous = ProjectStats { total_lines: 7, ..Default::default() };
+        let deltas = stats_deltas(&stats, &previous);
+        assert!(deltas.contains(&("Total Lines", -2)));
+        assert!(deltas.contains(&("Complexity", 3)));
+        assert_eq!(stats.most_complex_functions(5), [("src/lib.rs", "main", 3)]);
+    }
+
+    #[test]
+    fn test_version_const() {
+        let source = "pub const GIT_COMMIT: &str = \"abc1234\";\npub const GIT_TAG: &str = \"v1.2.0\";\n";
+
+        assert_eq!(version_const(source, "GIT_COMMIT").as_deref(), Some("abc1234"));
+        assert_eq!(version_const(source, "GIT_TAG").as_deref(), Some("v1.2.0"));
+        assert_eq!(version_const(source, "GIT_BRANCH"), None);
+    }
 }
@@ -0,0 +1,167 @@
+//! Approximate cyclomatic complexity of Rust source
+//!
+//! Each function scores 1 plus one per `if`, `match`, `while`, `for`,
+//! `loop`, `&&` and `||` in its body; `else if` counts once through its `if`.
+//! Comments and string literals are blanked out first, `for` in `impl ... for`
+//! headers is skipped and `||` or `&&` only count after an expression, so
+//! closures and double references are not mistaken for operators.
+
+use regex::Regex;
+use std::sync::LazyLock;
+
+static FUNCTION: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\bfn\s+(\w+)").unwrap());
+static DECISION: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\b(?:if|match|while|for|loop)\b|&&|\|\|").unwrap());
+
+/// Complexity of each function in `source`, in order of appearance
+pub fn function_complexities(source: &str) -> Vec<(String, usize)> {
+    let code = strip_comments_and_strings(source);
+    let functions: Vec<(usize, String)> = FUNCTION
+        .captures_iter(&code)
+        .map(|captures| (captures.get(0).map_or(0, |m| m.start()), captures[1].to_string()))
+        .collect();
+
+    functions
+        .iter()
+        .enumerate()
+        .map(|(index, (start, name))| {
+            let end = functions.get(index + 1).map_or(code.len(), |(next, _)| *next);
+            (name.clone(), 1 + decision_points(&code, *start, end))
+        })
+        .collect()
+}
+
+/// Decision points between `start` and `end` of comment-free `code`
+fn decision_points(code: &str, start: usize, end: usize) -> usize {
+    DECISION
+        .find_iter(&code[start..end])
+        .filter(|token| {
+            let offset = start + token.start();
+            match token.as_str() {
+                "for" => !is_impl_for(code, offset) && !code[offset + 3..].starts_with('<'),
+                "&&" | "||" => follows_expression(&code[..offset]),
+                _ => true,
+            }
+        })
+        .count()
+}
+
+/// Whether the `for` at `offset` belongs to an `impl Trait for Type` header
+fn is_impl_for(code: &str, offset: usize) -> bool {
+    let line_start = code[..offset].rfind('\n').map_or(0, |index| index + 1);
+    let line = code[line_start..offset].trim_start();
+    line.starts_with("impl") || line.starts_with("unsafe impl")
+}
+
+/// Whether the text before a `&&` or `||` ends with an operand rather than an operator
+fn follows_expression(before: &str) -> bool {
+    let before = before.trim_end();
+    let Some(last) = before.chars().last() else {
+        return false;
+    };
+    if last == ')' || last == ']' || last == '?' {
+        return true;
+    }
+    if !(last.is_alphanumeric() || last == '_') {
+        return false;
+    }
+
+    let word: String = before
+        .chars()
+        .rev()
+        .take_while(|c| c.is_alphanumeric() || *c == '_')
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect();
+    !matches!(word.as_str(), "move" | "return" | "in" | "mut")
+}
+
+/// Replace comments and string and char literals with spaces, keeping line breaks
+pub fn strip_comments_and_strings(source: &str) -> String {
+    let chars: Vec<char> = source.chars().collect();
+    let mut output = String::with_capacity(source.len());
+    let mut index = 0;
+
+    let blank = |output: &mut String, chars: &[char]| {
+        for c in chars {
+            output.push(if *c == '\n' { '\n' } else { ' ' });
+        }
+    };
+
+    while index < chars.len() {
+        let rest = &chars[index..];
+        let end = match rest {
+            ['/', '/', ..] => rest.iter().position(|c| *c == '\n').unwrap_or(rest.len()),
+            ['/', '*', ..] => block_comment_end(rest),
+            ['"', ..] => quoted_end(rest, '"'),
+            ['r', '#' | '"', ..] if index == 0 || !is_identifier(chars[index - 1]) => raw_string_end(rest).unwrap_or(0),
+            ['b', '"', ..] if index == 0 || !is_identifier(chars[index - 1]) => 1 + quoted_end(&rest[1..], '"'),
+            ['\'', '\\', ..] => quoted_end(rest, '\''),
+            ['\'', _, '\'', ..] => 3,
+            _ => 0,
+        };
+
+        if end == 0 {
+            output.push(rest[0]);
+            index += 1;
+        } else {
+            blank(&mut output, &rest[..end]);
+            index += end;
+        }
+    }
+
+    output
+}
+
+fn is_identifier(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Length of a possibly nested block comment starting at `/*`
+fn block_comment_end(rest: &[char]) -> usize {
+    let mut depth = 0;
+    let mut index = 0;
+    while index + 1 < rest.len() {
+        match (rest[index], rest[index + 1]) {
+            ('/', '*') => {
+                depth += 1;
+                index += 2;
+            }
+            ('*', '/') => {
+                depth -= 1;
+                index += 2;
+                if depth == 0 {
+                    return index;
+                }
+            }
+            _ => index += 1,
+        }
+    }
+    rest.len()
+}
+
+/// Length of a literal opened and closed by `quote`, honouring backslash escapes
+fn quoted_end(rest: &[char], quote: char) -> usize {
+    let mut index = 1;
+    while index < rest.len() {
+        match rest[index] {
+            '\\' => index += 2,
+            c if c == quote => return index + 1,
+            _ => index += 1,
+        }
+    }
+    rest.len()
+}
+
+/// Length of a raw string such as `r#"..."#`, or `None` for identifiers starting with `r`
+fn raw_string_end(rest: &[char]) -> Option<usize> {
+    let hashes = rest[1..].iter().take_while(|c| **c == '#').count();
+    if rest.get(1 + hashes) != Some(&'"') {
+        return None;
+    }
+
+    let body = 2 + hashes;
+    (body..rest.len())
+        .find(|&index| rest[index] == '"' && rest[index + 1..].iter().take(hashes).filter(|c| **c == '#').count() == hashes)
+        .map(|index| index + 1 + hashes)
+}
@@ -0,0 +1,298 @@
+//! Live metrics for `info --watch`
+//!
+//! Each refresh samples the resident memory of this process, the disk usage
+//! of the project outside `target/` and, when the development server from
+//! `storage/framework/server.pid` is running, the number of requests the
+//! access log middleware appended to `storage/logs/access.log` since the
+//! previous refresh.
+
+use anyhow::Result;
+use colored::*;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::commands::build::format_size;
+use crate::commands::serve::SERVER_PID_FILE;
+use crate::utils::TextUtils;
+
+const ACCESS_LOG: &str = "storage/logs/access.log";
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// How long a disk usage measurement is reused, walking the project is slow
+const DISK_USAGE_REFRESH: Duration = Duration::from_secs(30);
+
+/// Build output left out of the disk usage, it dwarfs the project and churns on every build
+const DISK_USAGE_EXCLUDED: &[&str] = &["target"];
+
+/// Render `frame` every `interval` until Ctrl+C, clearing the screen first when `clear` is set
+pub async fn watch_loop<W, F>(interval: Duration, clear: bool, out: &mut W, mut frame: F) -> Result<()>
+where
+    W: Write,
+    F: FnMut() -> Result<String>,
+{
+    let mut ticker = tokio::time::interval(interval);
+
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {
+                if clear {
+                    write!(out, "{}", CLEAR_SCREEN)?;
+                }
+                writeln!(out, "{}", frame()?)?;
+                writeln!(out, "{}", format!("Refreshing every {}s, press Ctrl+C to stop", interval.as_secs()).dimmed())?;
+                out.flush()?;
+            }
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+    }
+}
+
+/// Samples the live metrics of a project between refreshes
+pub struct Monitor {
+    root: PathBuf,
+    access_log: AccessLogCursor,
+    /// Last disk usage and when it was measured
+    disk_usage: Option<(u64, Instant)>,
+}
+
+impl Monitor {
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            access_log: AccessLogCursor::new(&root.join(ACCESS_LOG)),
+            disk_usage: None,
+        }
+    }
+
+    /// Current metrics, with the request rate measured over the last `interval`
+    pub fn sample(&mut self, interval: Duration) -> LiveMetrics {
+        let server_pid = running_server_pid(&self.root.join(SERVER_PID_FILE));
+        let requests = self.access_log.new_lines(&self.root.join(ACCESS_LOG));
+
+        LiveMetrics {
+            memory: process_memory(),
+            disk_usage: self.disk_usage(),
+            server_pid,
+            requests_per_second: server_pid.map(|_| requests as f64 / interval.as_secs_f64().max(1.0)),
+        }
+    }
+
+    /// Disk usage of the project, measured again once [`DISK_USAGE_REFRESH`] has passed
+    fn disk_usage(&mut self) -> u64 {
+        match self.disk_usage {
+            Some((size, measured)) if measured.elapsed() < DISK_USAGE_REFRESH => size,
+            _ => {
+                let size = project_disk_usage(&self.root);
+                self.disk_usage = Some((size, Instant::now()));
+                size
+            }
+        }
+    }
+}
+
+/// Total size of the files under `root`, without the top-level [`DISK_USAGE_EXCLUDED`] directories
+fn project_disk_usage(root: &Path) -> u64 {
+    walkdir::WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() != 1 || !DISK_USAGE_EXCLUDED.iter().any(|excluded| entry.file_name() == *excluded)
+        })
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// One sample of the live metrics
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiveMetrics {
+    /// Resident memory of this process in bytes, when the platform exposes it
+    pub memory: Option<u64>,
+    pub disk_usage: u64,
+    pub server_pid: Option<u32>,
+    pub requests_per_second: Option<f64>,
+}
+
+impl LiveMetrics {
+    pub fn render(&self) -> String {
+        let rows = vec![
+            vec!["Process Memory".to_string(), self.memory.map_or_else(|| "unavailable".to_string(), format_size)],
+            vec!["Project Disk Usage".to_string(), format_size(self.disk_usage)],
+            vec![
+                "Server".to_string(),
+                self.server_pid.map_or_else(|| "not running".to_string(), |pid| format!("running (pid {})", pid)),
+            ],
+            vec![
+                "Request Rate".to_string(),
+                self.requests_per_second.map_or_else(|| "-".to_string(), |rate| format!("{:.2} req/s", rate)),
+            ],
+        ];
+
+        format!(
+            "{}\n{}",
+            "Live Metrics:".bold(),
+            TextUtils::table_format(&["Metric", "Value"], &rows, &[18, 55])
+        )
+    }
+}
+
+/// Tracks how far the access log has been read between refreshes
+struct AccessLogCursor {
+    offset: u64,
+}
+
+impl AccessLogCursor {
+    /// Start at the end of the log so only requests made while watching are counted
+    fn new(path: &Path) -> Self {
+        Self { offset: std::fs::metadata(path).map_or(0, |metadata| metadata.len()) }
+    }
+
+    /// Number of lines appended to the log since the previous call
+    fn new_lines(&mut self, path: &Path) -> usize {
+        let Ok(content) = std::fs::read(path) else {
+            self.offset = 0;
+            return 0;
+        };
+
+        // The log was rotated or truncated, start over
+        if (content.len() as u64) < self.offset {
+            self.offset = 0;
+        }
+
+        let count = content[self.offset as usize..].iter().filter(|byte| **byte == b'\n').count();
+        self.offset = content.len() as u64;
+        count
+    }
+}
+
+/// Resident memory of this process from `/proc/self/status`
+fn process_memory() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    parse_vm_rss(&status)
+}
+
+/// The `VmRSS` line of a `/proc/<pid>/status` file, in bytes
+fn parse_vm_rss(status: &str) -> Option<u64> {
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kilobytes: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kilobytes * 1024)
+}
+
+/// The pid in `pid_file`, if that process is still running
+fn running_server_pid(pid_file: &Path) -> Option<u32> {
+    let pid: u32 = std::fs::read_to_string(pid_file).ok()?.trim().parse().ok()?;
+    is_running(pid).then_some(pid)
+}
+
+#[cfg(unix)]
+fn is_running(pid: u32) -> bool {
+    std::process::Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stderr(std::process::Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+#[cfg(not(unix))]
+fn is_running(_pid: u32) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_vm_rss() {
+        let status = "Name:\trustisan\nVmPeak:\t  20480 kB\nVmRSS:\t   1024 kB\nThreads:\t4\n";
+
+        assert_eq!(parse_vm_rss(status), Some(1024 * 1024));
+        assert_eq!(parse_vm_rss("Name:\trustisan\n"), None);
+    }
+
+    #[test]
+    fn test_access_log_cursor_counts_only_new_lines() {
+        let dir = TempDir::new().unwrap();
+        let log = dir.path().join("access.log");
+        std::fs::write(&log, "GET /\nGET /users\n").unwrap();
+
+        let mut cursor = AccessLogCursor::new(&log);
+        assert_eq!(cursor.new_lines(&log), 0);
+
+        std::fs::write(&log, "GET /\nGET /users\nPOST /users\nGET /posts\nGET /\n").unwrap();
+        assert_eq!(cursor.new_lines(&log), 3);
+
+        std::fs::write(&log, "GET /health\n").unwrap();
+        assert_eq!(cursor.new_lines(&log), 1);
+    }
+
+    #[test]
+    fn test_project_disk_usage_skips_target() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::create_dir_all(dir.path().join("target/debug")).unwrap();
+        std::fs::write(dir.path().join("src/main.rs"), "fn main() {}\n").unwrap();
+        std::fs::write(dir.path().join("target/debug/app"), vec![0u8; 4096]).unwrap();
+
+        assert_eq!(project_disk_usage(dir.path()), 13);
+
+        let mut monitor = Monitor::new(dir.path());
+        assert_eq!(monitor.disk_usage(), 13);
+        std::fs::write(dir.path().join("src/lib.rs"), "pub fn lib() {}\n").unwrap();
+        // Reused until the refresh interval has passed
+        assert_eq!(monitor.disk_usage(), 13);
+    }
+
+    #[test]
+    fn test_stale_server_pid_is_not_running() {
+        let dir = TempDir::new().unwrap();
+        let pid_file = dir.path().join("server.pid");
+
+        assert_eq!(running_server_pid(&pid_file), None);
+
+        std::fs::write(&pid_file, format!("{}\n", std::process::id())).unwrap();
+        #[cfg(unix)]
+        assert_eq!(running_server_pid(&pid_file), Some(std::process::id()));
+
+        std::fs::write(&pid_file, "not a pid").unwrap();
+        assert_eq!(running_server_pid(&pid_file), None);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_watch_loop_renders_at_least_two_cycles_in_twelve_seconds() {
+        let mut out = Vec::new();
+        let mut frames = 0;
+
+        let result = tokio::time::timeout(
+            Duration::from_secs(12),
+            watch_loop(Duration::from_secs(5), true, &mut out, || {
+                frames += 1;
+                Ok(format!("frame {}", frames))
+            }),
+        )
+        .await;
+
+        assert!(result.is_err(), "watch loop should run until interrupted");
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.matches(CLEAR_SCREEN).count() >= 2);
+        assert!(output.contains("frame 1") && output.contains("frame 2"));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_watch_loop_without_clear_appends_frames() {
+        let mut out = Vec::new();
+
+        let _ = tokio::time::timeout(
+            Duration::from_secs(6),
+            watch_loop(Duration::from_secs(5), false, &mut out, || Ok("frame".to_string())),
+        )
+        .await;
+
+        let output = String::from_utf8(out).unwrap();
+        assert!(!output.contains(CLEAR_SCREEN));
+        assert_eq!(output.matches("frame").count(), 2);
+    }
+}
@@ -0,0 +1,316 @@
+//! Log command implementations for the Rustisan CLI
+//!
+//! The log file is `logging.path` from rustisan.toml, defaulting to
+//! `storage/logs/rustisan.log`. When it does not exist, the most recently
+//! modified file in its directory is used instead, which covers rotated logs
+//! such as `rustisan-2024-05-01.log`.
+
+use anyhow::Result;
+use colored::*;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+use super::CommandUtils;
+use super::config::get_nested_value;
+use crate::LogCommands;
+
+const DEFAULT_LOG_FILE: &str = "storage/logs/rustisan.log";
+
+/// How often `--follow` checks the log file for new lines
+const FOLLOW_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Number of leading words searched for a level, e.g. `[2024-05-01 10:00:00] local.ERROR:`
+const LEVEL_SEARCH_WORDS: usize = 4;
+
+/// Log levels, most severe first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl FromStr for Level {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "error" | "err" | "critical" | "fatal" => Ok(Self::Error),
+            "warn" | "warning" => Ok(Self::Warn),
+            "info" => Ok(Self::Info),
+            "debug" => Ok(Self::Debug),
+            "trace" => Ok(Self::Trace),
+            _ => Err(anyhow::anyhow!("Unknown log level '{}' (expected error, warn, info or debug)", s)),
+        }
+    }
+}
+
+/// Filters lines by level, keeping continuation lines such as stack traces
+/// with the entry they belong to
+#[derive(Debug)]
+struct LevelFilter {
+    max: Option<Level>,
+    current_matches: bool,
+}
+
+impl LevelFilter {
+    fn new(max: Option<Level>) -> Self {
+        Self { max, current_matches: true }
+    }
+
+    fn matches(&mut self, line: &str) -> bool {
+        let Some(max) = self.max else {
+            return true;
+        };
+
+        if let Some(level) = line_level(line) {
+            self.current_matches = level <= max;
+        }
+        self.current_matches
+    }
+}
+
+/// Handle log commands
+pub async fn handle(operation: LogCommands) -> Result<()> {
+    CommandUtils::ensure_rustisan_project()?;
+
+    match operation {
+        LogCommands::Show { lines, level, follow } => {
+            let level = level.as_deref().map(Level::from_str).transpose()?;
+            show_log(lines, level, follow).await
+        }
+        LogCommands::Clear => clear_logs(),
+    }
+}
+
+async fn show_log(lines: usize, level: Option<Level>, follow: bool) -> Result<()> {
+    let path = log_file(&configured_log_path())
+        .ok_or_else(|| anyhow::anyhow!("No log files found in {}", configured_log_dir().display()))?;
+
+    CommandUtils::info(&format!("Showing {}", path.display()));
+    println!();
+
+    let content = std::fs::read_to_string(&path)?;
+    let mut filter = LevelFilter::new(level);
+    for line in tail(&content, lines, &mut filter) {
+        print_line(line);
+    }
+
+    if follow {
+        follow_log(&path, content.len() as u64, filter).await?;
+    }
+
+    Ok(())
+}
+
+/// Print lines appended to the log until interrupted
+async fn follow_log(path: &Path, mut position: u64, mut filter: LevelFilter) -> Result<()> {
+    let mut interval = tokio::time::interval(FOLLOW_INTERVAL);
+    let mut pending = String::new();
+
+    loop {
+        interval.tick().await;
+
+        let mut file = std::fs::File::open(path)?;
+        let len = file.metadata()?.len();
+        if len < position {
+            // The log was truncated or rotated
+            position = 0;
+            pending.clear();
+        }
+        if len == position {
+            continue;
+        }
+
+        file.seek(SeekFrom::Start(position))?;
+        let mut buffer = Vec::new();
+        file.read_to_end(&mut buffer)?;
+        position += buffer.len() as u64;
+        pending.push_str(&String::from_utf8_lossy(&buffer));
+
+        // Hold back a partially written last line
+        let complete = pending.rfind('\n').map_or(0, |index| index + 1);
+        for line in pending[..complete].lines() {
+            if filter.matches(line) {
+                print_line(line);
+            }
+        }
+        pending.drain(..complete);
+    }
+}
+
+fn clear_logs() -> Result<()> {
+    let dir = configured_log_dir();
+    let files = log_files(&dir);
+
+    if files.is_empty() {
+        CommandUtils::info(&format!("No log files in {}", dir.display()));
+        return Ok(());
+    }
+
+    CommandUtils::warning(&format!("This will delete {} log file(s) in {}", files.len(), dir.display()));
+    let confirmed = dialoguer::Confirm::new()
+        .with_prompt("Delete all log files?")
+        .default(false)
+        .interact()?;
+
+    if !confirmed {
+        CommandUtils::info("Operation cancelled");
+        return Ok(());
+    }
+
+    for file in &files {
+        std::fs::remove_file(file)?;
+    }
+
+    CommandUtils::success(&format!("Deleted {} log file(s)", files.len()));
+    Ok(())
+}
+
+/// `logging.path` from rustisan.toml, or the default log file
+fn configured_log_path() -> PathBuf {
+    std::fs::read_to_string("rustisan.toml")
+        .ok()
+        .and_then(|content| toml::from_str::<toml::Value>(&content).ok())
+        .and_then(|config| get_nested_value(&config, "logging.path"))
+        .and_then(|path| path.as_str().map(PathBuf::from))
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_LOG_FILE))
+}
+
+fn configured_log_dir() -> PathBuf {
+    match configured_log_path().parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+        _ => PathBuf::from("."),
+    }
+}
+
+/// The configured log file, or the newest file next to it
+fn log_file(configured: &Path) -> Option<PathBuf> {
+    if configured.is_file() {
+        return Some(configured.to_path_buf());
+    }
+
+    let dir = configured.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(Path::new("."));
+    log_files(dir)
+        .into_iter()
+        .max_by_key(|path| path.metadata().and_then(|metadata| metadata.modified()).ok())
+}
+
+fn log_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && path.file_name().is_some_and(|name| name != ".gitkeep"))
+        .collect()
+}
+
+/// The last `count` lines passing the filter
+fn tail<'a>(content: &'a str, count: usize, filter: &mut LevelFilter) -> Vec<&'a str> {
+    let lines: Vec<&str> = content.lines().filter(|line| filter.matches(line)).collect();
+    lines[lines.len().saturating_sub(count)..].to_vec()
+}
+
+/// Find the level in the prefix of a log line
+fn line_level(line: &str) -> Option<Level> {
+    line.split_whitespace()
+        .take(LEVEL_SEARCH_WORDS)
+        .flat_map(|word| word.split(|c: char| !c.is_ascii_alphabetic()))
+        .find_map(|word| {
+            // Only upper or lowercase words, so "Error:" in a message is not a level
+            let marked = word.len() >= 4
+                && (word.chars().all(|c| c.is_ascii_uppercase()) || word.chars().all(|c| c.is_ascii_lowercase()));
+            if marked { word.parse().ok() } else { None }
+        })
+}
+
+fn print_line(line: &str) {
+    match line_level(line) {
+        Some(Level::Error) => println!("{}", line.red()),
+        Some(Level::Warn) => println!("{}", line.yellow()),
+        Some(Level::Debug | Level::Trace) => println!("{}", line.dimmed()),
+        _ => println!("{}", line),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOG: &str = "\
+[2024-05-01 10:00:00] INFO Server started on 127.0.0.1:3000
+[2024-05-01 10:00:01] DEBUG Loaded 12 routes
+[2024-05-01 10:00:02] WARN Slow query took 1200ms
+[2024-05-01 10:00:03] ERROR Failed to connect to redis
+    at cache::redis::connect (src/cache/redis.rs:42)
+[2024-05-01 10:00:04] INFO Request GET /users completed: Error count 0
+2024-05-01T10:00:05Z error shop::jobs: job failed
+";
+
+    fn filtered(level: Option<&str>, count: usize) -> Vec<&'static str> {
+        let mut filter = LevelFilter::new(level.map(|level| level.parse().unwrap()));
+        tail(LOG, count, &mut filter)
+    }
+
+    #[test]
+    fn test_line_level() {
+        assert_eq!(line_level("[2024-05-01 10:00:03] ERROR Failed"), Some(Level::Error));
+        assert_eq!(line_level("[2024-05-01 10:00:00] local.WARNING: Disk almost full"), Some(Level::Warn));
+        assert_eq!(line_level("2024-05-01T10:00:05Z debug shop: tick"), Some(Level::Debug));
+        assert_eq!(line_level("[INFO] started"), Some(Level::Info));
+        assert_eq!(line_level("    at cache::redis::connect"), None);
+        assert!("verbose".parse::<Level>().is_err());
+    }
+
+    #[test]
+    fn test_level_filtering() {
+        assert_eq!(
+            filtered(Some("error"), 50),
+            [
+                "[2024-05-01 10:00:03] ERROR Failed to connect to redis",
+                "    at cache::redis::connect (src/cache/redis.rs:42)",
+                "2024-05-01T10:00:05Z error shop::jobs: job failed",
+            ]
+        );
+
+        let warnings = filtered(Some("warn"), 50);
+        assert_eq!(warnings.len(), 4);
+        assert_eq!(warnings[0], "[2024-05-01 10:00:02] WARN Slow query took 1200ms");
+
+        let info = filtered(Some("info"), 50);
+        assert_eq!(info.len(), 6);
+        assert!(!info.iter().any(|line| line.contains("DEBUG")));
+
+        assert_eq!(filtered(Some("debug"), 50).len(), 7);
+        assert_eq!(filtered(None, 50).len(), 7);
+    }
+
+    #[test]
+    fn test_tail_applies_after_filter() {
+        assert_eq!(filtered(None, 2), [
+            "[2024-05-01 10:00:04] INFO Request GET /users completed: Error count 0",
+            "2024-05-01T10:00:05Z error shop::jobs: job failed",
+        ]);
+        assert_eq!(filtered(Some("error"), 1), ["2024-05-01T10:00:05Z error shop::jobs: job failed"]);
+    }
+
+    #[test]
+    fn test_log_file_falls_back_to_newest() {
+        let dir = tempfile::tempdir().unwrap();
+        let configured = dir.path().join("rustisan.log");
+        assert_eq!(log_file(&configured), None);
+
+        std::fs::write(dir.path().join(".gitkeep"), "").unwrap();
+        std::fs::write(dir.path().join("rustisan-2024-05-01.log"), "old").unwrap();
+        assert_eq!(log_file(&configured), Some(dir.path().join("rustisan-2024-05-01.log")));
+
+        std::fs::write(&configured, "current").unwrap();
+        assert_eq!(log_file(&configured), Some(configured));
+    }
+}
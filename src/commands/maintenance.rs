@@ -0,0 +1,146 @@
+//! Maintenance command implementations for the Rustisan CLI
+//!
+//! `maintenance on` writes `storage/framework/maintenance.json`; `maintenance off`
+//! removes it. The framework's maintenance middleware checks for this file on
+//! every request and, while it is active, answers with `503 Service Unavailable`
+//! and the configured message. Requests from an allowed IP, or carrying the
+//! secret (as `?secret=…` or a cookie set by visiting `/{secret}`), bypass it.
+
+use anyhow::Result;
+use colored::*;
+use rand::RngCore;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use crate::MaintenanceCommands;
+use super::CommandUtils;
+
+/// Location of the maintenance file, relative to the project root
+const MAINTENANCE_FILE: &str = "storage/framework/maintenance.json";
+
+const DEFAULT_MESSAGE: &str = "Service temporarily unavailable for maintenance";
+
+/// Contents of the maintenance file
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+struct MaintenanceState {
+    active: bool,
+    message: String,
+    allowed_ips: Vec<String>,
+    secret: String,
+}
+
+/// Handle maintenance command
+pub async fn handle(mode: MaintenanceCommands) -> Result<()> {
+    CommandUtils::ensure_rustisan_project()?;
+
+    match mode {
+        MaintenanceCommands::On { message, allow } => {
+            let state = enable_maintenance(Path::new("."), message, allow)?;
+
+            CommandUtils::success("Application is now in maintenance mode");
+            CommandUtils::info(&format!("Message: {}", state.message));
+            if !state.allowed_ips.is_empty() {
+                CommandUtils::info(&format!("Allowed IPs: {}", state.allowed_ips.join(", ")));
+            }
+            CommandUtils::info(&format!("Bypass secret: {}", state.secret.yellow().bold()));
+
+            Ok(())
+        }
+        MaintenanceCommands::Off => {
+            if disable_maintenance(Path::new("."))? {
+                CommandUtils::success("Application is now live");
+            } else {
+                CommandUtils::warning("Application is not in maintenance mode");
+            }
+
+            Ok(())
+        }
+    }
+}
+
+fn maintenance_path(root: &Path) -> PathBuf {
+    root.join(MAINTENANCE_FILE)
+}
+
+/// Write the maintenance file under `root`
+fn enable_maintenance(root: &Path, message: Option<String>, allow: Vec<String>) -> Result<MaintenanceState> {
+    for ip in &allow {
+        ip.parse::<IpAddr>()
+            .map_err(|_| anyhow::anyhow!("Invalid IP address: {}", ip))?;
+    }
+
+    let state = MaintenanceState {
+        active: true,
+        message: message.unwrap_or_else(|| DEFAULT_MESSAGE.to_string()),
+        allowed_ips: allow,
+        secret: generate_secret(),
+    };
+
+    let path = maintenance_path(root);
+    if let Some(parent) = path.parent() {
+        CommandUtils::ensure_directory(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(&state)?)?;
+
+    Ok(state)
+}
+
+/// Remove the maintenance file under `root`, returning whether it existed
+fn disable_maintenance(root: &Path) -> Result<bool> {
+    let path = maintenance_path(root);
+    if !path.exists() {
+        return Ok(false);
+    }
+
+    std::fs::remove_file(path)?;
+    Ok(true)
+}
+
+/// Random 32-byte secret encoded as hex
+fn generate_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_maintenance_on_and_off() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let state = enable_maintenance(
+            dir.path(),
+            Some("Upgrading database".to_string()),
+            vec!["127.0.0.1".to_string(), "::1".to_string()],
+        )
+        .unwrap();
+
+        let content = std::fs::read_to_string(dir.path().join(MAINTENANCE_FILE)).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(json["active"], true);
+        assert_eq!(json["message"], "Upgrading database");
+        assert_eq!(json["allowed_ips"], serde_json::json!(["127.0.0.1", "::1"]));
+        assert_eq!(json["secret"], state.secret.as_str());
+
+        assert_eq!(state.secret.len(), 64);
+        assert!(state.secret.chars().all(|c| c.is_ascii_hexdigit()));
+
+        assert!(disable_maintenance(dir.path()).unwrap());
+        assert!(!dir.path().join(MAINTENANCE_FILE).exists());
+        assert!(!disable_maintenance(dir.path()).unwrap());
+    }
+
+    #[test]
+    fn test_maintenance_defaults_and_validation() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let state = enable_maintenance(dir.path(), None, Vec::new()).unwrap();
+        assert_eq!(state.message, DEFAULT_MESSAGE);
+        assert!(state.allowed_ips.is_empty());
+
+        assert!(enable_maintenance(dir.path(), None, vec!["not-an-ip".to_string()]).is_err());
+        assert_ne!(generate_secret(), generate_secret());
+    }
+}
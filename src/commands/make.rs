@@ -7,62 +7,137 @@ use anyhow::Result;
 use colored::*;
 
 use super::CommandUtils;
-use crate::generators::{TemplateManager, GeneratorOptions};
+use crate::generators::{GeneratorUtils, TemplateManager, GeneratorOptions};
+use crate::utils::{FileUtils, TextUtils};
 use crate::MakeCommands;
 
 /// Handle make commands
 pub async fn handle(component: MakeCommands) -> Result<()> {
+    let force = match &component {
+        MakeCommands::Controller { force, .. }
+        | MakeCommands::Model { force, .. }
+        | MakeCommands::Migration { force, .. }
+        | MakeCommands::Middleware { force, .. }
+        | MakeCommands::Request { force, .. }
+        | MakeCommands::Resource { force, .. }
+        | MakeCommands::Seeder { force, .. }
+        | MakeCommands::Factory { force, .. }
+        | MakeCommands::Command { force, .. }
+        | MakeCommands::Job { force, .. }
+        | MakeCommands::Event { force, .. }
+        | MakeCommands::Listener { force, .. }
+        | MakeCommands::Policy { force, .. }
+        | MakeCommands::Trait { force, .. }
+        | MakeCommands::Test { force, .. }
+        | MakeCommands::Service { force, .. }
+        | MakeCommands::Repository { force, .. }
+        | MakeCommands::Observer { force, .. }
+        | MakeCommands::Notification { force, .. }
+        | MakeCommands::Exception { force, .. }
+        | MakeCommands::Enum { force, .. }
+        | MakeCommands::Scope { force, .. }
+        | MakeCommands::Provider { force, .. }
+        | MakeCommands::Cast { force, .. }
+        | MakeCommands::Action { force, .. }
+        | MakeCommands::Mail { force, .. }
+        | MakeCommands::Config { force, .. }
+        | MakeCommands::K8s { force, .. } => *force,
+    };
+    let options = GeneratorOptions { force, ..Default::default() };
+
     match component {
-        MakeCommands::Controller { name, resource, api, model } => {
-            make_controller(name, resource, api, model).await
+        MakeCommands::Controller { name, resource, api, model, .. } => {
+            make_controller(name, resource, api, model, &options).await
+        }
+        MakeCommands::Model { name, migration, factory, seeder, .. } => {
+            make_model(name, migration, factory, seeder, &options).await
+        }
+        MakeCommands::Migration { name, from_schema: Some(file), .. } => {
+            make_migration_from_schema(name, file, &options).await
+        }
+        MakeCommands::Migration { name, create, table, columns, .. } => {
+            make_migration(name, create, table, columns, &options).await
+        }
+        MakeCommands::Middleware { name, template, .. } => {
+            make_middleware(name, template, &options).await
+        }
+        MakeCommands::Request { name, fields, .. } => {
+            make_request(name, fields, &options).await
+        }
+        MakeCommands::Resource { name, collection, fields, model, .. } => {
+            make_resource(name, collection, fields, model, &options).await
+        }
+        MakeCommands::Seeder { name, model, from_csv, from_json, .. } => {
+            make_seeder(name, model, from_csv, from_json, &options).await
+        }
+        MakeCommands::Factory { name, model, .. } => {
+            make_factory(name, model, &options).await
+        }
+        MakeCommands::Command { name, arguments, options: flags, schedule, .. } => {
+            make_command(name, arguments, flags, schedule, &options).await
+        }
+        MakeCommands::Job { name, sync, queue, tries, timeout, .. } => {
+            make_job(name, sync, queue, tries, timeout, &options).await
+        }
+        MakeCommands::Event { name, .. } => {
+            make_event(name, &options).await
+        }
+        MakeCommands::Listener { name, event, .. } => {
+            make_listener(name, event, &options).await
+        }
+        MakeCommands::Policy { name, model, .. } => {
+            make_policy(name, model, &options).await
+        }
+        MakeCommands::Trait { name, methods, no_async, .. } => {
+            make_trait(name, methods, no_async, &options).await
         }
-        MakeCommands::Model { name, migration, factory, seeder } => {
-            make_model(name, migration, factory, seeder).await
+        MakeCommands::Test { name, unit, integration, feature, .. } => {
+            make_test(name, unit, integration, feature, &options).await
         }
-        MakeCommands::Migration { name, create, table } => {
-            make_migration(name, create, table).await
+        MakeCommands::Service { name, model, test, .. } => {
+            make_service(name, model, test, &options).await
         }
-        MakeCommands::Middleware { name } => {
-            make_middleware(name).await
+        MakeCommands::Repository { name, model, .. } => {
+            make_repository(name, model, &options).await
         }
-        MakeCommands::Request { name } => {
-            make_request(name).await
+        MakeCommands::Observer { name, model, .. } => {
+            make_observer(name, model, &options).await
         }
-        MakeCommands::Resource { name, collection } => {
-            make_resource(name, collection).await
+        MakeCommands::Notification { name, channels, .. } => {
+            make_notification(name, channels, &options).await
         }
-        MakeCommands::Seeder { name, model } => {
-            make_seeder(name, model).await
+        MakeCommands::Exception { name, kind, .. } => {
+            make_exception(name, kind, &options).await
         }
-        MakeCommands::Factory { name, model } => {
-            make_factory(name, model).await
+        MakeCommands::Enum { name, variants, serde, display, .. } => {
+            make_enum(name, variants, serde, display, &options).await
         }
-        MakeCommands::Command { name } => {
-            make_command(name).await
+        MakeCommands::Scope { name, model, .. } => {
+            make_scope(name, model, &options).await
         }
-        MakeCommands::Job { name, sync } => {
-            make_job(name, sync).await
+        MakeCommands::Provider { name, deferred, .. } => {
+            make_provider(name, deferred, &options).await
         }
-        MakeCommands::Event { name } => {
-            make_event(name).await
+        MakeCommands::Cast { name, cast_type, .. } => {
+            make_cast(name, cast_type, &options).await
         }
-        MakeCommands::Listener { name, event } => {
-            make_listener(name, event).await
+        MakeCommands::Action { name, model, .. } => {
+            make_action(name, model, &options).await
         }
-        MakeCommands::Policy { name, model } => {
-            make_policy(name, model).await
+        MakeCommands::Mail { name, markdown, .. } => {
+            make_mail(name, markdown, &options).await
         }
-        MakeCommands::Trait { name } => {
-            make_trait(name).await
+        MakeCommands::Config { name, section, .. } => {
+            make_config(name, section, &options).await
         }
-        MakeCommands::Test { name, unit, integration } => {
-            make_test(name, unit, integration).await
+        MakeCommands::K8s { name, replicas, image, .. } => {
+            super::deploy::make_k8s(name, replicas, image, options.force).await
         }
     }
 }
 
 /// Generate a controller
-async fn make_controller(name: String, resource: bool, api: bool, model: Option<String>) -> Result<()> {
+async fn make_controller(name: String, resource: bool, api: bool, model: Option<String>, options: &GeneratorOptions) -> Result<()> {
     CommandUtils::ensure_rustisan_project()?;
 
     CommandUtils::info(&format!("Creating controller {}...", name.cyan().bold()));
@@ -96,14 +171,14 @@ async fn make_controller(name: String, resource: bool, api: bool, model: Option<
     let content = template_manager.render(template_name, &template_vars)?;
 
     // Write file
-    let file_path = std::path::Path::new("src/controllers")
+    let file_path = std::path::Path::new(&app_path("src/controllers"))
         .join(format!("{}.rs", CommandUtils::to_snake_case(&name)));
 
     CommandUtils::ensure_directory(file_path.parent().unwrap())?;
-    CommandUtils::write_file(&file_path, &content)?;
+    write_generated(&file_path, &content, options)?;
 
     // Update mod.rs
-    update_module_file("src/controllers", &name)?;
+    update_module_file(&app_path("src/controllers"), &name)?;
 
     CommandUtils::success(&format!("Controller {} created successfully!", name.cyan().bold()));
 
@@ -115,7 +190,7 @@ async fn make_controller(name: String, resource: bool, api: bool, model: Option<
 }
 
 /// Generate a model
-async fn make_model(name: String, migration: bool, factory: bool, seeder: bool) -> Result<()> {
+async fn make_model(name: String, migration: bool, factory: bool, seeder: bool, options: &GeneratorOptions) -> Result<()> {
     CommandUtils::ensure_rustisan_project()?;
 
     CommandUtils::info(&format!("Creating model {}...", name.cyan().bold()));
@@ -126,505 +201,6027 @@ async fn make_model(name: String, migration: bool, factory: bool, seeder: bool)
 
     // Generate additional components if requested
     if migration {
-        make_migration(
-            format!("create_{}_table", pluralize(&CommandUtils::to_snake_case(&name))),
-            Some(pluralize(&CommandUtils::to_snake_case(&name))),
-            None
-        ).await?;
+        let table = GeneratorOptions::for_model_migration(&name).create_table.unwrap_or_default();
+        make_migration(format!("create_{}_table", table), Some(table), None, None, options).await?;
     }
 
     if factory {
-        make_factory(format!("{}Factory", name), Some(name.clone())).await?;
+        make_factory(format!("{}Factory", name), Some(name.clone()), options).await?;
     }
 
     if seeder {
-        make_seeder(format!("{}Seeder", name), Some(name)).await?;
+        make_seeder(format!("{}Seeder", name), Some(name), None, None, options).await?;
     }
 
     Ok(())
 }
 
 /// Generate a migration
-async fn make_migration(name: String, create: Option<String>, table: Option<String>) -> Result<()> {
+async fn make_migration(name: String, create: Option<String>, table: Option<String>, columns: Option<String>, options: &GeneratorOptions) -> Result<()> {
     CommandUtils::ensure_rustisan_project()?;
 
     CommandUtils::info(&format!("Creating migration {}...", name.cyan().bold()));
 
-    // Generate timestamp
+    let target = match (create, table) {
+        (Some(_), Some(_)) => return Err(anyhow::anyhow!("--create and --table cannot be used together")),
+        (Some(table), None) => Some(MigrationTarget::Create(table)),
+        (None, Some(table)) => Some(MigrationTarget::Alter(table)),
+        (None, None) => None,
+    };
+    let columns = match columns {
+        Some(spec) if target.is_none() => {
+            return Err(anyhow::anyhow!("--columns '{}' needs --create or --table", spec));
+        }
+        Some(spec) => parse_migration_columns(&spec)?,
+        None => Vec::new(),
+    };
+
+    write_migration(&name, &migration_source(&name, target.as_ref(), &columns), options)
+}
+
+/// Generate a migration turning the current database schema into the one declared in a SQL file
+async fn make_migration_from_schema(name: String, file: String, options: &GeneratorOptions) -> Result<()> {
+    use super::db::{execute_sql, schema, schema_diff, Connection};
+
+    CommandUtils::ensure_rustisan_project()?;
+
+    CommandUtils::info(&format!("Creating migration {} from {}...", name.cyan().bold(), file));
+
+    let sql = std::fs::read_to_string(&file).map_err(|e| anyhow::anyhow!("Failed to read schema file {}: {}", file, e))?;
+    let desired = schema_diff::parse_schema_file(&sql);
+
+    let connection = Connection::from_config()?;
+    let query = |sql: &str| execute_sql(&connection, sql);
+    let (driver, database) = (connection.driver.as_str(), connection.database.as_str());
+
+    let existing = schema::tables(driver, database, query)?;
+    let mut current = Vec::new();
+    for (table, _) in &desired.tables {
+        if !existing.iter().any(|existing| existing.name.eq_ignore_ascii_case(&table.name)) {
+            continue;
+        }
+        current.push(schema_diff::TableDefinition {
+            name: table.name.clone(),
+            columns: schema::columns(driver, database, &table.name, query)?.into_iter().map(Into::into).collect(),
+            indexes: schema::indexes(driver, database, &table.name, query)?,
+        });
+    }
+
+    let diff = schema_diff::diff_schema(driver, &desired, &current);
+    if diff.up.is_empty() {
+        CommandUtils::warning("The database already matches the schema file, nothing to migrate");
+        return Ok(());
+    }
+
+    write_migration(&name, &render_migration(&name, &diff.up_body(), &diff.down_body()), options)
+}
+
+/// Write a migration to database/migrations/ under a timestamped name
+fn write_migration(name: &str, content: &str, options: &GeneratorOptions) -> Result<()> {
     let timestamp = chrono::Utc::now().format("%Y_%m_%d_%H%M%S");
-    let migration_name = format!("{}_{}", timestamp, CommandUtils::to_snake_case(&name));
-    let class_name = CommandUtils::to_pascal_case(&name);
+    let migration_name = format!("{}_{}", timestamp, CommandUtils::to_snake_case(name));
 
-    // Generate basic migration content
-    let content = format!(
-        r#"//! Migration: {}
+    let file_path = format!("database/migrations/{}.rs", migration_name);
+    CommandUtils::ensure_directory(&std::path::Path::new(&file_path).parent().unwrap())?;
+    write_generated(&file_path, content, options)?;
+
+    CommandUtils::success(&format!("Migration created: {}", file_path));
+
+    Ok(())
+}
+
+/// Table a migration creates or alters
+#[derive(Debug, Clone, PartialEq)]
+enum MigrationTarget {
+    Create(String),
+    Alter(String),
+}
+
+/// Column types accepted by `--columns` and the Blueprint method adding each
+const COLUMN_TYPES: &[(&str, &str)] = &[
+    ("string", "string"),
+    ("text", "text"),
+    ("integer", "integer"),
+    ("bigint", "big_integer"),
+    ("boolean", "boolean"),
+    ("float", "float"),
+    ("decimal", "decimal"),
+    ("datetime", "date_time"),
+    ("date", "date"),
+    ("json", "json"),
+    ("uuid", "uuid"),
+];
+
+/// Column modifiers accepted by `--columns`
+const COLUMN_MODIFIERS: &[&str] = &["nullable", "unique", "index", "unsigned"];
+
+/// A column from `--columns`, e.g. `email:string:unique`
+#[derive(Debug, Clone, PartialEq)]
+struct MigrationColumn {
+    name: String,
+    method: &'static str,
+    modifiers: Vec<String>,
+}
+
+impl MigrationColumn {
+    /// Blueprint call adding the column, e.g. `table.string("email").unique();`
+    fn definition(&self) -> String {
+        let modifiers: String = self.modifiers.iter().map(|modifier| format!(".{}()", modifier)).collect();
+        format!("table.{}(\"{}\"){};", self.method, self.name, modifiers)
+    }
+}
+
+/// Parse `--columns`, e.g. `name:string,email:string:unique,age:integer:nullable`
+fn parse_migration_columns(spec: &str) -> Result<Vec<MigrationColumn>> {
+    spec.split(',')
+        .map(str::trim)
+        .filter(|column| !column.is_empty())
+        .map(|column| {
+            let mut parts = column.split(':').map(str::trim);
+            let name = parts.next().unwrap_or_default().to_string();
+            let column_type = parts
+                .next()
+                .ok_or_else(|| anyhow::anyhow!("Column '{}' needs a type, e.g. {}:string", name, name))?;
+            let method = COLUMN_TYPES
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(column_type))
+                .map(|(_, method)| *method)
+                .ok_or_else(|| {
+                    let types: Vec<&str> = COLUMN_TYPES.iter().map(|(name, _)| *name).collect();
+                    anyhow::anyhow!("Unknown column type '{}' for '{}' (expected {})", column_type, name, types.join(", "))
+                })?;
+
+            let modifiers = parts
+                .map(|modifier| {
+                    if COLUMN_MODIFIERS.contains(&modifier) {
+                        Ok(modifier.to_string())
+                    } else {
+                        Err(anyhow::anyhow!(
+                            "Unknown modifier '{}' for '{}' (expected {})",
+                            modifier,
+                            name,
+                            COLUMN_MODIFIERS.join(", ")
+                        ))
+                    }
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            Ok(MigrationColumn { name, method, modifiers })
+        })
+        .collect()
+}
+
+/// Source of a migration creating or altering a table with the given columns
+fn migration_source(name: &str, target: Option<&MigrationTarget>, columns: &[MigrationColumn]) -> String {
+    let definitions: String = columns
+        .iter()
+        .map(|column| format!("            {}\n", column.definition()))
+        .collect();
+
+    let (up, down) = match target {
+        Some(MigrationTarget::Create(table)) => (
+            format!(
+                "        schema.create(\"{table}\", |table: &mut Blueprint| {{\n            table.id();\n{definitions}            table.timestamps();\n        }})?;\n        Ok(())",
+                table = table,
+                definitions = definitions
+            ),
+            format!("        schema.drop_if_exists(\"{}\")?;\n        Ok(())", table),
+        ),
+        Some(MigrationTarget::Alter(table)) if !columns.is_empty() => {
+            let names: Vec<String> = columns.iter().map(|column| format!("\"{}\"", column.name)).collect();
+            (
+                format!(
+                    "        schema.table(\"{table}\", |table: &mut Blueprint| {{\n{definitions}        }})?;\n        Ok(())",
+                    table = table,
+                    definitions = definitions
+                ),
+                format!("        schema.drop_columns(\"{}\", &[{}])?;\n        Ok(())", table, names.join(", ")),
+            )
+        }
+        Some(MigrationTarget::Alter(table)) => (
+            format!(
+                "        schema.table(\"{}\", |table: &mut Blueprint| {{\n            // Add your column changes here\n        }})?;\n        Ok(())",
+                table
+            ),
+            "        // Add your rollback logic here\n        Ok(())".to_string(),
+        ),
+        None => (
+            "        // Add your migration logic here\n        Ok(())".to_string(),
+            "        // Add your rollback logic here\n        Ok(())".to_string(),
+        ),
+    };
+
+    render_migration(name, &up, &down)
+}
+
+/// Source of a migration with the given `up()` and `down()` bodies
+fn render_migration(name: &str, up: &str, down: &str) -> String {
+    format!(
+        r#"//! Migration: {name}
 //! Generated by Rustisan CLI
 
 use rustisan_core::{{Migration, Schema}};
 use rustisan_core::database::{{Blueprint, Column}};
 use anyhow::Result;
 
-pub struct {} {{}}
+pub struct {class_name} {{}}
 
-impl Migration for {} {{
-    fn up(&self, schema: &mut Schema) -> Result<()> {{
-        // Add your migration logic here
-        Ok(())
+impl Migration for {class_name} {{
+    /// When `dry_run` is set, schema operations are rolled back and only their SQL is reported
+    fn up(&self, schema: &mut Schema, dry_run: bool) -> Result<()> {{
+        schema.dry_run(dry_run);
+{up}
     }}
 
     fn down(&self, schema: &mut Schema) -> Result<()> {{
-        // Add your rollback logic here
-        Ok(())
+{down}
     }}
 }}
 "#,
-        name, class_name, class_name
-    );
-
-    // Write to file
-    let file_path = format!("database/migrations/{}.rs", migration_name);
-    CommandUtils::ensure_directory(&std::path::Path::new(&file_path).parent().unwrap())?;
-    CommandUtils::write_file(&file_path, &content)?;
-
-    CommandUtils::success(&format!("Migration created: {}", file_path));
-
-    Ok(())
+        name = name,
+        class_name = CommandUtils::to_pascal_case(name),
+        up = up,
+        down = down,
+    )
 }
 
 /// Generate middleware
-async fn make_middleware(name: String) -> Result<()> {
+async fn make_middleware(name: String, template: Option<String>, options: &GeneratorOptions) -> Result<()> {
     CommandUtils::ensure_rustisan_project()?;
 
     CommandUtils::info(&format!("Creating middleware {}...", name.cyan().bold()));
 
-    // TODO: Implement middleware generation
+    let base_name = strip_suffix(&name, "Middleware");
+    let class_name = CommandUtils::to_pascal_case(&base_name);
+    let module_name = format!("{}_middleware", CommandUtils::to_snake_case(&base_name));
+
+    let content = match template.as_deref() {
+        Some("access-log") => Some(access_log_middleware(&class_name)),
+        Some("jwt") => Some(jwt_middleware(&class_name)),
+        Some("basic") => Some(basic_auth_middleware(&class_name)),
+        Some("api-key") => Some(api_key_middleware(&class_name)),
+        Some(other) => {
+            return Err(anyhow::anyhow!(
+                "Unknown middleware template '{}'. Available templates: {}",
+                other,
+                MIDDLEWARE_TEMPLATES.join(", ")
+            ));
+        }
+        // TODO: Implement middleware generation
+        None => None,
+    };
+
+    if let Some(content) = content {
+        let file_path = write_component(&app_path("src/middleware"), &module_name, &content, options)?;
+        CommandUtils::success(&format!("Middleware created: {}", file_path));
+    }
+
     CommandUtils::success(&format!("Middleware {} created successfully!", name.cyan().bold()));
 
     Ok(())
 }
 
-/// Generate a request validator
-async fn make_request(name: String) -> Result<()> {
-    CommandUtils::ensure_rustisan_project()?;
+/// `src/events/mod.rs` written with the first event
+const EVENTS_MODULE: &str = r#"//! Events module
+//!
+//! Events are dispatched through an [`EventDispatcher`], which calls every
+//! listener registered for the event's type in registration order.
+//!
+//! Requires the following dependencies in Cargo.toml:
+//!
+//! ```toml
+//! anyhow = "1.0"
+//! async-trait = "0.1"
+//! ```
 
-    CommandUtils::info(&format!("Creating request {}...", name.cyan().bold()));
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
 
-    // TODO: Implement request generation
-    CommandUtils::success(&format!("Request {} created successfully!", name.cyan().bold()));
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::Value;
 
-    Ok(())
+/// An event that listeners can subscribe to
+pub trait Event: Any + Send + Sync {
+    fn name() -> &'static str;
+    fn payload(&self) -> &Value;
 }
 
-/// Generate a resource transformer
-async fn make_resource(name: String, collection: bool) -> Result<()> {
-    CommandUtils::ensure_rustisan_project()?;
-
-    CommandUtils::info(&format!("Creating resource {}...", name.cyan().bold()));
-
-    // TODO: Implement resource generation
-
-    let class_name = CommandUtils::to_pascal_case(&name);
-    let snake_case = CommandUtils::to_snake_case(&name);
+/// Reacts to events of type `E`
+#[async_trait]
+pub trait Listener<E: Event>: Send + Sync {
+    async fn handle(&self, event: &E) -> Result<()>;
+}
 
-    let content = if collection {
-        format!(
-            r#"//! {} Resource Collection
+type HandlerFuture = Pin<Box<dyn Future<Output = Result<()>> + Send>>;
+type Handler = Box<dyn Fn(Arc<dyn Any + Send + Sync>) -> HandlerFuture + Send + Sync>;
 
-use serde::{{Deserialize, Serialize}};
+/// Routes dispatched events to their registered listeners
+#[derive(Default)]
+pub struct EventDispatcher {
+    listeners: HashMap<TypeId, Vec<Handler>>,
+}
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct {}Collection {{
-    pub data: Vec<{}Resource>,
-}}
+impl EventDispatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-impl {}Collection {{
-    pub fn new(data: Vec<{}Resource>) -> Self {{
-        Self {{ data }}
-    }}
-}}
+    /// Register a listener for events of type `E`
+    pub fn listen<E: Event, L: Listener<E> + 'static>(&mut self, listener: L) {
+        let listener = Arc::new(listener);
+        self.listeners.entry(TypeId::of::<E>()).or_default().push(Box::new(move |event| {
+            let listener = Arc::clone(&listener);
+            Box::pin(async move {
+                match event.downcast::<E>() {
+                    Ok(event) => listener.handle(&event).await,
+                    Err(_) => Ok(()),
+                }
+            })
+        }));
+    }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct {}Resource {{
-    // Add your resource fields here
-}}
-"#,
-            name, class_name, class_name, class_name, class_name, class_name
-        )
-    } else {
-        format!(
-            r#"//! {} Resource
+    /// Call every listener registered for the event, stopping at the first error
+    pub async fn dispatch<E: Event>(&self, event: E) -> Result<()> {
+        let Some(handlers) = self.listeners.get(&TypeId::of::<E>()) else {
+            return Ok(());
+        };
 
-use serde::{{Deserialize, Serialize}};
+        let event: Arc<dyn Any + Send + Sync> = Arc::new(event);
+        for handler in handlers {
+            handler(Arc::clone(&event)).await?;
+        }
+        Ok(())
+    }
+}
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct {}Resource {{
-    // Add your resource fields here
-}}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
 
-impl {}Resource {{
-    pub fn new() -> Self {{
-        Self {{
-            // Initialize fields
-        }}
-    }}
-}}
-"#,
-            name, class_name, class_name
-        )
-    };
+    struct Pinged(Value);
 
-    let file_path = format!("src/resources/{}.rs", snake_case);
-    CommandUtils::ensure_directory(&std::path::Path::new(&file_path).parent().unwrap())?;
-    CommandUtils::write_file(&file_path, &content)?;
+    impl Event for Pinged {
+        fn name() -> &'static str {
+            "pinged"
+        }
 
-    CommandUtils::success(&format!("Resource {} created successfully!", name.cyan().bold()));
+        fn payload(&self) -> &Value {
+            &self.0
+        }
+    }
 
-    Ok(())
-}
+    #[derive(Default)]
+    struct Recorder {
+        received: Arc<Mutex<Vec<Value>>>,
+    }
 
-/// Generate seeder
-async fn make_seeder(name: String, model: Option<String>) -> Result<()> {
-    CommandUtils::ensure_rustisan_project()?;
+    #[async_trait]
+    impl Listener<Pinged> for Recorder {
+        async fn handle(&self, event: &Pinged) -> Result<()> {
+            self.received.lock().unwrap().push(event.payload().clone());
+            Ok(())
+        }
+    }
 
-    CommandUtils::info(&format!("Creating seeder {}...", name.cyan().bold()));
+    #[tokio::test]
+    async fn test_dispatch_invokes_listener() {
+        let recorder = Recorder::default();
+        let received = Arc::clone(&recorder.received);
 
-    let class_name = CommandUtils::to_pascal_case(&name);
-    let snake_case = CommandUtils::to_snake_case(&name);
-    let model_name = model.unwrap_or_else(|| name.clone());
+        let mut dispatcher = EventDispatcher::new();
+        dispatcher.listen::<Pinged, _>(recorder);
+        dispatcher.dispatch(Pinged(serde_json::json!({ "id": 1 }))).await.unwrap();
 
-    let content = format!(
-        r#"//! {} Seeder
+        assert_eq!(*received.lock().unwrap(), vec![serde_json::json!({ "id": 1 })]);
+    }
+}
+"#;
 
-use anyhow::Result;
+/// Templates accepted by `make middleware --template`
+pub const MIDDLEWARE_TEMPLATES: &[&str] = &["access-log", "jwt", "basic", "api-key"];
 
-pub struct {}Seeder {{}}
+/// Source of an access log middleware
+fn access_log_middleware(class_name: &str) -> String {
+    format!(
+        r#"//! {class_name} middleware
+//!
+//! Logs every request with its method, URI, status code, duration, remote IP
+//! and a request ID generated per request. The `format` field selects
+//! structured `"json"` fields or Apache `"combined"` log lines.
+//!
+//! Every request is also appended to `storage/logs/access.log` as a combined
+//! log line, which `rustisan info --watch` reads for the request rate.
+//!
+//! Requires the following dependencies in Cargo.toml:
+//!
+//! ```toml
+//! uuid = {{ version = "1.0", features = ["v4"] }}
+//!
+//! [dev-dependencies]
+//! tracing-subscriber = "0.3"
+//! ```
+
+use std::fs::OpenOptions;
+use std::future::Future;
+use std::io::Write;
+use std::net::IpAddr;
+use std::path::{{Path, PathBuf}};
+use std::time::Instant;
+
+use chrono::{{DateTime, Local}};
+use tracing::info;
+use uuid::Uuid;
+
+/// Log file every request is appended to
+pub const ACCESS_LOG: &str = "storage/logs/access.log";
+
+/// Request details recorded in the access log
+#[derive(Debug, Clone, Default)]
+pub struct AccessLogRequest {{
+    pub method: String,
+    pub uri: String,
+    pub remote_ip: Option<IpAddr>,
+    pub referer: Option<String>,
+    pub user_agent: Option<String>,
+}}
 
-impl {}Seeder {{
-    pub async fn run() -> Result<()> {{
-        // Add your seeding logic here
-        // Example: Create {} records
-        println!("Seeding {} data...");
+/// Responses whose status code can be logged
+pub trait ResponseStatus {{
+    fn status_code(&self) -> u16;
+}}
 
-        Ok(())
+impl ResponseStatus for u16 {{
+    fn status_code(&self) -> u16 {{
+        *self
     }}
 }}
-"#,
-        name, class_name, class_name, model_name, model_name
-    );
-
-    let file_path = format!("database/seeders/{}.rs", snake_case);
-    CommandUtils::ensure_directory(&std::path::Path::new(&file_path).parent().unwrap())?;
-    CommandUtils::write_file(&file_path, &content)?;
-
-    CommandUtils::success(&format!("Seeder {} created successfully!", name.cyan().bold()));
 
-    Ok(())
-}
+/// Logs one entry per handled request
+#[derive(Debug, Clone)]
+pub struct {class_name}Middleware {{
+    /// `"json"` or `"combined"`
+    pub format: String,
+    /// File requests are appended to, `None` to only log through tracing
+    pub log_file: Option<PathBuf>,
+}}
 
-/// Generate factory
-async fn make_factory(name: String, model: Option<String>) -> Result<()> {
-    CommandUtils::ensure_rustisan_project()?;
+impl {class_name}Middleware {{
+    pub fn new(format: &str) -> Self {{
+        Self {{ format: format.to_string(), log_file: Some(PathBuf::from(ACCESS_LOG)) }}
+    }}
 
-    CommandUtils::info(&format!("Creating factory {}...", name.cyan().bold()));
+    /// Run `next` with a new request ID and log the request once it completes
+    pub async fn handle<F, Fut, R>(&self, request: &AccessLogRequest, next: F) -> R
+    where
+        F: FnOnce(String) -> Fut,
+        Fut: Future<Output = R>,
+        R: ResponseStatus,
+    {{
+        let request_id = Uuid::new_v4().to_string();
+        let started = Instant::now();
+        let received_at = Local::now();
 
-    let class_name = CommandUtils::to_pascal_case(&name);
-    let snake_case = CommandUtils::to_snake_case(&name);
-    let model_name = model.unwrap_or_else(|| name.clone());
+        let response = next(request_id.clone()).await;
 
-    let content = format!(
-        r#"//! {} Factory
+        let duration_ms = started.elapsed().as_secs_f64() * 1000.0;
+        self.log(request, &request_id, response.status_code(), duration_ms, received_at);
 
-use anyhow::Result;
-use fake::{{Fake, Faker}};
+        response
+    }}
 
-pub struct {}Factory {{}}
+    fn log(&self, request: &AccessLogRequest, request_id: &str, status: u16, duration_ms: f64, received_at: DateTime<Local>) {{
+        let remote_ip = request.remote_ip.map(|ip| ip.to_string()).unwrap_or_else(|| "-".to_string());
+        let combined = format!(
+            "{{}} - - [{{}}] \"{{}} {{}} HTTP/1.1\" {{}} - \"{{}}\" \"{{}}\" request_id={{}} duration_ms={{:.2}}",
+            remote_ip,
+            received_at.format("%d/%b/%Y:%H:%M:%S %z"),
+            request.method,
+            request.uri,
+            status,
+            request.referer.as_deref().unwrap_or("-"),
+            request.user_agent.as_deref().unwrap_or("-"),
+            request_id,
+            duration_ms,
+        );
+
+        if let Some(path) = &self.log_file {{
+            if let Err(error) = append_line(path, &combined) {{
+                tracing::warn!(target: "access", "Failed to write {{}}: {{}}", path.display(), error);
+            }}
+        }}
 
-impl {}Factory {{
-    pub fn create() -> {} {{
-        // Add factory logic here using fake data
-        // Example factory implementation
-        {} {{
-            // Generate fake data
+        if self.format == "combined" {{
+            info!(target: "access", "{{}}", combined);
+        }} else {{
+            info!(
+                target: "access",
+                request_id = %request_id,
+                method = %request.method,
+                uri = %request.uri,
+                status,
+                duration_ms,
+                remote_ip = %remote_ip,
+                "request completed"
+            );
         }}
     }}
+}}
 
-    pub fn create_many(count: usize) -> Vec<{}> {{
-        (0..count).map(|_| Self::create()).collect()
+impl Default for {class_name}Middleware {{
+    fn default() -> Self {{
+        Self::new("json")
     }}
 }}
-"#,
-        name, class_name, class_name, model_name, model_name, model_name
-    );
 
-    let file_path = format!("database/factories/{}.rs", snake_case);
-    CommandUtils::ensure_directory(&std::path::Path::new(&file_path).parent().unwrap())?;
-    CommandUtils::write_file(&file_path, &content)?;
+fn append_line(path: &Path, line: &str) -> std::io::Result<()> {{
+    if let Some(parent) = path.parent() {{
+        std::fs::create_dir_all(parent)?;
+    }}
+    // One write per line keeps concurrent requests from interleaving
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(format!("{{}}\n", line).as_bytes())
+}}
 
-    CommandUtils::success(&format!("Factory {} created successfully!", name.cyan().bold()));
+#[cfg(test)]
+mod tests {{
+    use super::*;
+    use std::sync::{{Arc, Mutex}};
 
-    Ok(())
-}
+    /// Collects formatted tracing output
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
 
-/// Generate command
-async fn make_command(name: String) -> Result<()> {
-    CommandUtils::ensure_rustisan_project()?;
+    impl Write for Captured {{
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {{
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }}
 
-    CommandUtils::info(&format!("Creating command {}...", name.cyan().bold()));
+        fn flush(&mut self) -> std::io::Result<()> {{
+            Ok(())
+        }}
+    }}
 
-    let class_name = CommandUtils::to_pascal_case(&name);
-    let snake_case = CommandUtils::to_snake_case(&name);
+    async fn log_request(middleware: &{class_name}Middleware) -> String {{
+        let captured = Captured::default();
+        let writer = captured.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .with_ansi(false)
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let request = AccessLogRequest {{
+            method: "GET".to_string(),
+            uri: "/users?page=2".to_string(),
+            remote_ip: Some("203.0.113.7".parse().unwrap()),
+            user_agent: Some("curl/8.0".to_string()),
+            ..Default::default()
+        }};
+        let status = middleware.handle(&request, |_request_id| async {{ 200u16 }}).await;
+        assert_eq!(status, 200);
+
+        let output = captured.0.lock().unwrap().clone();
+        String::from_utf8(output).unwrap()
+    }}
 
-    let content = format!(
-        r#"//! {} Command
+    #[tokio::test]
+    async fn test_json_fields() {{
+        let middleware = {class_name}Middleware {{ log_file: None, ..Default::default() }};
+        let output = log_request(&middleware).await;
+
+        assert!(output.contains("request_id="));
+        assert!(output.contains("method=GET"));
+        assert!(output.contains("uri=/users?page=2"));
+        assert!(output.contains("status=200"));
+        assert!(output.contains("duration_ms="));
+        assert!(output.contains("remote_ip=203.0.113.7"));
+    }}
 
-use anyhow::Result;
-use clap::Parser;
+    #[tokio::test]
+    async fn test_combined_format() {{
+        let middleware = {class_name}Middleware {{ log_file: None, ..{class_name}Middleware::new("combined") }};
+        let output = log_request(&middleware).await;
 
-#[derive(Parser)]
-pub struct {}Command {{
-    /// Add command arguments here
-}}
+        assert!(output.contains("203.0.113.7 - - ["));
+        assert!(output.contains("\"GET /users?page=2 HTTP/1.1\" 200 - \"-\" \"curl/8.0\""));
+        assert!(output.contains("request_id="));
+    }}
 
-impl {}Command {{
-    pub async fn execute(self) -> Result<()> {{
-        // Add command logic here
-        println!("Executing {} command...");
+    #[tokio::test]
+    async fn test_appends_to_log_file() {{
+        let path = std::env::temp_dir().join(format!("access-{{}}.log", Uuid::new_v4()));
+        let middleware = {class_name}Middleware {{ log_file: Some(path.clone()), ..Default::default() }};
 
-        Ok(())
+        log_request(&middleware).await;
+        log_request(&middleware).await;
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(content.lines().count(), 2);
+        assert!(content.contains("\"GET /users?page=2 HTTP/1.1\" 200"));
     }}
 }}
 "#,
-        name, class_name, class_name, name
-    );
-
-    let file_path = format!("src/commands/{}.rs", snake_case);
-    CommandUtils::ensure_directory(&std::path::Path::new(&file_path).parent().unwrap())?;
-    CommandUtils::write_file(&file_path, &content)?;
+        class_name = class_name,
+    )
+}
 
-    CommandUtils::success(&format!("Command {} created successfully!", name.cyan().bold()));
+/// Response type shared by the authentication middleware templates
+const AUTH_RESPONSE: &str = r#"/// JSON response returned by `next` or when authentication fails
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonResponse {
+    pub status: u16,
+    pub body: Value,
+}
 
-    Ok(())
+impl JsonResponse {
+    pub fn unauthorized(message: &str) -> Self {
+        Self {
+            status: 401,
+            body: json!({ "error": "Unauthorized", "message": message }),
+        }
+    }
 }
 
-/// Generate job
-async fn make_job(name: String, sync: bool) -> Result<()> {
-    CommandUtils::ensure_rustisan_project()?;
+/// Look up a header case-insensitively
+fn header<'a>(headers: &'a HashMap<String, String>, name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+"#;
 
-    CommandUtils::info(&format!("Creating job {}...", name.cyan().bold()));
+/// Secret comparison helper for the basic and api-key templates
+const CONSTANT_TIME_EQ: &str = r#"
+/// Compare secrets without returning early on the first differing byte
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+"#;
 
-    let class_name = CommandUtils::to_pascal_case(&name);
-    let snake_case = CommandUtils::to_snake_case(&name);
+/// Source of a JWT authentication middleware
+fn jwt_middleware(class_name: &str) -> String {
+    format!(
+        r#"//! {class_name} middleware
+//!
+//! Authenticates requests carrying an `Authorization: Bearer <token>` header
+//! signed with HS256. Requests without a valid token receive a 401 JSON response.
+//!
+//! Requires the following dependencies in Cargo.toml:
+//!
+//! ```toml
+//! jsonwebtoken = "9"
+//! serde = {{ version = "1.0", features = ["derive"] }}
+//! ```
 
-    let content = if sync {
-        format!(
-            r#"//! {} Synchronous Job
+use std::collections::HashMap;
+use std::future::Future;
 
-use anyhow::Result;
+use jsonwebtoken::{{decode, Algorithm, DecodingKey, Validation}};
 use serde::{{Deserialize, Serialize}};
+use serde_json::{{json, Value}};
+
+/// Token claims
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Claims {{
+    /// Subject, usually the user ID
+    pub sub: String,
+    /// Expiry as a Unix timestamp
+    pub exp: usize,
+    /// Issue time as a Unix timestamp
+    pub iat: usize,
+}}
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct {}Job {{
-    // Add job data fields here
+{auth_response}
+/// Rejects requests without a valid bearer token
+#[derive(Debug, Clone)]
+pub struct {class_name}Middleware {{
+    pub secret: String,
 }}
 
-impl {}Job {{
-    pub fn new() -> Self {{
-        Self {{
-            // Initialize fields
-        }}
+impl {class_name}Middleware {{
+    pub fn new(secret: &str) -> Self {{
+        Self {{ secret: secret.to_string() }}
     }}
 
-    pub fn handle(&self) -> Result<()> {{
-        // Add synchronous job logic here
-        println!("Processing {} job synchronously...");
+    /// Decode a token, checking its signature and expiry
+    pub fn validate_token(&self, token: &str) -> jsonwebtoken::errors::Result<Claims> {{
+        let key = DecodingKey::from_secret(self.secret.as_bytes());
+        decode::<Claims>(token, &key, &Validation::new(Algorithm::HS256)).map(|data| data.claims)
+    }}
 
-        Ok(())
+    /// Pass the request's claims to `next`, or respond with 401
+    pub async fn handle<F, Fut>(&self, headers: &HashMap<String, String>, next: F) -> JsonResponse
+    where
+        F: FnOnce(Claims) -> Fut,
+        Fut: Future<Output = JsonResponse>,
+    {{
+        let Some(token) = header(headers, "authorization").and_then(|value| value.strip_prefix("Bearer ")) else {{
+            return JsonResponse::unauthorized("Missing bearer token");
+        }};
+
+        match self.validate_token(token.trim()) {{
+            Ok(claims) => next(claims).await,
+            Err(_) => JsonResponse::unauthorized("Invalid or expired token"),
+        }}
     }}
 }}
-"#,
-            name, class_name, class_name, name
-        )
-    } else {
-        format!(
-            r#"//! {} Asynchronous Job
-
-use anyhow::Result;
-use serde::{{Deserialize, Serialize}};
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct {}Job {{
-    // Add job data fields here
-}}
+#[cfg(test)]
+mod tests {{
+    use super::*;
+    use jsonwebtoken::{{encode, EncodingKey, Header}};
+
+    const SECRET: &str = "test-secret";
+
+    fn token(expires_in: i64) -> String {{
+        let now = chrono::Utc::now().timestamp();
+        let claims = Claims {{
+            sub: "42".to_string(),
+            exp: (now + expires_in) as usize,
+            iat: now as usize,
+        }};
+        encode(&Header::default(), &claims, &EncodingKey::from_secret(SECRET.as_bytes())).unwrap()
+    }}
 
-impl {}Job {{
-    pub fn new() -> Self {{
-        Self {{
-            // Initialize fields
+    async fn request(authorization: Option<String>) -> JsonResponse {{
+        let mut headers = HashMap::new();
+        if let Some(authorization) = authorization {{
+            headers.insert("Authorization".to_string(), authorization);
         }}
+
+        {class_name}Middleware::new(SECRET)
+            .handle(&headers, |claims| async move {{
+                JsonResponse {{ status: 200, body: json!({{ "user": claims.sub }}) }}
+            }})
+            .await
     }}
 
-    pub async fn handle(&self) -> Result<()> {{
-        // Add asynchronous job logic here
-        println!("Processing {} job asynchronously...");
+    #[tokio::test]
+    async fn test_valid_token_passes() {{
+        let response = request(Some(format!("Bearer {{}}", token(3600)))).await;
 
-        Ok(())
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body["user"], "42");
+    }}
+
+    #[tokio::test]
+    async fn test_expired_token_is_rejected() {{
+        let response = request(Some(format!("Bearer {{}}", token(-3600)))).await;
+
+        assert_eq!(response.status, 401);
+        assert_eq!(response.body["error"], "Unauthorized");
+    }}
+
+    #[tokio::test]
+    async fn test_missing_or_forged_token_is_rejected() {{
+        assert_eq!(request(None).await.status, 401);
+
+        let forged = encode(
+            &Header::default(),
+            &Claims {{ sub: "1".to_string(), exp: usize::MAX / 2, iat: 0 }},
+            &EncodingKey::from_secret(b"other-secret"),
+        )
+        .unwrap();
+        assert_eq!(request(Some(format!("Bearer {{}}", forged))).await.status, 401);
     }}
 }}
 "#,
-            name, class_name, class_name, name
-        )
-    };
+        class_name = class_name,
+        auth_response = AUTH_RESPONSE,
+    )
+}
 
-    let file_path = format!("src/jobs/{}.rs", snake_case);
-    CommandUtils::ensure_directory(&std::path::Path::new(&file_path).parent().unwrap())?;
-    CommandUtils::write_file(&file_path, &content)?;
+/// Source of an HTTP Basic authentication middleware
+fn basic_auth_middleware(class_name: &str) -> String {
+    format!(
+        r#"//! {class_name} middleware
+//!
+//! Authenticates requests with HTTP Basic credentials. Requests without
+//! valid credentials receive a 401 JSON response.
+//!
+//! Requires the following dependencies in Cargo.toml:
+//!
+//! ```toml
+//! base64 = "0.22"
+//! ```
+
+use std::collections::HashMap;
+use std::future::Future;
+
+use base64::{{engine::general_purpose, Engine as _}};
+use serde_json::{{json, Value}};
+
+{auth_response}{constant_time_eq}
+/// Rejects requests without the configured username and password
+#[derive(Debug, Clone)]
+pub struct {class_name}Middleware {{
+    pub username: String,
+    pub password: String,
+}}
 
-    CommandUtils::success(&format!("Job {} created successfully!", name.cyan().bold()));
+impl {class_name}Middleware {{
+    pub fn new(username: &str, password: &str) -> Self {{
+        Self {{
+            username: username.to_string(),
+            password: password.to_string(),
+        }}
+    }}
 
-    Ok(())
+    /// Check an `Authorization: Basic ...` header value
+    pub fn authenticate(&self, authorization: &str) -> bool {{
+        let Some(encoded) = authorization.strip_prefix("Basic ") else {{
+            return false;
+        }};
+        let Ok(decoded) = general_purpose::STANDARD.decode(encoded.trim()) else {{
+            return false;
+        }};
+        let Some((username, password)) = std::str::from_utf8(&decoded).ok().and_then(|text| text.split_once(':')) else {{
+            return false;
+        }};
+
+        // Check both so the response time doesn't reveal which one was wrong
+        let username_ok = constant_time_eq(username.as_bytes(), self.username.as_bytes());
+        let password_ok = constant_time_eq(password.as_bytes(), self.password.as_bytes());
+        username_ok & password_ok
+    }}
+
+    /// Pass the request to `next`, or respond with 401
+    pub async fn handle<F, Fut>(&self, headers: &HashMap<String, String>, next: F) -> JsonResponse
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = JsonResponse>,
+    {{
+        match header(headers, "authorization") {{
+            Some(authorization) if self.authenticate(authorization) => next().await,
+            _ => JsonResponse::unauthorized("Invalid credentials"),
+        }}
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    async fn request(credentials: &str) -> JsonResponse {{
+        let headers = HashMap::from([(
+            "Authorization".to_string(),
+            format!("Basic {{}}", general_purpose::STANDARD.encode(credentials)),
+        )]);
+
+        {class_name}Middleware::new("admin", "s3cret")
+            .handle(&headers, || async {{ JsonResponse {{ status: 200, body: json!({{}}) }} }})
+            .await
+    }}
+
+    #[tokio::test]
+    async fn test_credentials() {{
+        assert_eq!(request("admin:s3cret").await.status, 200);
+        assert_eq!(request("admin:wrong").await.status, 401);
+        assert_eq!(request("guest:s3cret").await.status, 401);
+        assert_eq!(request("admin").await.status, 401);
+    }}
+}}
+"#,
+        class_name = class_name,
+        auth_response = AUTH_RESPONSE,
+        constant_time_eq = CONSTANT_TIME_EQ,
+    )
 }
 
-/// Generate event
-async fn make_event(name: String) -> Result<()> {
-    CommandUtils::ensure_rustisan_project()?;
+/// Source of an API key authentication middleware
+fn api_key_middleware(class_name: &str) -> String {
+    format!(
+        r#"//! {class_name} middleware
+//!
+//! Authenticates requests carrying one of the configured keys in the
+//! `X-API-Key` header. Other requests receive a 401 JSON response.
 
-    CommandUtils::info(&format!("Creating event {}...", name.cyan().bold()));
+use std::collections::HashMap;
+use std::future::Future;
 
-    // TODO: Implement event generation
-    CommandUtils::success(&format!("Event {} created successfully!", name.cyan().bold()));
+use serde_json::{{json, Value}};
 
-    Ok(())
+{auth_response}{constant_time_eq}
+/// Rejects requests without a known API key
+#[derive(Debug, Clone)]
+pub struct {class_name}Middleware {{
+    pub keys: Vec<String>,
+    /// Header the key is read from
+    pub header: String,
+}}
+
+impl {class_name}Middleware {{
+    pub fn new(keys: Vec<String>) -> Self {{
+        Self {{
+            keys,
+            header: "X-API-Key".to_string(),
+        }}
+    }}
+
+    /// Whether `key` is one of the configured keys
+    pub fn is_valid_key(&self, key: &str) -> bool {{
+        self.keys.iter().any(|known| constant_time_eq(known.as_bytes(), key.as_bytes()))
+    }}
+
+    /// Pass the request to `next`, or respond with 401
+    pub async fn handle<F, Fut>(&self, headers: &HashMap<String, String>, next: F) -> JsonResponse
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = JsonResponse>,
+    {{
+        match header(headers, &self.header) {{
+            Some(key) if self.is_valid_key(key) => next().await,
+            Some(_) => JsonResponse::unauthorized("Invalid API key"),
+            None => JsonResponse::unauthorized("Missing API key"),
+        }}
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    async fn request(key: Option<&str>) -> JsonResponse {{
+        let headers: HashMap<String, String> =
+            key.map(|key| ("x-api-key".to_string(), key.to_string())).into_iter().collect();
+
+        {class_name}Middleware::new(vec!["key-1".to_string(), "key-2".to_string()])
+            .handle(&headers, || async {{ JsonResponse {{ status: 200, body: json!({{}}) }} }})
+            .await
+    }}
+
+    #[tokio::test]
+    async fn test_api_keys() {{
+        assert_eq!(request(Some("key-2")).await.status, 200);
+        assert_eq!(request(Some("key-3")).await.status, 401);
+        assert_eq!(request(None).await.body["message"], "Missing API key");
+    }}
+}}
+"#,
+        class_name = class_name,
+        auth_response = AUTH_RESPONSE,
+        constant_time_eq = CONSTANT_TIME_EQ,
+    )
 }
 
-/// Generate a listener
-async fn make_listener(name: String, event: Option<String>) -> Result<()> {
+/// Generate a request validator
+async fn make_request(name: String, fields: Option<String>, options: &GeneratorOptions) -> Result<()> {
     CommandUtils::ensure_rustisan_project()?;
 
-    CommandUtils::info(&format!("Creating listener {}...", name.cyan().bold()));
+    CommandUtils::info(&format!("Creating request {}...", name.cyan().bold()));
 
-    // TODO: Implement listener generation
-    CommandUtils::success(&format!("Listener {} created successfully!", name.cyan().bold()));
+    let base_name = strip_suffix(&name, "Request");
+    let class_name = CommandUtils::to_pascal_case(&base_name);
+    let module_name = format!("{}_request", CommandUtils::to_snake_case(&base_name));
+
+    let fields = match fields {
+        Some(fields) => parse_request_fields(&fields)?,
+        None => Vec::new(),
+    };
+
+    let content = request_source(&class_name, &fields);
+    let file_path = write_component(&app_path("src/requests"), &module_name, &content, options)?;
+    CommandUtils::success(&format!("Request created: {}", file_path));
+
+    if fields.iter().any(RequestField::has_regex) {
+        CommandUtils::warning("Fill in matching and non-matching values for the regex rules in the generated tests");
+    }
+
+    CommandUtils::success(&format!("Request {} created successfully!", name.cyan().bold()));
 
     Ok(())
 }
 
-/// Generate a policy
-async fn make_policy(name: String, model: Option<String>) -> Result<()> {
-    CommandUtils::ensure_rustisan_project()?;
+/// A validation rule from `--fields`
+#[derive(Debug, Clone, PartialEq)]
+enum RequestRule {
+    Required,
+    Email,
+    Url,
+    Min(u64),
+    Max(u64),
+    Regex(String),
+}
 
-    CommandUtils::info(&format!("Creating policy {}...", name.cyan().bold()));
+impl RequestRule {
+    fn parse(rule: &str) -> Result<Self> {
+        let (name, argument) = match rule.split_once(':') {
+            Some((name, argument)) => (name, Some(argument)),
+            None => (rule, None),
+        };
+        let number = |argument: Option<&str>| -> Result<u64> {
+            argument
+                .and_then(|argument| argument.parse().ok())
+                .ok_or_else(|| anyhow::anyhow!("Rule '{}' needs a number, e.g. {}:3", name, name))
+        };
+
+        match name {
+            "required" => Ok(Self::Required),
+            "email" => Ok(Self::Email),
+            "url" => Ok(Self::Url),
+            "min" => Ok(Self::Min(number(argument)?)),
+            "max" => Ok(Self::Max(number(argument)?)),
+            "regex" => {
+                let pattern = argument.unwrap_or_default();
+                regex::Regex::new(pattern).map_err(|e| anyhow::anyhow!("Invalid regex '{}': {}", pattern, e))?;
+                Ok(Self::Regex(pattern.to_string()))
+            }
+            _ => Err(anyhow::anyhow!(
+                "Unknown rule '{}' (expected required, email, url, min:N, max:N or regex:pattern)",
+                rule
+            )),
+        }
+    }
 
-    // TODO: Implement policy generation
-    CommandUtils::success(&format!("Policy {} created successfully!", name.cyan().bold()));
+    /// Suffix of the generated test for this rule
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Required => "required",
+            Self::Email => "email",
+            Self::Url => "url",
+            Self::Min(_) => "min",
+            Self::Max(_) => "max",
+            Self::Regex(_) => "regex",
+        }
+    }
+}
 
-    Ok(())
+/// A request field from `--fields`, e.g. `email:string:required|email`
+#[derive(Debug, Clone, PartialEq)]
+struct RequestField {
+    name: String,
+    rust_type: &'static str,
+    rules: Vec<RequestRule>,
 }
 
-/// Generate a trait
-async fn make_trait(name: String) -> Result<()> {
-    CommandUtils::ensure_rustisan_project()?;
+impl RequestField {
+    fn is_numeric(&self) -> bool {
+        matches!(self.rust_type, "i64" | "f64")
+    }
 
-    CommandUtils::info(&format!("Creating trait {}...", name.cyan().bold()));
+    fn min(&self) -> Option<u64> {
+        self.rules.iter().find_map(|rule| if let RequestRule::Min(min) = rule { Some(*min) } else { None })
+    }
 
-    let template_vars = serde_json::json!({
-        "name": name,
-        "snake_case": CommandUtils::to_snake_case(&name),
-        "pascal_case": CommandUtils::to_pascal_case(&name)
-    });
+    fn max(&self) -> Option<u64> {
+        self.rules.iter().find_map(|rule| if let RequestRule::Max(max) = rule { Some(*max) } else { None })
+    }
 
-    let content = format!(
-        r#"//! {} trait
-//!
-//! This trait defines the interface for {}.
+    fn has_regex(&self) -> bool {
+        self.rules.iter().any(|rule| matches!(rule, RequestRule::Regex(_)))
+    }
 
-use async_trait::async_trait;
-use rustisan_core::Result;
+    fn pattern_name(&self) -> String {
+        format!("{}_PATTERN", self.name.to_uppercase())
+    }
 
-/// {} trait
-#[async_trait]
-pub trait {} {{
-    /// Implementation required
-    async fn handle(&self) -> Result<()>;
-}}
-"#,
-        name,
-        CommandUtils::to_snake_case(&name),
-        name,
-        CommandUtils::to_pascal_case(&name)
-    );
+    /// `#[validate(...)]` arguments for the field's rules
+    fn validate_attribute(&self) -> Option<String> {
+        let mut checks = Vec::new();
+        for rule in &self.rules {
+            match rule {
+                RequestRule::Required => checks.push("required".to_string()),
+                RequestRule::Email => checks.push("email".to_string()),
+                RequestRule::Url => checks.push("url".to_string()),
+                RequestRule::Regex(_) => checks.push(format!("regex(path = *{})", self.pattern_name())),
+                RequestRule::Min(_) | RequestRule::Max(_) => {}
+            }
+        }
 
-    let file_path = std::path::Path::new("src/traits")
-        .join(format!("{}.rs", CommandUtils::to_snake_case(&name)));
+        let bounds: Vec<String> = [("min", self.min()), ("max", self.max())]
+            .into_iter()
+            .filter_map(|(name, value)| {
+                value.map(|value| if self.rust_type == "f64" { format!("{} = {}.0", name, value) } else { format!("{} = {}", name, value) })
+            })
+            .collect();
+        if !bounds.is_empty() {
+            let check = if self.is_numeric() { "range" } else { "length" };
+            checks.push(format!("{}({})", check, bounds.join(", ")));
+        }
 
-    CommandUtils::ensure_directory(file_path.parent().unwrap())?;
-    CommandUtils::write_file(&file_path, &content)?;
+        (!checks.is_empty()).then(|| format!("#[validate({})]", checks.join(", ")))
+    }
 
-    CommandUtils::success(&format!("Trait {} created successfully!", name.cyan().bold()));
+    /// A JSON value passing every rule of the field
+    fn valid_value(&self) -> String {
+        if self.rust_type == "bool" {
+            return "json!(true)".to_string();
+        }
+        if self.is_numeric() {
+            return format!("json!({})", self.min().or(self.max().map(|max| max.min(1))).unwrap_or(1));
+        }
+        if self.rules.contains(&RequestRule::Email) {
+            return "json!(\"user@example.com\")".to_string();
+        }
+        if self.rules.contains(&RequestRule::Url) {
+            return "json!(\"https://example.com\")".to_string();
+        }
+        let len = self.min().unwrap_or(1).max(1).min(self.max().unwrap_or(u64::MAX));
+        format!("json!(\"a\".repeat({}))", len)
+    }
 
-    Ok(())
+    /// Values passing and failing a rule, or `None` when they cannot be derived
+    fn rule_cases(&self, rule: &RequestRule) -> Option<(String, String)> {
+        let numeric = self.is_numeric();
+        match rule {
+            RequestRule::Required if self.has_regex() => None,
+            RequestRule::Required => Some((self.valid_value(), "Value::Null".to_string())),
+            RequestRule::Email => Some(("json!(\"user@example.com\")".to_string(), "json!(\"not-an-email\")".to_string())),
+            RequestRule::Url => Some(("json!(\"https://example.com\")".to_string(), "json!(\"not a url\")".to_string())),
+            RequestRule::Min(0) => None,
+            RequestRule::Min(min) if numeric => Some((format!("json!({})", min), format!("json!({})", min - 1))),
+            RequestRule::Min(min) => Some((format!("json!(\"a\".repeat({}))", min), format!("json!(\"a\".repeat({}))", min - 1))),
+            RequestRule::Max(max) if numeric => Some((format!("json!({})", max), format!("json!({})", max + 1))),
+            RequestRule::Max(max) => Some((format!("json!(\"a\".repeat({}))", max), format!("json!(\"a\".repeat({}))", max + 1))),
+            RequestRule::Regex(_) => None,
+        }
+    }
 }
 
-/// Generate a test
-async fn make_test(name: String, unit: bool, integration: bool) -> Result<()> {
-    CommandUtils::ensure_rustisan_project()?;
+/// Parse `--fields`, e.g. `name:string:required|min:3,email:string:email`
+///
+/// Rules are separated by `|`. A `regex:` rule takes the rest of the field spec,
+/// so it must come last.
+fn parse_request_fields(spec: &str) -> Result<Vec<RequestField>> {
+    // Commas inside a regex such as `\d{2,4}` do not start a new field
+    let field_start = regex::Regex::new(r"^\s*[A-Za-z_][A-Za-z0-9_]*:[A-Za-z]+(:|$)")?;
+    let mut specs: Vec<String> = Vec::new();
+    for part in spec.split(',') {
+        match specs.last_mut() {
+            Some(last) if !field_start.is_match(part) => {
+                last.push(',');
+                last.push_str(part);
+            }
+            _ => specs.push(part.trim().to_string()),
+        }
+    }
 
-    CommandUtils::info(&format!("Creating test {}...", name.cyan().bold()));
+    specs.iter().filter(|spec| !spec.is_empty()).map(|spec| parse_request_field(spec)).collect()
+}
 
-    // TODO: Implement test generation
+fn parse_request_field(spec: &str) -> Result<RequestField> {
+    let mut parts = spec.splitn(3, ':');
+    let name = CommandUtils::to_snake_case(parts.next().unwrap_or_default());
+    let field_type = parts.next().unwrap_or_default();
+    let rules = parts.next().unwrap_or_default();
 
-    // Create template manager
-    let template_manager = TemplateManager::new()?;
+    let rust_type = field_rust_type(field_type, &name)?;
 
-    let template_name = if integration {
-        "test_integration"
+    let mut parsed = Vec::new();
+    let mut rest = rules;
+    while !rest.is_empty() {
+        if rest.starts_with("regex:") {
+            parsed.push(RequestRule::parse(rest)?);
+            break;
+        }
+        let (rule, remaining) = rest.split_once('|').unwrap_or((rest, ""));
+        if !rule.is_empty() {
+            parsed.push(RequestRule::parse(rule)?);
+        }
+        rest = remaining;
+    }
+
+    let field = RequestField { name, rust_type, rules: parsed };
+    if field.rust_type != "String" && field.rules.iter().any(|rule| matches!(rule, RequestRule::Email | RequestRule::Url | RequestRule::Regex(_))) {
+        return Err(anyhow::anyhow!("Field '{}' must be a string to use email, url or regex rules", field.name));
+    }
+    Ok(field)
+}
+
+/// Rust type for a field type given on the command line
+fn field_rust_type(field_type: &str, field: &str) -> Result<&'static str> {
+    match field_type.to_lowercase().as_str() {
+        "string" | "text" | "str" => Ok("String"),
+        "integer" | "int" | "bigint" | "i64" => Ok("i64"),
+        "float" | "decimal" | "double" | "f64" => Ok("f64"),
+        "boolean" | "bool" => Ok("bool"),
+        other => Err(anyhow::anyhow!(
+            "Unknown type '{}' for field '{}' (expected string, integer, float or boolean)",
+            other,
+            field
+        )),
+    }
+}
+
+/// Source of a form request validating the given fields
+fn request_source(class_name: &str, fields: &[RequestField]) -> String {
+    let has_regex = fields.iter().any(RequestField::has_regex);
+
+    let patterns: String = fields
+        .iter()
+        .flat_map(|field| field.rules.iter().map(move |rule| (field, rule)))
+        .filter_map(|(field, rule)| match rule {
+            RequestRule::Regex(pattern) => Some(format!(
+                "static {}: LazyLock<Regex> = LazyLock::new(|| Regex::new({:?}).unwrap());\n",
+                field.pattern_name(),
+                pattern
+            )),
+            _ => None,
+        })
+        .collect();
+
+    let struct_fields = if fields.is_empty() {
+        "    // Add your fields here, for example:\n    // #[validate(required, email)]\n    // pub email: Option<String>,\n".to_string()
     } else {
-        "test_unit"
+        fields
+            .iter()
+            .map(|field| {
+                let attribute = field.validate_attribute().map(|attribute| format!("    {}\n", attribute)).unwrap_or_default();
+                format!("{}    pub {}: Option<{}>,\n", attribute, field.name, field.rust_type)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
     };
 
-    let test_dir = if integration {
-        "tests/integration"
+    // No value is known to match a pattern, so regex fields are left out of the valid body
+    let valid_body: String = fields
+        .iter()
+        .filter(|field| !field.has_regex())
+        .map(|field| format!("        body[\"{}\"] = {};\n", field.name, field.valid_value()))
+        .collect();
+
+    // Rules without derivable passing and failing values, such as patterns, get no test
+    let rule_tests: String = fields
+        .iter()
+        .flat_map(|field| field.rules.iter().map(move |rule| (field, rule)))
+        .filter_map(|(field, rule)| field.rule_cases(rule).map(|cases| (field, rule, cases)))
+        .map(|(field, rule, (valid, invalid))| {
+            format!(
+                r#"
+    #[test]
+    fn test_{field}_{rule}() {{
+        assert!(!fails("{field}", {valid}));
+        assert!(fails("{field}", {invalid}));
+    }}
+"#,
+                field = field.name,
+                rule = rule.label(),
+                valid = valid,
+                invalid = invalid,
+            )
+        })
+        .collect();
+
+    let fails = format!(
+        r#"
+    /// Whether the valid body with `field` set to `value` fails validation for that field
+    fn fails(field: &str, value: Value) -> bool {{
+        let mut body = valid_body();
+        body[field] = value;
+        let request: {class_name}Request = serde_json::from_value(body).unwrap();
+        validator::Validate::validate(&request).is_err_and(|errors| errors.field_errors().contains_key(field))
+    }}
+"#,
+        class_name = class_name
+    );
+
+    // A required pattern has no known matching value, so the valid body cannot pass
+    let valid_body_test = if fields.iter().any(|field| field.has_regex() && field.rules.contains(&RequestRule::Required)) {
+        String::new()
     } else {
-        "tests/unit"
+        format!(
+            r#"
+    #[test]
+    fn test_valid_body() {{
+        assert!({class_name}Request::try_from(valid_body()).is_ok());
+    }}
+"#,
+            class_name = class_name
+        )
     };
 
-    let template_vars = serde_json::json!({
-        "name": name,
-        "snake_case": CommandUtils::to_snake_case(&name),
-        "pascal_case": CommandUtils::to_pascal_case(&name),
-        "unit": unit,
-        "integration": integration
-    });
+    let tests = if valid_body_test.is_empty() && rule_tests.is_empty() {
+        String::new()
+    } else {
+        format!(
+            r#"
+#[cfg(test)]
+mod tests {{
+    use super::*;
+    use serde_json::json;
+
+    /// A body passing every rule
+    fn valid_body() -> Value {{
+{valid_body}    }}
+{fails}{valid_body_test}{rule_tests}}}
+"#,
+            valid_body = if valid_body.is_empty() {
+                "        json!({})\n".to_string()
+            } else {
+                format!("        let mut body = json!({{}});\n{}        body\n", valid_body)
+            },
+            fails = if rule_tests.is_empty() { String::new() } else { fails },
+            valid_body_test = valid_body_test,
+            rule_tests = rule_tests,
+        )
+    };
 
-    let content = template_manager.render(template_name, &template_vars)?;
+    let (regex_dependency, regex_imports) = if has_regex {
+        ("\n//! regex = \"1\"", "use std::sync::LazyLock;\n\nuse regex::Regex;\n")
+    } else {
+        ("", "")
+    };
 
-    let file_path = std::path::Path::new(test_dir)
-        .join(format!("{}.rs", CommandUtils::to_snake_case(&name)));
+    format!(
+        r#"//! {class_name} Request
+//!
+//! Parses and validates the JSON body of a request.
+//!
+//! Requires the following dependencies in Cargo.toml:
+//!
+//! ```toml
+//! anyhow = "1.0"
+//! serde = {{ version = "1.0", features = ["derive"] }}
+//! validator = {{ version = "0.20", features = ["derive"] }}{regex_dependency}
+//! ```
+
+{regex_imports}use serde::Deserialize;
+use serde_json::Value;
+use validator::Validate;
+
+{patterns}{blank}/// Validated {class_name} request body
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct {class_name}Request {{
+{struct_fields}}}
+
+impl {class_name}Request {{
+    /// Check every validation rule
+    pub fn validate(&self) -> anyhow::Result<()> {{
+        validator::Validate::validate(self).map_err(|e| anyhow::anyhow!("{{}}", e))
+    }}
+}}
 
-    CommandUtils::ensure_directory(file_path.parent().unwrap())?;
-    CommandUtils::write_file(&file_path, &content)?;
+impl TryFrom<Value> for {class_name}Request {{
+    type Error = anyhow::Error;
 
-    CommandUtils::success(&format!("Test {} created successfully!", name.cyan().bold()));
+    /// Parse a JSON body and validate it
+    fn try_from(body: Value) -> anyhow::Result<Self> {{
+        let request: Self = serde_json::from_value(body)?;
+        request.validate()?;
+        Ok(request)
+    }}
+}}
+{tests}"#,
+        class_name = class_name,
+        regex_dependency = regex_dependency,
+        regex_imports = regex_imports,
+        patterns = patterns,
+        blank = if patterns.is_empty() { "" } else { "\n" },
+        struct_fields = struct_fields,
+        tests = tests,
+    )
+}
+
+/// Generate a resource transformer
+async fn make_resource(name: String, collection: bool, fields: Option<String>, model: Option<String>, options: &GeneratorOptions) -> Result<()> {
+    CommandUtils::ensure_rustisan_project()?;
+
+    CommandUtils::info(&format!("Creating resource {}...", name.cyan().bold()));
+
+    let base_name = strip_suffix(&strip_suffix(&name, "Collection"), "Resource");
+    let class_name = CommandUtils::to_pascal_case(&base_name);
+    let model_name = CommandUtils::to_pascal_case(model.as_deref().unwrap_or(&base_name));
+
+    let fields = match fields {
+        Some(spec) => parse_resource_fields(&spec)?,
+        None => model_fields(&model_name).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Model {} not found in src/models; pass the fields with --fields \"id:integer,name:string\"",
+                model_name
+            )
+        })?,
+    };
+
+    let content = resource_source(&class_name, &model_name, &fields, collection);
+    let file_path = write_component(&app_path("src/resources"), &CommandUtils::to_snake_case(&base_name), &content, options)?;
+    CommandUtils::success(&format!("Resource created: {}", file_path));
+
+    CommandUtils::success(&format!("Resource {} created successfully!", name.cyan().bold()));
 
     Ok(())
 }
 
-/// Update module file to include new component
-fn update_module_file(module_dir: &str, component_name: &str) -> Result<()> {
-    // TODO: Implement module file updates
-    Ok(())
+/// Parse `--fields` for a resource, e.g. `id:integer,name:string`
+fn parse_resource_fields(spec: &str) -> Result<Vec<(String, String)>> {
+    spec.split(',')
+        .map(str::trim)
+        .filter(|field| !field.is_empty())
+        .map(|field| {
+            let (name, field_type) = field.split_once(':').unwrap_or((field, "string"));
+            let name = CommandUtils::to_snake_case(name.trim());
+            let rust_type = field_rust_type(field_type.trim(), &name)?;
+            Ok((name, rust_type.to_string()))
+        })
+        .collect()
+}
+
+/// Whether values of a field type can be copied instead of cloned
+fn is_copy_type(ty: &str) -> bool {
+    INTEGER_TYPES.contains(&ty) || matches!(ty, "bool" | "char" | "f32" | "f64")
 }
 
-/// Simple pluralization function
-fn pluralize(word: &str) -> String {
-    if word.ends_with('y') && word.len() > 1 {
-        format!("{}ies", &word[..word.len() - 1])
-    } else if word.ends_with('s') || word.ends_with("sh") || word.ends_with("ch") || word.ends_with('x') || word.ends_with('z') {
-        format!("{}es", word)
-    } else if word.ends_with('f') {
-        format!("{}ves", &word[..word.len() - 1])
-    } else if word.ends_with("fe") {
-        format!("{}ves", &word[..word.len() - 2])
+/// Source of an API resource projecting `model`, with a paginated collection if requested
+fn resource_source(class_name: &str, model: &str, fields: &[(String, String)], collection: bool) -> String {
+    let (_, model_import) = model_type(&Some(model.to_string()));
+
+    let struct_fields: String = fields.iter().map(|(field, ty)| format!("    pub {}: {},\n", field, ty)).collect();
+    let copies: String = fields
+        .iter()
+        .map(|(field, ty)| {
+            let value = if is_copy_type(ty) { format!("model.{}", field) } else { format!("model.{}.clone()", field) };
+            format!("            {}: {},\n", field, value)
+        })
+        .collect();
+    let entries: String = fields
+        .iter()
+        .map(|(field, _)| {
+            format!(
+                "        if self.is_visible(\"{field}\") {{\n            map.serialize_entry(\"{field}\", &self.{field})?;\n        }}\n",
+                field = field
+            )
+        })
+        .collect();
+
+    // Known values for the test model; other fields keep their defaults
+    let samples: Vec<(String, Option<String>)> = fields
+        .iter()
+        .enumerate()
+        .map(|(index, (field, ty))| {
+            let sample = match ty.as_str() {
+                "String" => Some(format!("\"example {}\"", field.replace('_', " "))),
+                "bool" => Some("true".to_string()),
+                "f32" | "f64" => Some("1.5".to_string()),
+                ty if INTEGER_TYPES.contains(&ty) => Some((index + 1).to_string()),
+                _ => None,
+            };
+            (field.clone(), sample)
+        })
+        .collect();
+    let model_literal: String = samples
+        .iter()
+        .filter_map(|(field, sample)| {
+            let sample = sample.as_ref()?;
+            let value = if sample.starts_with('"') { format!("{}.to_string()", sample) } else { sample.clone() };
+            Some(format!("            {}: {},\n", field, value))
+        })
+        .collect();
+    let expected: String = samples
+        .iter()
+        .map(|(field, sample)| {
+            let value = sample.clone().unwrap_or_else(|| format!("model.{}", field));
+            format!("            \"{}\": {},\n", field, value)
+        })
+        .collect();
+    let hidden_field = fields.first().map(|(field, _)| field.as_str()).unwrap_or("id");
+
+    let collection_source = if collection {
+        format!(
+            r#"
+/// Pagination details sent with a collection
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PaginationMeta {{
+    pub total: u64,
+    pub per_page: u64,
+    pub current_page: u64,
+    pub last_page: u64,
+}}
+
+impl PaginationMeta {{
+    pub fn new(total: u64, per_page: u64, current_page: u64) -> Self {{
+        Self {{
+            total,
+            per_page,
+            current_page,
+            last_page: total.div_ceil(per_page.max(1)).max(1),
+        }}
+    }}
+}}
+
+/// A page of {class_name} resources
+#[derive(Debug, Clone, Serialize)]
+pub struct {class_name}Collection {{
+    pub data: Vec<{class_name}Resource>,
+    pub meta: PaginationMeta,
+}}
+
+impl {class_name}Collection {{
+    pub fn from_vec(models: Vec<{model}>, meta: PaginationMeta) -> Self {{
+        Self {{
+            data: models.iter().map({class_name}Resource::new).collect(),
+            meta,
+        }}
+    }}
+}}
+"#,
+            class_name = class_name,
+            model = model,
+        )
     } else {
-        format!("{}s", word)
+        String::new()
+    };
+
+    let collection_test = if collection {
+        format!(
+            r#"
+    #[test]
+    fn test_collection_serialization() {{
+        let collection = {class_name}Collection::from_vec(vec![model()], PaginationMeta::new(31, 15, 1));
+
+        assert_eq!(
+            serde_json::to_value(&collection).unwrap(),
+            json!({{
+                "data": [expected(&model())],
+                "meta": {{ "total": 31, "per_page": 15, "current_page": 1, "last_page": 3 }}
+            }})
+        );
+    }}
+"#,
+            class_name = class_name
+        )
+    } else {
+        String::new()
+    };
+
+    format!(
+        r#"//! {class_name} Resource
+//!
+//! Shapes {model} for API responses. Fields listed in `hidden` are left
+//! out of the JSON.
+//!
+//! Requires the following dependencies in Cargo.toml:
+//!
+//! ```toml
+//! serde = {{ version = "1.0", features = ["derive"] }}
+//! ```
+
+{chrono_import}use serde::ser::SerializeMap;
+use serde::{{Serialize, Serializer}};
+{model_import}
+/// JSON representation of {model}
+#[derive(Debug, Clone)]
+pub struct {class_name}Resource {{
+{struct_fields}    /// Fields left out when serializing
+    pub hidden: Vec<&'static str>,
+}}
+
+impl {class_name}Resource {{
+    pub fn new(model: &{model}) -> Self {{
+        Self {{
+{copies}            hidden: Vec::new(),
+        }}
+    }}
+
+    /// Leave the given fields out of the JSON
+    pub fn hide(mut self, fields: &[&'static str]) -> Self {{
+        self.hidden.extend_from_slice(fields);
+        self
+    }}
+
+    fn is_visible(&self, field: &str) -> bool {{
+        !self.hidden.contains(&field)
+    }}
+}}
+
+impl Serialize for {class_name}Resource {{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {{
+        let mut map = serializer.serialize_map(None)?;
+{entries}        map.end()
+    }}
+}}
+{collection_source}
+#[cfg(test)]
+mod tests {{
+    use super::*;
+    use serde_json::{{json, Value}};
+
+    fn model() -> {model} {{
+        {model} {{
+{model_literal}            ..Default::default()
+        }}
+    }}
+
+    fn expected({model_param}: &{model}) -> Value {{
+        json!({{
+{expected}        }})
+    }}
+
+    #[test]
+    fn test_resource_serialization() {{
+        let model = model();
+        let resource = {class_name}Resource::new(&model);
+
+        assert_eq!(serde_json::to_value(&resource).unwrap(), expected(&model));
+    }}
+
+    #[test]
+    fn test_hidden_fields() {{
+        let resource = {class_name}Resource::new(&model()).hide(&["{hidden_field}"]);
+        let json = serde_json::to_value(&resource).unwrap();
+
+        assert!(json.get("{hidden_field}").is_none());
+    }}
+{collection_test}}}
+"#,
+        class_name = class_name,
+        model = model,
+        model_import = model_import,
+        chrono_import = chrono_import(fields),
+        struct_fields = struct_fields,
+        copies = copies,
+        entries = entries,
+        collection_source = collection_source,
+        model_literal = model_literal,
+        expected = expected,
+        hidden_field = hidden_field,
+        model_param = if samples.iter().all(|(_, sample)| sample.is_some()) { "_model" } else { "model" },
+        collection_test = collection_test,
+    )
+}
+
+/// Generate seeder
+async fn make_seeder(name: String, model: Option<String>, from_csv: Option<String>, from_json: Option<String>, options: &GeneratorOptions) -> Result<()> {
+    CommandUtils::ensure_rustisan_project()?;
+
+    CommandUtils::info(&format!("Creating seeder {}...", name.cyan().bold()));
+
+    let data = match (from_csv, from_json) {
+        (Some(file), _) => Some(SeedData::from_csv(std::path::Path::new(&file))?),
+        (None, Some(file)) => Some(SeedData::from_json(std::path::Path::new(&file))?),
+        (None, None) => None,
+    };
+    if let Some(data) = data {
+        return write_data_seeder(&name, model, data, options);
+    }
+
+    let class_name = CommandUtils::to_pascal_case(&name);
+    let snake_case = CommandUtils::to_snake_case(&name);
+    let model_name = model.unwrap_or_else(|| name.clone());
+
+    let content = format!(
+        r#"//! {} Seeder
+
+use anyhow::Result;
+
+pub struct {}Seeder {{}}
+
+impl {}Seeder {{
+    pub async fn run() -> Result<()> {{
+        // Add your seeding logic here
+        // Example: Create {} records
+        println!("Seeding {} data...");
+
+        Ok(())
+    }}
+
+    /// Delete the records created by `run`, run by `rustisan seed rollback`
+    ///
+    /// Failing keeps the seeder's tracking record, so the rollback can be retried.
+    pub async fn rollback() -> Result<()> {{
+        // Delete the {} records seeded above, e.g. by the primary keys `run` created
+        anyhow::bail!("rollback not implemented for {}Seeder")
+    }}
+}}
+"#,
+        name, class_name, class_name, model_name, model_name, model_name, class_name
+    );
+
+    let file_path = format!("database/seeders/{}.rs", snake_case);
+    CommandUtils::ensure_directory(&std::path::Path::new(&file_path).parent().unwrap())?;
+    write_generated(&file_path, &content, options)?;
+
+    CommandUtils::success(&format!("Seeder {} created successfully!", name.cyan().bold()));
+
+    Ok(())
+}
+
+/// Format of a seeder's embedded data file
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SeedFormat {
+    Csv,
+    Json,
+}
+
+impl SeedFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+        }
+    }
+}
+
+/// Data imported by `make seeder --from-csv` or `--from-json`
+#[derive(Debug, Clone, PartialEq)]
+struct SeedData {
+    format: SeedFormat,
+    columns: Vec<String>,
+    rows: usize,
+    content: String,
+}
+
+impl SeedData {
+    /// Read a CSV file, taking the column names from its header row
+    fn from_csv(path: &std::path::Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+        let mut reader = csv::Reader::from_reader(content.as_bytes());
+        let columns: Vec<String> = reader.headers()?.iter().map(str::to_string).collect();
+        if columns.is_empty() {
+            return Err(anyhow::anyhow!("{} has no header row", path.display()));
+        }
+
+        let mut rows = 0;
+        for record in reader.records() {
+            record.map_err(|e| anyhow::anyhow!("Invalid CSV in {}: {}", path.display(), e))?;
+            rows += 1;
+        }
+
+        Ok(Self { format: SeedFormat::Csv, columns, rows, content })
+    }
+
+    /// Read a JSON array of objects, taking the column names from their keys
+    fn from_json(path: &std::path::Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+        let records: Vec<serde_json::Map<String, serde_json::Value>> = serde_json::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("{} must contain an array of objects: {}", path.display(), e))?;
+
+        let mut columns: Vec<String> = Vec::new();
+        for key in records.iter().flat_map(|record| record.keys()) {
+            if !columns.contains(key) {
+                columns.push(key.clone());
+            }
+        }
+
+        Ok(Self { format: SeedFormat::Json, columns, rows: records.len(), content })
+    }
+
+    /// Column identifying seeded records when rolling back, if the data has one
+    fn key_column(&self) -> Option<&'static str> {
+        SEED_KEY_COLUMNS.into_iter().find(|key| self.columns.iter().any(|column| column == key))
+    }
+}
+
+/// Columns that can identify seeded records, in order of preference
+const SEED_KEY_COLUMNS: [&str; 3] = ["id", "email", "slug"];
+
+/// Write a seeder for imported data, copying the data next to it
+fn write_data_seeder(name: &str, model: Option<String>, data: SeedData, options: &GeneratorOptions) -> Result<()> {
+    let base_name = strip_suffix(name, "Seeder");
+    let class_name = format!("{}Seeder", CommandUtils::to_pascal_case(&base_name));
+    let data_file = format!("data/{}.{}", CommandUtils::to_snake_case(&base_name), data.format.extension());
+    let model_name = CommandUtils::to_pascal_case(&model.unwrap_or_else(|| TextUtils::singularize(&base_name)));
+
+    let seeders_dir = std::path::Path::new("database/seeders");
+    let data_path = seeders_dir.join(&data_file);
+    CommandUtils::ensure_directory(data_path.parent().unwrap())?;
+    write_generated(&data_path, &data.content, options)?;
+
+    let file_path = seeders_dir.join(format!("{}.rs", CommandUtils::to_snake_case(&class_name)));
+    write_generated(&file_path, &seeder_source(&class_name, &model_name, &data_file, &data), options)?;
+
+    if data.key_column().is_none() {
+        CommandUtils::warning(&format!(
+            "No {} column to identify seeded records, so the seeder cannot be rolled back",
+            SEED_KEY_COLUMNS.join(", ")
+        ));
+    }
+
+    CommandUtils::success(&format!(
+        "Seeder created: {} ({} rows, columns: {})",
+        file_path.display(),
+        data.rows,
+        data.columns.join(", ")
+    ));
+    CommandUtils::info(&format!("Run it with {}::run::<{}>().await", class_name, model_name));
+
+    Ok(())
+}
+
+/// Source of a seeder creating one record per row of an embedded data file
+fn seeder_source(class_name: &str, model: &str, data_file: &str, data: &SeedData) -> String {
+    let columns: Vec<String> = data.columns.iter().map(|column| format!("{:?}", column)).collect();
+
+    let (dependency, rows_body) = match data.format {
+        SeedFormat::Csv => (
+            "\n//!\n//! Requires the following dependencies in Cargo.toml:\n//!\n//! ```toml\n//! csv = \"1.3\"\n//! ```",
+            r#"        let mut reader = csv::Reader::from_reader(DATA.as_bytes());
+        reader
+            .records()
+            .map(|record| {
+                let record = record?;
+                Ok(COLUMNS
+                    .iter()
+                    .zip(record.iter())
+                    .map(|(column, value)| (column.to_string(), parse_value(value)))
+                    .collect())
+            })
+            .collect()"#,
+        ),
+        SeedFormat::Json => ("", "        Ok(serde_json::from_str(DATA)?)"),
+    };
+
+    let parse_value = match data.format {
+        SeedFormat::Csv => r#"
+/// Type a CSV value: empty cells become null, numbers and booleans are parsed
+fn parse_value(value: &str) -> Value {
+    if value.is_empty() {
+        Value::Null
+    } else if let Ok(number) = value.parse::<i64>() {
+        Value::from(number)
+    } else if let Ok(number) = value.parse::<f64>() {
+        Value::from(number)
+    } else if let Ok(flag) = value.parse::<bool>() {
+        Value::from(flag)
+    } else {
+        Value::from(value)
+    }
+}
+"#,
+        SeedFormat::Json => "",
+    };
+
+    format!(
+        r#"//! {class_name}
+//!
+//! Seeds {model} records from `{data_file}`, embedded at compile time.{dependency}
+
+use std::future::Future;
+
+use anyhow::Result;
+use serde_json::{{Map, Value}};
+
+/// Seeded rows
+const DATA: &str = include_str!("{data_file}");
+
+/// Columns of each row
+pub const COLUMNS: &[&str] = &[{columns}];
+
+/// Column identifying the seeded records on rollback
+pub const KEY_COLUMN: Option<&str> = {key_column};
+
+/// Persistence the seeder needs, implemented by the model being seeded
+pub trait Seedable {{
+    fn create(row: Map<String, Value>) -> impl Future<Output = Result<()>> + Send;
+
+    /// Delete the records whose `column` equals `value`
+    fn delete_where(column: &str, value: &Value) -> impl Future<Output = Result<()>> + Send;
+}}
+
+pub struct {class_name} {{}}
+
+impl {class_name} {{
+    /// Rows keyed by column name
+    pub fn rows() -> Result<Vec<Map<String, Value>>> {{
+{rows_body}
+    }}
+
+    /// Create a record for every row, e.g. `{class_name}::run::<{model}>()`
+    pub async fn run<M: Seedable>() -> Result<()> {{
+        for row in Self::rows()? {{
+            M::create(row).await?;
+        }}
+        Ok(())
+    }}
+
+    /// Delete the records created by `run`, matched on `KEY_COLUMN`
+    pub async fn rollback<M: Seedable>() -> Result<()> {{
+        let Some(key_column) = KEY_COLUMN else {{
+            anyhow::bail!("{class_name} cannot be rolled back: no {key_columns} column identifies its records");
+        }};
+        for row in Self::rows()? {{
+            if let Some(key) = row.get(key_column) {{
+                M::delete_where(key_column, key).await?;
+            }}
+        }}
+        Ok(())
+    }}
+}}
+{parse_value}"#,
+        class_name = class_name,
+        model = model,
+        data_file = data_file,
+        dependency = dependency,
+        columns = columns.join(", "),
+        key_column = data.key_column().map_or("None".to_string(), |column| format!("Some({:?})", column)),
+        key_columns = SEED_KEY_COLUMNS.join(", "),
+        rows_body = rows_body,
+        parse_value = parse_value,
+    )
+}
+
+/// Generate factory
+async fn make_factory(name: String, model: Option<String>, options: &GeneratorOptions) -> Result<()> {
+    CommandUtils::ensure_rustisan_project()?;
+
+    CommandUtils::info(&format!("Creating factory {}...", name.cyan().bold()));
+
+    let base_name = strip_suffix(&name, "Factory");
+    let model_name = CommandUtils::to_pascal_case(model.as_deref().unwrap_or(&base_name));
+    let snake_case = CommandUtils::to_snake_case(&base_name);
+
+    let fields = match model_fields(&model_name) {
+        Some(fields) => fields,
+        None => {
+            CommandUtils::warning(&format!(
+                "Model {} not found in src/models, using conventional fields",
+                model_name.cyan()
+            ));
+            default_factory_fields(&model_name)
+        }
+    };
+
+    let content = factory_source(&model_name, &fields, |related| model_path(related).exists());
+
+    let file_path = format!("database/factories/{}.rs", snake_case);
+    CommandUtils::ensure_directory(&std::path::Path::new(&file_path).parent().unwrap())?;
+    write_generated(&file_path, &content, options)?;
+
+    CommandUtils::success(&format!("Factory {} created successfully!", name.cyan().bold()));
+
+    Ok(())
+}
+
+/// `(name, type)` pairs of a model struct's fields, read from `src/models`
+fn model_fields(model: &str) -> Option<Vec<(String, String)>> {
+    let source = std::fs::read_to_string(model_path(model)).ok()?;
+    parse_struct_fields(&source, model)
+}
+
+fn model_path(model: &str) -> std::path::PathBuf {
+    std::path::Path::new(&app_path("src/models")).join(format!("{}.rs", CommandUtils::to_snake_case(model)))
+}
+
+fn parse_struct_fields(source: &str, name: &str) -> Option<Vec<(String, String)>> {
+    let pattern = format!(r"pub struct {}\s*\{{([^}}]*)\}}", regex::escape(name));
+    let body = regex::Regex::new(&pattern).ok()?.captures(source)?.get(1)?.as_str();
+
+    let field = regex::Regex::new(r"^(?:pub(?:\([^)]*\))?\s+)?([a-z_][a-z0-9_]*)\s*:\s*(.+?),?$").ok()?;
+    let fields: Vec<(String, String)> = body
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with("//") && !line.starts_with("#["))
+        .filter_map(|line| field.captures(line))
+        .map(|captures| (captures[1].to_string(), captures[2].trim().to_string()))
+        .collect();
+
+    (!fields.is_empty()).then_some(fields)
+}
+
+/// Fields assumed for a model that has not been written yet
+///
+/// Users get account fields; anything else is treated as content owned by a user.
+fn default_factory_fields(model: &str) -> Vec<(String, String)> {
+    let fields: &[(&str, &str)] = if model == "User" {
+        &[
+            ("id", "u64"),
+            ("name", "String"),
+            ("email", "String"),
+            ("username", "String"),
+            ("created_at", "DateTime<Utc>"),
+            ("updated_at", "DateTime<Utc>"),
+        ]
+    } else {
+        &[
+            ("id", "u64"),
+            ("user_id", "u64"),
+            ("title", "String"),
+            ("body", "String"),
+            ("created_at", "DateTime<Utc>"),
+            ("updated_at", "DateTime<Utc>"),
+        ]
+    };
+    fields.iter().map(|(name, ty)| (name.to_string(), ty.to_string())).collect()
+}
+
+/// Fields that must be unique and are filled from the factory's sequence
+const SEQUENCED_FIELDS: &[&str] = &["username", "slug", "code", "sku"];
+
+const INTEGER_TYPES: &[&str] = &["i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize"];
+
+/// Expression producing a fake value for a field, and the faker it needs, if any
+fn fake_value(field: &str, ty: &str) -> (String, Option<&'static str>) {
+    if ty.starts_with("Option<") {
+        return ("None".to_string(), None);
+    }
+    if field == "created_at" || field == "updated_at" || ty.contains("DateTime") {
+        let now = if ty.contains("Naive") { "chrono::Utc::now().naive_utc()" } else { "chrono::Utc::now()" };
+        return (now.to_string(), None);
+    }
+    if INTEGER_TYPES.contains(&ty) {
+        return match field {
+            "id" if ty == "u64" => ("next_sequence()".to_string(), None),
+            "id" => (format!("next_sequence() as {}", ty), None),
+            _ if field.ends_with("_id") => ("0".to_string(), None),
+            _ => ("Faker.fake()".to_string(), Some("Faker")),
+        };
+    }
+    if ty == "bool" || ty == "f32" || ty == "f64" {
+        return ("Faker.fake()".to_string(), Some("Faker"));
+    }
+    if ty != "String" {
+        return ("Default::default()".to_string(), None);
+    }
+
+    match field {
+        _ if SEQUENCED_FIELDS.contains(&field) => (format!("format!(\"{}_{{}}\", next_sequence())", field), None),
+        "email" => ("FakeEmail().fake()".to_string(), Some("FakeEmail")),
+        _ if field.ends_with("_email") => ("FakeEmail().fake()".to_string(), Some("FakeEmail")),
+        "name" => ("FakeName().fake()".to_string(), Some("FakeName")),
+        _ if field.ends_with("_name") => ("FakeName().fake()".to_string(), Some("FakeName")),
+        "title" | "subject" => ("FakeTitle(3..8).fake()".to_string(), Some("FakeTitle")),
+        "body" | "description" | "content" | "bio" => ("FakeLoremParagraph(1..3).fake()".to_string(), Some("FakeLoremParagraph")),
+        _ => ("FakeWord().fake()".to_string(), Some("FakeWord")),
+    }
+}
+
+/// Import line for each faker used by generated factories
+const FAKERS: &[(&str, &str)] = &[
+    ("Faker", "use fake::Faker;"),
+    ("FakeEmail", "use fake::faker::internet::en::SafeEmail as FakeEmail;"),
+    ("FakeName", "use fake::faker::name::en::Name as FakeName;"),
+    ("FakeTitle", "use fake::faker::lorem::en::Sentence as FakeTitle;"),
+    ("FakeLoremParagraph", "use fake::faker::lorem::en::Paragraph as FakeLoremParagraph;"),
+    ("FakeWord", "use fake::faker::lorem::en::Word as FakeWord;"),
+];
+
+/// `use chrono::...` line for the chrono types fields name without a path, such as `DateTime<Utc>`
+fn chrono_import(fields: &[(String, String)]) -> String {
+    let types: Vec<&str> = ["DateTime", "NaiveDate", "NaiveDateTime", "Utc"]
+        .into_iter()
+        .filter(|name| {
+            let unqualified = regex::Regex::new(&format!(r"(^|[^:\w]){}\b", name)).unwrap();
+            fields.iter().any(|(_, ty)| unqualified.is_match(ty))
+        })
+        .collect();
+
+    match types.as_slice() {
+        [] => String::new(),
+        [single] => format!("use chrono::{};\n", single),
+        types => format!("use chrono::{{{}}};\n", types.join(", ")),
+    }
+}
+
+/// Source of a factory building `model` from the given fields
+fn factory_source(model: &str, fields: &[(String, String)], model_exists: impl Fn(&str) -> bool) -> String {
+    let model_module = CommandUtils::to_snake_case(model);
+
+    let values: Vec<(String, String, String)> = fields
+        .iter()
+        .map(|(field, ty)| (field.clone(), ty.clone(), fake_value(field, ty).0))
+        .collect();
+    let used: Vec<&str> = fields.iter().filter_map(|(field, ty)| fake_value(field, ty).1).collect();
+    let mut fake_imports: String = FAKERS
+        .iter()
+        .filter(|(faker, _)| used.contains(faker))
+        .map(|(_, import)| format!("{}\n", import))
+        .collect();
+    if !used.is_empty() {
+        fake_imports.insert_str(0, "use fake::Fake;\n");
+    }
+
+    let chrono_import = chrono_import(fields);
+
+    // An integer `user_id` field implies the model belongs to a user, when a User model exists
+    let relations: Vec<(String, String)> = fields
+        .iter()
+        .filter(|(_, ty)| INTEGER_TYPES.contains(&ty.as_str()))
+        .filter_map(|(field, _)| field.strip_suffix("_id"))
+        .filter(|relation| !relation.is_empty())
+        .map(|relation| (relation.to_string(), CommandUtils::to_pascal_case(relation)))
+        .filter(|(_, class)| model_exists(class))
+        .collect();
+    let relation_imports: String = relations
+        .iter()
+        .filter(|(_, class)| class != model)
+        .map(|(relation, class)| format!("use crate::models::{}::{};\n", relation, class))
+        .collect();
+
+    let struct_fields: String = values
+        .iter()
+        .map(|(field, ty, _)| format!("    pub {}: {},\n", field, ty))
+        .collect();
+    let defaults: String = values
+        .iter()
+        .map(|(field, _, value)| format!("            {}: {},\n", field, value))
+        .collect();
+    let assignments: String = values
+        .iter()
+        .map(|(field, _, _)| format!("            {field}: self.{field},\n", field = field))
+        .collect();
+    let relation_methods: String = relations
+        .iter()
+        .map(|(relation, class)| {
+            format!(
+                "\n    /// Attach the model to an existing {class}\n    pub fn with_{relation}(mut self, related: &{class}) -> Self {{\n        self.{relation}_id = related.id;\n        self\n    }}\n",
+                class = class,
+                relation = relation
+            )
+        })
+        .collect();
+    let string_assertions: String = values
+        .iter()
+        .filter(|(_, ty, _)| ty == "String")
+        .map(|(field, _, _)| format!("        assert!(!model.{field}.is_empty(), \"{field} is empty\");\n", field = field))
+        .collect();
+    let sequenced = values
+        .iter()
+        .find(|(field, ty, _)| ty == "String" && SEQUENCED_FIELDS.contains(&field.as_str()))
+        .or_else(|| values.iter().find(|(field, ty, _)| field == "id" && INTEGER_TYPES.contains(&ty.as_str())))
+        .map(|(field, _, _)| field.as_str());
+    let (sequence_import, sequence) = if values.iter().any(|(_, _, value)| value.contains("next_sequence")) {
+        (
+            "use std::sync::atomic::{AtomicU64, Ordering};\n\n",
+            "\n/// Counter for fields that must be unique across generated models\nstatic SEQUENCE: AtomicU64 = AtomicU64::new(1);\n\nfn next_sequence() -> u64 {\n    SEQUENCE.fetch_add(1, Ordering::Relaxed)\n}\n",
+        )
+    } else {
+        ("", "")
+    };
+    let sequence_test = match sequenced {
+        Some(field) => format!(
+            r#"
+    #[test]
+    fn test_sequence_is_unique() {{
+        let models = {model}Factory::create_many(3);
+
+        assert_ne!(models[0].{field}, models[1].{field});
+        assert_ne!(models[1].{field}, models[2].{field});
+    }}
+"#,
+            model = model,
+            field = field
+        ),
+        None => String::new(),
+    };
+    let state_field = values
+        .iter()
+        .find(|(_, ty, _)| ty == "String")
+        .map(|(field, _, _)| field.as_str());
+    let state_test = match state_field {
+        Some(field) => format!(
+            r#"
+    #[test]
+    fn test_state() {{
+        let model = {model}Factory::new().state(|mut factory| {{
+            factory.{field} = "custom".to_string();
+            factory
+        }}).create();
+
+        assert_eq!(model.{field}, "custom");
+    }}
+"#,
+            model = model,
+            field = field
+        ),
+        None => String::new(),
+    };
+
+    format!(
+        r#"//! {model} Factory
+//!
+//! Builds {model} instances filled with fake data. Adjust attributes with
+//! `state`, or attach related models with the `with_*` methods:
+//!
+//! ```ignore
+//! let model = {model}Factory::new().state(|factory| factory).create();
+//! ```
+//!
+//! Requires the following dependencies in Cargo.toml:
+//!
+//! ```toml
+//! fake = "4"
+//! ```
+
+{sequence_import}{chrono_import}{fake_imports}
+use crate::models::{model_module}::{model};
+{relation_imports}{sequence}
+/// Attributes of the {model} being built
+#[derive(Debug, Clone)]
+pub struct {model}Factory {{
+{struct_fields}}}
+
+impl {model}Factory {{
+    pub fn new() -> Self {{
+        Self {{
+{defaults}        }}
+    }}
+
+    /// Transform the attributes before the model is created
+    pub fn state<F: Fn(Self) -> Self>(self, f: F) -> Self {{
+        f(self)
+    }}
+{relation_methods}
+    pub fn create(self) -> {model} {{
+        {model} {{
+{assignments}        }}
+    }}
+
+    pub fn create_many(count: usize) -> Vec<{model}> {{
+        (0..count).map(|_| Self::new().create()).collect()
+    }}
+}}
+
+impl Default for {model}Factory {{
+    fn default() -> Self {{
+        Self::new()
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    #[test]
+    fn test_create_fills_string_fields() {{
+        let model = {model}Factory::new().create();
+
+{string_assertions}    }}
+{sequence_test}{state_test}}}
+"#,
+        model = model,
+        model_module = model_module,
+        sequence_import = sequence_import,
+        sequence = sequence,
+        chrono_import = chrono_import,
+        fake_imports = fake_imports,
+        relation_imports = relation_imports,
+        struct_fields = struct_fields,
+        defaults = defaults,
+        relation_methods = relation_methods,
+        assignments = assignments,
+        string_assertions = string_assertions,
+        sequence_test = sequence_test,
+        state_test = state_test,
+    )
+}
+
+/// Generate command
+async fn make_command(
+    name: String,
+    arguments: Option<String>,
+    flags: Option<String>,
+    schedule: Option<String>,
+    options: &GeneratorOptions,
+) -> Result<()> {
+    CommandUtils::ensure_rustisan_project()?;
+
+    CommandUtils::info(&format!("Creating command {}...", name.cyan().bold()));
+
+    let arguments = arguments.as_deref().map(parse_command_arguments).transpose()?.unwrap_or_default();
+    let flags = flags.as_deref().map(parse_command_options).transpose()?.unwrap_or_default();
+    if let Some(schedule) = &schedule {
+        super::schedule::parse_schedule(schedule)?;
+    }
+
+    let snake_case = CommandUtils::to_snake_case(&name);
+    let content = command_source(&name, &arguments, &flags, schedule.as_deref());
+
+    let file_path = app_path(&format!("src/commands/{}.rs", snake_case));
+    CommandUtils::ensure_directory(&std::path::Path::new(&file_path).parent().unwrap())?;
+    write_generated(&file_path, &content, options)?;
+
+    CommandUtils::success(&format!("Command {} created successfully!", name.cyan().bold()));
+
+    Ok(())
+}
+
+/// A positional argument of a generated command
+#[derive(Debug, PartialEq)]
+struct CommandArgument {
+    name: String,
+    required: bool,
+}
+
+/// A flag of a generated command
+#[derive(Debug, PartialEq)]
+struct CommandOption {
+    name: String,
+    rust_type: String,
+}
+
+/// Parse `--arguments` given as "name:required,env:optional"
+fn parse_command_arguments(spec: &str) -> Result<Vec<CommandArgument>> {
+    spec.split(',')
+        .map(str::trim)
+        .filter(|argument| !argument.is_empty())
+        .map(|argument| {
+            let (name, kind) = argument.split_once(':').unwrap_or((argument, "required"));
+            let required = match kind.trim() {
+                "required" => true,
+                "optional" => false,
+                other => anyhow::bail!("Unknown argument kind '{}' for '{}' (expected required or optional)", other, name),
+            };
+            Ok(CommandArgument { name: CommandUtils::to_snake_case(name.trim()), required })
+        })
+        .collect()
+}
+
+/// Parse `--options` given as "verbose:bool,count:usize"
+fn parse_command_options(spec: &str) -> Result<Vec<CommandOption>> {
+    let type_pattern = regex::Regex::new(r"^[A-Za-z_][A-Za-z0-9_:<>]*$")?;
+    spec.split(',')
+        .map(str::trim)
+        .filter(|option| !option.is_empty())
+        .map(|option| {
+            let (name, rust_type) = option.split_once(':').unwrap_or((option, "bool"));
+            let rust_type = rust_type.trim();
+            if !type_pattern.is_match(rust_type) {
+                anyhow::bail!("Invalid type '{}' for option '{}'", rust_type, name);
+            }
+            Ok(CommandOption { name: CommandUtils::to_snake_case(name.trim()), rust_type: rust_type.to_string() })
+        })
+        .collect()
+}
+
+/// Source of a command with clap annotations for its arguments and options
+fn command_source(name: &str, arguments: &[CommandArgument], options: &[CommandOption], schedule: Option<&str>) -> String {
+    let class_name = CommandUtils::to_pascal_case(name);
+    let command_name = CommandUtils::to_snake_case(name).replace('_', "-");
+
+    let mut fields = Vec::new();
+    for argument in arguments {
+        let rust_type = if argument.required { "String" } else { "Option<String>" };
+        fields.push(format!("    #[arg(value_name = \"{}\")]\n    pub {}: {},", argument.name.to_uppercase(), argument.name, rust_type));
+    }
+
+    // `-h` belongs to --help and two options cannot share a short flag
+    let mut shorts = vec!['h'];
+    for option in options {
+        let short = option.name.chars().next().filter(|c| !shorts.contains(c));
+        let attribute = match short {
+            Some(c) => {
+                shorts.push(c);
+                "#[arg(short, long)]"
+            }
+            None => "#[arg(long)]",
+        };
+        let rust_type = if option.rust_type == "bool" { "bool".to_string() } else { format!("Option<{}>", option.rust_type) };
+        fields.push(format!("    {}\n    pub {}: {},", attribute, option.name, rust_type));
+    }
+
+    let names: Vec<&str> = arguments.iter().map(|a| a.name.as_str()).chain(options.iter().map(|o| o.name.as_str())).collect();
+    let (destructure, logged) = if names.is_empty() {
+        (String::new(), String::new())
+    } else {
+        let logged: Vec<String> = names.iter().map(|name| format!("?{}", name)).collect();
+        (format!("        let Self {{ {} }} = self;\n", names.join(", ")), format!("{}, ", logged.join(", ")))
+    };
+
+    let schedule = schedule
+        .map(|cron| {
+            format!(
+                "\n// Run this command on a schedule with:\n//     rustisan schedule add \"cargo run -- {}\" --cron \"{}\"",
+                command_name, cron
+            )
+        })
+        .unwrap_or_default();
+
+    let fields = if fields.is_empty() { "    // Add command arguments here".to_string() } else { fields.join("\n") };
+
+    format!(
+        r#"//! {name} Command
+
+use anyhow::Result;
+use clap::Parser;
+use tracing::info;
+{schedule}
+#[derive(Debug, Parser)]
+#[command(name = "{command_name}")]
+pub struct {class_name}Command {{
+{fields}
+}}
+
+impl {class_name}Command {{
+    pub async fn execute(self) -> Result<()> {{
+{destructure}        info!({logged}"Running {command_name} command");
+
+        // Add command logic here
+
+        info!("Command {command_name} completed");
+
+        Ok(())
+    }}
+}}
+"#
+    )
+}
+
+/// Generate job
+async fn make_job(name: String, sync: bool, queue: Option<String>, tries: Option<u32>, timeout: Option<u32>, options: &GeneratorOptions) -> Result<()> {
+    CommandUtils::ensure_rustisan_project()?;
+
+    CommandUtils::info(&format!("Creating job {}...", name.cyan().bold()));
+
+    let snake_case = CommandUtils::to_snake_case(&name);
+    let content = job_source(
+        &name,
+        sync,
+        queue.as_deref().unwrap_or("default"),
+        tries.unwrap_or(3),
+        timeout.unwrap_or(60),
+    );
+
+    let file_path = app_path(&format!("src/jobs/{}.rs", snake_case));
+    CommandUtils::ensure_directory(&std::path::Path::new(&file_path).parent().unwrap())?;
+    write_generated(&file_path, &content, options)?;
+
+    CommandUtils::success(&format!("Job {} created successfully!", name.cyan().bold()));
+
+    Ok(())
+}
+
+/// Source of a job with its queue, retry and timeout settings as associated constants
+fn job_source(name: &str, sync: bool, queue: &str, tries: u32, timeout: u32) -> String {
+    let class_name = CommandUtils::to_pascal_case(name);
+    let (kind, handle) = if sync {
+        ("Synchronous", format!(
+            r#"    pub fn handle(&self) -> Result<()> {{
+        // Add synchronous job logic here
+        println!("Processing {name} job synchronously...");
+
+        Ok(())
+    }}"#
+        ))
+    } else {
+        ("Asynchronous", format!(
+            r#"    pub async fn handle(&self) -> Result<()> {{
+        // Add asynchronous job logic here
+        println!("Processing {name} job asynchronously...");
+
+        Ok(())
+    }}
+
+    /// Called once all {tries} attempts have failed
+    pub fn failed(&self, error: &anyhow::Error) {{
+        eprintln!("{name} job failed after {{}} attempts: {{}}", Self::MAX_TRIES, error);
+    }}"#
+        ))
+    };
+
+    format!(
+        r#"//! {name} {kind} Job
+
+use anyhow::Result;
+use serde::{{Deserialize, Serialize}};
+
+/// Entry pushed onto a queue, in the format read by `rustisan queue:work`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JobPayload<T> {{
+    pub id: String,
+    pub job: String,
+    pub payload: T,
+    pub attempts: u32,
+}}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct {class_name}Job {{
+    // Add job data fields here
+}}
+
+impl {class_name}Job {{
+    /// Queue the job is pushed onto
+    pub const QUEUE: &'static str = "{queue}";
+    /// Attempts before the job is marked as failed
+    pub const MAX_TRIES: u32 = {tries};
+    /// Seconds a single attempt may run
+    pub const TIMEOUT: u64 = {timeout};
+
+    pub fn new() -> Self {{
+        Self {{
+            // Initialize fields
+        }}
+    }}
+
+{handle}
+
+    /// Whether a failed attempt should be retried
+    pub fn should_retry(&self, attempt: u32, _error: &anyhow::Error) -> bool {{
+        attempt < Self::MAX_TRIES
+    }}
+
+    /// Wrap the job for pushing onto [`Self::QUEUE`]
+    pub fn payload(&self, id: impl Into<String>) -> JobPayload<&Self> {{
+        JobPayload {{
+            id: id.into(),
+            job: "{class_name}Job".to_string(),
+            payload: self,
+            attempts: 0,
+        }}
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    #[test]
+    fn test_settings() {{
+        assert_eq!({class_name}Job::QUEUE, "{queue}");
+        assert_eq!({class_name}Job::MAX_TRIES, {tries});
+        assert_eq!({class_name}Job::TIMEOUT, {timeout});
+    }}
+
+    #[test]
+    fn test_should_retry() {{
+        let job = {class_name}Job::new();
+        let error = anyhow::anyhow!("boom");
+        assert!(job.should_retry(0, &error));
+        assert!(!job.should_retry({class_name}Job::MAX_TRIES, &error));
+    }}
+
+    #[test]
+    fn test_payload() {{
+        let job = {class_name}Job::new();
+        let payload = job.payload("1");
+        assert_eq!(payload.job, "{class_name}Job");
+        assert_eq!(payload.attempts, 0);
+    }}
+}}
+"#
+    )
+}
+
+/// Generate event
+async fn make_event(name: String, options: &GeneratorOptions) -> Result<()> {
+    CommandUtils::ensure_rustisan_project()?;
+
+    CommandUtils::info(&format!("Creating event {}...", name.cyan().bold()));
+
+    let file_path = write_event(&name, options)?;
+    CommandUtils::success(&format!("Event created: {}", file_path));
+
+    CommandUtils::success(&format!("Event {} created successfully!", name.cyan().bold()));
+
+    Ok(())
+}
+
+/// Write an event into `src/events`, creating the events module with the dispatcher if needed
+fn write_event(name: &str, options: &GeneratorOptions) -> Result<String> {
+    let base_name = strip_suffix(name, "Event");
+    let class_name = CommandUtils::to_pascal_case(&base_name);
+    let module_name = CommandUtils::to_snake_case(&base_name);
+
+    let events_dir = app_path("src/events");
+    ensure_events_module(&std::path::Path::new(&events_dir).join("mod.rs"))?;
+
+    let content = format!(
+        r#"//! {class_name} event
+
+use serde_json::Value;
+
+use super::Event;
+
+/// Dispatched when {label}
+#[derive(Debug, Clone)]
+pub struct {class_name}Event {{
+    pub payload: Value,
+}}
+
+impl {class_name}Event {{
+    pub fn new(payload: Value) -> Self {{
+        Self {{ payload }}
+    }}
+}}
+
+impl Event for {class_name}Event {{
+    fn name() -> &'static str {{
+        "{module_name}"
+    }}
+
+    fn payload(&self) -> &Value {{
+        &self.payload
+    }}
+}}
+"#,
+        class_name = class_name,
+        module_name = module_name,
+        label = module_name.replace('_', " "),
+    );
+
+    write_component(&events_dir, &module_name, &content, options)
+}
+
+/// Add the event traits and dispatcher to `src/events/mod.rs` unless it already defines them
+fn ensure_events_module(mod_file: &std::path::Path) -> Result<()> {
+    let existing = match std::fs::read_to_string(mod_file) {
+        Ok(existing) => existing,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+
+    if existing.contains("trait Event") {
+        return Ok(());
+    }
+
+    FileUtils::atomic_write(mod_file, &merge_events_module(&existing))
+}
+
+/// Merge [`EVENTS_MODULE`] into an existing `mod.rs`, keeping its doc header and declarations
+///
+/// `rustisan new` writes a comment-only `src/events/mod.rs`, so the inner doc
+/// comments have to stay in front of every item.
+fn merge_events_module(existing: &str) -> String {
+    fn split_docs(source: &str) -> (String, String) {
+        let docs_len = source
+            .lines()
+            .take_while(|line| line.starts_with("//!"))
+            .map(|line| line.len() + 1)
+            .sum::<usize>()
+            .min(source.len());
+        (source[..docs_len].to_string(), source[docs_len..].to_string())
+    }
+
+    let (docs, declarations) = split_docs(existing);
+    let (module_docs, body) = split_docs(EVENTS_MODULE);
+
+    let docs = if docs.trim().is_empty() {
+        module_docs
+    } else {
+        // Keep the project's title line in place of the module's own
+        let details = module_docs.split_once('\n').map(|(_, details)| details).unwrap_or_default();
+        format!("{}\n{}", docs.trim_end(), details)
+    };
+
+    let declarations = declarations.trim();
+    if declarations.is_empty() {
+        format!("{}{}", docs, body)
+    } else {
+        format!("{}\n{}\n{}", docs, declarations, body)
+    }
+}
+
+/// Generate a listener
+async fn make_listener(name: String, event: Option<String>, options: &GeneratorOptions) -> Result<()> {
+    CommandUtils::ensure_rustisan_project()?;
+
+    CommandUtils::info(&format!("Creating listener {}...", name.cyan().bold()));
+
+    let base_name = strip_suffix(&name, "Listener");
+    let class_name = CommandUtils::to_pascal_case(&base_name);
+    let module_name = CommandUtils::to_snake_case(&base_name);
+
+    let content = match event {
+        Some(ref event) => {
+            let event_base = strip_suffix(event, "Event");
+            let event_class = format!("{}Event", CommandUtils::to_pascal_case(&event_base));
+            let event_module = CommandUtils::to_snake_case(&event_base);
+
+            let event_file = std::path::Path::new(&app_path("src/events")).join(format!("{}.rs", event_module));
+            if !event_file.exists() {
+                CommandUtils::info(&format!("Event {} does not exist yet, creating it", event_class.cyan()));
+                write_event(event, options)?;
+            }
+
+            format!(
+                r#"//! {class_name} listener
+
+use anyhow::Result;
+use async_trait::async_trait;
+use tracing::info;
+
+use crate::events::{event_module}::{event_class};
+use crate::events::{{Event, Listener}};
+
+/// Handles {event_class}
+#[derive(Debug, Default)]
+pub struct {class_name}Listener;
+
+#[async_trait]
+impl Listener<{event_class}> for {class_name}Listener {{
+    async fn handle(&self, event: &{event_class}) -> Result<()> {{
+        info!(listener = "{class_name}", event = {event_class}::name(), payload = %event.payload());
+
+        // Add your handling logic here
+        Ok(())
+    }}
+}}
+"#,
+                class_name = class_name,
+                event_class = event_class,
+                event_module = event_module,
+            )
+        }
+        None => format!(
+            r#"//! {class_name} listener
+
+use anyhow::Result;
+use async_trait::async_trait;
+use tracing::info;
+
+use crate::events::{{Event, Listener}};
+
+/// Handles any event it is registered for
+#[derive(Debug, Default)]
+pub struct {class_name}Listener;
+
+#[async_trait]
+impl<E: Event> Listener<E> for {class_name}Listener {{
+    async fn handle(&self, event: &E) -> Result<()> {{
+        info!(listener = "{class_name}", event = E::name(), payload = %event.payload());
+
+        // Add your handling logic here
+        Ok(())
+    }}
+}}
+"#,
+            class_name = class_name,
+        ),
+    };
+
+    let file_path = write_component(&app_path("src/listeners"), &module_name, &content, options)?;
+    CommandUtils::success(&format!("Listener created: {}", file_path));
+    if let Some(event) = event {
+        CommandUtils::info(&format!(
+            "Register it with dispatcher.listen::<{}Event, _>({}Listener)",
+            CommandUtils::to_pascal_case(&strip_suffix(&event, "Event")),
+            class_name
+        ));
+    }
+
+    CommandUtils::success(&format!("Listener {} created successfully!", name.cyan().bold()));
+
+    Ok(())
+}
+
+/// Generate a policy
+///
+/// Abilities receive the acting `User` and a record of `model`, or a JSON
+/// value when the policy is generated with `--no-model`.
+async fn make_policy(name: String, model: Option<String>, options: &GeneratorOptions) -> Result<()> {
+    CommandUtils::ensure_rustisan_project()?;
+
+    CommandUtils::info(&format!("Creating policy {}...", name.cyan().bold()));
+
+    let base_name = strip_suffix(&name, "Policy");
+    let class_name = CommandUtils::to_pascal_case(&base_name);
+    let module_name = format!("{}_policy", CommandUtils::to_snake_case(&base_name));
+
+    let content = policy_source(&class_name, model.as_deref());
+
+    let file_path = write_component(&app_path("src/policies"), &module_name, &content, options)?;
+    CommandUtils::success(&format!("Policy created: {}", file_path));
+
+    CommandUtils::success(&format!("Policy {} created successfully!", name.cyan().bold()));
+
+    Ok(())
+}
+
+/// Render a policy authorizing `model`, or JSON values when there is no model
+fn policy_source(class_name: &str, model: Option<&str>) -> String {
+    let (entity, mut model_import) = model_type(&model.map(str::to_string));
+    let (resource, test_resource) = match model {
+        // A policy for users already takes the acting `User`, so the record needs its own name
+        Some(_) if entity == "User" => ("record".to_string(), "User::default()".to_string()),
+        Some(model) => (CommandUtils::to_snake_case(model), format!("{}::default()", entity)),
+        None => ("resource".to_string(), "serde_json::json!({ \"id\": 1 })".to_string()),
+    };
+    if entity == "User" {
+        model_import.clear();
+    }
+    let model_label = model.map(CommandUtils::to_pascal_case).unwrap_or_else(|| "the resource".to_string());
+
+    format!(
+        r#"//! {class_name} Policy
+//!
+//! Authorization rules for {model_label}. Every ability receives the acting
+//! user and, for abilities on an existing record, the record itself.
+//!
+//! Abilities that change data are denied until you implement them.
+
+use std::any::{{Any, TypeId}};
+use std::collections::HashMap;
+
+use crate::models::user::User;
+{model_import}
+/// Type-erased policy, looked up by the type of the resource being authorized
+pub trait PolicyHandler: Send + Sync {{
+    /// Whether `user` may perform `ability` on `resource`
+    fn allows(&self, ability: &str, user: &User, resource: Option<&dyn Any>) -> bool;
+}}
+
+/// {class_name} policy
+#[derive(Debug, Default, Clone, Copy)]
+pub struct {class_name}Policy;
+
+impl {class_name}Policy {{
+    /// Whether the user can list records
+    pub fn view_any(&self, _user: &User, _{resource}: Option<&{entity}>) -> bool {{
+        true
+    }}
+
+    /// Whether the user can view the record
+    pub fn view(&self, _user: &User, {resource}: Option<&{entity}>) -> bool {{
+        {resource}.is_some()
+    }}
+
+    /// Whether the user can create records
+    pub fn create(&self, _user: &User, _{resource}: Option<&{entity}>) -> bool {{
+        true
+    }}
+
+    /// Whether the user can update the record
+    pub fn update(&self, _user: &User, _{resource}: Option<&{entity}>) -> bool {{
+        // Add your ownership check here
+        false
+    }}
+
+    /// Whether the user can delete the record
+    pub fn delete(&self, _user: &User, _{resource}: Option<&{entity}>) -> bool {{
+        false
+    }}
+
+    /// Whether the user can restore the soft-deleted record
+    pub fn restore(&self, _user: &User, _{resource}: Option<&{entity}>) -> bool {{
+        false
+    }}
+
+    /// Whether the user can permanently delete the record
+    pub fn force_delete(&self, _user: &User, _{resource}: Option<&{entity}>) -> bool {{
+        false
+    }}
+}}
+
+impl PolicyHandler for {class_name}Policy {{
+    fn allows(&self, ability: &str, user: &User, resource: Option<&dyn Any>) -> bool {{
+        let {resource} = resource.and_then(|resource| resource.downcast_ref::<{entity}>());
+
+        match ability {{
+            "view_any" => self.view_any(user, {resource}),
+            "view" => self.view(user, {resource}),
+            "create" => self.create(user, {resource}),
+            "update" => self.update(user, {resource}),
+            "delete" => self.delete(user, {resource}),
+            "restore" => self.restore(user, {resource}),
+            "force_delete" => self.force_delete(user, {resource}),
+            _ => false,
+        }}
+    }}
+}}
+
+/// Policies keyed by the resource type they authorize
+pub fn register_policies() -> HashMap<TypeId, Box<dyn PolicyHandler>> {{
+    let mut policies: HashMap<TypeId, Box<dyn PolicyHandler>> = HashMap::new();
+    policies.insert(TypeId::of::<{entity}>(), Box::new({class_name}Policy));
+    policies
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    fn fixtures() -> ({class_name}Policy, User, {entity}) {{
+        ({class_name}Policy, User::default(), {test_resource})
+    }}
+
+    #[test]
+    fn test_view_any() {{
+        let (policy, user, _) = fixtures();
+        assert!(policy.view_any(&user, None));
+    }}
+
+    #[test]
+    fn test_view() {{
+        let (policy, user, {resource}) = fixtures();
+        assert!(policy.view(&user, Some(&{resource})));
+        assert!(!policy.view(&user, None));
+    }}
+
+    #[test]
+    fn test_create() {{
+        let (policy, user, _) = fixtures();
+        assert!(policy.create(&user, None));
+    }}
+
+    #[test]
+    fn test_update() {{
+        let (policy, user, {resource}) = fixtures();
+        assert!(!policy.update(&user, Some(&{resource})));
+    }}
+
+    #[test]
+    fn test_delete() {{
+        let (policy, user, {resource}) = fixtures();
+        assert!(!policy.delete(&user, Some(&{resource})));
+    }}
+
+    #[test]
+    fn test_restore() {{
+        let (policy, user, {resource}) = fixtures();
+        assert!(!policy.restore(&user, Some(&{resource})));
+    }}
+
+    #[test]
+    fn test_force_delete() {{
+        let (policy, user, {resource}) = fixtures();
+        assert!(!policy.force_delete(&user, Some(&{resource})));
+    }}
+
+    #[test]
+    fn test_registered_handler() {{
+        let (_, user, {resource}) = fixtures();
+        let policies = register_policies();
+        let handler = &policies[&TypeId::of::<{entity}>()];
+
+        assert!(handler.allows("view", &user, Some(&{resource})));
+        assert!(!handler.allows("delete", &user, Some(&{resource})));
+        assert!(!handler.allows("publish", &user, Some(&{resource})));
+    }}
+}}
+"#,
+        class_name = class_name,
+        model_label = model_label,
+        model_import = model_import,
+        entity = entity,
+        resource = resource,
+        test_resource = test_resource,
+    )
+}
+
+/// Generate a trait
+async fn make_trait(name: String, methods: Vec<String>, no_async: bool, options: &GeneratorOptions) -> Result<()> {
+    CommandUtils::ensure_rustisan_project()?;
+
+    CommandUtils::info(&format!("Creating trait {}...", name.cyan().bold()));
+
+    let mut signatures = Vec::new();
+    for spec in &methods {
+        signatures.extend(parse_trait_methods(spec)?);
+    }
+    if signatures.is_empty() {
+        signatures.push(TraitMethod { name: "handle".to_string(), return_type: "Result<()>".to_string() });
+    }
+
+    let content = trait_source(&name, &signatures, !no_async);
+
+    let file_path = std::path::Path::new(&app_path("src/traits"))
+        .join(format!("{}.rs", CommandUtils::to_snake_case(&name)));
+
+    CommandUtils::ensure_directory(file_path.parent().unwrap())?;
+    write_generated(&file_path, &content, options)?;
+
+    CommandUtils::success(&format!("Trait {} created successfully!", name.cyan().bold()));
+
+    Ok(())
+}
+
+/// A required method of a generated trait
+#[derive(Debug, PartialEq)]
+struct TraitMethod {
+    name: String,
+    return_type: String,
+}
+
+/// Parse `--methods` given as "find:Result<Option<Self>>,save:Result<()>"
+///
+/// Commas inside generic arguments, as in `Result<HashMap<String, u32>>`, stay part of the type.
+fn parse_trait_methods(spec: &str) -> Result<Vec<TraitMethod>> {
+    let mut entries = vec![String::new()];
+    let mut depth = 0;
+    for c in spec.chars() {
+        match c {
+            '<' | '(' | '[' => depth += 1,
+            '>' | ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                entries.push(String::new());
+                continue;
+            }
+            _ => {}
+        }
+        entries.last_mut().unwrap().push(c);
+    }
+
+    entries
+        .iter()
+        .map(|entry| entry.trim())
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (name, return_type) = entry.split_once(':').unwrap_or((entry, "Result<()>"));
+            let name = CommandUtils::to_snake_case(name.trim());
+            if name.is_empty() {
+                anyhow::bail!("Method '{}' needs a name", entry);
+            }
+            Ok(TraitMethod { name, return_type: return_type.trim().to_string() })
+        })
+        .collect()
+}
+
+/// Source of a trait and its instrumented extension trait, implemented for every implementor
+fn trait_source(name: &str, methods: &[TraitMethod], is_async: bool) -> String {
+    let class_name = CommandUtils::to_pascal_case(name);
+    let (attribute, import, asyncness, await_call) = if is_async {
+        ("#[async_trait]\n", "use async_trait::async_trait;\n", "async ", ".await")
+    } else {
+        ("", "", "", "")
+    };
+
+    // Returning `Self` by value, as in `Option<Self>`, needs a sized implementor
+    let returns_self = regex::Regex::new(r"\bSelf\b").unwrap();
+    let mut supertraits = Vec::new();
+    if methods.iter().any(|method| returns_self.is_match(&method.return_type)) {
+        supertraits.push("Sized");
+    }
+    if is_async {
+        supertraits.extend(["Send", "Sync"]);
+    }
+    let bounds = if supertraits.is_empty() { String::new() } else { format!(": {}", supertraits.join(" + ")) };
+
+    let required: Vec<String> = methods
+        .iter()
+        .map(|method| format!("    {}fn {}(&self) -> {};", asyncness, method.name, method.return_type))
+        .collect();
+    let provided: Vec<String> = methods
+        .iter()
+        .map(|method| {
+            format!(
+                r#"    /// Call [`{class_name}::{method}`] inside a tracing span
+    #[tracing::instrument(skip_all, name = "{class_name}::{method}")]
+    {asyncness}fn traced_{method}(&self) -> {return_type} {{
+        self.{method}(){await_call}
+    }}"#,
+                method = method.name,
+                return_type = method.return_type,
+            )
+        })
+        .collect();
+
+    format!(
+        r#"//! {name} trait
+//!
+//! This trait defines the interface for {snake_case}.
+
+use anyhow::Result;
+{import}
+/// {name} trait
+{attribute}pub trait {class_name}{bounds} {{
+{required}
+}}
+
+/// Instrumented calls to the methods of [`{class_name}`]
+{attribute}pub trait {class_name}Ext: {class_name} {{
+{provided}
+}}
+
+impl<T> {class_name}Ext for T where T: {class_name} {{}}
+"#,
+        snake_case = CommandUtils::to_snake_case(name),
+        required = required.join("\n"),
+        provided = provided.join("\n\n"),
+    )
+}
+
+/// Generate a test
+async fn make_test(name: String, unit: bool, integration: bool, feature: Option<String>, options: &GeneratorOptions) -> Result<()> {
+    CommandUtils::ensure_rustisan_project()?;
+
+    CommandUtils::info(&format!("Creating test {}...", name.cyan().bold()));
+
+    if feature.as_deref() == Some("http") {
+        return make_http_test(&name, options);
+    }
+
+    // TODO: Implement test generation
+
+    // Create template manager
+    let template_manager = TemplateManager::new()?;
+
+    let template_name = if integration {
+        "test_integration"
+    } else {
+        "test_unit"
+    };
+
+    let test_dir = if integration {
+        "tests/integration"
+    } else {
+        "tests/unit"
+    };
+
+    let template_vars = serde_json::json!({
+        "name": name,
+        "snake_case": CommandUtils::to_snake_case(&name),
+        "pascal_case": CommandUtils::to_pascal_case(&name),
+        "unit": unit,
+        "integration": integration
+    });
+
+    let content = template_manager.render(template_name, &template_vars)?;
+
+    let file_path = std::path::Path::new(&app_path(test_dir))
+        .join(format!("{}.rs", CommandUtils::to_snake_case(&name)));
+
+    CommandUtils::ensure_directory(file_path.parent().unwrap())?;
+    write_generated(&file_path, &content, options)?;
+
+    CommandUtils::success(&format!("Test {} created successfully!", name.cyan().bold()));
+
+    Ok(())
+}
+
+/// Generate an HTTP integration test that runs against the application binary
+fn make_http_test(name: &str, options: &GeneratorOptions) -> Result<()> {
+    let base_name = strip_suffix(name, "Test");
+    let class_name = CommandUtils::to_pascal_case(&base_name);
+    let test_name = format!("{}_test", CommandUtils::to_snake_case(&base_name));
+    let endpoint = format!("/{}", TextUtils::pluralize(&CommandUtils::to_snake_case(&base_name)).replace('_', "-"));
+
+    let manifest_path = std::path::PathBuf::from(app_path("Cargo.toml"));
+    let mut manifest: toml_edit::DocumentMut = std::fs::read_to_string(&manifest_path)?.parse()?;
+    let binary = binary_name(&manifest).ok_or_else(|| anyhow::anyhow!("No binary target found in {}", manifest_path.display()))?;
+
+    let content = format!(
+        r#"//! HTTP integration tests for {class_name}
+//!
+//! Each test starts the application binary on a free port and sends real
+//! requests to it. Set `TEST_SERVER_URL` to run against a server that is
+//! already running, such as a staging deployment, instead.
+//!
+//! The tests start the server, so they are ignored by default. Run them with:
+//!
+//! ```sh
+//! cargo test --test {test_name} -- --ignored
+//! ```
+//!
+//! Requires the following dev-dependencies in Cargo.toml:
+//!
+//! ```toml
+//! reqwest = {{ version = "0.12", features = ["json"] }}
+//! ```
+
+use std::net::TcpListener;
+use std::time::Duration;
+
+use reqwest::{{Client, Response}};
+use serde_json::{{json, Value}};
+use tokio::task::JoinHandle;
+
+/// Endpoint exercised by the tests
+const ENDPOINT: &str = "{endpoint}";
+
+/// How long to wait for the server to accept connections
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A server under test, stopped when dropped
+struct TestServer {{
+    url: String,
+    client: Client,
+    handle: Option<JoinHandle<()>>,
+}}
+
+impl TestServer {{
+    fn url(&self, path: &str) -> String {{
+        format!("{{}}{{}}", self.url, path)
+    }}
+
+    async fn wait_until_ready(&self) {{
+        let deadline = tokio::time::Instant::now() + STARTUP_TIMEOUT;
+        while tokio::time::Instant::now() < deadline {{
+            if self.client.get(&self.url).send().await.is_ok() {{
+                return;
+            }}
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }}
+        panic!("Server did not start at {{}} within {{:?}}", self.url, STARTUP_TIMEOUT);
+    }}
+
+    /// Stop the background server, if this test started one
+    fn teardown(&mut self) {{
+        if let Some(handle) = self.handle.take() {{
+            handle.abort();
+        }}
+    }}
+}}
+
+impl Drop for TestServer {{
+    fn drop(&mut self) {{
+        self.teardown();
+    }}
+}}
+
+/// Start the server in a background task, or use `TEST_SERVER_URL`
+async fn setup() -> TestServer {{
+    let client = Client::builder().timeout(Duration::from_secs(10)).build().unwrap();
+
+    if let Ok(url) = std::env::var("TEST_SERVER_URL") {{
+        return TestServer {{
+            url: url.trim_end_matches('/').to_string(),
+            client,
+            handle: None,
+        }};
+    }}
+
+    // Let the OS pick a free port so tests can run in parallel
+    let port = TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .map(|addr| addr.port())
+        .expect("Failed to find a free port");
+
+    let handle = tokio::spawn(async move {{
+        // Aborting the task drops the child process, which kills the server
+        let status = tokio::process::Command::new(env!("CARGO_BIN_EXE_{binary}"))
+            .env("SERVER_HOST", "127.0.0.1")
+            .env("SERVER_PORT", port.to_string())
+            .kill_on_drop(true)
+            .status()
+            .await;
+        if let Err(error) = status {{
+            eprintln!("Failed to start server: {{}}", error);
+        }}
+    }});
+
+    let server = TestServer {{
+        url: format!("http://127.0.0.1:{{}}", port),
+        client,
+        handle: Some(handle),
+    }};
+    server.wait_until_ready().await;
+    server
+}}
+
+/// Assert the response has a 2xx status
+fn assert_status_ok(response: &Response) {{
+    assert!(
+        response.status().is_success(),
+        "Expected a successful status, got {{}}",
+        response.status()
+    );
+}}
+
+/// Assert a dotted field such as `user.id` is present and return its value
+fn assert_json_field<'a>(body: &'a Value, field: &str) -> &'a Value {{
+    let pointer = format!("/{{}}", field.replace('.', "/"));
+    body.pointer(&pointer)
+        .unwrap_or_else(|| panic!("Expected field '{{}}' in {{}}", field, body))
+}}
+
+#[tokio::test]
+#[ignore = "starts the application server"]
+async fn test_get_{snake_name}() {{
+    let server = setup().await;
+
+    let response = server.client.get(server.url(ENDPOINT)).send().await.unwrap();
+
+    assert_status_ok(&response);
+    let body: Value = response.json().await.unwrap();
+    assert!(body.is_object() || body.is_array());
+}}
+
+#[tokio::test]
+#[ignore = "starts the application server"]
+async fn test_post_{snake_name}() {{
+    let server = setup().await;
+
+    let response = server
+        .client
+        .post(server.url(ENDPOINT))
+        .json(&json!({{ "name": "Example" }}))
+        .send()
+        .await
+        .unwrap();
+
+    assert_status_ok(&response);
+    let body: Value = response.json().await.unwrap();
+    assert_json_field(&body, "message");
+}}
+
+#[tokio::test]
+#[ignore = "starts the application server"]
+async fn test_put_{snake_name}() {{
+    let server = setup().await;
+
+    let response = server
+        .client
+        .put(server.url(&format!("{{}}/1", ENDPOINT)))
+        .json(&json!({{ "name": "Updated" }}))
+        .send()
+        .await
+        .unwrap();
+
+    assert_status_ok(&response);
+    let body: Value = response.json().await.unwrap();
+    assert_json_field(&body, "message");
+}}
+
+#[tokio::test]
+#[ignore = "starts the application server"]
+async fn test_delete_{snake_name}() {{
+    let server = setup().await;
+
+    let response = server.client.delete(server.url(&format!("{{}}/1", ENDPOINT))).send().await.unwrap();
+
+    assert_status_ok(&response);
+    let body: Value = response.json().await.unwrap();
+    assert_json_field(&body, "message");
+}}
+"#,
+        class_name = class_name,
+        test_name = test_name,
+        snake_name = CommandUtils::to_snake_case(&base_name),
+        endpoint = endpoint,
+        binary = binary,
+    );
+
+    let relative_path = format!("tests/integration/{}.rs", test_name);
+    let file_path = std::path::Path::new(&app_path(&relative_path)).to_path_buf();
+    CommandUtils::ensure_directory(file_path.parent().unwrap())?;
+    write_generated(&file_path, &content, options)?;
+
+    // Cargo only discovers tests directly in tests/, so register the file as a test target
+    if register_test_target(&mut manifest, &test_name, &relative_path) {
+        FileUtils::atomic_write(&manifest_path, &manifest.to_string())?;
+        CommandUtils::info(&format!("Registered test target {} in {}", test_name.cyan(), manifest_path.display()));
+    }
+
+    CommandUtils::success(&format!("Test created: {}", file_path.display()));
+    CommandUtils::warning("Add reqwest to [dev-dependencies]: reqwest = { version = \"0.12\", features = [\"json\"] }");
+    CommandUtils::info(&format!("Run it with: cargo test --test {} -- --ignored", test_name));
+
+    Ok(())
+}
+
+/// Name of the first binary target, falling back to the package name
+fn binary_name(manifest: &toml_edit::DocumentMut) -> Option<String> {
+    manifest
+        .get("bin")
+        .and_then(|bins| bins.as_array_of_tables())
+        .and_then(|bins| bins.iter().find_map(|bin| bin.get("name")?.as_str().map(str::to_string)))
+        .or_else(|| manifest.get("package")?.get("name")?.as_str().map(str::to_string))
+}
+
+/// Add a `[[test]]` target unless one with the same name exists
+///
+/// Returns whether the manifest changed.
+fn register_test_target(manifest: &mut toml_edit::DocumentMut, name: &str, path: &str) -> bool {
+    let tests = manifest
+        .entry("test")
+        .or_insert(toml_edit::Item::ArrayOfTables(toml_edit::ArrayOfTables::new()));
+    let Some(tests) = tests.as_array_of_tables_mut() else {
+        return false;
+    };
+    if tests.iter().any(|test| test.get("name").and_then(|item| item.as_str()) == Some(name)) {
+        return false;
+    }
+
+    let mut test = toml_edit::Table::new();
+    test["name"] = toml_edit::value(name);
+    test["path"] = toml_edit::value(path);
+    tests.push(test);
+    true
+}
+
+/// Generate a service
+async fn make_service(name: String, model: Option<String>, test: bool, options: &GeneratorOptions) -> Result<()> {
+    CommandUtils::ensure_rustisan_project()?;
+
+    CommandUtils::info(&format!("Creating service {}...", name.cyan().bold()));
+
+    let base_name = strip_suffix(&name, "Service");
+    let class_name = CommandUtils::to_pascal_case(&base_name);
+    let module_name = format!("{}_service", CommandUtils::to_snake_case(&base_name));
+    let (entity, model_import) = model_type(&model);
+
+    let (model_field, create_body, update_body, delete_body, find_body, all_body) = if model.is_some() {
+        (
+            format!("\n    /// Model instance shared with the service\n    pub model: Option<Arc<{}>>,\n", entity),
+            format!("{}::create(data).await", entity),
+            format!("{}::update(id, data).await", entity),
+            format!("{}::destroy(id).await", entity),
+            format!("{}::find(id).await", entity),
+            format!("{}::all().await", entity),
+        )
+    } else {
+        (
+            String::new(),
+            "// Add your creation logic here\n        Ok(data)".to_string(),
+            "// Add your update logic here\n        let _ = id;\n        Ok(data)".to_string(),
+            "// Add your deletion logic here\n        let _ = id;\n        Ok(())".to_string(),
+            "// Add your lookup logic here\n        let _ = id;\n        Ok(None)".to_string(),
+            "// Add your listing logic here\n        Ok(Vec::new())".to_string(),
+        )
+    };
+
+    let content = format!(
+        r#"//! {class_name} Service
+//!
+//! Business logic for {class_name}, kept out of the controllers.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use std::sync::Arc;
+{model_import}
+/// Operations provided by the {class_name} service
+///
+/// Depend on this trait rather than the concrete service so it can be mocked in tests.
+#[async_trait]
+pub trait {class_name}ServiceTrait: Send + Sync {{
+    async fn create(&self, data: {entity}) -> Result<{entity}>;
+    async fn update(&self, id: u64, data: {entity}) -> Result<{entity}>;
+    async fn delete(&self, id: u64) -> Result<()>;
+    async fn find_by_id(&self, id: u64) -> Result<Option<{entity}>>;
+    async fn all(&self) -> Result<Vec<{entity}>>;
+}}
+
+/// {class_name} service
+#[derive(Default)]
+pub struct {class_name}Service {{{model_field}}}
+
+impl {class_name}Service {{
+    pub fn new() -> Arc<Self> {{
+        Arc::new(Self::default())
+    }}
+}}
+
+#[async_trait]
+impl {class_name}ServiceTrait for {class_name}Service {{
+    async fn create(&self, data: {entity}) -> Result<{entity}> {{
+        {create_body}
+    }}
+
+    async fn update(&self, id: u64, data: {entity}) -> Result<{entity}> {{
+        {update_body}
+    }}
+
+    async fn delete(&self, id: u64) -> Result<()> {{
+        {delete_body}
+    }}
+
+    async fn find_by_id(&self, id: u64) -> Result<Option<{entity}>> {{
+        {find_body}
+    }}
+
+    async fn all(&self) -> Result<Vec<{entity}>> {{
+        {all_body}
+    }}
+}}
+"#,
+        class_name = class_name,
+        entity = entity,
+        model_import = model_import,
+        model_field = model_field,
+        create_body = create_body,
+        update_body = update_body,
+        delete_body = delete_body,
+        find_body = find_body,
+        all_body = all_body,
+    );
+
+    let file_path = write_component(&app_path("src/services"), &module_name, &content, options)?;
+    CommandUtils::success(&format!("Service created: {}", file_path));
+
+    if test {
+        let test_content = format!(
+            r#"//! {class_name} Service unit tests
+
+use anyhow::Result;
+use async_trait::async_trait;
+use std::sync::Mutex;
+use {crate_name}::services::{module_name}::{class_name}ServiceTrait;
+{test_model_import}
+/// In-memory mock of the {class_name} service
+#[derive(Default)]
+struct Mock{class_name}Service {{
+    records: Mutex<Vec<{entity}>>,
+}}
+
+#[async_trait]
+impl {class_name}ServiceTrait for Mock{class_name}Service {{
+    async fn create(&self, data: {entity}) -> Result<{entity}> {{
+        self.records.lock().unwrap().push(data.clone());
+        Ok(data)
+    }}
+
+    async fn update(&self, id: u64, data: {entity}) -> Result<{entity}> {{
+        let mut records = self.records.lock().unwrap();
+        if let Some(record) = records.get_mut(id as usize) {{
+            *record = data.clone();
+        }}
+        Ok(data)
+    }}
+
+    async fn delete(&self, id: u64) -> Result<()> {{
+        let mut records = self.records.lock().unwrap();
+        if (id as usize) < records.len() {{
+            records.remove(id as usize);
+        }}
+        Ok(())
+    }}
+
+    async fn find_by_id(&self, id: u64) -> Result<Option<{entity}>> {{
+        Ok(self.records.lock().unwrap().get(id as usize).cloned())
+    }}
+
+    async fn all(&self) -> Result<Vec<{entity}>> {{
+        Ok(self.records.lock().unwrap().clone())
+    }}
+}}
+
+#[tokio::test]
+async fn test_{module_name}_mock() -> Result<()> {{
+    let service = Mock{class_name}Service::default();
+
+    service.create({entity}::default()).await?;
+
+    assert_eq!(service.all().await?.len(), 1);
+    assert!(service.find_by_id(0).await?.is_some());
+
+    service.delete(0).await?;
+    assert!(service.all().await?.is_empty());
+
+    Ok(())
+}}
+"#,
+            class_name = class_name,
+            crate_name = project_crate_name(),
+            module_name = module_name,
+            entity = entity,
+            test_model_import = model_import.replace("crate::", &format!("{}::", project_crate_name())),
+        );
+
+        let test_path = app_path(&format!("tests/unit/{}_test.rs", module_name));
+        write_generated(&test_path, &test_content, options)?;
+        CommandUtils::success(&format!("Service test created: {}", test_path));
+    }
+
+    CommandUtils::success(&format!("Service {} created successfully!", name.cyan().bold()));
+
+    Ok(())
+}
+
+/// Generate a repository
+async fn make_repository(name: String, model: Option<String>, options: &GeneratorOptions) -> Result<()> {
+    CommandUtils::ensure_rustisan_project()?;
+
+    CommandUtils::info(&format!("Creating repository {}...", name.cyan().bold()));
+
+    let base_name = strip_suffix(&name, "Repository");
+    let class_name = CommandUtils::to_pascal_case(&base_name);
+    let module_name = format!("{}_repository", CommandUtils::to_snake_case(&base_name));
+    let (entity, model_import) = model_type(&model);
+
+    let bodies = if model.is_some() {
+        [
+            format!("{}::find(id).await", entity),
+            format!("{}::all().await", entity),
+            format!("{}::where_eq(column, value).await", entity),
+            format!("{}::create(data).await", entity),
+            format!("{}::update(id, data).await", entity),
+            format!("{}::destroy(id).await", entity),
+        ]
+    } else {
+        [
+            "// Query a single record here\n        let _ = id;\n        Ok(None)".to_string(),
+            "// Query all records here\n        Ok(Vec::new())".to_string(),
+            "// Query records matching the condition here\n        let _ = (column, value);\n        Ok(Vec::new())".to_string(),
+            "// Insert the record here\n        Ok(data)".to_string(),
+            "// Update the record here\n        let _ = id;\n        Ok(data)".to_string(),
+            "// Delete the record here\n        let _ = id;\n        Ok(())".to_string(),
+        ]
+    };
+    let [find_by_id, find_all, find_where, create, update, delete] = bodies;
+
+    let content = format!(
+        r#"//! {class_name} Repository
+//!
+//! Data access for {class_name} behind a trait, so callers can swap in a mock.
+
+use anyhow::Result;
+use async_trait::async_trait;
+{model_import}
+/// Data access operations for {class_name}
+#[async_trait]
+pub trait {class_name}Repository: Send + Sync {{
+    async fn find_by_id(&self, id: u64) -> Result<Option<{entity}>>;
+    async fn find_all(&self) -> Result<Vec<{entity}>>;
+    async fn find_where(&self, column: &str, value: &str) -> Result<Vec<{entity}>>;
+    async fn create(&self, data: {entity}) -> Result<{entity}>;
+    async fn update(&self, id: u64, data: {entity}) -> Result<{entity}>;
+    async fn delete(&self, id: u64) -> Result<()>;
+}}
+
+/// Database-backed {class_name} repository
+#[derive(Debug, Default)]
+pub struct {class_name}DbRepository;
+
+impl {class_name}DbRepository {{
+    pub fn new() -> Self {{
+        Self
+    }}
+}}
+
+#[async_trait]
+impl {class_name}Repository for {class_name}DbRepository {{
+    async fn find_by_id(&self, id: u64) -> Result<Option<{entity}>> {{
+        {find_by_id}
+    }}
+
+    async fn find_all(&self) -> Result<Vec<{entity}>> {{
+        {find_all}
+    }}
+
+    async fn find_where(&self, column: &str, value: &str) -> Result<Vec<{entity}>> {{
+        {find_where}
+    }}
+
+    async fn create(&self, data: {entity}) -> Result<{entity}> {{
+        {create}
+    }}
+
+    async fn update(&self, id: u64, data: {entity}) -> Result<{entity}> {{
+        {update}
+    }}
+
+    async fn delete(&self, id: u64) -> Result<()> {{
+        {delete}
+    }}
+}}
+"#,
+        class_name = class_name,
+        entity = entity,
+        model_import = model_import,
+        find_by_id = find_by_id,
+        find_all = find_all,
+        find_where = find_where,
+        create = create,
+        update = update,
+        delete = delete,
+    );
+
+    let file_path = write_component(&app_path("src/repositories"), &module_name, &content, options)?;
+    CommandUtils::success(&format!("Repository created: {}", file_path));
+
+    let crate_name = project_crate_name();
+    let test_content = format!(
+        r#"//! {class_name} Repository unit tests
+
+use anyhow::Result;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use {crate_name}::repositories::{module_name}::{class_name}Repository;
+{test_model_import}
+/// In-memory repository for testing controllers and services in isolation
+#[derive(Default)]
+pub struct MockRepository {{
+    records: Mutex<HashMap<u64, {entity}>>,
+}}
+
+#[async_trait]
+impl {class_name}Repository for MockRepository {{
+    async fn find_by_id(&self, id: u64) -> Result<Option<{entity}>> {{
+        Ok(self.records.lock().unwrap().get(&id).cloned())
+    }}
+
+    async fn find_all(&self) -> Result<Vec<{entity}>> {{
+        Ok(self.records.lock().unwrap().values().cloned().collect())
+    }}
+
+    async fn find_where(&self, _column: &str, _value: &str) -> Result<Vec<{entity}>> {{
+        self.find_all().await
+    }}
+
+    async fn create(&self, data: {entity}) -> Result<{entity}> {{
+        let mut records = self.records.lock().unwrap();
+        let id = records.len() as u64 + 1;
+        records.insert(id, data.clone());
+        Ok(data)
+    }}
+
+    async fn update(&self, id: u64, data: {entity}) -> Result<{entity}> {{
+        self.records.lock().unwrap().insert(id, data.clone());
+        Ok(data)
+    }}
+
+    async fn delete(&self, id: u64) -> Result<()> {{
+        self.records.lock().unwrap().remove(&id);
+        Ok(())
+    }}
+}}
+
+#[tokio::test]
+async fn test_mock_repository() -> Result<()> {{
+    let repository = MockRepository::default();
+
+    repository.create({entity}::default()).await?;
+    assert!(repository.find_by_id(1).await?.is_some());
+    assert_eq!(repository.find_all().await?.len(), 1);
+
+    repository.delete(1).await?;
+    assert!(repository.find_all().await?.is_empty());
+
+    Ok(())
+}}
+"#,
+        class_name = class_name,
+        crate_name = crate_name,
+        module_name = module_name,
+        entity = entity,
+        test_model_import = model_import.replace("crate::", &format!("{}::", crate_name)),
+    );
+
+    let test_path = app_path(&format!("tests/unit/{}_test.rs", module_name));
+    write_generated(&test_path, &test_content, options)?;
+    CommandUtils::success(&format!("Repository test created: {}", test_path));
+
+    CommandUtils::success(&format!("Repository {} created successfully!", name.cyan().bold()));
+
+    Ok(())
+}
+
+/// Generate a model observer
+async fn make_observer(name: String, model: Option<String>, options: &GeneratorOptions) -> Result<()> {
+    CommandUtils::ensure_rustisan_project()?;
+
+    CommandUtils::info(&format!("Creating observer {}...", name.cyan().bold()));
+
+    let base_name = strip_suffix(&name, "Observer");
+    let class_name = CommandUtils::to_pascal_case(&base_name);
+    let module_name = format!("{}_observer", CommandUtils::to_snake_case(&base_name));
+    let (subject, model_import, test_subject) = match model {
+        Some(ref model) => {
+            let (model_class, import) = model_type(&Some(model.clone()));
+            (model_class.clone(), import, format!("{}::default()", model_class))
+        }
+        None => ("(dyn Any + Send + Sync)".to_string(), "use std::any::Any;\n".to_string(), "()".to_string()),
+    };
+    let model_label = model.as_deref().map(CommandUtils::to_pascal_case).unwrap_or_else(|| "YourModel".to_string());
+
+    let hooks: String = ["creating", "created", "updating", "updated", "deleting", "deleted"]
+        .iter()
+        .map(|hook| {
+            format!(
+                "    async fn {hook}(&self, model: &{subject}) -> Result<()>;\n",
+                hook = hook,
+                subject = subject
+            )
+        })
+        .collect();
+
+    let logging_hooks: String = ["creating", "created", "updating", "updated", "deleting", "deleted"]
+        .iter()
+        .map(|hook| {
+            format!(
+                "    async fn {hook}(&self, _model: &{subject}) -> Result<()> {{\n        info!(observer = \"{class_name}\", event = \"{hook}\");\n        Ok(())\n    }}\n",
+                hook = hook,
+                subject = subject,
+                class_name = class_name
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let recording_hooks: String = ["creating", "created", "updating", "updated", "deleting", "deleted"]
+        .iter()
+        .map(|hook| {
+            format!(
+                "        async fn {hook}(&self, _model: &{subject}) -> Result<()> {{\n            self.events.lock().unwrap().push(\"{hook}\");\n            Ok(())\n        }}\n",
+                hook = hook,
+                subject = subject
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let content = format!(
+        r#"//! {class_name} Observer
+//!
+//! Reacts to {model_label} lifecycle events.
+//!
+//! Register the observer when building the application in `main.rs`:
+//!
+//! ```ignore
+//! {model_label}::observe(Arc::new(Logging{class_name}Observer));
+//! ```
+
+use anyhow::Result;
+use async_trait::async_trait;
+use tracing::info;
+{model_import}
+/// Lifecycle hooks for {model_label}
+///
+/// The `*ing` hooks run before the change is persisted and can abort it by returning an error.
+#[async_trait]
+pub trait {class_name}Observer: Send + Sync {{
+{hooks}}}
+
+/// Lifecycle operations observers are notified about
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LifecycleEvent {{
+    Create,
+    Update,
+    Delete,
+}}
+
+/// Notify an observer around a lifecycle operation, calling the `*ing` hook then the `*ed` hook
+pub async fn dispatch<O>(observer: &O, event: LifecycleEvent, model: &{subject}) -> Result<()>
+where
+    O: {class_name}Observer + ?Sized,
+{{
+    match event {{
+        LifecycleEvent::Create => {{
+            observer.creating(model).await?;
+            observer.created(model).await
+        }}
+        LifecycleEvent::Update => {{
+            observer.updating(model).await?;
+            observer.updated(model).await
+        }}
+        LifecycleEvent::Delete => {{
+            observer.deleting(model).await?;
+            observer.deleted(model).await
+        }}
+    }}
+}}
+
+/// Observer that logs every lifecycle event
+#[derive(Debug, Default)]
+pub struct Logging{class_name}Observer;
+
+#[async_trait]
+impl {class_name}Observer for Logging{class_name}Observer {{
+{logging_hooks}}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingObserver {{
+        events: Mutex<Vec<&'static str>>,
+    }}
+
+    #[async_trait]
+    impl {class_name}Observer for RecordingObserver {{
+{recording_hooks}    }}
+
+    #[tokio::test]
+    async fn test_lifecycle_order() {{
+        let observer = RecordingObserver::default();
+        let model = {test_subject};
+
+        dispatch(&observer, LifecycleEvent::Create, &model).await.unwrap();
+        dispatch(&observer, LifecycleEvent::Update, &model).await.unwrap();
+        dispatch(&observer, LifecycleEvent::Delete, &model).await.unwrap();
+
+        assert_eq!(
+            *observer.events.lock().unwrap(),
+            vec!["creating", "created", "updating", "updated", "deleting", "deleted"]
+        );
+    }}
+}}
+"#,
+        class_name = class_name,
+        model_label = model_label,
+        model_import = model_import,
+        subject = subject,
+        hooks = hooks,
+        logging_hooks = logging_hooks,
+        recording_hooks = recording_hooks,
+        test_subject = test_subject,
+    );
+
+    let file_path = write_component(&app_path("src/observers"), &module_name, &content, options)?;
+    CommandUtils::success(&format!("Observer created: {}", file_path));
+
+    CommandUtils::success(&format!("Observer {} created successfully!", name.cyan().bold()));
+
+    Ok(())
+}
+
+/// Generate a notification
+async fn make_notification(name: String, channels: Vec<String>, options: &GeneratorOptions) -> Result<()> {
+    CommandUtils::ensure_rustisan_project()?;
+
+    CommandUtils::info(&format!("Creating notification {}...", name.cyan().bold()));
+
+    let base_name = strip_suffix(&name, "Notification");
+    let class_name = CommandUtils::to_pascal_case(&base_name);
+    let module_name = CommandUtils::to_snake_case(&base_name);
+
+    let mut channels = channels
+        .iter()
+        .map(|channel| channel.trim().to_lowercase())
+        .filter(|channel| !channel.is_empty())
+        .fold(Vec::new(), |mut unique: Vec<String>, channel| {
+            if !unique.contains(&channel) {
+                unique.push(channel);
+            }
+            unique
+        });
+    if channels.is_empty() {
+        channels.push("database".to_string());
+    }
+    if let Some(invalid) = channels.iter().find(|channel| !crate::utils::TextUtils::is_valid_identifier(channel)) {
+        anyhow::bail!("Invalid notification channel: {}", invalid);
+    }
+
+    let via_list = channels
+        .iter()
+        .map(|channel| format!("\"{}\"", channel))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let methods = channels
+        .iter()
+        .map(|channel| match channel.as_str() {
+            "mail" => format!(
+                r#"    /// Build the mail representation of the notification
+    pub fn via_mail(&self) -> MailMessage {{
+        MailMessage {{
+            subject: "{title}".to_string(),
+            body: self.message.clone(),
+        }}
+    }}
+"#,
+                title = crate::utils::TextUtils::capitalize(&module_name.replace('_', " "))
+            ),
+            "slack" => r#"    /// Build the Slack representation of the notification
+    pub fn via_slack(&self) -> SlackMessage {
+        SlackMessage {
+            text: self.message.clone(),
+        }
+    }
+"#
+            .to_string(),
+            channel => format!(
+                r#"    /// Build the {channel} representation of the notification
+    pub fn via_{channel}(&self) -> serde_json::Value {{
+        serde_json::json!({{
+            "type": "{module_name}",
+            "message": self.message,
+        }})
+    }}
+"#,
+                channel = channel,
+                module_name = module_name
+            ),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let assertions = channels
+        .iter()
+        .map(|channel| match channel.as_str() {
+            "mail" => "        let mail = notification.via_mail();\n        assert!(!mail.subject.is_empty());\n        assert!(!mail.body.is_empty());\n".to_string(),
+            "slack" => "        assert!(!notification.via_slack().text.is_empty());\n".to_string(),
+            channel => format!("        assert!(!notification.via_{}().is_null());\n", channel),
+        })
+        .collect::<String>();
+
+    let content = format!(
+        r#"//! {class_name} Notification
+
+use serde::{{Deserialize, Serialize}};
+
+#[allow(unused_imports)]
+use super::{{MailMessage, Notifiable, SlackMessage}};
+
+/// {class_name} notification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct {class_name}Notification {{
+    pub message: String,
+}}
+
+impl {class_name}Notification {{
+    pub fn new(message: impl Into<String>) -> Self {{
+        Self {{
+            message: message.into(),
+        }}
+    }}
+
+{methods}}}
+
+impl Notifiable for {class_name}Notification {{
+    fn via(&self) -> Vec<&'static str> {{
+        vec![{via_list}]
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    #[test]
+    fn test_channel_payloads() {{
+        let notification = {class_name}Notification::new("Test notification");
+
+        assert_eq!(notification.via(), vec![{via_list}]);
+{assertions}    }}
+}}
+"#,
+        class_name = class_name,
+        methods = methods,
+        via_list = via_list,
+        assertions = assertions,
+    );
+
+    let mod_file = app_path("src/notifications/mod.rs");
+    if !std::path::Path::new(&mod_file).exists() {
+        FileUtils::atomic_write(&mod_file, NOTIFICATIONS_MODULE)?;
+    }
+
+    let file_path = write_component(&app_path("src/notifications"), &module_name, &content, options)?;
+    CommandUtils::success(&format!("Notification created: {}", file_path));
+
+    CommandUtils::success(&format!(
+        "Notification {} created successfully! Channels: {}",
+        name.cyan().bold(),
+        channels.join(", ")
+    ));
+
+    Ok(())
+}
+
+/// Shared notification types written to `src/notifications/mod.rs`
+const NOTIFICATIONS_MODULE: &str = r#"//! Notifications module
+
+use serde::{Deserialize, Serialize};
+
+/// A notification that can be delivered on one or more channels
+pub trait Notifiable {
+    /// Channels this notification is delivered on
+    fn via(&self) -> Vec<&'static str>;
+}
+
+/// Payload for the mail channel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MailMessage {
+    pub subject: String,
+    pub body: String,
+}
+
+/// Payload for the Slack channel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlackMessage {
+    pub text: String,
+}
+"#;
+
+/// Generate a custom error type
+async fn make_exception(name: String, kind: Option<String>, options: &GeneratorOptions) -> Result<()> {
+    CommandUtils::ensure_rustisan_project()?;
+
+    CommandUtils::info(&format!("Creating exception {}...", name.cyan().bold()));
+
+    let kind = kind.unwrap_or_else(|| "custom".to_string()).to_lowercase().replace('-', "_");
+    let base_name = strip_suffix(&strip_suffix(&name, "Exception"), "Error");
+    let class_name = CommandUtils::to_pascal_case(&base_name);
+    let module_name = CommandUtils::to_snake_case(&base_name);
+
+    // (declaration, status code match arm, test construction, expected display)
+    let not_found = (
+        "    #[error(\"{0} not found\")]\n    NotFound(String),",
+        "Self::NotFound(_) => 404,",
+        "Error::NotFound(\"User\".to_string())",
+        "User not found",
+    );
+    let unauthorized = (
+        "    #[error(\"Unauthenticated\")]\n    Unauthorized,",
+        "Self::Unauthorized => 401,",
+        "Error::Unauthorized",
+        "Unauthenticated",
+    );
+    let forbidden = (
+        "    #[error(\"This action is unauthorized\")]\n    Forbidden,",
+        "Self::Forbidden => 403,",
+        "Error::Forbidden",
+        "This action is unauthorized",
+    );
+    let token_expired = (
+        "    #[error(\"Authentication token has expired\")]\n    TokenExpired,",
+        "Self::TokenExpired => 401,",
+        "Error::TokenExpired",
+        "Authentication token has expired",
+    );
+    let validation_failed = (
+        "    #[error(\"Validation failed: {}\", .0.join(\", \"))]\n    ValidationFailed(Vec<String>),",
+        "Self::ValidationFailed(_) => 422,",
+        "Error::ValidationFailed(vec![\"name is required\".to_string(), \"email is invalid\".to_string()])",
+        "Validation failed: name is required, email is invalid",
+    );
+    let invalid_field = (
+        "    #[error(\"Invalid value for '{field}': {message}\")]\n    InvalidField { field: String, message: String },",
+        "Self::InvalidField { .. } => 422,",
+        "Error::InvalidField { field: \"email\".to_string(), message: \"must be a valid address\".to_string() }",
+        "Invalid value for 'email': must be a valid address",
+    );
+    let internal = (
+        "    #[error(\"Internal error: {0}\")]\n    Internal(String),",
+        "Self::Internal(_) => 500,",
+        "Error::Internal(\"database unavailable\".to_string())",
+        "Internal error: database unavailable",
+    );
+
+    let variants = match kind.as_str() {
+        "validation" => vec![validation_failed, invalid_field],
+        "auth" => vec![unauthorized, forbidden, token_expired],
+        "not_found" => vec![not_found],
+        "custom" => vec![not_found, unauthorized, forbidden, validation_failed, internal],
+        _ => anyhow::bail!("Unknown exception kind: {} (expected validation, auth, not_found or custom)", kind),
+    };
+
+    let declarations = variants.iter().map(|v| v.0).collect::<Vec<_>>().join("\n\n");
+    let status_arms: String = variants
+        .iter()
+        .map(|v| format!("            {}\n", v.1))
+        .collect();
+    let display_tests: String = variants
+        .iter()
+        .map(|v| {
+            format!(
+                "        assert_eq!({}{}.to_string(), \"{}\");\n",
+                class_name,
+                v.2,
+                v.3
+            )
+        })
+        .collect();
+    let first_variant = format!("{}{}", class_name, variants[0].2);
+
+    let content = format!(
+        r#"//! {class_name} errors
+//!
+//! Requires the following dependencies in Cargo.toml:
+//!
+//! ```toml
+//! thiserror = "1.0"
+//! anyhow = "1.0"
+//! ```
+
+use thiserror::Error;
+
+/// {class_name} errors
+#[derive(Debug, Error)]
+pub enum {class_name}Error {{
+{declarations}
+}}
+
+impl {class_name}Error {{
+    /// HTTP status code to respond with for this error
+    pub fn status_code(&self) -> u16 {{
+        match self {{
+{status_arms}        }}
+    }}
+}}
+
+// `{class_name}Error` converts into `anyhow::Error` through anyhow's blanket
+// `impl<E: std::error::Error + Send + Sync + 'static> From<E>`, so `?` works in
+// functions returning `anyhow::Result`.
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    #[test]
+    fn test_display() {{
+{display_tests}    }}
+
+    #[test]
+    fn test_into_anyhow() {{
+        let error = anyhow::Error::from({first_variant});
+        assert!(error.downcast_ref::<{class_name}Error>().is_some());
+    }}
+}}
+"#,
+        class_name = class_name,
+        declarations = declarations,
+        status_arms = status_arms,
+        display_tests = display_tests,
+        first_variant = first_variant,
+    );
+
+    let file_path = write_component(&app_path("src/exceptions"), &module_name, &content, options)?;
+    CommandUtils::success(&format!("Exception created: {}", file_path));
+
+    CommandUtils::success(&format!("Exception {} created successfully!", name.cyan().bold()));
+
+    Ok(())
+}
+
+/// Generate an enum
+async fn make_enum(name: String, variants: Vec<String>, serde: bool, display: bool, options: &GeneratorOptions) -> Result<()> {
+    CommandUtils::ensure_rustisan_project()?;
+
+    CommandUtils::info(&format!("Creating enum {}...", name.cyan().bold()));
+
+    let variants = parse_enum_variants(&variants)?;
+    let content = enum_source(&name, &variants, serde, display)?;
+
+    let module_name = CommandUtils::to_snake_case(&name);
+    let file_path = write_component(&app_path("src/enums"), &module_name, &content, options)?;
+    CommandUtils::success(&format!("Enum created: {}", file_path));
+
+    Ok(())
+}
+
+/// A variant of a generated enum
+#[derive(Debug, PartialEq)]
+struct EnumVariant {
+    name: String,
+    /// Explicit discriminant, e.g. `3` from `Pending:3`
+    discriminant: Option<String>,
+    /// Integer representation, e.g. `u8` from `Pending:u8`
+    repr: Option<String>,
+}
+
+impl EnumVariant {
+    /// String form used by `as_ref`, `from_str` and serde
+    fn key(&self) -> String {
+        CommandUtils::to_snake_case(&self.name)
+    }
+
+    /// Human-readable label used by `Display`
+    fn label(&self) -> String {
+        self.key().split('_').map(TextUtils::capitalize).collect::<Vec<_>>().join(" ")
+    }
+}
+
+/// Parse `--variants` given as "Active,Inactive,Pending:u8" or "Low:1,High:10"
+fn parse_enum_variants(specs: &[String]) -> Result<Vec<EnumVariant>> {
+    let variants: Vec<EnumVariant> = specs
+        .iter()
+        .map(|spec| spec.trim())
+        .filter(|spec| !spec.is_empty())
+        .map(|spec| {
+            let (name, suffix) = spec.split_once(':').map(|(name, suffix)| (name, Some(suffix.trim()))).unwrap_or((spec, None));
+            let name = CommandUtils::to_pascal_case(name.trim());
+            let (discriminant, repr) = match suffix {
+                None => (None, None),
+                Some(value) if value.parse::<i128>().is_ok() => (Some(value.to_string()), None),
+                Some(value) if INTEGER_TYPES.contains(&value) => (None, Some(value.to_string())),
+                Some(value) => anyhow::bail!("Invalid discriminant '{}' for variant '{}' (expected a number or an integer type)", value, name),
+            };
+            Ok(EnumVariant { name, discriminant, repr })
+        })
+        .collect::<Result<_>>()?;
+
+    if variants.is_empty() {
+        anyhow::bail!("--variants needs at least one variant, e.g. \"Active,Inactive\"");
+    }
+
+    Ok(variants)
+}
+
+/// Source of an enum with string conversions and round-trip tests
+fn enum_source(name: &str, variants: &[EnumVariant], serde: bool, display: bool) -> Result<String> {
+    let class_name = CommandUtils::to_pascal_case(name);
+
+    let mut reprs: Vec<&str> = variants.iter().filter_map(|variant| variant.repr.as_deref()).collect();
+    reprs.dedup();
+    let repr = match reprs.as_slice() {
+        [] => String::new(),
+        [repr] => format!("#[repr({})]\n", repr),
+        _ => anyhow::bail!("Variants of {} use different integer types: {}", class_name, reprs.join(", ")),
+    };
+
+    let (serde_derive, serde_attribute) = if serde {
+        (", serde::Serialize, serde::Deserialize", "#[serde(rename_all = \"snake_case\")]\n")
+    } else {
+        ("", "")
+    };
+
+    let declarations: Vec<String> = variants
+        .iter()
+        .map(|variant| match &variant.discriminant {
+            Some(value) => format!("    {} = {},", variant.name, value),
+            None => format!("    {},", variant.name),
+        })
+        .collect();
+    let keys: Vec<String> = variants
+        .iter()
+        .map(|variant| format!("            Self::{} => \"{}\",", variant.name, variant.key()))
+        .collect();
+    let parse_arms: Vec<String> = variants
+        .iter()
+        .map(|variant| format!("            \"{}\" => Some(Self::{}),", variant.key(), variant.name))
+        .collect();
+    let all: Vec<String> = variants.iter().map(|variant| format!("{}::{}", class_name, variant.name)).collect();
+
+    let display_impl = if display {
+        let labels: Vec<String> = variants
+            .iter()
+            .map(|variant| format!("            Self::{} => \"{}\",", variant.name, variant.label()))
+            .collect();
+        format!(
+            r#"
+impl std::fmt::Display for {class_name} {{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{
+        let label = match self {{
+{labels}
+        }};
+        f.write_str(label)
+    }}
+}}
+"#,
+            labels = labels.join("\n"),
+        )
+    } else {
+        String::new()
+    };
+
+    Ok(format!(
+        r#"//! {class_name} enum
+
+#[derive(Debug, Clone, PartialEq{serde_derive})]
+{serde_attribute}{repr}pub enum {class_name} {{
+{declarations}
+}}
+
+impl {class_name} {{
+    /// Parse the snake_case name of a variant
+    pub fn from_str(s: &str) -> Option<Self> {{
+        match s {{
+{parse_arms}
+            _ => None,
+        }}
+    }}
+}}
+
+impl TryFrom<&str> for {class_name} {{
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {{
+        Self::from_str(value).ok_or_else(|| format!("Unknown {label} '{{}}'", value))
+    }}
+}}
+
+impl AsRef<str> for {class_name} {{
+    fn as_ref(&self) -> &str {{
+        match self {{
+{keys}
+        }}
+    }}
+}}
+{display_impl}
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    #[test]
+    fn test_from_str_round_trips() {{
+        for variant in [{all}] {{
+            assert_eq!({class_name}::from_str(variant.as_ref()), Some(variant.clone()));
+            assert_eq!({class_name}::try_from(variant.as_ref()), Ok(variant));
+        }}
+        assert_eq!({class_name}::from_str("unknown"), None);
+        assert!({class_name}::try_from("unknown").is_err());
+    }}
+}}
+"#,
+        label = CommandUtils::to_snake_case(name).replace('_', " "),
+        declarations = declarations.join("\n"),
+        parse_arms = parse_arms.join("\n"),
+        keys = keys.join("\n"),
+        all = all.join(", "),
+    ))
+}
+
+/// Generate a query scope
+async fn make_scope(name: String, model: Option<String>, options: &GeneratorOptions) -> Result<()> {
+    CommandUtils::ensure_rustisan_project()?;
+
+    CommandUtils::info(&format!("Creating scope {}...", name.cyan().bold()));
+
+    let base_name = strip_suffix(&name, "Scope");
+    let content = scope_source(&base_name, model.as_deref());
+
+    let scopes_dir = app_path("src/scopes");
+    let module_name = CommandUtils::to_snake_case(&base_name);
+    let file_path = write_component(&scopes_dir, &module_name, &content, options)?;
+
+    // Every scope of a model implements the same query, so only the first one creates it
+    if let Some(model) = model {
+        let query_module = format!("{}_query", CommandUtils::to_snake_case(&model));
+        if !std::path::Path::new(&scopes_dir).join(format!("{}.rs", query_module)).exists() {
+            let query_path = write_component(&scopes_dir, &query_module, &model_query_source(&model), options)?;
+            CommandUtils::info(&format!("Created model query {}", query_path));
+        }
+    }
+
+    CommandUtils::success(&format!("Scope created: {}", file_path));
+
+    Ok(())
+}
+
+/// Source of the query over a model that its scopes build
+fn model_query_source(model: &str) -> String {
+    let model = CommandUtils::to_pascal_case(model);
+    let label = TextUtils::pluralize(&CommandUtils::to_snake_case(&model)).replace('_', " ");
+
+    format!(
+        r#"//! Query over {label}, built by the {model} scopes
+
+use serde_json::Value;
+
+/// `WHERE` and `ORDER BY` clauses with the values bound to their `?` placeholders
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct {model}Query {{
+    pub sql: String,
+    pub bindings: Vec<Value>,
+}}
+"#
+    )
+}
+
+/// Source of a scope trait, its chainable query builder and optionally its model query
+fn scope_source(name: &str, model: Option<&str>) -> String {
+    let class_name = CommandUtils::to_pascal_case(name);
+
+    let (model_import, model_query) = model
+        .map(|model| {
+            let module = CommandUtils::to_snake_case(model);
+            let model = CommandUtils::to_pascal_case(model);
+            let label = TextUtils::pluralize(&module).replace('_', " ");
+            (
+                format!("\nuse super::{module}_query::{model}Query;"),
+                format!(
+                    r#"
+impl From<{class_name}QueryBuilder> for {model}Query {{
+    fn from(builder: {class_name}QueryBuilder) -> Self {{
+        let (sql, bindings) = builder.to_sql();
+        Self {{ sql, bindings }}
+    }}
+}}
+
+impl {class_name}Scope for {model}Query {{
+    fn active() -> Self {{
+        // Narrow the condition to active {label} here
+        {class_name}QueryBuilder::active().into()
+    }}
+
+    fn created_after(date: DateTime<Utc>) -> Self {{
+        {class_name}QueryBuilder::created_after(date).into()
+    }}
+
+    fn order_by(column: &str) -> Result<Self> {{
+        {class_name}QueryBuilder::order_by(column).map(Self::from)
+    }}
+}}
+"#
+                ),
+            )
+        })
+        .unwrap_or_default();
+
+    format!(
+        r#"//! {class_name} query scope
+//!
+//! Values are bound to `?` placeholders and column names are checked against
+//! `COLUMNS`, so user input never ends up in the SQL itself.
+
+use anyhow::Result;
+use chrono::{{DateTime, Utc}};
+use serde_json::Value;
+{model_import}
+/// Columns conditions and ordering may name
+pub const COLUMNS: &[&str] = &["id", "active", "created_at", "updated_at"];
+
+/// Operators conditions may compare with
+pub const OPERATORS: &[&str] = &["=", "!=", "<", "<=", ">", ">="];
+
+/// Reusable query conditions
+pub trait {class_name}Scope: Sized {{
+    /// Only active records
+    fn active() -> Self;
+
+    /// Records created after `date`
+    fn created_after(date: DateTime<Utc>) -> Self;
+
+    /// Records ordered by `column`, one of `COLUMNS`
+    fn order_by(column: &str) -> Result<Self>;
+}}
+
+/// Conditions joined with AND, built by chaining
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct {class_name}QueryBuilder {{
+    pub conditions: Vec<String>,
+    pub bindings: Vec<Value>,
+    pub order: Option<String>,
+}}
+
+impl {class_name}QueryBuilder {{
+    pub fn new() -> Self {{
+        Self::default()
+    }}
+
+    /// Add a `column operator ?` condition bound to `value`
+    pub fn filter(self, column: &str, operator: &str, value: impl Into<Value>) -> Result<Self> {{
+        anyhow::ensure!(OPERATORS.contains(&operator), "Unsupported operator '{{}}'", operator);
+        Ok(self.condition(checked_column(column)?, operator, value.into()))
+    }}
+
+    /// Order the results by `column`
+    pub fn ordered_by(mut self, column: &str) -> Result<Self> {{
+        self.order = Some(checked_column(column)?.to_string());
+        Ok(self)
+    }}
+
+    /// The `WHERE` and `ORDER BY` clauses of the query and the values to bind
+    pub fn to_sql(&self) -> (String, Vec<Value>) {{
+        let mut sql = Vec::new();
+        if !self.conditions.is_empty() {{
+            sql.push(format!("WHERE {{}}", self.conditions.join(" AND ")));
+        }}
+        if let Some(order) = &self.order {{
+            sql.push(format!("ORDER BY {{}}", order));
+        }}
+        (sql.join(" "), self.bindings.clone())
+    }}
+
+    fn condition(mut self, column: &str, operator: &str, value: Value) -> Self {{
+        self.conditions.push(format!("{{}} {{}} ?", column, operator));
+        self.bindings.push(value);
+        self
+    }}
+}}
+
+/// The allowed column named `column`
+fn checked_column(column: &str) -> Result<&'static str> {{
+    COLUMNS
+        .iter()
+        .copied()
+        .find(|allowed| *allowed == column)
+        .ok_or_else(|| anyhow::anyhow!("Unknown column '{{}}'", column))
+}}
+
+impl {class_name}Scope for {class_name}QueryBuilder {{
+    fn active() -> Self {{
+        Self::new().condition("active", "=", Value::from(true))
+    }}
+
+    fn created_after(date: DateTime<Utc>) -> Self {{
+        Self::new().condition("created_at", ">", Value::from(date.to_rfc3339()))
+    }}
+
+    fn order_by(column: &str) -> Result<Self> {{
+        Self::new().ordered_by(column)
+    }}
+}}
+{model_query}
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    #[test]
+    fn test_chained_conditions() {{
+        let query = {class_name}QueryBuilder::active()
+            .filter("id", ">", 10)
+            .unwrap()
+            .ordered_by("created_at")
+            .unwrap();
+
+        let (sql, bindings) = query.to_sql();
+        assert_eq!(sql, "WHERE active = ? AND id > ? ORDER BY created_at");
+        assert_eq!(bindings, [Value::from(true), Value::from(10)]);
+    }}
+
+    #[test]
+    fn test_created_after() {{
+        let date = DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z").unwrap().with_timezone(&Utc);
+        let query = {class_name}QueryBuilder::created_after(date);
+
+        assert_eq!(query.conditions, ["created_at > ?"]);
+        assert_eq!(query.bindings, [Value::from("2024-01-01T00:00:00+00:00")]);
+    }}
+
+    #[test]
+    fn test_rejects_unknown_columns_and_operators() {{
+        assert!({class_name}QueryBuilder::order_by("id; DROP TABLE users").is_err());
+        assert!({class_name}QueryBuilder::new().filter("1 = 1 OR id", "=", 1).is_err());
+        assert!({class_name}QueryBuilder::new().filter("id", "LIKE", 1).is_err());
+    }}
+}}
+"#
+    )
+}
+
+/// Generate a service provider
+async fn make_provider(name: String, deferred: bool, options: &GeneratorOptions) -> Result<()> {
+    CommandUtils::ensure_rustisan_project()?;
+
+    CommandUtils::info(&format!("Creating provider {}...", name.cyan().bold()));
+
+    let base_name = strip_suffix(&name, "Provider");
+    let module_name = format!("{}_provider", CommandUtils::to_snake_case(&base_name));
+
+    let mod_file = app_path("src/providers/mod.rs");
+    if !std::path::Path::new(&mod_file).exists() {
+        FileUtils::atomic_write(&mod_file, PROVIDERS_MODULE)?;
+    }
+
+    let content = provider_source(&base_name, deferred);
+    let file_path = write_component(&app_path("src/providers"), &module_name, &content, options)?;
+    CommandUtils::success(&format!("Provider created: {}", file_path));
+
+    Ok(())
+}
+
+/// Source of a service provider, optionally deferred until one of its services is needed
+fn provider_source(name: &str, deferred: bool) -> String {
+    let class_name = CommandUtils::to_pascal_case(name);
+    let key = CommandUtils::to_snake_case(name);
+
+    let (provides, provides_test) = if deferred {
+        (
+            format!(
+                r#"
+
+    /// Services bound in `register`, which runs once one of them is needed
+    fn provides(&self) -> Vec<&'static str> {{
+        vec!["{key}"]
+    }}"#
+            ),
+            format!(
+                r#"
+
+    #[test]
+    fn test_is_deferred() {{
+        assert!({class_name}Provider.is_deferred());
+        assert_eq!({class_name}Provider.provides(), ["{key}"]);
+    }}"#
+            ),
+        )
+    } else {
+        (String::new(), String::new())
+    };
+
+    format!(
+        r#"//! {class_name} service provider
+//!
+//! Register it when the application starts in `src/main.rs`:
+//!
+//! ```ignore
+//! let mut container = Container::new();
+//! let provider = providers::{key}_provider::{class_name}Provider;
+//! provider.register(&mut container);
+//! provider.boot(&container);
+//! ```
+
+use super::{{Container, ServiceProvider}};
+
+pub struct {class_name}Provider;
+
+impl ServiceProvider for {class_name}Provider {{
+    fn register(&self, container: &mut Container) {{
+        // Bind services here, e.g. container.bind("{key}", {class_name}Service::new);
+        let _ = container;
+    }}
+
+    fn boot(&self, container: &Container) {{
+        // Use services registered by any provider here
+        let _ = container;
+    }}{provides}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    #[test]
+    fn test_register_and_boot() {{
+        let mut container = Container::new();
+        {class_name}Provider.register(&mut container);
+        {class_name}Provider.boot(&container);
+    }}{provides_test}
+}}
+"#
+    )
+}
+
+/// Service container and provider trait written to `src/providers/mod.rs`
+const PROVIDERS_MODULE: &str = r#"//! Service providers
+//!
+//! Providers bind services into a [`Container`] in `register`, then use
+//! services bound by other providers in `boot`.
+
+use std::any::Any;
+use std::collections::HashMap;
+
+/// Factories producing services by key
+#[derive(Default)]
+pub struct Container {
+    bindings: HashMap<String, Box<dyn Fn() -> Box<dyn Any>>>,
+}
+
+impl Container {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bind a factory producing the service for `key`
+    pub fn bind<T: 'static>(&mut self, key: &str, factory: impl Fn() -> T + 'static) {
+        self.bindings.insert(key.to_string(), Box::new(move || Box::new(factory()) as Box<dyn Any>));
+    }
+
+    /// Build the service bound to `key`, if it is bound to a `T`
+    pub fn make<T: 'static>(&self, key: &str) -> Option<T> {
+        let service = self.bindings.get(key)?();
+        service.downcast::<T>().ok().map(|service| *service)
+    }
+
+    /// Whether a service is bound to `key`
+    pub fn has(&self, key: &str) -> bool {
+        self.bindings.contains_key(key)
+    }
+}
+
+/// Registers services into the container when the application starts
+pub trait ServiceProvider {
+    /// Bind services into the container
+    fn register(&self, container: &mut Container);
+
+    /// Run once every provider is registered
+    fn boot(&self, container: &Container);
+
+    /// Services of a deferred provider, registered only when first needed
+    fn provides(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+
+    fn is_deferred(&self) -> bool {
+        !self.provides().is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bind_and_make() {
+        let mut container = Container::new();
+        container.bind("greeting", || "hello".to_string());
+
+        assert!(container.has("greeting"));
+        assert_eq!(container.make::<String>("greeting").as_deref(), Some("hello"));
+        assert_eq!(container.make::<u32>("greeting"), None);
+        assert_eq!(container.make::<String>("missing"), None);
+    }
+}
+"#;
+
+/// Cast types accepted by `make cast --cast-type`
+pub const CAST_TYPES: &[&str] = &["json", "encrypted", "comma-separated", "datetime"];
+
+/// Generate a model attribute cast
+async fn make_cast(name: String, cast_type: String, options: &GeneratorOptions) -> Result<()> {
+    CommandUtils::ensure_rustisan_project()?;
+
+    CommandUtils::info(&format!("Creating {} cast {}...", cast_type, name.cyan().bold()));
+
+    let base_name = strip_suffix(&name, "Cast");
+    let content = cast_source(&base_name, &cast_type)?;
+
+    let mod_file = app_path("src/casts/mod.rs");
+    if !std::path::Path::new(&mod_file).exists() {
+        FileUtils::atomic_write(&mod_file, CASTS_MODULE)?;
+    }
+
+    let module_name = CommandUtils::to_snake_case(&base_name);
+    let file_path = write_component(&app_path("src/casts"), &module_name, &content, options)?;
+    CommandUtils::success(&format!("Cast created: {}", file_path));
+
+    Ok(())
+}
+
+/// Source of a cast converting between a stored string and a JSON value
+fn cast_source(name: &str, cast_type: &str) -> Result<String> {
+    let class_name = CommandUtils::to_pascal_case(name);
+
+    // (extra dependencies, imports, get body, set body, helpers, tests)
+    let (dependencies, imports, get, set, helpers, tests) = match cast_type {
+        "json" => (
+            "",
+            "",
+            "        Ok(serde_json::from_str(value)?)",
+            "        Ok(value.to_string())",
+            String::new(),
+            format!(
+                r##"    #[test]
+    fn test_set() {{
+        assert_eq!({class_name}Cast::set(json!({{ "theme": "dark" }})).unwrap(), r#"{{"theme":"dark"}}"#);
+    }}
+
+    #[test]
+    fn test_get() {{
+        assert_eq!({class_name}Cast::get(r#"{{"theme":"dark"}}"#).unwrap(), json!({{ "theme": "dark" }}));
+        assert!({class_name}Cast::get("not json").is_err());
+    }}"##
+            ),
+        ),
+        "encrypted" => (
+            "//! aes-gcm = \"0.10\"\n//! base64 = \"0.22\"\n//! toml = \"0.8\"\n",
+            "use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};\nuse aes_gcm::{Aes256Gcm, Key, Nonce};\nuse base64::Engine as _;\nuse base64::engine::general_purpose::STANDARD;\n",
+            "        decrypt(&app_key()?, value)",
+            "        encrypt(&app_key()?, &value)",
+            r#"
+/// Length of the nonce stored in front of the ciphertext
+const NONCE_LEN: usize = 12;
+
+/// The `base64:` application key from `APP__KEY` or `app.key` in rustisan.toml
+fn app_key() -> Result<Vec<u8>> {
+    let key = match std::env::var("APP__KEY") {
+        Ok(key) => key,
+        Err(_) => {
+            let config: toml::Value = std::fs::read_to_string("rustisan.toml")?.parse()?;
+            config
+                .get("app")
+                .and_then(|app| app.get("key"))
+                .and_then(|key| key.as_str())
+                .ok_or_else(|| anyhow::anyhow!("No application key, set APP__KEY or run `rustisan config:generate-key`"))?
+                .to_string()
+        }
+    };
+    let key = key.strip_prefix("base64:").unwrap_or(&key);
+    let key = STANDARD.decode(key)?;
+    anyhow::ensure!(key.len() == 32, "The application key must be 32 bytes, run `rustisan config:generate-key`");
+    Ok(key)
+}
+
+/// Encrypt the JSON form of a value as base64 of the nonce followed by the ciphertext
+fn encrypt(key: &[u8], value: &Value) -> Result<String> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, value.to_string().as_bytes())
+        .map_err(|_| anyhow::anyhow!("Failed to encrypt value"))?;
+
+    let mut payload = nonce.to_vec();
+    payload.extend(ciphertext);
+    Ok(STANDARD.encode(payload))
+}
+
+fn decrypt(key: &[u8], value: &str) -> Result<Value> {
+    let payload = STANDARD.decode(value)?;
+    anyhow::ensure!(payload.len() > NONCE_LEN, "Encrypted value is too short");
+
+    let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| anyhow::anyhow!("Failed to decrypt value, was it encrypted with another key?"))?;
+
+    Ok(serde_json::from_slice(&plaintext)?)
+}
+"#
+            .to_string(),
+            r#"    const KEY: [u8; 32] = [7; 32];
+
+    #[test]
+    fn test_encrypt_round_trips() {
+        let encrypted = encrypt(&KEY, &json!("4242 4242 4242 4242")).unwrap();
+
+        assert!(!encrypted.contains("4242"));
+        assert_eq!(decrypt(&KEY, &encrypted).unwrap(), json!("4242 4242 4242 4242"));
+    }
+
+    #[test]
+    fn test_decrypt_with_another_key_fails() {
+        let encrypted = encrypt(&KEY, &json!({ "secret": true })).unwrap();
+
+        assert!(decrypt(&[8; 32], &encrypted).is_err());
+        assert!(decrypt(&KEY, "c2hvcnQ=").is_err());
+    }"#
+            .to_string(),
+        ),
+        "comma-separated" => (
+            "",
+            "",
+            r#"        let items = value
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(|item| Value::String(item.to_string()))
+            .collect();
+        Ok(Value::Array(items))"#,
+            r#"        let Value::Array(items) = value else {
+            anyhow::bail!("Expected an array, got {}", value);
+        };
+        let items: Vec<String> = items
+            .into_iter()
+            .map(|item| match item {
+                Value::String(item) => item,
+                item => item.to_string(),
+            })
+            .collect();
+        Ok(items.join(","))"#,
+            String::new(),
+            format!(
+                r#"    #[test]
+    fn test_set() {{
+        assert_eq!({class_name}Cast::set(json!(["rust", "web", 3])).unwrap(), "rust,web,3");
+        assert!({class_name}Cast::set(json!("rust")).is_err());
+    }}
+
+    #[test]
+    fn test_get() {{
+        assert_eq!({class_name}Cast::get("rust, web").unwrap(), json!(["rust", "web"]));
+        assert_eq!({class_name}Cast::get("").unwrap(), json!([]));
+    }}"#
+            ),
+        ),
+        "datetime" => (
+            "//! chrono = \"0.4\"\n",
+            "use chrono::{DateTime, NaiveDateTime, Utc};\n",
+            r#"        let date = NaiveDateTime::parse_from_str(value, STORAGE_FORMAT)?.and_utc();
+        Ok(Value::String(date.to_rfc3339()))"#,
+            r#"        let value = value.as_str().ok_or_else(|| anyhow::anyhow!("Expected an RFC 3339 string, got {}", value))?;
+        let date = DateTime::parse_from_rfc3339(value)?.with_timezone(&Utc);
+        Ok(date.format(STORAGE_FORMAT).to_string())"#,
+            "\n/// Format of the stored UTC timestamp\nconst STORAGE_FORMAT: &str = \"%Y-%m-%d %H:%M:%S\";\n".to_string(),
+            format!(
+                r#"    #[test]
+    fn test_set() {{
+        assert_eq!({class_name}Cast::set(json!("2024-05-01T12:30:00+02:00")).unwrap(), "2024-05-01 10:30:00");
+        assert!({class_name}Cast::set(json!("yesterday")).is_err());
+    }}
+
+    #[test]
+    fn test_get() {{
+        assert_eq!({class_name}Cast::get("2024-05-01 10:30:00").unwrap(), json!("2024-05-01T10:30:00+00:00"));
+    }}"#
+            ),
+        ),
+        other => anyhow::bail!("Unknown cast type '{}' (expected {})", other, CAST_TYPES.join(", ")),
+    };
+
+    Ok(format!(
+        r#"//! {class_name} cast ({cast_type})
+//!
+//! Requires the following dependencies in Cargo.toml:
+//!
+//! ```toml
+//! anyhow = "1.0"
+//! serde_json = "1.0"
+{dependencies}//! ```
+
+use anyhow::Result;
+{imports}use serde_json::Value;
+
+use super::CastAttribute;
+
+pub struct {class_name}Cast;
+
+impl CastAttribute for {class_name}Cast {{
+    fn get(value: &str) -> Result<Value> {{
+{get}
+    }}
+
+    fn set(value: Value) -> Result<String> {{
+{set}
+    }}
+}}
+{helpers}
+#[cfg(test)]
+mod tests {{
+    use super::*;
+    use serde_json::json;
+
+{tests}
+}}
+"#
+    ))
+}
+
+/// Cast trait written to `src/casts/mod.rs`
+const CASTS_MODULE: &str = r#"//! Attribute casts
+//!
+//! A cast converts a model attribute between the string stored in the
+//! database and the JSON value used by the application.
+
+/// Conversion between a stored attribute and its value
+pub trait CastAttribute {
+    /// Convert the stored string into a value
+    fn get(value: &str) -> anyhow::Result<serde_json::Value>;
+
+    /// Convert a value into the string to store
+    fn set(value: serde_json::Value) -> anyhow::Result<String>;
+}
+"#;
+
+/// Generate a single-purpose action
+async fn make_action(name: String, model: Option<String>, options: &GeneratorOptions) -> Result<()> {
+    CommandUtils::ensure_rustisan_project()?;
+
+    CommandUtils::info(&format!("Creating action {}...", name.cyan().bold()));
+
+    let base_name = strip_suffix(&name, "Action");
+    let content = action_source(&base_name, &model);
+
+    let module_name = CommandUtils::to_snake_case(&base_name);
+    let file_path = write_component(&app_path("src/actions"), &module_name, &content, options)?;
+    CommandUtils::success(&format!("Action created: {}", file_path));
+
+    Ok(())
+}
+
+/// Source of an action with its input and output types
+fn action_source(name: &str, model: &Option<String>) -> String {
+    let class_name = CommandUtils::to_pascal_case(name);
+    let label = CommandUtils::to_snake_case(name).replace('_', " ");
+
+    let (model_import, input_fields, output_fields, body, test_input) = match model {
+        Some(_) => {
+            let (model_class, import) = model_type(model);
+            (
+                format!("\n{}", import),
+                format!("    /// Id of the {} to act on\n    pub id: u64,", CommandUtils::to_snake_case(&model_class).replace('_', " ")),
+                format!("    pub model: {},", model_class),
+                format!(
+                    "        // Load the {} with `input.id` and act on it here\n        let _ = input.id;\n\n        Ok({}Output {{\n            model: {}::default(),\n        }})",
+                    CommandUtils::to_snake_case(&model_class).replace('_', " "),
+                    class_name,
+                    model_class
+                ),
+                format!("{}Input {{ id: 1 }}", class_name),
+            )
+        }
+        None => (
+            String::new(),
+            "    // Add input fields here".to_string(),
+            "    // Add output fields here".to_string(),
+            format!("        // {} here\n        let _ = input;\n\n        Ok({}Output {{}})", TextUtils::capitalize(&label), class_name),
+            format!("{}Input::default()", class_name),
+        ),
+    };
+
+    format!(
+        r#"//! {class_name} action
+
+use anyhow::Result;
+{model_import}
+/// Input of [`{class_name}Action`]
+#[derive(Debug, Clone, Default)]
+pub struct {class_name}Input {{
+{input_fields}
+}}
+
+/// Output of [`{class_name}Action`]
+#[derive(Debug, Clone)]
+pub struct {class_name}Output {{
+{output_fields}
+}}
+
+/// Performs a single task: {label}
+#[derive(Debug, Default)]
+pub struct {class_name}Action {{
+    // Add injected dependencies here, e.g. a repository
+}}
+
+impl {class_name}Action {{
+    pub fn new() -> Self {{
+        Self::default()
+    }}
+
+    pub async fn execute(&self, input: {class_name}Input) -> Result<{class_name}Output> {{
+{body}
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    #[tokio::test]
+    async fn test_execute() {{
+        let action = {class_name}Action::new();
+
+        assert!(action.execute({test_input}).await.is_ok());
+    }}
+}}
+"#
+    )
+}
+
+/// Generate a mailable
+async fn make_mail(name: String, markdown: bool, options: &GeneratorOptions) -> Result<()> {
+    CommandUtils::ensure_rustisan_project()?;
+
+    CommandUtils::info(&format!("Creating mail {}...", name.cyan().bold()));
+
+    let base_name = strip_suffix(&name, "Mail");
+    let module_name = CommandUtils::to_snake_case(&base_name);
+
+    let mod_file = app_path("src/mail/mod.rs");
+    if !std::path::Path::new(&mod_file).exists() {
+        FileUtils::atomic_write(&mod_file, MAIL_MODULE)?;
+    }
+
+    let content = mail_source(&base_name, markdown);
+    let file_path = write_component(&app_path("src/mail"), &module_name, &content, options)?;
+    CommandUtils::success(&format!("Mail created: {}", file_path));
+
+    if markdown {
+        let template_path = app_path(&format!("resources/views/mail/{}.md", module_name));
+        CommandUtils::ensure_directory(std::path::Path::new(&template_path).parent().unwrap())?;
+        write_generated(&template_path, &mail_template(&base_name), options)?;
+        CommandUtils::success(&format!("Mail template created: {}", template_path));
+    }
+
+    Ok(())
+}
+
+/// Subject line derived from a mail name, e.g. "Order shipped" for `OrderShipped`
+fn mail_subject(name: &str) -> String {
+    TextUtils::capitalize(&CommandUtils::to_snake_case(name).replace('_', " "))
+}
+
+/// Source of a mailable, rendered from a Markdown template or with inline HTML and text
+fn mail_source(name: &str, markdown: bool) -> String {
+    let class_name = CommandUtils::to_pascal_case(name);
+    let module_name = CommandUtils::to_snake_case(name);
+    let subject = mail_subject(name);
+
+    let (import, body, body_test) = if markdown {
+        (
+            "use serde_json::json;\n\n",
+            format!(
+                r#"MailBody::Markdown {{
+                template: "resources/views/mail/{module_name}.md".to_string(),
+                data: json!({{ "to": self.to }}),
+            }}"#
+            ),
+            format!(
+                r#"let MailBody::Markdown {{ template, .. }} = content.body else {{
+            panic!("expected a Markdown body");
+        }};
+        assert_eq!(template, "resources/views/mail/{module_name}.md");"#
+            ),
+        )
+    } else {
+        (
+            "",
+            r#"MailBody::Plain {
+                html: format!("<p>Hello {},</p>", self.to),
+                text: format!("Hello {},", self.to),
+            }"#
+            .to_string(),
+            r#"let MailBody::Plain { text, .. } = content.body else {
+            panic!("expected a plain body");
+        };
+        assert_eq!(text, "Hello jane@example.com,");"#
+                .to_string(),
+        )
+    };
+
+    format!(
+        r#"//! {class_name} mail
+
+{import}use super::{{Envelope, MailBody, MailContent, MailDriver, Mailable}};
+
+pub struct {class_name}Mail {{
+    pub to: String,
+    pub subject: String,
+    /// Driver used by `send`, e.g. SMTP
+    pub mailer: Option<Box<dyn MailDriver>>,
+}}
+
+impl {class_name}Mail {{
+    pub fn new(to: impl Into<String>) -> Self {{
+        Self {{
+            to: to.into(),
+            subject: "{subject}".to_string(),
+            mailer: None,
+        }}
+    }}
+
+    pub fn with_mailer(mut self, mailer: Box<dyn MailDriver>) -> Self {{
+        self.mailer = Some(mailer);
+        self
+    }}
+
+    /// Send the mail with the configured mailer
+    pub async fn send(&self) -> anyhow::Result<()> {{
+        let Some(mailer) = &self.mailer else {{
+            anyhow::bail!("No mailer configured for {class_name}Mail");
+        }};
+        mailer.send(&self.envelope(), &self.content()).await
+    }}
+}}
+
+impl Mailable for {class_name}Mail {{
+    fn envelope(&self) -> Envelope {{
+        Envelope {{
+            from: Envelope::default_from(),
+            to: self.to.clone(),
+            reply_to: None,
+        }}
+    }}
+
+    fn content(&self) -> MailContent {{
+        MailContent {{
+            subject: self.subject.clone(),
+            body: {body},
+        }}
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    #[test]
+    fn test_envelope() {{
+        let mail = {class_name}Mail::new("jane@example.com");
+
+        assert_eq!(mail.envelope().to, "jane@example.com");
+        assert!(mail.envelope().reply_to.is_none());
+    }}
+
+    #[test]
+    fn test_content() {{
+        let content = {class_name}Mail::new("jane@example.com").content();
+
+        assert_eq!(content.subject, "{subject}");
+        {body_test}
+    }}
+
+    #[tokio::test]
+    async fn test_send_requires_a_mailer() {{
+        let mail = {class_name}Mail::new("jane@example.com");
+
+        assert!(mail.send().await.is_err());
+    }}
+}}
+"#
+    )
+}
+
+/// Stub Markdown template for a mailable
+fn mail_template(name: &str) -> String {
+    format!(
+        "# {}\n\nHello {{{{ to }}}},\n\nAdd the message here.\n\nThanks,\n\nThe team\n",
+        mail_subject(name)
+    )
+}
+
+/// Shared mail types written to `src/mail/mod.rs`
+const MAIL_MODULE: &str = r#"//! Mail module
+//!
+//! Requires the following dependencies in Cargo.toml:
+//!
+//! ```toml
+//! anyhow = "1.0"
+//! async-trait = "0.1"
+//! serde_json = "1.0"
+//! ```
+
+use async_trait::async_trait;
+
+/// Sender and recipients of a mail
+#[derive(Debug, Clone, PartialEq)]
+pub struct Envelope {
+    pub from: String,
+    pub to: String,
+    pub reply_to: Option<String>,
+}
+
+impl Envelope {
+    /// Sender from `MAIL__FROM_ADDRESS`, the environment form of `mail.from_address`
+    pub fn default_from() -> String {
+        std::env::var("MAIL__FROM_ADDRESS").unwrap_or_else(|_| "hello@example.com".to_string())
+    }
+}
+
+/// Subject and body of a mail
+#[derive(Debug, Clone, PartialEq)]
+pub struct MailContent {
+    pub subject: String,
+    pub body: MailBody,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MailBody {
+    /// HTML with a plain text alternative
+    Plain { html: String, text: String },
+    /// Markdown template rendered with `data`
+    Markdown {
+        template: String,
+        data: serde_json::Value,
+    },
+}
+
+/// A mail that can be sent
+pub trait Mailable {
+    fn envelope(&self) -> Envelope;
+
+    fn content(&self) -> MailContent;
+}
+
+/// Transport delivering mail, e.g. SMTP or an HTTP API
+#[async_trait]
+pub trait MailDriver: Send + Sync {
+    async fn send(&self, envelope: &Envelope, content: &MailContent) -> anyhow::Result<()>;
+}
+"#;
+
+/// Example keys of a generated config module as (key, Rust type, TOML value)
+const CONFIG_EXAMPLE_KEYS: &[(&str, &str, &str)] = &[
+    ("enabled", "bool", "false"),
+    ("url", "String", "\"\""),
+    ("timeout", "u64", "30"),
+];
+
+/// Generate a typed config module and its example TOML file
+async fn make_config(name: String, section: Option<String>, options: &GeneratorOptions) -> Result<()> {
+    CommandUtils::ensure_rustisan_project()?;
+
+    CommandUtils::info(&format!("Creating config {}...", name.cyan().bold()));
+
+    let base_name = strip_suffix(&name, "Config");
+    let module_name = CommandUtils::to_snake_case(&base_name);
+    let section = section.unwrap_or_else(|| module_name.clone());
+
+    let toml_path = format!("config/{}.toml", module_name);
+    CommandUtils::ensure_directory(std::path::Path::new("config"))?;
+    write_generated(&toml_path, &config_toml(&base_name, &section), options)?;
+    CommandUtils::success(&format!("Config file created: {}", toml_path));
+
+    let content = config_source(&base_name, &section);
+    let file_path = write_component(&app_path("src/config"), &format!("{}_config", module_name), &content, options)?;
+    CommandUtils::success(&format!("Config module created: {}", file_path));
+
+    let config = CommandUtils::read_file("rustisan.toml")?;
+    if let Some(updated) = add_config_section(&config, &section)? {
+        FileUtils::atomic_write("rustisan.toml", &updated)?;
+        CommandUtils::info(&format!("Added [{}] to rustisan.toml", section));
+    }
+
+    CommandUtils::info("Run `rustisan config validate` to check the new section");
+
+    Ok(())
+}
+
+/// Commented example keys of a config section
+fn config_example_keys() -> String {
+    CONFIG_EXAMPLE_KEYS
+        .iter()
+        .map(|(key, rust_type, value)| format!("# {} = {}  # {}\n", key, value, rust_type))
+        .collect()
+}
+
+/// Example TOML declaring the section read by a config module
+fn config_toml(name: &str, section: &str) -> String {
+    format!(
+        "# {} configuration\n#\n# Set these keys in the [{}] table of rustisan.toml\n# and read them with `{}Config::load()`.\n\n[{}]\n{}",
+        TextUtils::capitalize(&CommandUtils::to_snake_case(name).replace('_', " ")),
+        section,
+        CommandUtils::to_pascal_case(name),
+        section,
+        config_example_keys()
+    )
+}
+
+/// rustisan.toml with an empty `[section]` appended, or `None` when it already has one
+fn add_config_section(config: &str, section: &str) -> Result<Option<String>> {
+    let document: toml_edit::DocumentMut = config.parse()?;
+    if document.contains_key(section) {
+        return Ok(None);
+    }
+
+    let mut updated = config.trim_end().to_string();
+    updated.push_str(&format!("\n\n[{}]\n{}", section, config_example_keys()));
+    Ok(Some(updated))
+}
+
+/// Source of a config struct loaded from a table of rustisan.toml
+fn config_source(name: &str, section: &str) -> String {
+    let class_name = CommandUtils::to_pascal_case(name);
+    let fields: String = CONFIG_EXAMPLE_KEYS
+        .iter()
+        .map(|(key, rust_type, _)| format!("    pub {}: {},\n", key, rust_type))
+        .collect();
+
+    format!(
+        r#"//! {class_name} configuration, read from the `[{section}]` table of rustisan.toml
+//!
+//! Requires the following dependencies in Cargo.toml:
+//!
+//! ```toml
+//! anyhow = "1.0"
+//! serde = {{ version = "1.0", features = ["derive"] }}
+//! toml = "0.8"
+//! ```
+
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct {class_name}Config {{
+{fields}}}
+
+/// rustisan.toml, keeping only the `[{section}]` table
+#[derive(Deserialize)]
+struct ConfigFile {{
+    #[serde(rename = "{section}", default)]
+    section: {class_name}Config,
+}}
+
+impl {class_name}Config {{
+    /// Load the `[{section}]` table from rustisan.toml
+    pub fn load() -> anyhow::Result<Self> {{
+        Self::load_from("rustisan.toml")
+    }}
+
+    /// Load the `[{section}]` table from a TOML file
+    pub fn load_from(path: impl AsRef<std::path::Path>) -> anyhow::Result<Self> {{
+        let content = std::fs::read_to_string(path)?;
+        let file: ConfigFile = toml::from_str(&content)?;
+        Ok(file.section)
+    }}
+
+    /// Check the loaded values, e.g. that required keys are set
+    pub fn validate(&self) -> anyhow::Result<()> {{
+        Ok(())
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    #[test]
+    fn test_parse_section() {{
+        let content = "[{section}]\nenabled = true\ntimeout = 10\n";
+        let file: ConfigFile = toml::from_str(content).unwrap();
+
+        assert!(file.section.enabled);
+        assert_eq!(file.section.timeout, 10);
+        assert!(file.section.validate().is_ok());
+    }}
+
+    #[test]
+    fn test_missing_section_uses_defaults() {{
+        let file: ConfigFile = toml::from_str("[app]\nname = \"app\"\n").unwrap();
+
+        assert_eq!(file.section, {class_name}Config::default());
+    }}
+}}
+"#
+    )
+}
+
+/// Write a generated file, refusing to replace an existing one unless `--force` is given
+fn write_generated(path: impl AsRef<std::path::Path>, content: &str, options: &GeneratorOptions) -> Result<()> {
+    let path = path.as_ref();
+    GeneratorUtils::check_file_exists(path, options.force)?;
+    if path.exists() {
+        CommandUtils::warning(&format!("Overwriting existing file: {}", path.display()));
+    }
+    FileUtils::atomic_write(path, content)
+}
+
+/// Write a generated component and register it in the module file of its directory
+fn write_component(module_dir: &str, module_name: &str, content: &str, options: &GeneratorOptions) -> Result<String> {
+    let file_path = format!("{}/{}.rs", module_dir, module_name);
+    write_generated(&file_path, content, options)?;
+    update_module_file(module_dir, module_name)?;
+    Ok(file_path)
+}
+
+/// Update module file to include new component, creating it if needed
+fn update_module_file(module_dir: &str, component_name: &str) -> Result<()> {
+    let module_path = std::path::Path::new(module_dir);
+    let mod_file = module_path.join("mod.rs");
+
+    if !mod_file.exists() {
+        let module = module_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        FileUtils::atomic_write(&mod_file, &format!("//! {} module\n", CommandUtils::to_pascal_case(&module)))?;
+    }
+
+    GeneratorUtils::update_module_file(module_path, component_name)
+}
+
+/// Resolve a path inside the application crate, which lives in `app/` for workspaces
+fn app_path(relative: &str) -> String {
+    CommandUtils::app_dir().join(relative).to_string_lossy().into_owned()
+}
+
+/// Resolve the type a generated component works with and the `use` line importing it
+fn model_type(model: &Option<String>) -> (String, String) {
+    match model {
+        Some(model) => {
+            let model_class = CommandUtils::to_pascal_case(model);
+            let import = format!(
+                "use crate::models::{}::{};\n",
+                CommandUtils::to_snake_case(model),
+                model_class
+            );
+            (model_class, import)
+        }
+        None => ("serde_json::Value".to_string(), String::new()),
+    }
+}
+
+/// Strip a conventional suffix such as `Service` from a component name
+fn strip_suffix(name: &str, suffix: &str) -> String {
+    match name.strip_suffix(suffix) {
+        Some(base) if !base.is_empty() => base.to_string(),
+        _ => name.to_string(),
+    }
+}
+
+/// Get the crate name of the current project from Cargo.toml
+fn project_crate_name() -> String {
+    std::fs::read_to_string(app_path("Cargo.toml"))
+        .ok()
+        .and_then(|content| content.parse::<toml::Value>().ok())
+        .and_then(|manifest| {
+            manifest
+                .get("package")?
+                .get("name")?
+                .as_str()
+                .map(|name| name.replace('-', "_"))
+        })
+        .unwrap_or_else(|| "app".to_string())
+}
+
+/// Seeder generated from `tests/fixtures/seeders/data/users.csv`, compiled to check the generated code builds
+#[cfg(test)]
+#[path = "../../tests/fixtures/seeders/users_seeder.rs"]
+mod users_seeder_fixture;
+
+/// Action generated by `make action PublishPost`, compiled to check the generated code builds
+#[cfg(test)]
+#[path = "../../tests/fixtures/actions/publish_post.rs"]
+mod publish_post_action_fixture;
+
+/// Config module generated by `make config Payments`, compiled to load `tests/fixtures/config/payments.toml`
+#[cfg(test)]
+#[allow(dead_code)]
+#[path = "../../tests/fixtures/config/payments_config.rs"]
+mod payments_config_fixture;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_test_target() {
+        let mut manifest: toml_edit::DocumentMut = "[package]\nname = \"shop\"\n".parse().unwrap();
+        assert_eq!(binary_name(&manifest).as_deref(), Some("shop"));
+
+        assert!(register_test_target(&mut manifest, "users_test", "tests/integration/users_test.rs"));
+        assert!(!register_test_target(&mut manifest, "users_test", "tests/integration/users_test.rs"));
+        assert!(register_test_target(&mut manifest, "orders_test", "tests/integration/orders_test.rs"));
+
+        let tests = manifest["test"].as_array_of_tables().unwrap();
+        assert_eq!(tests.len(), 2);
+        assert_eq!(tests.get(0).unwrap()["path"].as_str(), Some("tests/integration/users_test.rs"));
+
+        let manifest: toml_edit::DocumentMut = "[package]\nname = \"shop\"\n\n[[bin]]\nname = \"shop-server\"\n".parse().unwrap();
+        assert_eq!(binary_name(&manifest).as_deref(), Some("shop-server"));
+    }
+
+    #[test]
+    fn test_parse_request_fields() {
+        let fields = parse_request_fields("name:string:required|min:3|max:255, age:integer:min:18,code:string:regex:^[A-Z]{2,4}|x$,active:bool").unwrap();
+        assert_eq!(fields.len(), 4);
+        assert_eq!(fields[0].rules, [RequestRule::Required, RequestRule::Min(3), RequestRule::Max(255)]);
+        assert_eq!(fields[0].validate_attribute().unwrap(), "#[validate(required, length(min = 3, max = 255))]");
+        assert_eq!(fields[1].rust_type, "i64");
+        assert_eq!(fields[1].validate_attribute().unwrap(), "#[validate(range(min = 18))]");
+        assert_eq!(fields[2].rules, [RequestRule::Regex("^[A-Z]{2,4}|x$".to_string())]);
+        assert_eq!(fields[2].validate_attribute().unwrap(), "#[validate(regex(path = *CODE_PATTERN))]");
+        assert!(fields[3].rules.is_empty());
+        assert!(fields[3].validate_attribute().is_none());
+
+        assert!(parse_request_fields("id:uuid").is_err());
+        assert!(parse_request_fields("age:integer:email").is_err());
+        assert!(parse_request_fields("name:string:min").is_err());
+        assert!(parse_request_fields("name:string:unique").is_err());
+        assert!(parse_request_fields("code:string:regex:(").is_err());
+    }
+
+    #[test]
+    fn test_request_source_leaves_out_tests_without_cases() {
+        let fields = parse_request_fields("name:string:required|min:3,code:string:regex:^[A-Z]+$").unwrap();
+        let source = request_source("Product", &fields);
+        assert!(source.contains("    fn test_name_required() {\n        assert!(!fails(\"name\", json!(\"a\".repeat(3))));\n        assert!(fails(\"name\", Value::Null));"));
+        assert!(source.contains("fn test_name_min() {"));
+        assert!(source.contains("fn test_valid_body() {"));
+        assert!(!source.contains("fn test_code_"));
+        assert!(!source.contains("#[ignore"));
+
+        let fields = parse_request_fields("code:string:required|regex:^[A-Z]+$").unwrap();
+        let source = request_source("Product", &fields);
+        assert!(!source.contains("mod tests"));
+    }
+
+    #[test]
+    fn test_resource_source() {
+        let fields = parse_resource_fields("id:integer, display_name:string,active:bool").unwrap();
+        assert_eq!(fields[1], ("display_name".to_string(), "String".to_string()));
+        assert!(parse_resource_fields("id:uuid").is_err());
+
+        let source = resource_source("User", "User", &fields, false);
+        assert!(source.contains("            display_name: model.display_name.clone(),\n"));
+        assert!(source.contains("            active: model.active,\n"));
+        assert!(source.contains("map.serialize_entry(\"display_name\", &self.display_name)?;"));
+        assert!(!source.contains("UserCollection"));
+
+        let source = resource_source("User", "Account", &fields, true);
+        assert!(source.contains("use crate::models::account::Account;"));
+        assert!(source.contains("pub fn from_vec(models: Vec<Account>, meta: PaginationMeta) -> Self"));
+    }
+
+    #[test]
+    fn test_migration_with_columns() {
+        let columns = parse_migration_columns("name:string,email:string:unique,age:integer:nullable").unwrap();
+        let target = MigrationTarget::Create("users".to_string());
+        let source = migration_source("create_users_table", Some(&target), &columns);
+
+        assert!(source.contains("pub struct CreateUsersTable {}"));
+        assert!(source.contains("schema.create(\"users\", |table: &mut Blueprint| {"));
+        assert!(source.contains("table.string(\"name\");"));
+        assert!(source.contains("table.string(\"email\").unique();"));
+        assert!(source.contains("table.integer(\"age\").nullable();"));
+        assert!(source.contains("schema.drop_if_exists(\"users\")?;"));
+
+        let target = MigrationTarget::Alter("users".to_string());
+        let source = migration_source("add_profile_to_users", Some(&target), &columns);
+        assert!(source.contains("schema.table(\"users\", |table: &mut Blueprint| {"));
+        assert!(source.contains("schema.drop_columns(\"users\", &[\"name\", \"email\", \"age\"])?;"));
+        assert!(!source.contains("table.id();"));
+    }
+
+    #[test]
+    fn test_parse_migration_columns() {
+        let columns = parse_migration_columns("id:uuid:unique, amount:decimal, paid_at:datetime:nullable:index, views:bigint").unwrap();
+        let definitions: Vec<String> = columns.iter().map(MigrationColumn::definition).collect();
+        assert_eq!(
+            definitions,
+            [
+                "table.uuid(\"id\").unique();",
+                "table.decimal(\"amount\");",
+                "table.date_time(\"paid_at\").nullable().index();",
+                "table.big_integer(\"views\");",
+            ]
+        );
+
+        assert!(parse_migration_columns("name").is_err());
+        assert!(parse_migration_columns("name:varchar").is_err());
+        assert!(parse_migration_columns("name:string:primary_key").is_err());
+    }
+
+    const SEEDER_FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/seeders");
+
+    #[test]
+    fn test_seeder_from_csv_matches_fixture() {
+        let data = SeedData::from_csv(&std::path::Path::new(SEEDER_FIXTURE).join("data/users.csv")).unwrap();
+        assert_eq!(data.columns, ["name", "email", "age", "active"]);
+        assert_eq!(data.rows, 3);
+        assert_eq!(data.key_column(), Some("email"));
+
+        let expected = std::fs::read_to_string(std::path::Path::new(SEEDER_FIXTURE).join("users_seeder.rs")).unwrap();
+        assert_eq!(seeder_source("UsersSeeder", "User", "data/users.csv", &data), expected);
+    }
+
+    #[tokio::test]
+    async fn test_generated_seeder_runs_and_rolls_back() {
+        use super::users_seeder_fixture::{Seedable, UsersSeeder};
+        use serde_json::{json, Map, Value};
+        use std::sync::Mutex;
+
+        static RECORDS: Mutex<Vec<Map<String, Value>>> = Mutex::new(Vec::new());
+
+        struct User;
+
+        impl Seedable for User {
+            async fn create(row: Map<String, Value>) -> Result<()> {
+                RECORDS.lock().unwrap().push(row);
+                Ok(())
+            }
+
+            async fn delete_where(column: &str, value: &Value) -> Result<()> {
+                RECORDS.lock().unwrap().retain(|record| record.get(column) != Some(value));
+                Ok(())
+            }
+        }
+
+        UsersSeeder::run::<User>().await.unwrap();
+        {
+            let records = RECORDS.lock().unwrap();
+            assert_eq!(records.len(), 3);
+            assert_eq!(
+                Value::Object(records[0].clone()),
+                json!({ "name": "Ada Lovelace", "email": "ada@example.com", "age": 36, "active": true })
+            );
+            assert_eq!(records[1]["name"], "Hopper, Grace");
+            assert_eq!(records[2]["age"], Value::Null);
+        }
+
+        RECORDS.lock().unwrap().push(json!({ "email": "other@example.com" }).as_object().unwrap().clone());
+        UsersSeeder::rollback::<User>().await.unwrap();
+        assert_eq!(RECORDS.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_seeder_from_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("posts.json");
+        std::fs::write(&path, r#"[{"id": 1, "title": "Hello"}, {"id": 2, "title": "World", "draft": true}]"#).unwrap();
+
+        let data = SeedData::from_json(&path).unwrap();
+        assert_eq!(data.columns, ["id", "title", "draft"]);
+        assert_eq!(data.key_column(), Some("id"));
+
+        let source = seeder_source("PostsSeeder", "Post", "data/posts.json", &data);
+        assert!(source.contains("const DATA: &str = include_str!(\"data/posts.json\");"));
+        assert!(source.contains("Ok(serde_json::from_str(DATA)?)"));
+        assert!(!source.contains("csv"));
+
+        std::fs::write(&path, r#"{"id": 1}"#).unwrap();
+        assert!(SeedData::from_json(&path).is_err());
+    }
+
+    #[test]
+    fn test_seeder_without_key_column_refuses_rollback() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tags.json");
+        std::fs::write(&path, r#"[{"name": "rust"}, {"name": "web"}]"#).unwrap();
+
+        let data = SeedData::from_json(&path).unwrap();
+        assert_eq!(data.key_column(), None);
+
+        let source = seeder_source("TagsSeeder", "Tag", "data/tags.json", &data);
+        assert!(source.contains("pub const KEY_COLUMN: Option<&str> = None;"));
+        assert!(source.contains("anyhow::bail!(\"TagsSeeder cannot be rolled back: no id, email, slug column identifies its records\");"));
+    }
+
+    #[test]
+    fn test_parse_struct_fields() {
+        let source = r#"
+#[derive(Debug)]
+pub struct Post {
+    pub id: u64,
+    /// Author
+    pub(crate) user_id: u64,
+    #[serde(default)]
+    pub tags: HashMap<String, Vec<String>>,
+    title: String
+}
+"#;
+        let fields = parse_struct_fields(source, "Post").unwrap();
+        let names: Vec<&str> = fields.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["id", "user_id", "tags", "title"]);
+        assert_eq!(fields[2].1, "HashMap<String, Vec<String>>");
+        assert!(parse_struct_fields(source, "User").is_none());
+    }
+
+    #[test]
+    fn test_command_source() {
+        let arguments = parse_command_arguments("name:required").unwrap();
+        let options = parse_command_options("verbose:bool").unwrap();
+        assert_eq!(arguments, [CommandArgument { name: "name".to_string(), required: true }]);
+
+        let source = command_source("SendReport", &arguments, &options, Some("0 * * * *"));
+        assert!(source.contains("    #[arg(value_name = \"NAME\")]\n    pub name: String,"));
+        assert!(source.contains("    #[arg(short, long)]\n    pub verbose: bool,"));
+        assert!(source.contains("let Self { name, verbose } = self;"));
+        assert!(source.contains("info!(?name, ?verbose, \"Running send-report command\");"));
+        assert!(source.contains("rustisan schedule add \"cargo run -- send-report\" --cron \"0 * * * *\""));
+
+        let options = parse_command_options("count:usize,color:String").unwrap();
+        let source = command_source("SendReport", &[], &options, None);
+        assert!(source.contains("    #[arg(short, long)]\n    pub count: Option<usize>,"));
+        assert!(source.contains("    #[arg(long)]\n    pub color: Option<String>,"));
+        assert!(parse_command_arguments("name:sometimes").is_err());
+    }
+
+    #[test]
+    fn test_policy_source_for_user_model() {
+        let source = policy_source("Account", Some("User"));
+        assert_eq!(source.matches("use crate::models::user::User;").count(), 1);
+        assert!(source.contains("pub fn update(&self, _user: &User, _record: Option<&User>) -> bool {"));
+        assert!(source.contains("let record = resource.and_then(|resource| resource.downcast_ref::<User>());"));
+        assert!(!source.contains("_user: &User, _user"));
+
+        let source = policy_source("Post", Some("Post"));
+        assert!(source.contains("use crate::models::post::Post;"));
+        assert!(source.contains("pub fn view(&self, _user: &User, post: Option<&Post>) -> bool {"));
+    }
+
+    #[test]
+    fn test_job_source() {
+        let source = job_source("SendEmail", false, "emails", 5, 120);
+        assert!(source.contains("pub const QUEUE: &'static str = \"emails\";"));
+        assert!(source.contains("pub const MAX_TRIES: u32 = 5;"));
+        assert!(source.contains("pub const TIMEOUT: u64 = 120;"));
+        assert!(source.contains("pub fn failed(&self, error: &anyhow::Error)"));
+        assert!(source.contains("pub struct JobPayload<T>"));
+
+        let source = job_source("SendEmail", true, "default", 3, 60);
+        assert!(source.contains("pub const MAX_TRIES: u32 = 3;"));
+        assert!(source.contains("pub fn handle(&self) -> Result<()>"));
+        assert!(!source.contains("pub fn failed"));
+    }
+
+    #[test]
+    fn test_write_generated_requires_force_to_overwrite() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("send_email.rs");
+
+        write_generated(&path, "first", &GeneratorOptions::default()).unwrap();
+        let error = write_generated(&path, "second", &GeneratorOptions::default()).unwrap_err();
+        assert!(error.to_string().contains("Use --force to overwrite"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "first");
+
+        let force = GeneratorOptions { force: true, ..Default::default() };
+        write_generated(&path, "second", &force).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "second");
+    }
+
+    #[test]
+    fn test_parse_trait_methods() {
+        let methods = parse_trait_methods("find:Result<Option<Self>>, counts:Result<HashMap<String, u32>>,save").unwrap();
+        assert_eq!(methods.len(), 3);
+        assert_eq!(methods[0], TraitMethod { name: "find".to_string(), return_type: "Result<Option<Self>>".to_string() });
+        assert_eq!(methods[1].return_type, "Result<HashMap<String, u32>>");
+        assert_eq!(methods[2].return_type, "Result<()>");
+    }
+
+    #[test]
+    fn test_trait_source_is_formatted_rust() {
+        if !crate::utils::ProcessUtils::command_exists("rustfmt") {
+            return;
+        }
+
+        let methods = parse_trait_methods("find:Result<Option<Self>>,save:Result<()>,count:Result<usize>").unwrap();
+        for is_async in [true, false] {
+            let source = trait_source("Repository", &methods, is_async);
+            assert_eq!(source.contains("#[async_trait]"), is_async);
+            assert!(source.contains("impl<T> RepositoryExt for T where T: Repository {}"));
+            assert!(source.contains(if is_async { "pub trait Repository: Sized + Send + Sync {" } else { "pub trait Repository: Sized {" }));
+
+            let mut rustfmt = std::process::Command::new("rustfmt")
+                .args(["--check", "--edition", "2021"])
+                .stdin(std::process::Stdio::piped())
+                .stdout(std::process::Stdio::piped())
+                .spawn()
+                .unwrap();
+            std::io::Write::write_all(rustfmt.stdin.as_mut().unwrap(), source.as_bytes()).unwrap();
+            let output = rustfmt.wait_with_output().unwrap();
+            assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stdout));
+        }
+    }
+
+    #[test]
+    fn test_enum_source() {
+        let variants = parse_enum_variants(&["Active".to_string(), "InProgress".to_string(), "Pending:u8".to_string()]).unwrap();
+        assert_eq!(variants[2], EnumVariant { name: "Pending".to_string(), discriminant: None, repr: Some("u8".to_string()) });
+        assert_eq!(variants[1].key(), "in_progress");
+        assert_eq!(variants[1].label(), "In Progress");
+
+        let source = enum_source("TaskStatus", &variants, true, true).unwrap();
+        assert!(source.contains("#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]\n#[serde(rename_all = \"snake_case\")]\n#[repr(u8)]\npub enum TaskStatus {"));
+        assert!(source.contains("            \"in_progress\" => Some(Self::InProgress),"));
+        assert!(source.contains("            Self::InProgress => \"In Progress\","));
+        assert!(source.contains("impl TryFrom<&str> for TaskStatus {"));
+
+        let variants = parse_enum_variants(&["Low:1".to_string(), "High:10".to_string()]).unwrap();
+        let source = enum_source("Priority", &variants, false, false).unwrap();
+        assert!(source.contains("    Low = 1,\n    High = 10,"));
+        assert!(!source.contains("serde") && !source.contains("impl std::fmt::Display"));
+
+        assert!(parse_enum_variants(&["Pending:maybe".to_string()]).is_err());
+        let mixed = parse_enum_variants(&["A:u8".to_string(), "B:i32".to_string()]).unwrap();
+        assert!(enum_source("Mixed", &mixed, false, false).is_err());
+    }
+
+    #[test]
+    fn test_scope_source() {
+        let source = scope_source("Active", None);
+        assert!(source.contains("pub trait ActiveScope: Sized {"));
+        assert!(source.contains("pub struct ActiveQueryBuilder {\n    pub conditions: Vec<String>,\n    pub bindings: Vec<Value>,"));
+        assert!(source.contains("self.conditions.push(format!(\"{} {} ?\", column, operator));"));
+        assert!(!source.contains("UserQuery"));
+
+        // The model query is defined once in its own module and shared by every scope
+        let source = scope_source("Active", Some("user"));
+        assert!(source.contains("use super::user_query::UserQuery;"));
+        assert!(source.contains("impl ActiveScope for UserQuery {"));
+        assert!(!source.contains("pub struct UserQuery"));
+        assert!(model_query_source("user").contains("pub struct UserQuery {\n    pub sql: String,\n    pub bindings: Vec<Value>,\n}"));
+    }
+
+    #[test]
+    fn test_provider_source() {
+        let source = provider_source("Payment", false);
+        assert!(source.contains("impl ServiceProvider for PaymentProvider {"));
+        assert!(source.contains("PaymentProvider.register(&mut container);\n        PaymentProvider.boot(&container);"));
+        assert!(!source.contains("fn provides"));
+
+        let source = provider_source("Payment", true);
+        assert!(source.contains("fn provides(&self) -> Vec<&'static str> {\n        vec![\"payment\"]"));
+    }
+
+    #[test]
+    fn test_cast_source() {
+        for cast_type in CAST_TYPES {
+            let source = cast_source("Tags", cast_type).unwrap();
+            assert!(source.contains("impl CastAttribute for TagsCast {"));
+            assert!(source.contains("fn test_"));
+        }
+
+        let source = cast_source("Card", "encrypted").unwrap();
+        assert!(source.contains("//! aes-gcm = \"0.10\""));
+        assert!(source.contains("encrypt(&app_key()?, &value)"));
+        assert!(source.contains(".and_then(|app| app.get(\"key\"))"));
+        assert!(!source.contains("config[\"app\"]"));
+        assert!(cast_source("Card", "rot13").is_err());
+    }
+
+    #[test]
+    fn test_action_source() {
+        let expected = include_str!("../../tests/fixtures/actions/publish_post.rs");
+        assert_eq!(action_source("PublishPost", &None), expected);
+
+        let source = action_source("PublishPost", &Some("post".to_string()));
+        assert!(source.contains("use anyhow::Result;\n\nuse crate::models::post::Post;\n\n"));
+        assert!(source.contains("pub struct PublishPostInput {\n    /// Id of the post to act on\n    pub id: u64,\n}"));
+        assert!(source.contains("pub struct PublishPostOutput {\n    pub model: Post,\n}"));
+        assert!(source.contains("action.execute(PublishPostInput { id: 1 }).await.is_ok()"));
+    }
+
+    #[test]
+    fn test_mail_source() {
+        let source = mail_source("OrderShipped", false);
+        assert!(source.contains("impl Mailable for OrderShippedMail {"));
+        assert!(source.contains("subject: \"Order shipped\".to_string(),"));
+        assert!(source.contains("assert_eq!(mail.envelope().to, \"jane@example.com\");"));
+        assert!(source.contains("MailBody::Plain {"));
+
+        let source = mail_source("OrderShipped", true);
+        assert!(source.contains("template: \"resources/views/mail/order_shipped.md\".to_string(),"));
+        assert_eq!(mail_template("OrderShipped"), "# Order shipped\n\nHello {{ to }},\n\nAdd the message here.\n\nThanks,\n\nThe team\n");
+    }
+
+    #[test]
+    fn test_config_source() {
+        let expected = include_str!("../../tests/fixtures/config/payments_config.rs");
+        assert_eq!(config_source("Payments", "payments"), expected);
+
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/config/payments.toml");
+        let config = payments_config_fixture::PaymentsConfig::load_from(path).unwrap();
+        assert!(config.enabled);
+        assert_eq!(config.url, "https://payments.example.com");
+        assert_eq!(config.timeout, 30);
+        assert!(config.validate().is_ok());
+
+        let source = config_source("Billing", "billing-service");
+        assert!(source.contains("#[serde(rename = \"billing-service\", default)]"));
+    }
+
+    #[test]
+    fn test_add_config_section() {
+        let config = "[app]\nname = \"shop\"\n";
+        let updated = add_config_section(config, "payments").unwrap().unwrap();
+
+        assert_eq!(updated, "[app]\nname = \"shop\"\n\n[payments]\n# enabled = false  # bool\n# url = \"\"  # String\n# timeout = 30  # u64\n");
+        assert!(add_config_section(&updated, "payments").unwrap().is_none());
+        assert!(config_toml("Payments", "payments").contains("[payments]\n# enabled = false  # bool\n"));
+    }
+
+    #[test]
+    fn test_fake_values() {
+        assert_eq!(fake_value("email", "String"), ("FakeEmail().fake()".to_string(), Some("FakeEmail")));
+        assert_eq!(fake_value("username", "String").0, "format!(\"username_{}\", next_sequence())");
+        assert_eq!(fake_value("id", "i32").0, "next_sequence() as i32");
+        assert_eq!(fake_value("user_id", "u64").0, "0");
+        assert_eq!(fake_value("created_at", "NaiveDateTime").0, "chrono::Utc::now().naive_utc()");
+        assert_eq!(fake_value("bio", "Option<String>").0, "None");
+
+        let source = factory_source("Post", &default_factory_fields("Post"), |_| true);
+        assert!(source.contains("pub fn with_user(mut self, related: &User) -> Self"));
+        assert!(source.contains("use chrono::{DateTime, Utc};"));
+        assert!(!factory_source("User", &default_factory_fields("User"), |_| true).contains("pub fn with_"));
+    }
+
+    #[test]
+    fn test_factory_relations_require_integer_field_and_model() {
+        let fields = vec![
+            ("id".to_string(), "u64".to_string()),
+            ("user_id".to_string(), "u64".to_string()),
+            ("external_id".to_string(), "String".to_string()),
+        ];
+
+        let source = factory_source("Post", &fields, |model| model == "User");
+        assert!(source.contains("use crate::models::user::User;"));
+        assert!(!source.contains("External"));
+
+        let source = factory_source("Post", &fields, |_| false);
+        assert!(!source.contains("use crate::models::user::User;"));
+        assert!(!source.contains("pub fn with_"));
     }
 }
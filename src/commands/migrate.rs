@@ -2,37 +2,154 @@
 
 use anyhow::Result;
 use colored::*;
+use std::collections::BTreeSet;
+use std::path::Path;
 use crate::MigrateCommands;
 use super::CommandUtils;
+use crate::utils::{FileUtils, ProcessUtils, ProgressBar};
+use super::db::{execute_sql, Connection};
+
+/// Directory containing the application's migration files
+const MIGRATIONS_DIR: &str = "database/migrations";
+
+/// Table used to track which migrations have been run
+const MIGRATIONS_TABLE: &str = "_migrations";
+
+/// Batch recording a squashed baseline whose original migrations are still recorded
+///
+/// Rolling such a baseline back would drop every table, so rollbacks skip this batch.
+const SUPERSEDED_BASELINE_BATCH: u32 = 0;
+
+/// Prefix printed before every statement during a dry run
+const DRY_RUN_PREFIX: &str = "[DRY RUN]";
+
+/// A migration recorded in the migrations table
+#[derive(Debug, Clone, PartialEq)]
+struct MigrationRecord {
+    name: String,
+    batch: u32,
+}
 
 /// Handle migrate command
-pub async fn handle(operation: Option<MigrateCommands>) -> Result<()> {
+pub async fn handle(operation: Option<MigrateCommands>, dry_run: bool) -> Result<()> {
     CommandUtils::ensure_rustisan_project()?;
 
-    match operation.unwrap_or(MigrateCommands::Up) {
-        MigrateCommands::Up => migrate_up().await,
+    match operation.unwrap_or(MigrateCommands::Up { dry_run }) {
+        MigrateCommands::Up { dry_run: true } => migrate_dry_run().await,
+        MigrateCommands::Up { dry_run: false } => migrate_up().await,
+        MigrateCommands::DryRun => migrate_dry_run().await,
         MigrateCommands::Down { steps } => migrate_down(steps).await,
+        MigrateCommands::Rollback { name, cascade } => migrate_rollback_by_name(&name, cascade).await,
         MigrateCommands::Reset => migrate_reset().await,
         MigrateCommands::Refresh => migrate_refresh().await,
         MigrateCommands::Status => migrate_status().await,
         MigrateCommands::Make { name } => make_migration(name).await,
+        MigrateCommands::Squash { delete_old, keep } => migrate_squash(delete_old, keep).await,
     }
 }
 
 async fn migrate_up() -> Result<()> {
     CommandUtils::info("Running pending migrations...");
 
-    // TODO: Implement migration logic
-    CommandUtils::success("All migrations completed successfully");
+    let connection = Connection::from_config()?;
+    ensure_migrations_table(&connection)?;
+
+    let files = discover_migrations()?;
+    let ran = ran_migrations(&connection)?;
+    let pending = pending_migrations(&files, &ran);
+
+    if pending.is_empty() {
+        CommandUtils::info("Nothing to migrate");
+        return Ok(());
+    }
+
+    let batch = ran.iter().map(|record| record.batch).max().unwrap_or(0) + 1;
+    let mut progress = ProgressBar::new("Migrating", pending.len());
+
+    for name in &pending {
+        run_migration(name, "up")?;
+        record_migration(&connection, name, batch)?;
+        progress.suspend(|| CommandUtils::success(&format!("Migrated: {}", name)));
+        progress.increment();
+    }
+
+    progress.finish_and_clear();
+    CommandUtils::success(&format!("{} migration(s) completed successfully", pending.len()));
+
+    Ok(())
+}
+
+async fn migrate_dry_run() -> Result<()> {
+    CommandUtils::info("Previewing pending migrations (dry run)...");
+
+    let connection = Connection::from_config()?;
+    ensure_migrations_table(&connection)?;
+
+    let files = discover_migrations()?;
+    let ran = ran_migrations(&connection)?;
+    let pending = pending_migrations(&files, &ran);
+
+    if pending.is_empty() {
+        CommandUtils::info("Nothing to migrate");
+        return Ok(());
+    }
+
+    for name in &pending {
+        CommandUtils::info(&format!("{} Migration: {}", DRY_RUN_PREFIX.yellow().bold(), name));
+
+        // The migrator runs `up()` inside a transaction that is rolled back and prints each statement
+        for statement in migration_sql(name)? {
+            CommandUtils::info(&format!("{} {}", DRY_RUN_PREFIX.yellow().bold(), statement));
+        }
+    }
+
+    CommandUtils::success(&format!("{} migration(s) previewed, no changes were made", pending.len()));
 
     Ok(())
 }
 
 async fn migrate_down(steps: u32) -> Result<()> {
-    CommandUtils::info(&format!("Rolling back {} migration(s)...", steps));
+    CommandUtils::info(&format!("Rolling back {} batch(es)...", steps));
+
+    let connection = Connection::from_config()?;
+    ensure_migrations_table(&connection)?;
+
+    let ran = ran_migrations(&connection)?;
+    let rollback = migrations_to_rollback(&ran, steps);
+
+    if rollback.is_empty() {
+        CommandUtils::info("Nothing to rollback");
+        return Ok(());
+    }
+
+    for name in &rollback {
+        CommandUtils::info(&format!("Rolling back: {}", name));
+        run_migration(name, "down")?;
+        delete_migration_record(&connection, name)?;
+        CommandUtils::success(&format!("Rolled back: {}", name));
+    }
+
+    CommandUtils::success(&format!("Rolled back {} migration(s)", rollback.len()));
+
+    Ok(())
+}
+
+/// Roll back a single migration by file name, with the migrations run after it when `cascade` is set
+async fn migrate_rollback_by_name(name: &str, cascade: bool) -> Result<()> {
+    let connection = Connection::from_config()?;
+    ensure_migrations_table(&connection)?;
 
-    // TODO: Implement rollback logic
-    CommandUtils::success(&format!("Rolled back {} migration(s)", steps));
+    let ran = ran_migrations(&connection)?;
+    let rollback = rollback_by_name(&ran, name, cascade)?;
+
+    for name in &rollback {
+        CommandUtils::info(&format!("Rolling back: {}", name));
+        run_migration(name, "down")?;
+        delete_migration_record(&connection, name)?;
+        CommandUtils::success(&format!("Rolled back: {}", name));
+    }
+
+    CommandUtils::success(&format!("Rolled back {} migration(s)", rollback.len()));
 
     Ok(())
 }
@@ -40,7 +157,18 @@ async fn migrate_down(steps: u32) -> Result<()> {
 async fn migrate_reset() -> Result<()> {
     CommandUtils::info("Resetting all migrations...");
 
-    // TODO: Implement reset logic
+    let connection = Connection::from_config()?;
+    ensure_migrations_table(&connection)?;
+
+    let ran = ran_migrations(&connection)?;
+    let batches = ran.iter().map(|record| record.batch).max().unwrap_or(0);
+
+    for name in migrations_to_rollback(&ran, batches) {
+        CommandUtils::info(&format!("Rolling back: {}", name));
+        run_migration(&name, "down")?;
+        delete_migration_record(&connection, &name)?;
+    }
+
     CommandUtils::success("All migrations have been reset");
 
     Ok(())
@@ -61,17 +189,107 @@ async fn migrate_refresh() -> Result<()> {
 async fn migrate_status() -> Result<()> {
     CommandUtils::info("Checking migration status...");
 
+    let connection = Connection::from_config()?;
+    ensure_migrations_table(&connection)?;
+
+    let files = discover_migrations()?;
+    let ran = ran_migrations(&connection)?;
+
     println!("\n{}", "Migration Status:".bold());
-    println!("┌─────────────────────────────────────────────────────────────────────────────┐");
-    println!("│ {} │ {} │ {} │", "Batch".bold(), "Migration".bold(), "Status".bold());
-    println!("├─────────────────────────────────────────────────────────────────────────────┤");
+    println!("┌───────┬──────────────────────────────────────────────────────────────┬─────────┐");
+    println!("│ {} │ {} │ {} │", "Batch".bold(), format!("{:60}", "Migration").bold(), format!("{:7}", "Status").bold());
+    println!("├───────┼──────────────────────────────────────────────────────────────┼─────────┤");
 
-    // TODO: Implement actual status check
-    println!("│ {} │ {} │ {} │", "1".green(), "2024_01_01_000000_create_users_table".dimmed(), "Ran".green());
-    println!("│ {} │ {} │ {} │", "1".green(), "2024_01_01_000001_create_posts_table".dimmed(), "Ran".green());
-    println!("│ {} │ {} │ {} │", "-".yellow(), "2024_01_01_000002_add_user_avatar".dimmed(), "Pending".yellow());
+    if files.is_empty() && ran.is_empty() {
+        println!("│ {} │ {} │ {} │", format!("{:5}", "-").dimmed(), format!("{:60}", "No migrations found").dimmed(), format!("{:7}", "").dimmed());
+    }
 
-    println!("└─────────────────────────────────────────────────────────────────────────────┘");
+    for name in &files {
+        match ran.iter().find(|record| &record.name == name) {
+            Some(record) => println!(
+                "│ {} │ {} │ {} │",
+                format!("{:5}", record.batch).green(),
+                format!("{:60}", name).dimmed(),
+                format!("{:7}", "Ran").green()
+            ),
+            None => println!(
+                "│ {} │ {} │ {} │",
+                format!("{:5}", "-").yellow(),
+                format!("{:60}", name).dimmed(),
+                format!("{:7}", "Pending").yellow()
+            ),
+        }
+    }
+
+    // Migrations recorded in the database whose files no longer exist
+    for record in ran.iter().filter(|record| !files.contains(&record.name)) {
+        println!(
+            "│ {} │ {} │ {} │",
+            format!("{:5}", record.batch).red(),
+            format!("{:60}", record.name).dimmed(),
+            format!("{:7}", "Missing").red()
+        );
+    }
+
+    println!("└───────┴──────────────────────────────────────────────────────────────┴─────────┘");
+
+    Ok(())
+}
+
+async fn migrate_squash(delete_old: bool, keep: bool) -> Result<()> {
+    CommandUtils::info("Squashing ran migrations...");
+
+    let connection = Connection::from_config()?;
+    ensure_migrations_table(&connection)?;
+
+    let files = discover_migrations()?;
+    let ran = ran_migrations(&connection)?;
+    let squashed: Vec<String> = files
+        .iter()
+        .filter(|name| ran.iter().any(|record| &record.name == *name))
+        .cloned()
+        .collect();
+
+    if squashed.is_empty() {
+        CommandUtils::info("Nothing to squash");
+        return Ok(());
+    }
+
+    let mut schema = SchemaSnapshot::default();
+    for name in &squashed {
+        let source = CommandUtils::read_file(Path::new(MIGRATIONS_DIR).join(format!("{}.rs", name)))?;
+        schema.apply(&source);
+    }
+
+    let timestamp = chrono::Utc::now().format("%Y_%m_%d_%H%M%S");
+    let baseline_name = format!("{}_squashed_baseline", timestamp);
+    let baseline_path = format!("{}/{}.rs", MIGRATIONS_DIR, baseline_name);
+
+    FileUtils::atomic_write(&baseline_path, &render_squashed_migration(&schema, &squashed))?;
+    CommandUtils::success(&format!("Baseline created: {}", baseline_path));
+
+    // The baseline describes the current schema, so it must never run against this database
+    let records = records_after_squash(&ran, &squashed, &baseline_name, delete_old, keep);
+
+    for record in ran.iter().filter(|record| !records.contains(record)) {
+        delete_migration_record(&connection, &record.name)?;
+    }
+    for record in records.iter().filter(|record| !ran.contains(record)) {
+        record_migration(&connection, &record.name, record.batch)?;
+    }
+
+    if delete_old && !keep {
+        for name in &squashed {
+            std::fs::remove_file(Path::new(MIGRATIONS_DIR).join(format!("{}.rs", name)))?;
+        }
+        CommandUtils::info(&format!("Removed {} squashed migration file(s)", squashed.len()));
+    } else if delete_old {
+        CommandUtils::info("Original migration files and their records were kept");
+    } else {
+        CommandUtils::warning("Original migrations were left in place; use --delete-old to replace them");
+    }
+
+    CommandUtils::success(&format!("Squashed {} migration(s) into {}", squashed.len(), baseline_name));
 
     Ok(())
 }
@@ -96,7 +314,9 @@ use anyhow::Result;
 pub struct {migration_class} {{}}
 
 impl Migration for {migration_class} {{
-    fn up(&self, schema: &mut Schema) -> Result<()> {{
+    /// When `dry_run` is set, schema operations are rolled back and only their SQL is reported
+    fn up(&self, schema: &mut Schema, dry_run: bool) -> Result<()> {{
+        schema.dry_run(dry_run);
         schema.create("{table_name}", |table: &mut Blueprint| {{
             table.id();
             table.timestamps();
@@ -113,9 +333,562 @@ impl Migration for {migration_class} {{
         table_name = CommandUtils::to_snake_case(&name).replace("create_", "").replace("_table", ""),
     );
 
-    std::fs::write(&migration_path, migration_content)?;
+    FileUtils::atomic_write(&migration_path, &migration_content)?;
 
     CommandUtils::success(&format!("Migration created: {}", migration_path));
 
     Ok(())
 }
+
+/// Discover migration files sorted by their timestamp prefix
+fn discover_migrations() -> Result<Vec<String>> {
+    let mut migrations: Vec<String> = FileUtils::find_files_with_extension(MIGRATIONS_DIR, "rs")?
+        .iter()
+        .filter_map(FileUtils::file_stem)
+        .filter(|name| name != "mod")
+        .collect();
+
+    // Timestamp prefixes (YYYY_MM_DD_HHMMSS) sort lexicographically
+    migrations.sort();
+
+    Ok(migrations)
+}
+
+/// Create the migrations table if it doesn't exist yet
+fn ensure_migrations_table(connection: &Connection) -> Result<()> {
+    let id_column = match connection.driver.as_str() {
+        "mysql" => "id INT UNSIGNED AUTO_INCREMENT PRIMARY KEY",
+        "postgres" => "id SERIAL PRIMARY KEY",
+        _ => "id INTEGER PRIMARY KEY AUTOINCREMENT",
+    };
+
+    let sql = format!(
+        "CREATE TABLE IF NOT EXISTS {} ({}, name VARCHAR(255) NOT NULL, batch INTEGER NOT NULL, ran_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP)",
+        MIGRATIONS_TABLE, id_column
+    );
+    execute_sql(connection, &sql)?;
+
+    Ok(())
+}
+
+/// Load all migrations recorded in the migrations table
+fn ran_migrations(connection: &Connection) -> Result<Vec<MigrationRecord>> {
+    let sql = format!("SELECT name, batch FROM {} ORDER BY batch, name", MIGRATIONS_TABLE);
+    let output = execute_sql(connection, &sql)?;
+
+    Ok(parse_migration_records(&output))
+}
+
+/// Parse tab-separated `name`/`batch` rows returned by the database client
+fn parse_migration_records(output: &str) -> Vec<MigrationRecord> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (name, batch) = line.trim().split_once('\t')?;
+            Some(MigrationRecord {
+                name: name.trim().to_string(),
+                batch: batch.trim().parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+/// Determine which migration files have not been run yet
+fn pending_migrations(files: &[String], ran: &[MigrationRecord]) -> Vec<String> {
+    files
+        .iter()
+        .filter(|name| !ran.iter().any(|record| &record.name == *name))
+        .cloned()
+        .collect()
+}
+
+/// The migrations table after squashing `squashed` into `baseline`
+///
+/// The squashed rows are only replaced when their files are deleted too, otherwise
+/// `migrate up` would run the kept files again. The original migrations then still
+/// own the tables, so only they may roll them back.
+fn records_after_squash(
+    ran: &[MigrationRecord],
+    squashed: &[String],
+    baseline: &str,
+    delete_old: bool,
+    keep: bool,
+) -> Vec<MigrationRecord> {
+    let replace = delete_old && !keep;
+
+    let mut records: Vec<MigrationRecord> = ran
+        .iter()
+        .filter(|record| !replace || !squashed.contains(&record.name))
+        .cloned()
+        .collect();
+    records.push(MigrationRecord {
+        name: baseline.to_string(),
+        batch: if replace { 1 } else { SUPERSEDED_BASELINE_BATCH },
+    });
+
+    records
+}
+
+/// Determine which migrations to roll back for the last `steps` batches, newest first
+fn migrations_to_rollback(ran: &[MigrationRecord], steps: u32) -> Vec<String> {
+    if steps == 0 {
+        return Vec::new();
+    }
+
+    let ran: Vec<&MigrationRecord> = ran.iter().filter(|record| record.batch != SUPERSEDED_BASELINE_BATCH).collect();
+
+    let mut batches: Vec<u32> = ran.iter().map(|record| record.batch).collect();
+    batches.sort_unstable();
+    batches.dedup();
+
+    let oldest_batch = batches
+        .len()
+        .checked_sub(steps as usize)
+        .map(|index| batches[index])
+        .unwrap_or(0);
+
+    let mut records: Vec<&MigrationRecord> = ran
+        .into_iter()
+        .filter(|record| record.batch >= oldest_batch)
+        .collect();
+    records.sort_by(|a, b| b.batch.cmp(&a.batch).then_with(|| b.name.cmp(&a.name)));
+
+    records.into_iter().map(|record| record.name.clone()).collect()
+}
+
+/// Determine which migrations to roll back to undo `name`, newest first
+///
+/// Migrations run after `name` may depend on it, so they block the rollback
+/// unless `cascade` is set, in which case they are rolled back first.
+fn rollback_by_name(ran: &[MigrationRecord], name: &str, cascade: bool) -> Result<Vec<String>> {
+    let name = name.strip_suffix(".rs").unwrap_or(name);
+
+    if ran.iter().any(|record| record.name == name && record.batch == SUPERSEDED_BASELINE_BATCH) {
+        anyhow::bail!("'{}' is a squashed baseline; roll back the migrations it squashed instead", name);
+    }
+
+    let mut records: Vec<&MigrationRecord> = ran
+        .iter()
+        .filter(|record| record.batch != SUPERSEDED_BASELINE_BATCH)
+        .collect();
+    records.sort_by(|a, b| a.batch.cmp(&b.batch).then_with(|| a.name.cmp(&b.name)));
+
+    let position = records
+        .iter()
+        .position(|record| record.name == name)
+        .ok_or_else(|| anyhow::anyhow!("Migration '{}' has not been run", name))?;
+
+    let dependents: Vec<String> = records[position + 1..].iter().rev().map(|record| record.name.clone()).collect();
+    if !dependents.is_empty() && !cascade {
+        anyhow::bail!(
+            "Cannot roll back '{}', these migrations ran after it:\n  {}\nRoll them back first or pass --cascade",
+            name,
+            dependents.join("\n  ")
+        );
+    }
+
+    Ok(dependents.into_iter().chain([name.to_string()]).collect())
+}
+
+/// Run a single migration in the given direction through the application's migrator binary
+fn run_migration(name: &str, direction: &str) -> Result<()> {
+    if !Path::new(MIGRATIONS_DIR).join(format!("{}.rs", name)).exists() {
+        anyhow::bail!("Migration file for '{}' not found in {}", name, MIGRATIONS_DIR);
+    }
+
+    let (success, _stdout, stderr) = ProcessUtils::execute_with_output(
+        "cargo",
+        &["run", "--quiet", "--bin", "migrate", "--", direction, name],
+    )?;
+
+    if !success {
+        CommandUtils::error(&format!("Migration {} failed: {}", name, stderr.trim()));
+        anyhow::bail!("Migration '{}' failed", name);
+    }
+
+    Ok(())
+}
+
+/// Collect the SQL statements a migration's `up()` would execute, without applying them
+fn migration_sql(name: &str) -> Result<Vec<String>> {
+    if !Path::new(MIGRATIONS_DIR).join(format!("{}.rs", name)).exists() {
+        anyhow::bail!("Migration file for '{}' not found in {}", name, MIGRATIONS_DIR);
+    }
+
+    let (success, stdout, stderr) = ProcessUtils::execute_with_output(
+        "cargo",
+        &["run", "--quiet", "--bin", "migrate", "--", "up", name, "--dry-run"],
+    )?;
+
+    if !success {
+        CommandUtils::error(&format!("Migration {} failed: {}", name, stderr.trim()));
+        anyhow::bail!("Migration '{}' failed", name);
+    }
+
+    Ok(stdout
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Record a migration as run in the given batch
+fn record_migration(connection: &Connection, name: &str, batch: u32) -> Result<()> {
+    let sql = format!(
+        "INSERT INTO {} (name, batch, ran_at) VALUES ('{}', {}, CURRENT_TIMESTAMP)",
+        MIGRATIONS_TABLE,
+        escape_sql(name),
+        batch
+    );
+    execute_sql(connection, &sql)?;
+
+    Ok(())
+}
+
+/// Remove a migration from the migrations table
+fn delete_migration_record(connection: &Connection, name: &str) -> Result<()> {
+    let sql = format!("DELETE FROM {} WHERE name = '{}'", MIGRATIONS_TABLE, escape_sql(name));
+    execute_sql(connection, &sql)?;
+
+    Ok(())
+}
+
+/// Escape a string for use inside a single-quoted SQL literal
+fn escape_sql(value: &str) -> String {
+    value.replace('\'', "''")
+}
+
+/// In-memory schema built by replaying the `schema.*` calls of migration `up()` bodies
+#[derive(Debug, Default)]
+struct SchemaSnapshot {
+    /// Tables in creation order with their column statements
+    tables: Vec<(String, Vec<String>)>,
+}
+
+impl SchemaSnapshot {
+    /// Apply the schema operations found in a migration's `up()` method
+    fn apply(&mut self, source: &str) {
+        let body = match extract_fn_body(source, "fn up(") {
+            Some(body) => body,
+            None => return,
+        };
+
+        let mut rest = body;
+        while let Some(index) = rest.find("schema.") {
+            rest = &rest[index + "schema.".len()..];
+
+            let operation: String = rest.chars().take_while(|c| c.is_alphanumeric() || *c == '_').collect();
+            let table = match first_string_literal(rest) {
+                Some(table) => table,
+                None => continue,
+            };
+
+            match operation.as_str() {
+                "create" => {
+                    let columns = extract_closure_statements(rest);
+                    self.tables.retain(|(name, _)| name != &table);
+                    self.tables.push((table, columns));
+                }
+                "table" => {
+                    let statements = extract_closure_statements(rest);
+                    if let Some((_, columns)) = self.tables.iter_mut().find(|(name, _)| name == &table) {
+                        for statement in statements {
+                            apply_table_statement(columns, statement);
+                        }
+                    }
+                }
+                "drop" | "drop_if_exists" => {
+                    self.tables.retain(|(name, _)| name != &table);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Apply a `schema.table(...)` statement to an existing table's column list
+fn apply_table_statement(columns: &mut Vec<String>, statement: String) {
+    if statement.starts_with("table.drop_column(") {
+        if let Some(column) = first_string_literal(&statement) {
+            let quoted = format!("(\"{}\"", column);
+            columns.retain(|existing| !existing.contains(&quoted));
+        }
+    } else {
+        columns.push(statement);
+    }
+}
+
+/// Extract the body of the first function whose signature starts with `signature`
+fn extract_fn_body<'a>(source: &'a str, signature: &str) -> Option<&'a str> {
+    let start = source.find(signature)?;
+    let open = start + source[start..].find('{')?;
+    let close = matching_brace(source, open)?;
+    Some(&source[open + 1..close])
+}
+
+/// Extract the `table.*;` statements from the first closure body in `source`
+fn extract_closure_statements(source: &str) -> Vec<String> {
+    let open = match source.find('{') {
+        Some(open) => open,
+        None => return Vec::new(),
+    };
+    let close = match matching_brace(source, open) {
+        Some(close) => close,
+        None => return Vec::new(),
+    };
+
+    source[open + 1..close]
+        .split(';')
+        .map(|statement| statement.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|statement| statement.starts_with("table."))
+        .map(|statement| format!("{};", statement))
+        .collect()
+}
+
+/// Find the index of the brace closing the one at `open`
+fn matching_brace(source: &str, open: usize) -> Option<usize> {
+    let mut depth = 0;
+    for (index, ch) in source[open..].char_indices() {
+        match ch {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + index);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Return the contents of the first double-quoted string literal in `source`
+fn first_string_literal(source: &str) -> Option<String> {
+    let start = source.find('"')? + 1;
+    let end = start + source[start..].find('"')?;
+    Some(source[start..end].to_string())
+}
+
+/// Render the baseline migration for a squashed schema
+fn render_squashed_migration(schema: &SchemaSnapshot, squashed: &[String]) -> String {
+    let squashed_list: String = squashed
+        .iter()
+        .map(|name| format!("//! - {}\n", name))
+        .collect();
+
+    let creates: String = schema
+        .tables
+        .iter()
+        .map(|(table, columns)| {
+            let columns: String = columns
+                .iter()
+                .map(|column| format!("            {}\n", column))
+                .collect();
+            format!(
+                "        schema.create(\"{}\", |table: &mut Blueprint| {{\n{}        }})?;\n",
+                table, columns
+            )
+        })
+        .collect();
+
+    let drops: String = schema
+        .tables
+        .iter()
+        .rev()
+        .map(|(table, _)| format!("        schema.drop_if_exists(\"{}\")?;\n", table))
+        .collect();
+
+    let tables: BTreeSet<&str> = schema.tables.iter().map(|(table, _)| table.as_str()).collect();
+
+    format!(
+        r#"//! Migration: squashed_baseline
+//! Created: {created}
+//!
+//! Baseline for tables: {tables}
+//!
+//! Squashed migrations:
+{squashed_list}
+use rustisan_core::database::{{Migration, Schema}};
+use rustisan_core::database::schema::{{Blueprint, Column}};
+use anyhow::Result;
+
+pub struct SquashedBaseline {{}}
+
+impl Migration for SquashedBaseline {{
+    /// When `dry_run` is set, schema operations are rolled back and only their SQL is reported
+    fn up(&self, schema: &mut Schema, dry_run: bool) -> Result<()> {{
+        schema.dry_run(dry_run);
+{creates}        Ok(())
+    }}
+
+    fn down(&self, schema: &mut Schema) -> Result<()> {{
+{drops}        Ok(())
+    }}
+}}
+"#,
+        created = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S"),
+        tables = tables.into_iter().collect::<Vec<_>>().join(", "),
+        squashed_list = squashed_list,
+        creates = creates,
+        drops = drops,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(name: &str, batch: u32) -> MigrationRecord {
+        MigrationRecord { name: name.to_string(), batch }
+    }
+
+    #[test]
+    fn test_parse_migration_records() {
+        let output = "2024_01_01_000000_create_users_table\t1\n2024_01_02_000000_create_posts_table\t2\n";
+        assert_eq!(
+            parse_migration_records(output),
+            vec![
+                record("2024_01_01_000000_create_users_table", 1),
+                record("2024_01_02_000000_create_posts_table", 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pending_migrations() {
+        let files = vec!["2024_01_01_a".to_string(), "2024_01_02_b".to_string(), "2024_01_03_c".to_string()];
+        let ran = vec![record("2024_01_01_a", 1)];
+        assert_eq!(pending_migrations(&files, &ran), vec!["2024_01_02_b", "2024_01_03_c"]);
+    }
+
+    #[test]
+    fn test_migrations_to_rollback() {
+        let ran = vec![record("a", 1), record("b", 2), record("c", 2), record("d", 3)];
+        assert_eq!(migrations_to_rollback(&ran, 1), vec!["d"]);
+        assert_eq!(migrations_to_rollback(&ran, 2), vec!["d", "c", "b"]);
+        assert_eq!(migrations_to_rollback(&ran, 10), vec!["d", "c", "b", "a"]);
+        assert!(migrations_to_rollback(&ran, 0).is_empty());
+    }
+
+    #[test]
+    fn test_rollback_by_name() {
+        let ran = vec![
+            record("2024_01_01_a", 1),
+            record("2024_01_02_b", 1),
+            record("2024_01_03_c", 2),
+            record("2024_01_04_d", 3),
+        ];
+
+        assert_eq!(rollback_by_name(&ran, "2024_01_04_d.rs", false).unwrap(), vec!["2024_01_04_d"]);
+        assert!(rollback_by_name(&ran, "2024_01_05_e", false).is_err());
+
+        let error = rollback_by_name(&ran, "2024_01_02_b", false).unwrap_err().to_string();
+        assert!(error.contains("2024_01_03_c") && error.contains("2024_01_04_d"));
+
+        let rollback = rollback_by_name(&ran, "2024_01_02_b", true).unwrap();
+        assert_eq!(rollback, vec!["2024_01_04_d", "2024_01_03_c", "2024_01_02_b"]);
+
+        // The named migration goes while the one run before it in the same batch stays
+        assert!(rollback.contains(&"2024_01_02_b".to_string()));
+        assert!(!rollback.contains(&"2024_01_01_a".to_string()));
+        assert_eq!(rollback_by_name(&ran, "2024_01_01_a", true).unwrap().last().unwrap(), "2024_01_01_a");
+    }
+
+    #[test]
+    fn test_rollback_after_squash_keeps_baseline() {
+        let baseline = "2024_02_01_000000_squashed_baseline";
+        let ran = vec![
+            record("2024_01_01_a", 1),
+            record("2024_01_02_b", 2),
+            record(baseline, SUPERSEDED_BASELINE_BATCH),
+        ];
+
+        // `migrate down` only undoes the newest original migration, the baseline's drops never run
+        assert_eq!(migrations_to_rollback(&ran, 1), vec!["2024_01_02_b"]);
+        assert_eq!(migrations_to_rollback(&ran, 10), vec!["2024_01_02_b", "2024_01_01_a"]);
+        assert!(rollback_by_name(&ran, baseline, false).is_err());
+        assert_eq!(rollback_by_name(&ran, "2024_01_02_b", false).unwrap(), vec!["2024_01_02_b"]);
+    }
+
+    #[test]
+    fn test_squash_with_keep_leaves_nothing_pending() {
+        let baseline = "2024_02_01_000000_squashed_baseline";
+        let squashed = vec!["2024_01_01_a".to_string(), "2024_01_02_b".to_string()];
+        let ran = vec![record("2024_01_01_a", 1), record("2024_01_02_b", 2)];
+        let files = vec!["2024_01_01_a".to_string(), "2024_01_02_b".to_string(), baseline.to_string()];
+
+        // --delete-old --keep leaves the files, so their rows have to stay as well
+        let kept = records_after_squash(&ran, &squashed, baseline, true, true);
+        assert!(pending_migrations(&files, &kept).is_empty());
+        assert_eq!(kept.last().unwrap(), &record(baseline, SUPERSEDED_BASELINE_BATCH));
+        assert_eq!(kept, records_after_squash(&ran, &squashed, baseline, false, false));
+
+        let replaced = records_after_squash(&ran, &squashed, baseline, true, false);
+        assert_eq!(replaced, vec![record(baseline, 1)]);
+        assert!(pending_migrations(&[baseline.to_string()], &replaced).is_empty());
+    }
+
+    #[test]
+    fn test_squash_migrations() {
+        let migrations = [
+            (
+                "2024_01_01_000000_create_users_table",
+                r#"impl Migration for CreateUsersTable {
+    fn up(&self, schema: &mut Schema, dry_run: bool) -> Result<()> {
+        schema.create("users", |table: &mut Blueprint| {
+            table.id();
+            table.string("email");
+            table.timestamps();
+        })
+    }
+
+    fn down(&self, schema: &mut Schema) -> Result<()> {
+        schema.drop_if_exists("users")
+    }
+}"#,
+            ),
+            (
+                "2024_01_02_000000_create_posts_table",
+                r#"impl Migration for CreatePostsTable {
+    fn up(&self, schema: &mut Schema, dry_run: bool) -> Result<()> {
+        schema.create("posts", |table: &mut Blueprint| {
+            table.id();
+            table.string("title");
+        })
+    }
+}"#,
+            ),
+            (
+                "2024_01_03_000000_add_avatar_to_users",
+                r#"impl Migration for AddAvatarToUsers {
+    fn up(&self, schema: &mut Schema, dry_run: bool) -> Result<()> {
+        schema.table("users", |table: &mut Blueprint| {
+            table.string("avatar").nullable();
+            table.drop_column("email");
+        })
+    }
+}"#,
+            ),
+        ];
+
+        let mut schema = SchemaSnapshot::default();
+        for (_, source) in &migrations {
+            schema.apply(source);
+        }
+
+        let names: Vec<String> = migrations.iter().map(|(name, _)| name.to_string()).collect();
+        let output = render_squashed_migration(&schema, &names);
+
+        assert!(output.contains(r#"schema.create("users""#));
+        assert!(output.contains(r#"schema.create("posts""#));
+        assert!(output.contains(r#"table.string("avatar").nullable();"#));
+        assert!(output.contains(r#"table.string("title");"#));
+        assert!(!output.contains(r#"table.string("email");"#));
+        assert!(output.contains(r#"schema.drop_if_exists("posts")"#));
+        for name in &names {
+            assert!(output.contains(&format!("//! - {}", name)));
+        }
+    }
+}
@@ -19,6 +19,14 @@ pub mod info;
 
 pub mod package;
 pub mod dev;
+pub mod completion;
+pub mod schedule;
+pub mod maintenance;
+pub mod optimize;
+pub mod log;
+pub mod storage;
+pub mod plugin;
+pub mod tinker;
 
 // Re-export command types for easier access
 pub use crate::{
@@ -29,16 +37,79 @@ pub use crate::{
 
 use anyhow::Result;
 use colored::*;
+use std::sync::OnceLock;
 use crate::utils::{FileUtils, ProcessUtils, TextUtils};
 
+/// Output format for command messages
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Colored, human-readable text
+    #[default]
+    Human,
+    /// Newline-delimited JSON objects
+    Json,
+}
+
+/// Output format selected for this invocation
+static OUTPUT_FORMAT: OnceLock<OutputFormat> = OnceLock::new();
+
 /// Common utilities for all commands
 pub struct CommandUtils;
 
 impl CommandUtils {
+    /// Set the output format used by the message helpers
+    pub fn set_output_format(format: OutputFormat) {
+        if format == OutputFormat::Json {
+            colored::control::set_override(false);
+        }
+        let _ = OUTPUT_FORMAT.set(format);
+    }
+
+    /// Get the output format used by the message helpers
+    pub fn output_format() -> OutputFormat {
+        OUTPUT_FORMAT.get().copied().unwrap_or_default()
+    }
+
+    /// Check if machine-readable JSON output was requested
+    pub fn is_json_output() -> bool {
+        Self::output_format() == OutputFormat::Json
+    }
+
+    /// Build a JSON message object
+    fn json_message(level: &str, message: &str) -> String {
+        serde_json::json!({
+            "level": level,
+            "message": message,
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+        })
+        .to_string()
+    }
+
     /// Check if we're in a Rustisan project
     pub fn is_rustisan_project() -> bool {
-        std::path::Path::new("Cargo.toml").exists() &&
-        std::path::Path::new("rustisan.toml").exists()
+        if !std::path::Path::new("Cargo.toml").exists() || !std::path::Path::new("rustisan.toml").exists() {
+            return false;
+        }
+
+        // Workspace layouts keep the application crate in `app/`
+        !Self::is_workspace_project() || std::path::Path::new("app/Cargo.toml").exists()
+    }
+
+    /// Check if the current project is a Cargo workspace
+    pub fn is_workspace_project() -> bool {
+        std::fs::read_to_string("Cargo.toml")
+            .ok()
+            .and_then(|content| content.parse::<toml::Value>().ok())
+            .is_some_and(|manifest| manifest.get("workspace").is_some())
+    }
+
+    /// Directory of the application crate, relative to the project root
+    pub fn app_dir() -> std::path::PathBuf {
+        if Self::is_workspace_project() {
+            std::path::PathBuf::from("app")
+        } else {
+            std::path::PathBuf::new()
+        }
     }
 
     /// Ensure we're in a Rustisan project
@@ -56,21 +127,37 @@ impl CommandUtils {
 
     /// Print success message
     pub fn success(message: &str) {
+        if Self::is_json_output() {
+            println!("{}", Self::json_message("success", message));
+            return;
+        }
         println!("{} {}", "✓".green().bold(), message);
     }
 
     /// Print info message
     pub fn info(message: &str) {
+        if Self::is_json_output() {
+            println!("{}", Self::json_message("info", message));
+            return;
+        }
         println!("{} {}", "ℹ".blue().bold(), message);
     }
 
     /// Print warning message
     pub fn warning(message: &str) {
+        if Self::is_json_output() {
+            println!("{}", Self::json_message("warning", message));
+            return;
+        }
         println!("{} {}", "⚠".yellow().bold(), message);
     }
 
     /// Print error message
     pub fn error(message: &str) {
+        if Self::is_json_output() {
+            println!("{}", Self::json_message("error", message));
+            return;
+        }
         eprintln!("{} {}", "✗".red().bold(), message);
     }
 
@@ -114,3 +201,19 @@ impl CommandUtils {
         FileUtils::read_file(path)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_message() {
+        let line = CommandUtils::json_message("info", "Hello \"world\"");
+        let message: serde_json::Value = serde_json::from_str(&line).unwrap();
+
+        assert_eq!(message["level"], "info");
+        assert_eq!(message["message"], "Hello \"world\"");
+        assert!(message["timestamp"].is_string());
+        assert!(!line.contains('\n'));
+    }
+}
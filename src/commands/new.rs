@@ -10,8 +10,28 @@ use std::process::Command;
 
 use super::CommandUtils;
 
+mod interactive;
+mod remote_template;
+
+pub use interactive::ProjectOptions;
+
+/// Layout flags of the new command that are not part of the questionnaire
+#[derive(Debug, Clone, Copy)]
+pub struct ProjectLayout {
+    pub workspace: bool,
+    pub docker: bool,
+    pub ci: bool,
+}
+
 /// Handle the new command
-pub async fn handle(name: String, path: Option<String>, template: Option<String>, git: bool) -> Result<()> {
+pub async fn handle(
+    name: String,
+    path: Option<String>,
+    template: Option<String>,
+    git: bool,
+    no_interaction: bool,
+    layout: ProjectLayout,
+) -> Result<()> {
     // Determine the project path
     let project_path = if let Some(p) = path {
         PathBuf::from(p).join(&name)
@@ -24,13 +44,39 @@ pub async fn handle(name: String, path: Option<String>, template: Option<String>
         anyhow::bail!("Directory '{}' already exists", project_path.display());
     }
 
+    // Ask how the project should be set up unless a template was chosen
+    let mut options = if template.is_none() && !no_interaction && interactive::is_interactive() {
+        interactive::prompt()?
+    } else {
+        ProjectOptions::default()
+    };
+    options.workspace = layout.workspace;
+    options.docker = layout.docker;
+    options.ci = layout.ci;
+    let template = template.or_else(|| options.scaffolding.clone());
+
+    // Clone remote templates before touching the project directory
+    let (template, remote) = match template {
+        Some(url) if remote_template::is_remote_template(&url) => {
+            CommandUtils::info(&format!("Cloning template '{}'...", url));
+            (None, Some(remote_template::clone_template(&url)?))
+        }
+        template => (template, None),
+    };
+
     CommandUtils::info(&format!("Creating new Rustisan application '{}'...", name));
 
     // Create project directory
     fs::create_dir_all(&project_path)?;
 
     // Create project structure
-    create_project_structure(&project_path, &name, template.as_deref()).await?;
+    create_project_structure(&project_path, &name, template.as_deref(), &options).await?;
+
+    // Copy the remote template over the generated project
+    if let Some(cloned) = &remote {
+        remote_template::apply_cloned_template(cloned, &project_path, &name, &options.author())?;
+        CommandUtils::info("Remote template applied");
+    }
 
     // Initialize git repository if requested
     if git {
@@ -50,28 +96,49 @@ pub async fn handle(name: String, path: Option<String>, template: Option<String>
 }
 
 /// Create the project structure
-async fn create_project_structure(path: &Path, name: &str, template: Option<&str>) -> Result<()> {
-    // Create main Cargo.toml
-    create_main_cargo_toml(path, name)?;
-
+async fn create_project_structure(
+    path: &Path,
+    name: &str,
+    template: Option<&str>,
+    options: &ProjectOptions,
+) -> Result<()> {
     // Create rustisan.toml configuration
-    create_rustisan_config(path)?;
+    create_rustisan_config(path, options)?;
 
     // Create .gitignore
     create_gitignore(path)?;
 
-    // Create src directory structure
-    create_src_structure(path)?;
-
     // Create other directories
     create_directory_structure(path)?;
 
-    // Create main.rs
-    create_main_rs(path, name)?;
+    if options.workspace {
+        // Create workspace members
+        create_workspace_structure(path, name, options)?;
+    } else {
+        // Create main Cargo.toml
+        create_main_cargo_toml(path, name, options)?;
+
+        // Create src directory structure
+        create_src_structure(path)?;
+
+        // Create main.rs
+        create_main_rs(path, name)?;
+    }
 
     // Create README.md
     create_readme(path, name)?;
 
+    // Create container files
+    if options.docker {
+        create_dockerfile(path, name, options)?;
+        create_docker_compose(path, name, options)?;
+    }
+
+    // Create CI workflow
+    if options.ci {
+        create_ci_workflow(path)?;
+    }
+
     // Apply template if specified
     if let Some(template_name) = template {
         apply_template(path, template_name).await?;
@@ -81,12 +148,13 @@ async fn create_project_structure(path: &Path, name: &str, template: Option<&str
 }
 
 /// Create main Cargo.toml
-fn create_main_cargo_toml(path: &Path, name: &str) -> Result<()> {
+fn create_main_cargo_toml(path: &Path, name: &str, options: &ProjectOptions) -> Result<()> {
     let cargo_toml = format!(r#"[package]
-name = "{}"
+name = "{name}"
 version = "0.1.0"
 edition = "2024"
-authors = ["Your Name <your.email@example.com>"]
+authors = [{author:?}]
+license = {license:?}
 description = "A Rustisan web application"
 
 [dependencies]
@@ -101,22 +169,148 @@ chrono = {{ version = "0.4", features = ["serde"] }}
 tokio-test = "0.4"
 
 [[bin]]
-name = "{}"
+name = "{name}"
 path = "src/main.rs"
 
-"#, name, name);
+"#, name = name, author = options.author(), license = options.license);
 
     fs::write(path.join("Cargo.toml"), cargo_toml)?;
     Ok(())
 }
 
+/// Create a Cargo workspace with `app`, `core` and `cli` members
+fn create_workspace_structure(path: &Path, name: &str, options: &ProjectOptions) -> Result<()> {
+    let workspace_toml = format!(r#"[workspace]
+resolver = "2"
+members = ["app", "core", "cli"]
+
+[workspace.package]
+version = "0.1.0"
+edition = "2024"
+authors = [{author:?}]
+license = {license:?}
+
+[workspace.dependencies]
+rustisan-core = "0.0.1"
+{name}-core = {{ path = "core" }}
+
+tokio = {{ version = "1.0", features = ["full"] }}
+serde_json = "1.0"
+tracing = "0.1"
+chrono = {{ version = "0.4", features = ["serde"] }}
+"#, name = name, author = options.author(), license = options.license);
+
+    fs::write(path.join("Cargo.toml"), workspace_toml)?;
+
+    // The application crate keeps the usual single-crate layout
+    let app_path = path.join("app");
+    fs::create_dir_all(&app_path)?;
+    fs::write(app_path.join("Cargo.toml"), format!(r#"[package]
+name = "{name}"
+description = "A Rustisan web application"
+version.workspace = true
+edition.workspace = true
+authors.workspace = true
+license.workspace = true
+
+[dependencies]
+rustisan-core.workspace = true
+{name}-core.workspace = true
+
+tokio.workspace = true
+serde_json.workspace = true
+tracing.workspace = true
+chrono.workspace = true
+
+[dev-dependencies]
+tokio-test = "0.4"
+
+[[bin]]
+name = "{name}"
+path = "src/main.rs"
+"#))?;
+    create_src_structure(&app_path)?;
+    create_main_rs(&app_path, name)?;
+    fs::create_dir_all(app_path.join("tests").join("unit"))?;
+    fs::create_dir_all(app_path.join("tests").join("integration"))?;
+
+    // Shared domain code
+    let core_path = path.join("core");
+    fs::create_dir_all(core_path.join("src"))?;
+    fs::write(core_path.join("Cargo.toml"), format!(r#"[package]
+name = "{name}-core"
+description = "Shared domain logic for {name}"
+version.workspace = true
+edition.workspace = true
+authors.workspace = true
+license.workspace = true
+
+[dependencies]
+serde_json.workspace = true
+chrono.workspace = true
+"#))?;
+    fs::write(core_path.join("src").join("lib.rs"), "//! Shared domain logic\n")?;
+
+    // Command line entry point
+    let cli_path = path.join("cli");
+    fs::create_dir_all(cli_path.join("src"))?;
+    fs::write(cli_path.join("Cargo.toml"), format!(r#"[package]
+name = "{name}-cli"
+description = "Command line interface for {name}"
+version.workspace = true
+edition.workspace = true
+authors.workspace = true
+license.workspace = true
+
+[dependencies]
+{name}-core.workspace = true
+
+tokio.workspace = true
+"#))?;
+    fs::write(cli_path.join("src").join("main.rs"), format!(r#"//! Command line interface for {name}
+
+#[tokio::main]
+async fn main() {{
+    println!("{name} CLI");
+}}
+"#))?;
+
+    Ok(())
+}
+
 /// Create rustisan.toml configuration
-fn create_rustisan_config(path: &Path) -> Result<()> {
-    let config = r#"[app]
+fn create_rustisan_config(path: &Path, options: &ProjectOptions) -> Result<()> {
+    let database = match options.database_driver.as_str() {
+        "sqlite" => r#"driver = "sqlite"
+database = "database/database.sqlite"
+"#
+        .to_string(),
+        driver => {
+            let (port, username) = if driver == "postgres" { (5432, "postgres") } else { (3306, "root") };
+            // utf8mb4 is a MySQL character set, PostgreSQL takes the encoding of the database
+            let charset = if driver == "mysql" { "charset = \"utf8mb4\"\n" } else { "" };
+            format!(r#"driver = "{driver}"
+host = "localhost"
+port = {port}
+database = "rustisan_app"
+username = "{username}"
+password = ""
+{charset}pool_min = 1
+pool_max = 10
+timeout = 30
+"#)
+        }
+    };
+
+    let workspace = if options.workspace { "workspace = true\n\n" } else { "" };
+
+    let port = options.port;
+
+    let mut config = format!(r#"{workspace}[app]
 name = "Rustisan App"
 env = "development"
 debug = true
-url = "http://localhost:3000"
+url = "http://localhost:{port}"
 timezone = "UTC"
 locale = "en"
 key = ""
@@ -124,7 +318,7 @@ cors_enabled = true
 
 [server]
 host = "127.0.0.1"
-port = 3000
+port = {port}
 timeout = 60
 max_connections = 1000
 https_enabled = false
@@ -133,21 +327,18 @@ https_enabled = false
 default = "default"
 
 [database.connections.default]
-driver = "mysql"
-host = "localhost"
-port = 3306
-database = "rustisan_app"
-username = "root"
-password = ""
-charset = "utf8mb4"
-pool_min = 1
-pool_max = 10
-timeout = 30
+{database}"#);
 
+    if options.cache {
+        config.push_str(r#"
 [cache]
 default = "memory"
 ttl = 3600
+"#);
+    }
 
+    if options.session {
+        config.push_str(r#"
 [session]
 driver = "cookie"
 lifetime = 120
@@ -157,10 +348,24 @@ cookie_name = "rustisan_session"
 cookie_path = "/"
 cookie_secure = false
 cookie_http_only = true
+"#);
+    }
 
+    if options.queue {
+        config.push_str(r#"
+[queue]
+default = "redis"
+connection = "redis://127.0.0.1:6379"
+queues = ["default"]
+retry_after = 90
+"#);
+    }
+
+    config.push_str(r#"
 [logging]
 level = "info"
 default = "console"
+path = "storage/logs/rustisan.log"
 
 # Additional configuration sections can be added here
 # For example:
@@ -185,7 +390,7 @@ default = "console"
 # default_version = "v1"
 # prefix = "api"
 
-"#;
+"#);
 
     fs::write(path.join("rustisan.toml"), config)?;
     Ok(())
@@ -193,6 +398,157 @@ default = "console"
 
 
 
+/// Create Dockerfile
+fn create_dockerfile(path: &Path, name: &str, options: &ProjectOptions) -> Result<()> {
+    let dockerfile = format!(r#"# Build stage
+FROM rust:latest AS builder
+
+WORKDIR /usr/src/{name}
+COPY . .
+RUN cargo build --release --bin {name}
+
+# Runtime stage
+FROM debian:bookworm-slim
+
+RUN apt-get update \
+    && apt-get install -y --no-install-recommends ca-certificates \
+    && rm -rf /var/lib/apt/lists/*
+
+WORKDIR /app
+COPY --from=builder /usr/src/{name}/target/release/{name} /usr/local/bin/{name}
+COPY rustisan.toml ./
+
+EXPOSE {port}
+
+CMD ["{name}"]
+"#, name = name, port = options.port);
+
+    fs::write(path.join("Dockerfile"), dockerfile)?;
+    Ok(())
+}
+
+/// Create docker-compose.yml
+fn create_docker_compose(path: &Path, name: &str, options: &ProjectOptions) -> Result<()> {
+    let port = options.port;
+    let database = match options.database_driver.as_str() {
+        "mysql" => Some(r#"  db:
+    image: mysql:8
+    environment:
+      MYSQL_DATABASE: rustisan_app
+      MYSQL_ALLOW_EMPTY_PASSWORD: "yes"
+    ports:
+      - "3306:3306"
+    volumes:
+      - db-data:/var/lib/mysql
+"#),
+        "postgres" => Some(r#"  db:
+    image: postgres:16
+    environment:
+      POSTGRES_DB: rustisan_app
+      POSTGRES_USER: postgres
+      POSTGRES_HOST_AUTH_METHOD: trust
+    ports:
+      - "5432:5432"
+    volumes:
+      - db-data:/var/lib/postgresql/data
+"#),
+        _ => None,
+    };
+
+    let mut depends_on = Vec::new();
+    if database.is_some() {
+        depends_on.push("db");
+    }
+    if options.queue {
+        depends_on.push("redis");
+    }
+
+    let mut compose = format!(r#"services:
+  app:
+    build: .
+    image: {name}
+    ports:
+      - "{port}:{port}"
+"#);
+
+    if !depends_on.is_empty() {
+        compose.push_str("    depends_on:\n");
+        for service in &depends_on {
+            compose.push_str(&format!("      - {}\n", service));
+        }
+    }
+
+    if let Some(database) = database {
+        compose.push('\n');
+        compose.push_str(database);
+    }
+
+    if options.queue {
+        compose.push_str(r#"
+  redis:
+    image: redis:7
+    ports:
+      - "6379:6379"
+"#);
+    }
+
+    if database.is_some() {
+        compose.push_str("\nvolumes:\n  db-data:\n");
+    }
+
+    fs::write(path.join("docker-compose.yml"), compose)?;
+    Ok(())
+}
+
+/// Create GitHub Actions CI workflow
+fn create_ci_workflow(path: &Path) -> Result<()> {
+    let workflow = r#"name: CI
+
+on:
+  push:
+    branches: [main]
+  pull_request:
+
+env:
+  CARGO_TERM_COLOR: always
+
+jobs:
+  check:
+    name: Check
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - uses: dtolnay/rust-toolchain@stable
+      - uses: Swatinem/rust-cache@v2
+      - run: cargo check --workspace --all-targets
+
+  test:
+    name: Test
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - uses: dtolnay/rust-toolchain@stable
+      - uses: Swatinem/rust-cache@v2
+      - run: cargo test --workspace
+
+  clippy:
+    name: Clippy
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/checkout@v4
+      - uses: dtolnay/rust-toolchain@stable
+        with:
+          components: clippy
+      - uses: Swatinem/rust-cache@v2
+      - run: cargo clippy --workspace --all-targets -- -D warnings
+"#;
+
+    let workflows = path.join(".github").join("workflows");
+    fs::create_dir_all(&workflows)?;
+    fs::write(workflows.join("ci.yml"), workflow)?;
+    Ok(())
+}
+
 /// Create .gitignore
 fn create_gitignore(path: &Path) -> Result<()> {
     let gitignore = r#"# Rust
@@ -227,6 +583,9 @@ storage/cache/
 storage/sessions/
 storage/logs/
 
+# Development TLS certificates
+storage/certs/
+
 # Build artifacts
 dist/
 build/
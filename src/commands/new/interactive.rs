@@ -0,0 +1,144 @@
+//! Interactive questionnaire for the new command
+//!
+//! Asks the user how the new project should be configured. The answers are
+//! collected into [`ProjectOptions`], which drives the generated
+//! `Cargo.toml` and `rustisan.toml`.
+
+use anyhow::Result;
+use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
+use std::io::IsTerminal;
+use std::process::Command;
+
+/// Database drivers offered by the questionnaire
+pub const DATABASE_DRIVERS: &[&str] = &["mysql", "postgres", "sqlite"];
+
+/// Scaffolding templates offered by the questionnaire
+pub const SCAFFOLDINGS: &[&str] = &["web", "api"];
+
+/// Licenses offered by the questionnaire
+pub const LICENSES: &[&str] = &["MIT", "Apache-2.0", "MIT OR Apache-2.0", "GPL-3.0", "Proprietary"];
+
+/// Options used to generate a new project
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectOptions {
+    pub database_driver: String,
+    pub scaffolding: Option<String>,
+    pub session: bool,
+    pub cache: bool,
+    pub queue: bool,
+    pub author_name: String,
+    pub author_email: String,
+    pub license: String,
+    pub port: u16,
+    pub workspace: bool,
+    pub docker: bool,
+    pub ci: bool,
+}
+
+impl Default for ProjectOptions {
+    fn default() -> Self {
+        Self {
+            database_driver: "mysql".to_string(),
+            scaffolding: None,
+            session: true,
+            cache: true,
+            queue: false,
+            author_name: "Your Name".to_string(),
+            author_email: "your.email@example.com".to_string(),
+            license: "MIT".to_string(),
+            port: 3000,
+            workspace: false,
+            docker: true,
+            ci: true,
+        }
+    }
+}
+
+impl ProjectOptions {
+    /// Author entry for the `authors` field of Cargo.toml
+    pub fn author(&self) -> String {
+        if self.author_email.is_empty() {
+            self.author_name.clone()
+        } else {
+            format!("{} <{}>", self.author_name, self.author_email)
+        }
+    }
+}
+
+/// Whether the questionnaire can be shown
+pub fn is_interactive() -> bool {
+    std::io::stdin().is_terminal() && std::io::stdout().is_terminal()
+}
+
+/// Ask the user how the project should be set up
+pub fn prompt() -> Result<ProjectOptions> {
+    let theme = ColorfulTheme::default();
+    let defaults = ProjectOptions::default();
+
+    let driver = Select::with_theme(&theme)
+        .with_prompt("Which database driver will you use?")
+        .items(&["MySQL", "PostgreSQL", "SQLite"])
+        .default(0)
+        .interact()?;
+
+    let scaffolding = Select::with_theme(&theme)
+        .with_prompt("Which scaffolding would you like?")
+        .items(&["Web application", "API only"])
+        .default(0)
+        .interact()?;
+
+    let session = Confirm::with_theme(&theme)
+        .with_prompt("Enable sessions?")
+        .default(defaults.session)
+        .interact()?;
+
+    let cache = Confirm::with_theme(&theme)
+        .with_prompt("Enable cache?")
+        .default(defaults.cache)
+        .interact()?;
+
+    let queue = Confirm::with_theme(&theme)
+        .with_prompt("Enable queues?")
+        .default(defaults.queue)
+        .interact()?;
+
+    let author_name: String = Input::with_theme(&theme)
+        .with_prompt("Author name")
+        .default(git_config("user.name").unwrap_or(defaults.author_name))
+        .interact_text()?;
+
+    let author_email: String = Input::with_theme(&theme)
+        .with_prompt("Author email")
+        .default(git_config("user.email").unwrap_or(defaults.author_email))
+        .allow_empty(true)
+        .interact_text()?;
+
+    let license = Select::with_theme(&theme)
+        .with_prompt("License")
+        .items(LICENSES)
+        .default(0)
+        .interact()?;
+
+    Ok(ProjectOptions {
+        database_driver: DATABASE_DRIVERS[driver].to_string(),
+        scaffolding: Some(SCAFFOLDINGS[scaffolding].to_string()),
+        session,
+        cache,
+        queue,
+        author_name,
+        author_email,
+        license: LICENSES[license].to_string(),
+        ..defaults
+    })
+}
+
+/// Read a value from the user's git configuration
+fn git_config(key: &str) -> Option<String> {
+    let output = Command::new("git").args(["config", "--get", key]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!value.is_empty()).then_some(value)
+}
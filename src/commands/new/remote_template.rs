@@ -0,0 +1,157 @@
+//! Remote Git templates for the new command
+//!
+//! A template given as a Git URL is cloned into a temporary directory and
+//! its contents are copied over the generated project. Templates may ship a
+//! `rustisan-template.toml` manifest describing placeholder substitutions.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use walkdir::WalkDir;
+
+/// Manifest file looked up in the root of a cloned template
+pub const MANIFEST_FILE: &str = "rustisan-template.toml";
+
+/// Template manifest
+#[derive(Debug, Default, Deserialize)]
+struct TemplateManifest {
+    #[serde(default)]
+    substitutions: Substitutions,
+}
+
+/// Post-clone substitution settings
+#[derive(Debug, Deserialize)]
+struct Substitutions {
+    /// Placeholders to replace, e.g. `app_name` for `{{app_name}}`
+    #[serde(default = "default_placeholders")]
+    placeholders: Vec<String>,
+    /// Paths, relative to the template root, that are copied verbatim
+    #[serde(default)]
+    exclude: Vec<String>,
+}
+
+impl Default for Substitutions {
+    fn default() -> Self {
+        Self {
+            placeholders: default_placeholders(),
+            exclude: Vec::new(),
+        }
+    }
+}
+
+fn default_placeholders() -> Vec<String> {
+    vec!["app_name".to_string(), "author".to_string()]
+}
+
+/// A cloned template waiting to be applied
+pub struct ClonedTemplate {
+    dir: PathBuf,
+}
+
+impl Drop for ClonedTemplate {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Check whether a template argument refers to a Git repository
+pub fn is_remote_template(template: &str) -> bool {
+    ["http://", "https://", "ssh://", "git://", "file://", "git@"]
+        .iter()
+        .any(|scheme| template.starts_with(scheme))
+        || template.ends_with(".git")
+}
+
+/// Clone a template repository into a temporary directory
+pub fn clone_template(url: &str) -> Result<ClonedTemplate> {
+    let dir = std::env::temp_dir().join(format!("rustisan-template-{}", uuid::Uuid::new_v4()));
+
+    let output = Command::new("git")
+        .args(["clone", "--depth", "1", "--quiet", url])
+        .arg(&dir)
+        .output()
+        .context("Failed to run git; is it installed?")?;
+
+    let template = ClonedTemplate { dir };
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "Failed to clone template '{}': {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    if !template.dir.is_dir() {
+        anyhow::bail!("Template '{}' was not cloned", url);
+    }
+
+    Ok(template)
+}
+
+/// Copy a cloned template over the project and apply its substitutions
+pub fn apply_cloned_template(template: &ClonedTemplate, project: &Path, app_name: &str, author: &str) -> Result<()> {
+    let manifest_path = template.dir.join(MANIFEST_FILE);
+    let manifest: TemplateManifest = if manifest_path.exists() {
+        toml::from_str(&fs::read_to_string(&manifest_path)?)
+            .with_context(|| format!("Invalid template manifest {}", MANIFEST_FILE))?
+    } else {
+        TemplateManifest::default()
+    };
+
+    let substitutions: Vec<(String, &str)> = manifest
+        .substitutions
+        .placeholders
+        .iter()
+        .filter_map(|placeholder| {
+            let value = match placeholder.as_str() {
+                "app_name" => app_name,
+                "author" => author,
+                _ => return None,
+            };
+            Some((format!("{{{{{}}}}}", placeholder), value))
+        })
+        .collect();
+
+    let entries = WalkDir::new(&template.dir)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|entry| entry.file_name() != ".git");
+
+    for entry in entries {
+        let entry = entry?;
+        let relative = entry.path().strip_prefix(&template.dir)?;
+        let destination = project.join(relative);
+
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&destination)?;
+            continue;
+        }
+
+        if relative == Path::new(MANIFEST_FILE) {
+            continue;
+        }
+
+        let excluded = manifest
+            .substitutions
+            .exclude
+            .iter()
+            .any(|prefix| relative.starts_with(prefix));
+
+        match fs::read_to_string(entry.path()) {
+            Ok(content) if !excluded => {
+                let content = substitutions
+                    .iter()
+                    .fold(content, |acc, (placeholder, value)| acc.replace(placeholder, value));
+                fs::write(&destination, content)?;
+            }
+            _ => {
+                fs::copy(entry.path(), &destination)?;
+            }
+        }
+    }
+
+    Ok(())
+}
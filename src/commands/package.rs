@@ -1,10 +1,17 @@
 //! Package command implementations for the Rustisan CLI
 
+mod audit;
+mod graph;
+mod search;
+
 use anyhow::Result;
 use colored::*;
 use crate::PackageCommands;
+use crate::utils::{ProcessUtils, TextUtils};
 use super::CommandUtils;
 
+pub use search::SearchSort;
+
 /// Handle package command
 pub async fn handle(operation: PackageCommands) -> Result<()> {
     CommandUtils::ensure_rustisan_project()?;
@@ -22,6 +29,15 @@ pub async fn handle(operation: PackageCommands) -> Result<()> {
         PackageCommands::Update => {
             update_packages().await
         }
+        PackageCommands::Audit { fix } => {
+            audit_packages(fix).await
+        }
+        PackageCommands::Graph { depth, package, output } => {
+            dependency_graph(depth, package, output).await
+        }
+        PackageCommands::Search { query, sort, page } => {
+            search_packages(&query, sort, page as usize).await
+        }
     }
 }
 
@@ -316,22 +332,34 @@ async fn show_update_summary() -> Result<()> {
     Ok(())
 }
 
-/// Search for packages in the registry
-pub async fn search_packages(query: &str) -> Result<()> {
-    CommandUtils::info(&format!("Searching for packages matching: {}", query));
+/// Search crates.io for packages
+pub async fn search_packages(query: &str, sort: SearchSort, page: usize) -> Result<()> {
+    if !CommandUtils::is_json_output() {
+        CommandUtils::info(&format!("Searching for packages matching: {}", query));
+    }
 
-    let output = std::process::Command::new("cargo")
-        .args(&["search", query])
-        .output()?;
+    let results = search::search(search::CRATES_IO_URL, query, sort, page).await?;
 
-    if output.status.success() {
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        println!("{}", stdout);
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        CommandUtils::error(&format!("Search failed: {}", stderr));
+    if CommandUtils::is_json_output() {
+        println!("{}", serde_json::to_string_pretty(&results.crates)?);
+        return Ok(());
     }
 
+    if results.crates.is_empty() {
+        CommandUtils::warning("No packages found");
+        return Ok(());
+    }
+
+    println!(
+        "{}",
+        TextUtils::table_format(
+            &["Name", "Version", "Description", "Downloads", "Link"],
+            &search::result_rows(&results),
+            &[25, 12, 50, 14, 45],
+        )
+    );
+    println!("{}", format!("Page {} of {} ({} crates)", page, results.pages(), results.meta.total).dimmed());
+
     Ok(())
 }
 
@@ -347,3 +375,112 @@ pub async fn check_outdated() -> Result<()> {
 
     Ok(())
 }
+
+/// Audit the locked dependencies for known vulnerabilities, failing on critical ones
+async fn audit_packages(fix: bool) -> Result<()> {
+    ensure_cargo_audit_installed()?;
+
+    if fix {
+        CommandUtils::info("Upgrading vulnerable dependencies...");
+        ProcessUtils::execute_or_fail("cargo", &["audit", "fix"])?;
+    }
+
+    if !CommandUtils::is_json_output() {
+        CommandUtils::info("Auditing dependencies...");
+    }
+
+    // cargo audit exits non-zero when it finds vulnerabilities, the report is still on stdout
+    let (_success, stdout, stderr) = ProcessUtils::execute_with_output("cargo", &["audit", "--json"])?;
+    if stdout.trim().is_empty() {
+        anyhow::bail!("cargo audit failed: {}", stderr.trim());
+    }
+    let advisories = audit::parse_audit_output(&stdout)?;
+
+    if CommandUtils::is_json_output() {
+        println!("{}", serde_json::to_string_pretty(&advisories)?);
+    } else if advisories.is_empty() {
+        CommandUtils::success("No known vulnerabilities found");
+    } else {
+        println!(
+            "{}",
+            TextUtils::table_format(
+                &["Severity", "Advisory", "Package", "Title", "URL"],
+                &audit::advisory_rows(&advisories),
+                &[8, 17, 30, 50, 50],
+            )
+        );
+        CommandUtils::warning(&format!("{} {} found", advisories.len(), vulnerabilities(advisories.len())));
+    }
+
+    let critical = advisories.iter().filter(|advisory| advisory.severity == audit::Severity::Critical).count();
+    if critical > 0 {
+        anyhow::bail!("{} critical {} found", critical, vulnerabilities(critical));
+    }
+
+    Ok(())
+}
+
+fn vulnerabilities(count: usize) -> &'static str {
+    if count == 1 { "vulnerability" } else { "vulnerabilities" }
+}
+
+fn ensure_cargo_audit_installed() -> Result<()> {
+    if ProcessUtils::command_exists("cargo-audit") {
+        return Ok(());
+    }
+
+    CommandUtils::warning("cargo-audit is not installed");
+
+    let install = std::io::IsTerminal::is_terminal(&std::io::stdin())
+        && dialoguer::Confirm::new()
+            .with_prompt("Install cargo-audit now?")
+            .default(true)
+            .interact()?;
+
+    if !install {
+        CommandUtils::info("Install it with: cargo install cargo-audit");
+        return Err(anyhow::anyhow!("cargo-audit is required to audit dependencies"));
+    }
+
+    CommandUtils::info("Installing cargo-audit...");
+    if !ProcessUtils::execute("cargo", &["install", "cargo-audit"])? {
+        return Err(anyhow::anyhow!("Failed to install cargo-audit"));
+    }
+
+    Ok(())
+}
+
+/// Show the dependency tree, or write it as Graphviz (.dot) or Mermaid (.mmd)
+async fn dependency_graph(depth: Option<usize>, package: Option<String>, output: Option<String>) -> Result<()> {
+    let (success, stdout, stderr) =
+        ProcessUtils::execute_with_output("cargo", &["tree", "--format", "{p}", "--prefix", "indent"])?;
+    if !success {
+        anyhow::bail!("cargo tree failed: {}", stderr.trim());
+    }
+
+    let graph = graph::DependencyGraph::parse(&stdout);
+    let root = match package {
+        Some(name) => graph
+            .find(&name)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Package '{}' is not in the dependency tree", name))?,
+        None => graph.root.clone(),
+    };
+
+    let Some(path) = output else {
+        println!("{}", graph.render_tree(&root, depth));
+        return Ok(());
+    };
+
+    let edges = graph.edges(&root, depth);
+    let content = match std::path::Path::new(&path).extension().and_then(|ext| ext.to_str()) {
+        Some("dot") => graph::to_dot(&edges),
+        Some("mmd") => graph::to_mermaid(&edges),
+        _ => anyhow::bail!("Unsupported graph format for '{}' (expected .dot or .mmd)", path),
+    };
+
+    CommandUtils::write_file(&path, &content)?;
+    CommandUtils::success(&format!("Wrote {} dependency edge(s) to {}", edges.len(), path));
+
+    Ok(())
+}
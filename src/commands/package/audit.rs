@@ -0,0 +1,239 @@
+//! Parsing of `cargo audit --json` reports
+//!
+//! cargo-audit reports a CVSS v3 vector rather than a severity, so the
+//! severity is derived from the vector's base score.
+
+use anyhow::{Context, Result};
+use colored::*;
+
+/// Severity rating of a CVSS v3 base score
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Unknown,
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl Severity {
+    fn from_score(score: f64) -> Self {
+        match score {
+            score if score >= 9.0 => Self::Critical,
+            score if score >= 7.0 => Self::High,
+            score if score >= 4.0 => Self::Medium,
+            score if score > 0.0 => Self::Low,
+            _ => Self::Unknown,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Unknown => "unknown",
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+            Self::Critical => "critical",
+        }
+    }
+
+    pub fn colored(self) -> ColoredString {
+        match self {
+            Self::Critical => self.label().red().bold(),
+            Self::High => self.label().truecolor(255, 165, 0),
+            Self::Medium => self.label().yellow(),
+            Self::Low => self.label().blue(),
+            Self::Unknown => self.label().dimmed(),
+        }
+    }
+}
+
+/// A vulnerability affecting a locked dependency
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct Advisory {
+    pub id: String,
+    pub package: String,
+    pub version: String,
+    pub title: String,
+    pub severity: Severity,
+    pub url: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+struct Report {
+    vulnerabilities: Vulnerabilities,
+}
+
+#[derive(serde::Deserialize)]
+struct Vulnerabilities {
+    list: Vec<Vulnerability>,
+}
+
+#[derive(serde::Deserialize)]
+struct Vulnerability {
+    advisory: RawAdvisory,
+    package: LockedPackage,
+}
+
+#[derive(serde::Deserialize)]
+struct RawAdvisory {
+    id: String,
+    package: String,
+    title: String,
+    cvss: Option<String>,
+    url: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+struct LockedPackage {
+    version: String,
+}
+
+/// Vulnerabilities in a `cargo audit --json` report, most severe first
+pub fn parse_audit_output(json: &str) -> Result<Vec<Advisory>> {
+    let report: Report = serde_json::from_str(json).context("Failed to parse cargo audit output")?;
+
+    let mut advisories: Vec<Advisory> = report
+        .vulnerabilities
+        .list
+        .into_iter()
+        .map(|vulnerability| {
+            let advisory = vulnerability.advisory;
+            Advisory {
+                severity: advisory.cvss.as_deref().and_then(cvss_score).map_or(Severity::Unknown, Severity::from_score),
+                id: advisory.id,
+                package: advisory.package,
+                version: vulnerability.package.version,
+                title: advisory.title,
+                url: advisory.url,
+            }
+        })
+        .collect();
+
+    advisories.sort_by_key(|advisory| std::cmp::Reverse(advisory.severity));
+    Ok(advisories)
+}
+
+/// Table rows of `package audit`
+pub fn advisory_rows(advisories: &[Advisory]) -> Vec<Vec<String>> {
+    advisories
+        .iter()
+        .map(|advisory| {
+            vec![
+                advisory.severity.colored().to_string(),
+                advisory.id.clone(),
+                format!("{} {}", advisory.package, advisory.version),
+                advisory.title.clone(),
+                advisory.url.clone().unwrap_or_default(),
+            ]
+        })
+        .collect()
+}
+
+/// Base score of a CVSS v3.0 or v3.1 vector such as `CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H`
+fn cvss_score(vector: &str) -> Option<f64> {
+    let metric = |name: &str| {
+        vector
+            .split('/')
+            .filter_map(|part| part.split_once(':'))
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value)
+    };
+    let changed = metric("S")? == "C";
+
+    let attack_vector = match metric("AV")? {
+        "N" => 0.85,
+        "A" => 0.62,
+        "L" => 0.55,
+        "P" => 0.2,
+        _ => return None,
+    };
+    let attack_complexity = match metric("AC")? {
+        "L" => 0.77,
+        "H" => 0.44,
+        _ => return None,
+    };
+    let privileges = match (metric("PR")?, changed) {
+        ("N", _) => 0.85,
+        ("L", false) => 0.62,
+        ("L", true) => 0.68,
+        ("H", false) => 0.27,
+        ("H", true) => 0.5,
+        _ => return None,
+    };
+    let user_interaction = match metric("UI")? {
+        "N" => 0.85,
+        "R" => 0.62,
+        _ => return None,
+    };
+    let impact_of = |name: &str| match metric(name) {
+        Some("H") => Some(0.56),
+        Some("L") => Some(0.22),
+        Some("N") => Some(0.0),
+        _ => None,
+    };
+
+    let base = 1.0 - (1.0 - impact_of("C")?) * (1.0 - impact_of("I")?) * (1.0 - impact_of("A")?);
+    let impact = if changed {
+        7.52 * (base - 0.029) - 3.25 * (base - 0.02_f64).powi(15)
+    } else {
+        6.42 * base
+    };
+    let exploitability = 8.22 * attack_vector * attack_complexity * privileges * user_interaction;
+
+    if impact <= 0.0 {
+        return Some(0.0);
+    }
+    let score = if changed { 1.08 * (impact + exploitability) } else { impact + exploitability };
+    Some(round_up(score.min(10.0)))
+}
+
+/// CVSS v3.1 round up to one decimal, avoiding floating point artifacts
+fn round_up(value: f64) -> f64 {
+    let scaled = (value * 100_000.0).round() as i64;
+    if scaled % 10_000 == 0 {
+        scaled as f64 / 100_000.0
+    } else {
+        (scaled / 10_000 + 1) as f64 / 10.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_audit_fixture() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/audit/cargo-audit.json");
+        let advisories = parse_audit_output(&std::fs::read_to_string(path).unwrap()).unwrap();
+
+        let summary: Vec<(&str, &str, &str, Severity)> = advisories
+            .iter()
+            .map(|advisory| (advisory.id.as_str(), advisory.package.as_str(), advisory.version.as_str(), advisory.severity))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("RUSTSEC-2022-0090", "libsqlite3-sys", "0.24.2", Severity::Critical),
+                ("RUSTSEC-2024-0332", "h2", "0.3.24", Severity::High),
+                ("RUSTSEC-2023-0071", "rsa", "0.9.6", Severity::Medium),
+            ]
+        );
+
+        let rows = advisory_rows(&advisories);
+        assert!(rows[0][0].contains("critical"));
+        assert_eq!(rows[1][2], "h2 0.3.24");
+        assert_eq!(rows[2][3], "Marvin Attack: potential key recovery through timing sidechannels");
+        assert_eq!(rows[2][4], "https://people.redhat.com/~hkario/marvin/");
+    }
+
+    #[test]
+    fn test_cvss_score() {
+        assert_eq!(cvss_score("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"), Some(9.8));
+        assert_eq!(cvss_score("CVSS:3.1/AV:N/AC:L/PR:N/UI:R/S:C/C:L/I:L/A:N"), Some(6.1));
+        assert_eq!(cvss_score("CVSS:3.1/AV:L/AC:H/PR:H/UI:R/S:U/C:L/I:N/A:N"), Some(1.8));
+        assert_eq!(cvss_score("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:N/I:N/A:N"), Some(0.0));
+        assert_eq!(cvss_score("not a vector"), None);
+    }
+}
@@ -0,0 +1,220 @@
+//! Dependency graph built from `cargo tree --prefix indent` output
+//!
+//! Packages are keyed by `name vX.Y.Z`. Cargo prints the dependencies of a
+//! package once and marks later occurrences with `(*)`, so every edge is
+//! still seen exactly once in the output.
+
+use colored::*;
+use std::collections::{HashMap, HashSet};
+
+/// Width of one indentation level of `cargo tree`
+const INDENT: usize = 4;
+
+/// Packages whose direct use is highlighted
+const CORE_PREFIX: &str = "rustisan";
+
+#[derive(Debug, Default, PartialEq)]
+pub struct DependencyGraph {
+    pub root: String,
+    pub children: HashMap<String, Vec<String>>,
+}
+
+impl DependencyGraph {
+    /// Parse the output of `cargo tree --format "{p}" --prefix indent`
+    pub fn parse(output: &str) -> Self {
+        let mut graph = Self::default();
+        let mut ancestors: Vec<String> = Vec::new();
+
+        for line in output.lines() {
+            let Some(column) = line.chars().position(|c| c.is_alphanumeric() || c == '[') else {
+                continue;
+            };
+            let text: String = line.chars().skip(column).collect();
+            // `[dev-dependencies]` and `[build-dependencies]` only label the lines below
+            if text.starts_with('[') {
+                continue;
+            }
+
+            let package = package_key(&text);
+            let depth = column / INDENT;
+            ancestors.truncate(depth);
+
+            match ancestors.last() {
+                Some(parent) => {
+                    let children = graph.children.entry(parent.clone()).or_default();
+                    if !children.contains(&package) {
+                        children.push(package.clone());
+                    }
+                }
+                None => graph.root = package.clone(),
+            }
+            graph.children.entry(package.clone()).or_default();
+            ancestors.push(package);
+        }
+
+        graph
+    }
+
+    /// The package named `name`, with or without its version
+    pub fn find(&self, name: &str) -> Option<&String> {
+        let mut packages: Vec<&String> = self.children.keys().collect();
+        packages.sort();
+        packages.into_iter().find(|package| *package == name || package_name(package) == name)
+    }
+
+    /// Edges reachable from `from` within `depth` levels, parents before children
+    pub fn edges(&self, from: &str, depth: Option<usize>) -> Vec<(String, String)> {
+        let mut edges = Vec::new();
+        let mut visited = HashSet::from([from.to_string()]);
+        let mut level = vec![from.to_string()];
+        let mut current_depth = 0;
+
+        while !level.is_empty() && depth.is_none_or(|depth| current_depth < depth) {
+            let mut next = Vec::new();
+            for parent in &level {
+                for child in self.children.get(parent).into_iter().flatten() {
+                    edges.push((parent.clone(), child.clone()));
+                    if visited.insert(child.clone()) {
+                        next.push(child.clone());
+                    }
+                }
+            }
+            level = next;
+            current_depth += 1;
+        }
+
+        edges
+    }
+
+    /// Indented tree rooted at `from`, marking packages expanded earlier with `(*)`
+    pub fn render_tree(&self, from: &str, depth: Option<usize>) -> String {
+        let mut lines = Vec::new();
+        let mut expanded = HashSet::new();
+        self.render_node(from, 0, depth, &mut expanded, &mut lines);
+        lines.join("\n")
+    }
+
+    fn render_node(&self, package: &str, level: usize, depth: Option<usize>, expanded: &mut HashSet<String>, lines: &mut Vec<String>) {
+        let children = self.children.get(package).map(Vec::as_slice).unwrap_or_default();
+        let label = if level == 1 && package_name(package).starts_with(CORE_PREFIX) {
+            package.cyan().bold().to_string()
+        } else if level == 0 {
+            package.bold().to_string()
+        } else {
+            package.to_string()
+        };
+
+        let first_visit = expanded.insert(package.to_string());
+        let repeated = if !first_visit && !children.is_empty() { " (*)".dimmed().to_string() } else { String::new() };
+        lines.push(format!("{}{}{}", "    ".repeat(level), label, repeated));
+
+        if first_visit && depth.is_none_or(|depth| level < depth) {
+            for child in children {
+                self.render_node(child, level + 1, depth, expanded, lines);
+            }
+        }
+    }
+}
+
+/// `name vX.Y.Z` of a `cargo tree` package, without its source or markers
+fn package_key(text: &str) -> String {
+    text.split_whitespace().take(2).collect::<Vec<_>>().join(" ")
+}
+
+fn package_name(package: &str) -> &str {
+    package.split_whitespace().next().unwrap_or(package)
+}
+
+/// Graphviz document of `edges`
+pub fn to_dot(edges: &[(String, String)]) -> String {
+    let mut dot = String::from("digraph dependencies {\n    node [shape=box];\n");
+    for (parent, child) in edges {
+        dot.push_str(&format!("    \"{}\" -> \"{}\";\n", parent, child));
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+/// Mermaid flowchart of `edges`, with node ids in order of appearance
+pub fn to_mermaid<'a>(edges: &'a [(String, String)]) -> String {
+    let mut ids: HashMap<&'a str, usize> = HashMap::new();
+    let mut mermaid = String::from("graph TD\n");
+
+    for (parent, child) in edges {
+        let mut node = |package: &'a str| {
+            let next = ids.len();
+            let id = *ids.entry(package).or_insert(next);
+            format!("n{}[\"{}\"]", id, package)
+        };
+        let parent = node(parent);
+        let child = node(child);
+        mermaid.push_str(&format!("    {} --> {}\n", parent, child));
+    }
+
+    mermaid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture() -> DependencyGraph {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/graph/cargo-tree.txt");
+        DependencyGraph::parse(&std::fs::read_to_string(path).unwrap())
+    }
+
+    fn children<'a>(graph: &'a DependencyGraph, package: &str) -> Vec<&'a str> {
+        graph.children[package].iter().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn test_parse_cargo_tree() {
+        let graph = fixture();
+
+        assert_eq!(graph.root, "shop v0.1.0");
+        assert_eq!(
+            children(&graph, "shop v0.1.0"),
+            ["anyhow v1.0.86", "rustisan-core v0.3.0", "rustisan-macros v0.3.0", "serde v1.0.203", "tempfile v3.10.1"]
+        );
+        assert_eq!(children(&graph, "rustisan-core v0.3.0"), ["serde v1.0.203", "tokio v1.38.0"]);
+        assert_eq!(children(&graph, "syn v2.0.66"), ["proc-macro2 v1.0.85", "quote v1.0.36", "unicode-ident v1.0.12"]);
+        assert_eq!(children(&graph, "tokio v1.38.0"), ["bytes v1.6.0", "pin-project-lite v0.2.14", "autocfg v1.3.0"]);
+        assert_eq!(children(&graph, "quote v1.0.36"), ["proc-macro2 v1.0.85"]);
+        assert!(children(&graph, "anyhow v1.0.86").is_empty());
+    }
+
+    #[test]
+    fn test_subtree_and_depth() {
+        let graph = fixture();
+        let core = graph.find("rustisan-core").unwrap().clone();
+
+        let edges = graph.edges(&core, Some(1));
+        assert_eq!(
+            edges,
+            vec![
+                (core.clone(), "serde v1.0.203".to_string()),
+                (core.clone(), "tokio v1.38.0".to_string()),
+            ]
+        );
+
+        let tree = graph.render_tree("serde v1.0.203", Some(2));
+        let lines: Vec<&str> = tree.lines().map(str::trim_end).collect();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[1].ends_with("serde_derive v1.0.203"));
+        assert!(lines[2].starts_with("        proc-macro2"));
+    }
+
+    #[test]
+    fn test_dot_and_mermaid_output() {
+        let edges = vec![
+            ("shop v0.1.0".to_string(), "serde v1.0.203".to_string()),
+            ("serde v1.0.203".to_string(), "serde_derive v1.0.203".to_string()),
+        ];
+
+        assert!(to_dot(&edges).contains("    \"shop v0.1.0\" -> \"serde v1.0.203\";\n"));
+        assert_eq!(
+            to_mermaid(&edges),
+            "graph TD\n    n0[\"shop v0.1.0\"] --> n1[\"serde v1.0.203\"]\n    n1[\"serde v1.0.203\"] --> n2[\"serde_derive v1.0.203\"]\n"
+        );
+    }
+}
@@ -0,0 +1,266 @@
+//! crates.io search for `package search`
+//!
+//! Inside a project, each page of results is cached in `storage/cache` for an
+//! hour, as crates.io asks clients to keep their request rate low.
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// crates.io API the search requests go to
+pub const CRATES_IO_URL: &str = "https://crates.io";
+
+/// Directory cached result pages are written to
+const CACHE_DIR: &str = "storage/cache";
+
+/// Age after which a cached result page is fetched again
+const CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Crates shown per page
+const PER_PAGE: usize = 10;
+
+/// Order of `package search` results
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum SearchSort {
+    /// Most downloaded first
+    #[default]
+    Downloads,
+    /// Most recently updated first
+    Recent,
+}
+
+impl SearchSort {
+    fn api_value(self) -> &'static str {
+        match self {
+            Self::Downloads => "downloads",
+            Self::Recent => "recent-updates",
+        }
+    }
+}
+
+/// A page of crates.io search results
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SearchResults {
+    pub crates: Vec<CrateSummary>,
+    pub meta: SearchMeta,
+}
+
+impl SearchResults {
+    pub fn pages(&self) -> usize {
+        self.meta.total.div_ceil(PER_PAGE).max(1)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SearchMeta {
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct CrateSummary {
+    pub name: String,
+    pub max_version: String,
+    pub description: Option<String>,
+    pub downloads: u64,
+}
+
+impl CrateSummary {
+    pub fn link(&self) -> String {
+        format!("{}/crates/{}", CRATES_IO_URL, self.name)
+    }
+}
+
+/// A page of results, from the cache when it is younger than an hour
+pub async fn search(base_url: &str, query: &str, sort: SearchSort, page: usize) -> Result<SearchResults> {
+    // Outside a project there is no storage directory to cache in
+    let cache_file = crate::commands::CommandUtils::is_rustisan_project()
+        .then(|| cache_path(Path::new(CACHE_DIR), query, sort, page));
+    if let Some(results) = cache_file.as_deref().and_then(|path| read_cache(path, CACHE_TTL)) {
+        return Ok(results);
+    }
+
+    let results = fetch(base_url, query, sort, page).await?;
+    if let Some(cache_file) = cache_file {
+        // The results are already fetched, so a cache that cannot be written only costs a request later
+        if let Err(e) = write_cache(&cache_file, &results) {
+            log::warn!("Failed to cache search results in {}: {}", cache_file.display(), e);
+        }
+    }
+
+    Ok(results)
+}
+
+/// Request a page of results from the crates.io API at `base_url`
+async fn fetch(base_url: &str, query: &str, sort: SearchSort, page: usize) -> Result<SearchResults> {
+    let client = reqwest::Client::builder()
+        .user_agent(concat!("rustisan-cli/", env!("CARGO_PKG_VERSION"), " (", env!("CARGO_PKG_REPOSITORY"), ")"))
+        .timeout(Duration::from_secs(10))
+        .build()?;
+
+    let response = client
+        .get(format!("{}/api/v1/crates", base_url.trim_end_matches('/')))
+        .query(&[
+            ("q", query.to_string()),
+            ("per_page", PER_PAGE.to_string()),
+            ("page", page.to_string()),
+            ("sort", sort.api_value().to_string()),
+        ])
+        .send()
+        .await
+        .context("Failed to reach crates.io")?;
+
+    if !response.status().is_success() {
+        anyhow::bail!("crates.io search failed with status {}", response.status());
+    }
+
+    response.json().await.context("Failed to parse crates.io response")
+}
+
+/// `crate_search_{hash}.json`, the hash covering the query, order and page
+fn cache_path(dir: &Path, query: &str, sort: SearchSort, page: usize) -> PathBuf {
+    use md5::{Digest, Md5};
+
+    let key = format!("{}|{}|{}", query, sort.api_value(), page);
+    let hash: String = Md5::digest(key.as_bytes()).iter().map(|byte| format!("{:02x}", byte)).collect();
+    dir.join(format!("crate_search_{}.json", hash))
+}
+
+fn write_cache(path: &Path, results: &SearchResults) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string(results)?)?;
+    Ok(())
+}
+
+fn read_cache(path: &Path, ttl: Duration) -> Option<SearchResults> {
+    let age = std::fs::metadata(path).ok()?.modified().ok()?.elapsed().ok()?;
+    if age > ttl {
+        return None;
+    }
+    serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()
+}
+
+/// Table rows of `package search`
+pub fn result_rows(results: &SearchResults) -> Vec<Vec<String>> {
+    results
+        .crates
+        .iter()
+        .map(|krate| {
+            vec![
+                krate.name.clone(),
+                krate.max_version.clone(),
+                krate.description.as_deref().unwrap_or("").split_whitespace().collect::<Vec<_>>().join(" "),
+                format_downloads(krate.downloads),
+                krate.link(),
+            ]
+        })
+        .collect()
+}
+
+/// Download count with thousands separators
+fn format_downloads(downloads: u64) -> String {
+    let digits = downloads.to_string();
+    let mut formatted = String::new();
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index).is_multiple_of(3) {
+            formatted.push(',');
+        }
+        formatted.push(digit);
+    }
+    formatted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mockito::Matcher;
+
+    const RESPONSE: &str = r#"{
+        "crates": [
+            {"id": "serde", "name": "serde", "max_version": "1.0.203", "newest_version": "1.0.203",
+             "description": "A generic serialization/deserialization framework", "downloads": 412345678,
+             "recent_downloads": 51234567, "updated_at": "2024-06-01T00:00:00Z"},
+            {"id": "serde_json", "name": "serde_json", "max_version": "1.0.117", "newest_version": "1.0.117",
+             "description": "A JSON serialization file format", "downloads": 298765432,
+             "recent_downloads": 40123456, "updated_at": "2024-05-01T00:00:00Z"},
+            {"id": "serde-wasm", "name": "serde-wasm", "max_version": "0.1.0", "newest_version": "0.1.0",
+             "description": null, "downloads": 512, "recent_downloads": 12, "updated_at": "2021-01-01T00:00:00Z"}
+        ],
+        "meta": {"total": 23, "next_page": "?q=serde&page=2", "prev_page": null}
+    }"#;
+
+    #[tokio::test]
+    async fn test_fetch_displays_crates_from_api() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api/v1/crates")
+            .match_query(Matcher::AllOf(vec![
+                Matcher::UrlEncoded("q".into(), "serde".into()),
+                Matcher::UrlEncoded("per_page".into(), "10".into()),
+                Matcher::UrlEncoded("page".into(), "1".into()),
+                Matcher::UrlEncoded("sort".into(), "downloads".into()),
+            ]))
+            .match_header("user-agent", Matcher::Regex("^rustisan-cli/".into()))
+            .with_header("content-type", "application/json")
+            .with_body(RESPONSE)
+            .create_async()
+            .await;
+
+        let results = fetch(&server.url(), "serde", SearchSort::Downloads, 1).await.unwrap();
+        mock.assert_async().await;
+
+        assert_eq!(results.pages(), 3);
+        assert_eq!(
+            result_rows(&results),
+            vec![
+                vec![
+                    "serde".to_string(),
+                    "1.0.203".to_string(),
+                    "A generic serialization/deserialization framework".to_string(),
+                    "412,345,678".to_string(),
+                    "https://crates.io/crates/serde".to_string(),
+                ],
+                vec![
+                    "serde_json".to_string(),
+                    "1.0.117".to_string(),
+                    "A JSON serialization file format".to_string(),
+                    "298,765,432".to_string(),
+                    "https://crates.io/crates/serde_json".to_string(),
+                ],
+                vec![
+                    "serde-wasm".to_string(),
+                    "0.1.0".to_string(),
+                    String::new(),
+                    "512".to_string(),
+                    "https://crates.io/crates/serde-wasm".to_string(),
+                ],
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_reports_api_errors() {
+        let mut server = mockito::Server::new_async().await;
+        server.mock("GET", Matcher::Any).with_status(503).create_async().await;
+
+        let error = fetch(&server.url(), "serde", SearchSort::Recent, 2).await.unwrap_err();
+        assert!(error.to_string().contains("503"));
+    }
+
+    #[test]
+    fn test_cache_expires_after_ttl() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = cache_path(dir.path(), "serde", SearchSort::Downloads, 1);
+        assert_ne!(path, cache_path(dir.path(), "serde", SearchSort::Downloads, 2));
+        assert!(path.file_name().unwrap().to_string_lossy().starts_with("crate_search_"));
+
+        let results: SearchResults = serde_json::from_str(RESPONSE).unwrap();
+        write_cache(&path, &results).unwrap();
+        assert!(write_cache(&path.join("nested.json"), &results).is_err());
+
+        assert_eq!(read_cache(&path, CACHE_TTL), Some(results));
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(read_cache(&path, Duration::from_millis(10)), None);
+    }
+}
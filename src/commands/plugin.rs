@@ -0,0 +1,141 @@
+//! Plugin command implementation for the Rustisan CLI
+//!
+//! Subcommands Rustisan doesn't know are forwarded to plugin executables.
+//! `rustisan auth` runs the binary configured for `auth` in `[[plugins]]` of
+//! rustisan.toml, or `rustisan-auth` otherwise, looked up in
+//! `.rustisan/plugins/` and then in `PATH`.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::process::ExitStatus;
+
+/// Project-local directory searched before `PATH`
+const PLUGINS_DIR: &str = ".rustisan/plugins";
+
+/// Prefix of plugin binaries without an explicit `binary`
+const BINARY_PREFIX: &str = "rustisan-";
+
+/// A plugin registered in rustisan.toml
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+struct PluginEntry {
+    name: String,
+    binary: String,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct PluginConfig {
+    #[serde(default)]
+    plugins: Vec<PluginEntry>,
+}
+
+/// Handle an external subcommand given as its name followed by its arguments
+pub async fn handle(command: Vec<String>) -> Result<()> {
+    let (name, args) = command.split_first().context("Missing plugin name")?;
+
+    let plugin = find_plugin(name)
+        .with_context(|| format!("Unknown command '{}' and no {}{} plugin was found", name, BINARY_PREFIX, name))?;
+
+    let status = run_plugin(&plugin, args)?;
+    if !status.success() {
+        std::process::exit(status.code().unwrap_or(1));
+    }
+
+    Ok(())
+}
+
+/// Locate the executable of a plugin in `.rustisan/plugins/` or `PATH`
+pub fn find_plugin(name: &str) -> Option<PathBuf> {
+    let config = std::fs::read_to_string("rustisan.toml").unwrap_or_default();
+    find_plugin_in(name, &parse_plugins(&config), Path::new(PLUGINS_DIR), std::env::var_os("PATH"))
+}
+
+fn find_plugin_in(
+    name: &str,
+    plugins: &[PluginEntry],
+    plugins_dir: &Path,
+    path: Option<std::ffi::OsString>,
+) -> Option<PathBuf> {
+    let binary = plugin_binary(name, plugins);
+
+    let local = plugins_dir.join(&binary);
+    if is_executable(&local) {
+        return Some(local);
+    }
+
+    let cwd = std::env::current_dir().ok()?;
+    which::which_in(&binary, path, cwd).ok()
+}
+
+/// Binary configured for a plugin, matching `rustisan-auth` entries by `auth` too
+fn plugin_binary(name: &str, plugins: &[PluginEntry]) -> String {
+    plugins
+        .iter()
+        .find(|plugin| plugin.name == name || plugin.name.strip_prefix(BINARY_PREFIX) == Some(name))
+        .map(|plugin| plugin.binary.clone())
+        .unwrap_or_else(|| format!("{}{}", BINARY_PREFIX, name))
+}
+
+fn parse_plugins(content: &str) -> Vec<PluginEntry> {
+    toml::from_str::<PluginConfig>(content).map(|config| config.plugins).unwrap_or_default()
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata().is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// Run a plugin with the remaining arguments, inheriting stdio
+fn run_plugin(plugin: &Path, args: &[String]) -> Result<ExitStatus> {
+    std::process::Command::new(plugin)
+        .args(args)
+        .status()
+        .with_context(|| format!("Failed to run plugin {}", plugin.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_local_plugin_is_discovered_and_run() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let plugins_dir = dir.path().join("plugins");
+        std::fs::create_dir_all(&plugins_dir).unwrap();
+
+        let output = dir.path().join("output.txt");
+        let script = plugins_dir.join("rustisan-auth");
+        std::fs::write(&script, format!("#!/bin/sh\necho \"$@\" > {}\n", output.display())).unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let plugin = find_plugin_in("auth", &[], &plugins_dir, None).unwrap();
+        assert_eq!(plugin, script);
+
+        let status = run_plugin(&plugin, &["install".to_string(), "--force".to_string()]).unwrap();
+        assert!(status.success());
+        assert_eq!(std::fs::read_to_string(output).unwrap(), "install --force\n");
+
+        assert!(find_plugin_in("admin", &[], &plugins_dir, None).is_none());
+    }
+
+    #[test]
+    fn test_plugin_binary() {
+        let plugins = parse_plugins(
+            "[project]\nname = \"shop\"\n\n[[plugins]]\nname = \"rustisan-auth\"\nbinary = \"auth-cli\"\n",
+        );
+
+        assert_eq!(plugins, [PluginEntry { name: "rustisan-auth".to_string(), binary: "auth-cli".to_string() }]);
+        assert_eq!(plugin_binary("auth", &plugins), "auth-cli");
+        assert_eq!(plugin_binary("rustisan-auth", &plugins), "auth-cli");
+        assert_eq!(plugin_binary("admin", &plugins), "rustisan-admin");
+    }
+}
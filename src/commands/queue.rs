@@ -1,22 +1,53 @@
 //! Queue command implementations for the Rustisan CLI
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
 use colored::*;
+use redis::aio::MultiplexedConnection;
 use crate::QueueCommands;
 use super::CommandUtils;
+use super::db::{get_config_value, load_config};
+use crate::utils::TextUtils;
+
+/// Redis URL used when neither `--connection` nor `[queue] connection` is set
+pub(crate) const DEFAULT_REDIS_URL: &str = "redis://127.0.0.1:6379";
+
+/// Job type the worker runs itself, executing `payload.command` through the shell
+pub(crate) const SCHEDULED_COMMAND_JOB: &str = "ScheduledCommand";
+
+/// Redis list holding failed jobs
+const FAILED_QUEUE: &str = "queue:failed";
+
+/// Number of minutes averaged into the throughput figure
+const THROUGHPUT_WINDOW_MINUTES: u64 = 5;
+
+/// Refresh interval of `queue:stats --watch`
+const STATS_REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Poll interval of `queue:failed --tail`
+const FAILED_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
 
 /// Handle queue command
 pub async fn handle(operation: QueueCommands) -> Result<()> {
     CommandUtils::ensure_rustisan_project()?;
 
     match operation {
-        QueueCommands::Work { queue, max_jobs, memory, sleep } => {
-            work_queue(queue, max_jobs, memory, sleep).await
+        QueueCommands::Work { queue, max_jobs, memory, sleep, connection } => {
+            work_queue(queue, max_jobs, memory, sleep, connection).await
         }
+        QueueCommands::Stats { queue, watch } => show_stats(queue, watch).await,
         QueueCommands::Restart => restart_workers().await,
-        QueueCommands::Failed => show_failed_jobs().await,
+        QueueCommands::Failed { tail, since, clear_on_exit } => {
+            let since = since.as_deref().map(parse_since).transpose()?;
+            if tail {
+                tail_failed_jobs(since, clear_on_exit).await
+            } else {
+                show_failed_jobs(since).await
+            }
+        }
         QueueCommands::Retry { id } => retry_failed_jobs(id).await,
         QueueCommands::Flush => flush_failed_jobs().await,
+        QueueCommands::Schedule { force_run } => super::schedule::enqueue_due_tasks(force_run).await,
     }
 }
 
@@ -25,8 +56,13 @@ async fn work_queue(
     max_jobs: Option<u32>,
     memory: Option<u32>,
     sleep: u64,
+    connection: Option<String>,
 ) -> Result<()> {
     let queue_name = queue.unwrap_or_else(|| "default".to_string());
+    // BRPOPLPUSH with a timeout of 0 blocks until a job arrives, so the worker would never tick
+    let sleep = sleep.max(1);
+    let redis_url = queue_connection_url(connection);
+    let mut redis = connect(&redis_url).await?;
 
     CommandUtils::info(&format!("Starting queue worker for queue: {}", queue_name));
 
@@ -40,36 +76,32 @@ async fn work_queue(
 
     CommandUtils::info(&format!("Sleep time when no jobs: {} seconds", sleep));
 
-    println!("\n{}", "Queue Worker Started".green().bold());
-    println!("{}", "Press Ctrl+C to stop the worker".dimmed());
-    println!("{}", "─".repeat(50));
+    if CommandUtils::is_json_output() {
+        CommandUtils::info("Queue worker started");
+    } else {
+        println!("\n{}", "Queue Worker Started".green().bold());
+        println!("{}", "Press Ctrl+C to stop the worker".dimmed());
+        println!("{}", "─".repeat(50));
+    }
 
     let mut processed_jobs = 0;
     let start_time = std::time::Instant::now();
 
-    // TODO: Implement actual queue processing logic
-    // This would typically involve:
-    // 1. Connecting to the queue backend (Redis, Database, etc.)
-    // 2. Polling for jobs
-    // 3. Processing jobs
-    // 4. Handling failures
-
     loop {
-        // Simulate job processing
-        tokio::time::sleep(tokio::time::Duration::from_secs(sleep)).await;
-
-        // Check for available jobs
-        if let Some(job) = get_next_job(&queue_name).await? {
+        // Block for up to `sleep` seconds waiting for a job
+        if let Some(job) = get_next_job(&mut redis, &queue_name, sleep).await? {
             CommandUtils::info(&format!("Processing job: {}", job.id));
 
             match process_job(&job).await {
                 Ok(_) => {
+                    complete_job(&mut redis, &job).await?;
+                    record_throughput(&mut redis, &queue_name).await?;
                     processed_jobs += 1;
                     CommandUtils::success(&format!("Job {} completed successfully", job.id));
                 }
                 Err(e) => {
                     CommandUtils::error(&format!("Job {} failed: {}", job.id, e));
-                    mark_job_as_failed(&job, &e.to_string()).await?;
+                    mark_job_as_failed(&mut redis, &job, &e.to_string()).await?;
                 }
             }
 
@@ -89,8 +121,8 @@ async fn work_queue(
                     break;
                 }
             }
-        } else {
-            // No jobs available
+        } else if !CommandUtils::is_json_output() {
+            // No jobs available, the dots would break the one-object-per-line JSON stream
             print!(".");
             std::io::Write::flush(&mut std::io::stdout()).ok();
         }
@@ -106,6 +138,152 @@ async fn work_queue(
     Ok(())
 }
 
+/// Depth and throughput of a single queue
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+struct QueueStats {
+    queue: String,
+    pending: u64,
+    processing: u64,
+    failed: u64,
+    /// Jobs per minute averaged over the throughput window
+    throughput: f64,
+}
+
+/// Read access to the Redis data behind `queue:stats`
+trait StatsSource {
+    /// Length of a Redis list (`LLEN`)
+    async fn list_length(&mut self, key: &str) -> Result<u64>;
+
+    /// Values of counter keys (`MGET`), with missing keys read as 0
+    async fn counters(&mut self, keys: &[String]) -> Result<Vec<u64>>;
+}
+
+impl StatsSource for MultiplexedConnection {
+    async fn list_length(&mut self, key: &str) -> Result<u64> {
+        Ok(redis::cmd("LLEN").arg(key).query_async(self).await?)
+    }
+
+    async fn counters(&mut self, keys: &[String]) -> Result<Vec<u64>> {
+        let values: Vec<Option<u64>> = redis::cmd("MGET").arg(keys).query_async(self).await?;
+        Ok(values.into_iter().map(Option::unwrap_or_default).collect())
+    }
+}
+
+async fn show_stats(queue: Option<String>, watch: bool) -> Result<()> {
+    let queues = match queue {
+        Some(queue) => vec![queue],
+        None => configured_queues(),
+    };
+
+    let mut redis = connect(&queue_connection_url(None)).await?;
+
+    if !watch {
+        let stats = fetch_stats(&mut redis, &queues, current_minute()).await?;
+        print_stats(&stats);
+        return Ok(());
+    }
+
+    let mut interval = tokio::time::interval(STATS_REFRESH_INTERVAL);
+    loop {
+        interval.tick().await;
+        let stats = fetch_stats(&mut redis, &queues, current_minute()).await?;
+
+        if !CommandUtils::is_json_output() {
+            // Clear the screen so the table refreshes in place
+            print!("\x1b[2J\x1b[H");
+            println!("{}", "Press Ctrl+C to stop watching".dimmed());
+        }
+        print_stats(&stats);
+    }
+}
+
+/// Queue names listed under `[queue] queues` in rustisan.toml, or `default`
+fn configured_queues() -> Vec<String> {
+    load_config()
+        .ok()
+        .and_then(|config| {
+            let queues = config.get("queue")?.get("queues")?.as_array()?;
+            Some(queues.iter().filter_map(|queue| queue.as_str().map(str::to_string)).collect::<Vec<_>>())
+        })
+        .filter(|queues| !queues.is_empty())
+        .unwrap_or_else(|| vec!["default".to_string()])
+}
+
+/// Minutes since the Unix epoch, used to bucket the throughput counters
+fn current_minute() -> u64 {
+    chrono::Utc::now().timestamp() as u64 / 60
+}
+
+fn throughput_key(queue: &str, minute: u64) -> String {
+    format!("queue:{}:throughput:{}", queue, minute)
+}
+
+/// Count a processed job in the current minute's throughput bucket
+async fn record_throughput(redis: &mut MultiplexedConnection, queue: &str) -> Result<()> {
+    let key = throughput_key(queue, current_minute());
+    redis::pipe()
+        .cmd("INCR").arg(&key).ignore()
+        .cmd("EXPIRE").arg(&key).arg((THROUGHPUT_WINDOW_MINUTES + 1) * 60).ignore()
+        .query_async::<()>(redis)
+        .await?;
+    Ok(())
+}
+
+async fn fetch_stats<S: StatsSource>(source: &mut S, queues: &[String], minute: u64) -> Result<Vec<QueueStats>> {
+    let failed = source.list_length(FAILED_QUEUE).await?;
+    let mut stats = Vec::with_capacity(queues.len());
+
+    for queue in queues {
+        let keys: Vec<String> = (0..THROUGHPUT_WINDOW_MINUTES)
+            .map(|offset| throughput_key(queue, minute.saturating_sub(offset)))
+            .collect();
+        let processed: u64 = source.counters(&keys).await?.iter().sum();
+
+        stats.push(QueueStats {
+            queue: queue.clone(),
+            pending: source.list_length(&pending_key(queue)).await?,
+            processing: source.list_length(&processing_key(queue)).await?,
+            failed,
+            throughput: processed as f64 / THROUGHPUT_WINDOW_MINUTES as f64,
+        });
+    }
+
+    Ok(stats)
+}
+
+fn format_stats_table(stats: &[QueueStats]) -> String {
+    let rows: Vec<Vec<String>> = stats
+        .iter()
+        .map(|stat| {
+            vec![
+                stat.queue.clone(),
+                stat.pending.to_string(),
+                stat.processing.to_string(),
+                stat.failed.to_string(),
+                format!("{:.1}", stat.throughput),
+            ]
+        })
+        .collect();
+
+    TextUtils::table_format(
+        &["Queue", "Pending", "Processing", "Failed", "Jobs/min"],
+        &rows,
+        &[30, 10, 10, 10, 10],
+    )
+}
+
+fn print_stats(stats: &[QueueStats]) {
+    if CommandUtils::is_json_output() {
+        let snapshot = serde_json::json!({
+            "queues": stats,
+            "timestamp": chrono::Utc::now().to_rfc3339(),
+        });
+        println!("{}", snapshot);
+    } else {
+        println!("{}", format_stats_table(stats));
+    }
+}
+
 async fn restart_workers() -> Result<()> {
     CommandUtils::info("Restarting queue workers...");
 
@@ -120,39 +298,121 @@ async fn restart_workers() -> Result<()> {
     Ok(())
 }
 
-async fn show_failed_jobs() -> Result<()> {
+async fn show_failed_jobs(since: Option<DateTime<Utc>>) -> Result<()> {
     CommandUtils::info("Retrieving failed jobs...");
 
-    let failed_jobs = get_failed_jobs().await?;
+    let mut failed_jobs = get_failed_jobs().await?;
+    failed_jobs.retain(|job| job.failed_since(since));
 
     if failed_jobs.is_empty() {
         CommandUtils::success("No failed jobs found");
         return Ok(());
     }
 
+    let rows: Vec<Vec<String>> = failed_jobs
+        .into_iter()
+        .map(|job| vec![job.id, job.job_type, job.failed_at, job.error])
+        .collect();
+
     println!("\n{}", "Failed Jobs:".bold());
-    println!("┌─────────────┬─────────────────────────────────────────────────────────────────────┐");
-    println!("│ {} │ {} │", "ID".bold(), "Job".bold());
-    println!("│ {} │ {} │", "Failed At".bold(), "Error".bold());
-    println!("├─────────────┼─────────────────────────────────────────────────────────────────────┤");
-
-    for job in failed_jobs {
-        println!("│ {} │ {} │",
-            format!("{:11}", job.id),
-            format!("{:67}", job.job_type)
-        );
-        println!("│ {} │ {} │",
-            format!("{:11}", job.failed_at),
-            format!("{:67}", job.error.chars().take(65).collect::<String>())
-        );
-        println!("├─────────────┼─────────────────────────────────────────────────────────────────────┤");
+    println!("{}", TextUtils::table_format(&["ID", "Job", "Failed At", "Error"], &rows, &[11, 24, 20, 40]));
+
+    Ok(())
+}
+
+/// Print failed jobs as they are added until Ctrl+C, optionally flushing them on exit
+async fn tail_failed_jobs(since: Option<DateTime<Utc>>, clear_on_exit: bool) -> Result<()> {
+    let mut redis = connect(&queue_connection_url(None)).await?;
+    let mut last_seen_id = None;
+
+    if !CommandUtils::is_json_output() {
+        println!("{}", "Watching for failed jobs, press Ctrl+C to stop".dimmed());
+    }
+
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+    let mut interval = tokio::time::interval(FAILED_POLL_INTERVAL);
+
+    loop {
+        tokio::select! {
+            _ = &mut ctrl_c => break,
+            _ = interval.tick() => {
+                for job in poll_failed_jobs(&mut redis, &mut last_seen_id, since).await? {
+                    println!("{}", format_failed_job(&job));
+                }
+            }
+        }
     }
 
-    println!("└─────────────┴─────────────────────────────────────────────────────────────────────┘");
+    if clear_on_exit {
+        println!();
+        flush_failed_jobs().await?;
+    }
 
     Ok(())
 }
 
+/// Failed jobs added after `last_seen_id`, moving the cursor to the newest one
+///
+/// When the cursor is gone from the list, e.g. after a flush, every entry counts as new.
+async fn poll_failed_jobs<S: FailedJobSource>(
+    source: &mut S,
+    last_seen_id: &mut Option<String>,
+    since: Option<DateTime<Utc>>,
+) -> Result<Vec<FailedJob>> {
+    let jobs: Vec<FailedJob> = source
+        .failed_entries()
+        .await?
+        .into_iter()
+        .filter_map(|raw| FailedJob::from_raw(&raw).ok())
+        .collect();
+
+    let start = last_seen_id
+        .as_ref()
+        .and_then(|id| jobs.iter().position(|job| &job.id == id))
+        .map_or(0, |position| position + 1);
+    if let Some(last) = jobs.last() {
+        *last_seen_id = Some(last.id.clone());
+    }
+
+    Ok(jobs.into_iter().skip(start).filter(|job| job.failed_since(since)).collect())
+}
+
+/// One failed job per line, or one JSON object per line with `--output-format json`
+fn format_failed_job(job: &FailedJob) -> String {
+    if CommandUtils::is_json_output() {
+        return serde_json::json!({
+            "id": job.id,
+            "job": job.job_type,
+            "payload": serde_json::from_str::<serde_json::Value>(&job.payload).unwrap_or(serde_json::Value::Null),
+            "error": job.error,
+            "failed_at": job.failed_at,
+        })
+        .to_string();
+    }
+
+    format!("{} {} {}: {}", format!("[{}]", job.failed_at).red(), job.id, job.job_type.bold(), job.error)
+}
+
+/// Parse `--since` as RFC 3339, or a local `YYYY-MM-DD[ HH:MM[:SS]]`
+fn parse_since(since: &str) -> Result<DateTime<Utc>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(since) {
+        return Ok(time.with_timezone(&Utc));
+    }
+
+    let naive = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(since, format).ok())
+        .or_else(|| NaiveDate::parse_from_str(since, "%Y-%m-%d").ok().and_then(|date| date.and_hms_opt(0, 0, 0)))
+        .ok_or_else(|| anyhow::anyhow!("Invalid --since '{}', expected e.g. 2024-05-17 10:00:00", since))?;
+
+    Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|time| time.with_timezone(&Utc))
+        .ok_or_else(|| anyhow::anyhow!("Invalid local time '{}'", since))
+}
+
 async fn retry_failed_jobs(id: Option<String>) -> Result<()> {
     if let Some(job_id) = id {
         CommandUtils::info(&format!("Retrying failed job: {}", job_id));
@@ -190,7 +450,8 @@ async fn flush_failed_jobs() -> Result<()> {
         return Ok(());
     }
 
-    // TODO: Implement failed jobs cleanup logic
+    let mut redis = connect(&queue_connection_url(None)).await?;
+    redis::cmd("DEL").arg(FAILED_QUEUE).query_async::<()>(&mut redis).await?;
     CommandUtils::success(&format!("Flushed {} failed jobs", failed_jobs.len()));
 
     Ok(())
@@ -203,9 +464,43 @@ struct Job {
     payload: String,
     queue: String,
     attempts: u32,
+    /// Entry as stored in Redis, needed to remove it from the processing list
+    raw: String,
 }
 
-#[derive(Debug, Clone)]
+impl Job {
+    /// Parse a job pushed onto a queue as `{"id", "job", "payload", "attempts"}` JSON
+    fn from_raw(queue: &str, raw: String) -> Result<Self> {
+        let value: serde_json::Value = serde_json::from_str(&raw)
+            .with_context(|| format!("Invalid job payload on queue '{}': {}", queue, raw))?;
+
+        Ok(Self {
+            id: value["id"]
+                .as_str()
+                .map(str::to_string)
+                .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+            job_type: value["job"].as_str().unwrap_or("UnknownJob").to_string(),
+            payload: value.get("payload").map(|payload| payload.to_string()).unwrap_or_else(|| "{}".to_string()),
+            queue: queue.to_string(),
+            attempts: value["attempts"].as_u64().unwrap_or(0) as u32,
+            raw,
+        })
+    }
+
+    /// Stand-in for an entry that is not valid JSON, keeping it as the payload of the failed job
+    fn unreadable(queue: &str, raw: String) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            job_type: "UnknownJob".to_string(),
+            payload: serde_json::Value::String(raw.clone()).to_string(),
+            queue: queue.to_string(),
+            attempts: 0,
+            raw,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 struct FailedJob {
     id: String,
     job_type: String,
@@ -214,61 +509,171 @@ struct FailedJob {
     failed_at: String,
 }
 
-async fn get_next_job(queue: &str) -> Result<Option<Job>> {
-    // TODO: Implement actual job retrieval from queue backend
-    // For now, return None to simulate no jobs available
+impl FailedJob {
+    /// Parse an entry of `queue:failed` as written by `mark_job_as_failed`
+    fn from_raw(raw: &str) -> Result<Self> {
+        let value: serde_json::Value =
+            serde_json::from_str(raw).with_context(|| format!("Invalid failed job entry: {}", raw))?;
+        let field = |name: &str| value[name].as_str().unwrap_or_default().to_string();
+
+        Ok(Self {
+            id: field("id"),
+            job_type: field("job"),
+            payload: value.get("payload").map(|payload| payload.to_string()).unwrap_or_else(|| "{}".to_string()),
+            error: field("error"),
+            failed_at: field("failed_at"),
+        })
+    }
 
-    // Simulate occasional job availability
-    if rand::random::<f64>() > 0.9 {
-        return Ok(Some(Job {
-            id: uuid::Uuid::new_v4().to_string(),
-            job_type: "ExampleJob".to_string(),
-            payload: "{}".to_string(),
-            queue: queue.to_string(),
-            attempts: 0,
-        }));
+    /// Whether the job failed at or after `since`, keeping jobs without a readable time
+    fn failed_since(&self, since: Option<DateTime<Utc>>) -> bool {
+        let Some(since) = since else { return true };
+        DateTime::parse_from_rfc3339(&self.failed_at).map_or(true, |failed_at| failed_at >= since)
     }
+}
 
-    Ok(None)
+/// Read access to the failed job list
+trait FailedJobSource {
+    /// Entries of `queue:failed`, oldest first (`LRANGE`)
+    async fn failed_entries(&mut self) -> Result<Vec<String>>;
 }
 
-async fn process_job(job: &Job) -> Result<()> {
-    // TODO: Implement actual job processing logic
-    // Simulate job processing time
-    tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+impl FailedJobSource for MultiplexedConnection {
+    async fn failed_entries(&mut self) -> Result<Vec<String>> {
+        Ok(redis::cmd("LRANGE").arg(FAILED_QUEUE).arg(0).arg(-1).query_async(self).await?)
+    }
+}
+
+/// Resolve the Redis URL: `--connection`, then `[queue] connection`, then the default
+pub(crate) fn queue_connection_url(connection: Option<String>) -> String {
+    connection
+        .or_else(|| {
+            load_config()
+                .ok()
+                .and_then(|config| get_config_value(&config, "queue.connection"))
+        })
+        .unwrap_or_else(|| DEFAULT_REDIS_URL.to_string())
+}
+
+pub(crate) async fn connect(url: &str) -> Result<MultiplexedConnection> {
+    let client = redis::Client::open(url).with_context(|| format!("Invalid Redis URL: {}", url))?;
+    client
+        .get_multiplexed_async_connection()
+        .await
+        .with_context(|| format!("Failed to connect to Redis at {}", url))
+}
+
+/// Push a job onto a queue, returning its id
+pub(crate) async fn dispatch_job(
+    redis: &mut MultiplexedConnection,
+    queue: &str,
+    job_type: &str,
+    payload: serde_json::Value,
+) -> Result<String> {
+    let id = uuid::Uuid::new_v4().to_string();
+    let raw = serde_json::json!({ "id": id, "job": job_type, "payload": payload, "attempts": 0 });
+    redis::cmd("LPUSH").arg(pending_key(queue)).arg(raw.to_string()).query_async::<()>(redis).await?;
+    Ok(id)
+}
+
+fn pending_key(queue: &str) -> String {
+    format!("queue:{}", queue)
+}
+
+fn processing_key(queue: &str) -> String {
+    format!("queue:{}:processing", queue)
+}
+
+/// Move the next job onto the processing list, waiting up to `timeout` seconds
+async fn get_next_job(redis: &mut MultiplexedConnection, queue: &str, timeout: u64) -> Result<Option<Job>> {
+    let raw: Option<String> = redis::cmd("BRPOPLPUSH")
+        .arg(pending_key(queue))
+        .arg(processing_key(queue))
+        .arg(timeout)
+        .query_async(redis)
+        .await?;
+
+    let Some(raw) = raw else {
+        return Ok(None);
+    };
+
+    match Job::from_raw(queue, raw.clone()) {
+        Ok(job) => Ok(Some(job)),
+        Err(e) => {
+            // The entry already sits on the processing list, which nothing reads again
+            let error = format!("{:#}", e);
+            CommandUtils::error(&error);
+            mark_job_as_failed(redis, &Job::unreadable(queue, raw), &error).await?;
+            Ok(None)
+        }
+    }
+}
 
-    // Simulate occasional job failure
-    if rand::random::<f64>() > 0.8 {
-        anyhow::bail!("Simulated job failure");
+/// Run a job through the application's `job` binary, or the shell for scheduled tasks
+async fn process_job(job: &Job) -> Result<()> {
+    let output = if job.job_type == SCHEDULED_COMMAND_JOB {
+        let payload: serde_json::Value = serde_json::from_str(&job.payload)?;
+        let task = payload["task"]
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("Scheduled job {} has no task name", job.id))?;
+        // Only commands configured in rustisan.toml run, whatever else the payload carries
+        let command = super::schedule::scheduled_task_command(task)?;
+        let (shell, flag) = crate::utils::ProcessUtils::get_shell_command();
+        tokio::process::Command::new(shell).args([flag, command.as_str()]).output().await?
+    } else {
+        tokio::process::Command::new("cargo")
+            .args(["run", "--quiet", "--bin", "job", "--", &job.job_type, &job.payload])
+            .output()
+            .await?
+    };
+
+    if !output.status.success() {
+        anyhow::bail!("{}", String::from_utf8_lossy(&output.stderr).trim());
     }
 
     Ok(())
 }
 
-async fn mark_job_as_failed(job: &Job, error: &str) -> Result<()> {
-    // TODO: Implement failed job storage logic
+/// Remove a processed job from the processing list
+async fn complete_job(redis: &mut MultiplexedConnection, job: &Job) -> Result<()> {
+    redis::cmd("LREM")
+        .arg(processing_key(&job.queue))
+        .arg(1)
+        .arg(&job.raw)
+        .query_async::<()>(redis)
+        .await?;
+    Ok(())
+}
+
+/// Move a job from the processing list to the failed queue
+async fn mark_job_as_failed(redis: &mut MultiplexedConnection, job: &Job, error: &str) -> Result<()> {
+    let failed = serde_json::json!({
+        "id": job.id,
+        "job": job.job_type,
+        "payload": serde_json::from_str::<serde_json::Value>(&job.payload).unwrap_or(serde_json::Value::Null),
+        "queue": job.queue,
+        "attempts": job.attempts + 1,
+        "error": error,
+        "failed_at": chrono::Utc::now().to_rfc3339(),
+    });
+
+    complete_job(redis, job).await?;
+    redis::cmd("RPUSH")
+        .arg(FAILED_QUEUE)
+        .arg(failed.to_string())
+        .query_async::<()>(redis)
+        .await?;
     Ok(())
 }
 
 async fn get_failed_jobs() -> Result<Vec<FailedJob>> {
-    // TODO: Implement failed jobs retrieval from storage
-    // For now, return some example failed jobs
-    Ok(vec![
-        FailedJob {
-            id: "failed-job-1".to_string(),
-            job_type: "SendEmailJob".to_string(),
-            payload: r#"{"email": "user@example.com"}"#.to_string(),
-            error: "Connection timeout".to_string(),
-            failed_at: "2024-01-01 12:00:00".to_string(),
-        },
-        FailedJob {
-            id: "failed-job-2".to_string(),
-            job_type: "ProcessImageJob".to_string(),
-            payload: r#"{"image_path": "/uploads/image.jpg"}"#.to_string(),
-            error: "File not found".to_string(),
-            failed_at: "2024-01-01 12:05:00".to_string(),
-        },
-    ])
+    let mut redis = connect(&queue_connection_url(None)).await?;
+    redis
+        .failed_entries()
+        .await?
+        .iter()
+        .map(|raw| FailedJob::from_raw(raw))
+        .collect()
 }
 
 fn get_memory_usage() -> Result<u32> {
@@ -277,6 +682,227 @@ fn get_memory_usage() -> Result<u32> {
     Ok(64) // MB
 }
 
-// Add these dependencies to Cargo.toml if not already present:
-// rand = "0.8"
-// uuid = { version = "1.0", features = ["v4"] }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// In-memory stand-in for Redis
+    #[derive(Default)]
+    struct MockRedis {
+        lists: HashMap<String, u64>,
+        counters: HashMap<String, u64>,
+        failed: Vec<String>,
+    }
+
+    impl FailedJobSource for MockRedis {
+        async fn failed_entries(&mut self) -> Result<Vec<String>> {
+            Ok(self.failed.clone())
+        }
+    }
+
+    fn failed_entry(id: &str, failed_at: &str) -> String {
+        serde_json::json!({
+            "id": id,
+            "job": "SendEmailJob",
+            "payload": { "to": "a@example.com" },
+            "queue": "emails",
+            "attempts": 1,
+            "error": "Connection timeout",
+            "failed_at": failed_at,
+        })
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn test_poll_failed_jobs() {
+        let mut redis = MockRedis::default();
+        let mut last_seen_id = None;
+        assert!(poll_failed_jobs(&mut redis, &mut last_seen_id, None).await.unwrap().is_empty());
+
+        redis.failed.push(failed_entry("job-1", "2024-05-17T10:00:00+00:00"));
+        let jobs = poll_failed_jobs(&mut redis, &mut last_seen_id, None).await.unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!((jobs[0].id.as_str(), jobs[0].error.as_str()), ("job-1", "Connection timeout"));
+        assert!(format_failed_job(&jobs[0]).contains("job-1"));
+
+        // Only jobs added since the last poll are returned
+        assert!(poll_failed_jobs(&mut redis, &mut last_seen_id, None).await.unwrap().is_empty());
+        redis.failed.push(failed_entry("job-2", "2024-05-17T09:00:00+00:00"));
+        redis.failed.push(failed_entry("job-3", "2024-05-17T11:00:00+00:00"));
+
+        let since = parse_since("2024-05-17T10:30:00Z").unwrap();
+        let jobs = poll_failed_jobs(&mut redis, &mut last_seen_id, Some(since)).await.unwrap();
+        assert_eq!(jobs.iter().map(|job| job.id.as_str()).collect::<Vec<_>>(), ["job-3"]);
+        assert_eq!(last_seen_id.as_deref(), Some("job-3"));
+    }
+
+    #[test]
+    fn test_unreadable_job_keeps_raw_entry() {
+        let raw = "{not json".to_string();
+        assert!(Job::from_raw("default", raw.clone()).is_err());
+
+        let job = Job::unreadable("default", raw.clone());
+        assert_eq!(job.raw, raw);
+        assert_eq!(serde_json::from_str::<serde_json::Value>(&job.payload).unwrap(), raw.as_str());
+    }
+
+    #[test]
+    fn test_parse_since() {
+        assert_eq!(parse_since("2024-05-17T10:30:00+02:00").unwrap(), Utc.with_ymd_and_hms(2024, 5, 17, 8, 30, 0).unwrap());
+        assert!(parse_since("2024-05-17 10:30").is_ok());
+        assert!(parse_since("2024-05-17").is_ok());
+        assert!(parse_since("yesterday").is_err());
+    }
+
+    impl StatsSource for MockRedis {
+        async fn list_length(&mut self, key: &str) -> Result<u64> {
+            Ok(self.lists.get(key).copied().unwrap_or_default())
+        }
+
+        async fn counters(&mut self, keys: &[String]) -> Result<Vec<u64>> {
+            Ok(keys.iter().map(|key| self.counters.get(key).copied().unwrap_or_default()).collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_and_format_stats() {
+        let mut redis = MockRedis::default();
+        redis.lists.insert("queue:emails".to_string(), 12);
+        redis.lists.insert("queue:emails:processing".to_string(), 2);
+        redis.lists.insert("queue:failed".to_string(), 3);
+        redis.counters.insert(throughput_key("emails", 100), 6);
+        redis.counters.insert(throughput_key("emails", 97), 4);
+        // Outside the five minute window
+        redis.counters.insert(throughput_key("emails", 95), 50);
+
+        let queues = vec!["emails".to_string(), "reports".to_string()];
+        let stats = fetch_stats(&mut redis, &queues, 100).await.unwrap();
+
+        assert_eq!(
+            stats[0],
+            QueueStats {
+                queue: "emails".to_string(),
+                pending: 12,
+                processing: 2,
+                failed: 3,
+                throughput: 2.0,
+            }
+        );
+        assert_eq!(stats[1].pending, 0);
+        assert_eq!(stats[1].throughput, 0.0);
+
+        let table = format_stats_table(&stats);
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines[1].contains("Queue") && lines[1].contains("Jobs/min"));
+        assert_eq!(
+            lines[3].split('│').map(str::trim).filter(|cell| !cell.is_empty()).collect::<Vec<_>>(),
+            ["emails", "12", "2", "3", "2.0"]
+        );
+
+        let json = serde_json::to_value(&stats[0]).unwrap();
+        assert_eq!(json["pending"], 12);
+        assert_eq!(json["throughput"], 2.0);
+    }
+
+    /// Connect to the Redis server named by `TEST_REDIS_URL`, if any
+    async fn test_redis() -> Option<MultiplexedConnection> {
+        let url = std::env::var("TEST_REDIS_URL").ok()?;
+        Some(connect(&url).await.expect("TEST_REDIS_URL is not reachable"))
+    }
+
+    async fn push_job(redis: &mut MultiplexedConnection, queue: &str, id: &str) {
+        let raw = serde_json::json!({ "id": id, "job": "SendEmailJob", "payload": { "to": "a@example.com" } });
+        redis::cmd("LPUSH")
+            .arg(pending_key(queue))
+            .arg(raw.to_string())
+            .query_async::<()>(redis)
+            .await
+            .unwrap();
+    }
+
+    async fn list(redis: &mut MultiplexedConnection, key: &str) -> Vec<String> {
+        redis::cmd("LRANGE").arg(key).arg(0).arg(-1).query_async(redis).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_redis_job_lifecycle() {
+        let Some(mut redis) = test_redis().await else { return };
+        let queue = format!("test-{}", uuid::Uuid::new_v4());
+
+        assert!(get_next_job(&mut redis, &queue, 1).await.unwrap().is_none());
+
+        push_job(&mut redis, &queue, "job-1").await;
+        let job = get_next_job(&mut redis, &queue, 1).await.unwrap().unwrap();
+        assert_eq!(job.id, "job-1");
+        assert_eq!(job.job_type, "SendEmailJob");
+        assert_eq!(list(&mut redis, &processing_key(&queue)).await, std::slice::from_ref(&job.raw));
+
+        complete_job(&mut redis, &job).await.unwrap();
+        assert!(list(&mut redis, &processing_key(&queue)).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_redis_malformed_job_is_moved_to_failed() {
+        let Some(mut redis) = test_redis().await else { return };
+        let queue = format!("test-{}", uuid::Uuid::new_v4());
+        let raw = format!("not json {}", queue);
+
+        redis::cmd("LPUSH").arg(pending_key(&queue)).arg(&raw).query_async::<()>(&mut redis).await.unwrap();
+
+        // The worker keeps going and the entry no longer sits on the processing list
+        assert!(get_next_job(&mut redis, &queue, 1).await.unwrap().is_none());
+        assert!(list(&mut redis, &processing_key(&queue)).await.is_empty());
+
+        let failed: Vec<String> = list(&mut redis, FAILED_QUEUE)
+            .await
+            .into_iter()
+            .filter(|entry| serde_json::from_str::<serde_json::Value>(entry).unwrap()["payload"] == raw.as_str())
+            .collect();
+        assert_eq!(failed.len(), 1);
+
+        redis::cmd("LREM")
+            .arg(FAILED_QUEUE)
+            .arg(0)
+            .arg(&failed[0])
+            .query_async::<()>(&mut redis)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_redis_failed_job() {
+        let Some(mut redis) = test_redis().await else { return };
+        let queue = format!("test-{}", uuid::Uuid::new_v4());
+
+        push_job(&mut redis, &queue, &queue).await;
+        let job = get_next_job(&mut redis, &queue, 1).await.unwrap().unwrap();
+        mark_job_as_failed(&mut redis, &job, "boom").await.unwrap();
+
+        assert!(list(&mut redis, &processing_key(&queue)).await.is_empty());
+
+        let failed: Vec<(String, serde_json::Value)> = list(&mut redis, FAILED_QUEUE)
+            .await
+            .into_iter()
+            .map(|raw| {
+                let entry = serde_json::from_str(&raw).unwrap();
+                (raw, entry)
+            })
+            .filter(|(_, entry): &(String, serde_json::Value)| entry["id"] == queue.as_str())
+            .collect();
+        assert_eq!(failed.len(), 1);
+
+        let (raw, entry) = &failed[0];
+        assert_eq!(entry["error"], "boom");
+        assert_eq!(entry["attempts"], 1);
+        assert!(entry["failed_at"].is_string());
+
+        redis::cmd("LREM")
+            .arg(FAILED_QUEUE)
+            .arg(0)
+            .arg(raw)
+            .query_async::<()>(&mut redis)
+            .await
+            .unwrap();
+    }
+}
@@ -2,23 +2,41 @@
 
 use anyhow::Result;
 use colored::*;
+use std::path::Path;
+use std::time::{Duration, Instant};
 use crate::RouteCommands;
+use crate::utils::TextUtils;
 use super::CommandUtils;
+use walkdir::WalkDir;
+
+/// Binary route cache, see `encode_route_cache`
+const ROUTE_CACHE: &str = "bootstrap/cache/routes.bin";
+
+/// JSON route cache written by earlier versions
+const LEGACY_ROUTE_CACHE: &str = "bootstrap/cache/routes.json";
+
+/// Base URL of `route test` when neither `--base-url` nor `app.url` is set
+const DEFAULT_BASE_URL: &str = "http://127.0.0.1:3000";
 
 /// Handle route command
 pub async fn handle(operation: RouteCommands) -> Result<()> {
     CommandUtils::ensure_rustisan_project()?;
 
     match operation {
-        RouteCommands::List { method, name, middleware } => {
-            list_routes(method, name, middleware).await
+        RouteCommands::List { method, name, middleware, source } => {
+            list_routes(method, name, middleware, source).await
         }
         RouteCommands::Clear => clear_route_cache().await,
+        RouteCommands::Check => check_routes().await,
         RouteCommands::Cache => cache_routes().await,
+        RouteCommands::Export { format, output } => export_routes(format, output).await,
+        RouteCommands::Test { base_url, method, auth, all_methods, timeout } => {
+            test_routes(RouteTestOptions { base_url, method, auth, all_methods, timeout }).await
+        }
     }
 }
 
-async fn list_routes(method: Option<String>, name: Option<String>, show_middleware: bool) -> Result<()> {
+async fn list_routes(method: Option<String>, name: Option<String>, show_middleware: bool, show_source: bool) -> Result<()> {
     CommandUtils::info("Listing application routes...");
 
     println!("\n{}", "Route List:".bold());
@@ -33,15 +51,15 @@ async fn list_routes(method: Option<String>, name: Option<String>, show_middlewa
 
     println!("├─────────────┼─────────────────────────────────────────────────────────────────────┤");
 
-    // TODO: Implement actual route discovery
-    let routes = discover_routes()?;
+    let routes = load_cached_routes()?;
+    warn_route_conflicts(&routes);
     let filtered_routes = filter_routes(routes, method, name);
 
     if filtered_routes.is_empty() {
         println!("│ {} │", "No routes found".dimmed());
     } else {
         for route in filtered_routes {
-            print_route(&route, show_middleware);
+            print_route(&route, show_middleware, show_source);
         }
     }
 
@@ -50,36 +68,457 @@ async fn list_routes(method: Option<String>, name: Option<String>, show_middlewa
     Ok(())
 }
 
+/// Fail when two routes share a method and URI pattern, for use in CI
+async fn check_routes() -> Result<()> {
+    let routes = discover_routes()?;
+    let duplicates = warn_route_conflicts(&routes);
+
+    if duplicates > 0 {
+        anyhow::bail!("Found {} duplicate route(s)", duplicates);
+    }
+
+    CommandUtils::success(&format!("No duplicate routes among {} route(s)", routes.len()));
+    Ok(())
+}
+
+/// Warn about duplicate and overlapping routes, returning the number of duplicates
+fn warn_route_conflicts(routes: &[Route]) -> usize {
+    let location = |route: &Route| format!("{}:{}", route.file, route.line_number);
+
+    let duplicates = detect_duplicate_routes(routes);
+    for &(first, second) in &duplicates {
+        CommandUtils::warning(&format!(
+            "Duplicate route {} {}: {} and {}",
+            routes[first].method,
+            routes[first].uri,
+            location(&routes[first]),
+            location(&routes[second])
+        ));
+    }
+
+    for (first, second) in detect_shadowed_routes(routes) {
+        CommandUtils::warning(&format!(
+            "Ambiguous routes {} {} ({}) and {} ({}) match the same URLs",
+            routes[first].method,
+            routes[first].uri,
+            location(&routes[first]),
+            routes[second].uri,
+            location(&routes[second])
+        ));
+    }
+
+    duplicates.len()
+}
+
+/// Index pairs of routes with the same method and URI pattern, parameter names aside
+fn detect_duplicate_routes(routes: &[Route]) -> Vec<(usize, usize)> {
+    route_pairs(routes)
+        .filter(|&(first, second)| {
+            let (a, b) = (uri_segments(&routes[first].uri), uri_segments(&routes[second].uri));
+            a.len() == b.len() && a.iter().zip(&b).all(|(a, b)| a == b || (is_parameter(a) && is_parameter(b)))
+        })
+        .collect()
+}
+
+/// Index pairs of routes where a literal segment of one overlaps a parameter of the other,
+/// such as `/users/stats` and `/users/{id}`
+fn detect_shadowed_routes(routes: &[Route]) -> Vec<(usize, usize)> {
+    route_pairs(routes)
+        .filter(|&(first, second)| {
+            let (a, b) = (uri_segments(&routes[first].uri), uri_segments(&routes[second].uri));
+            a.len() == b.len()
+                && a.iter().zip(&b).all(|(a, b)| a == b || is_parameter(a) || is_parameter(b))
+                && a.iter().zip(&b).any(|(a, b)| is_parameter(a) != is_parameter(b))
+        })
+        .collect()
+}
+
+/// Every pair of routes with the same method, in definition order
+fn route_pairs(routes: &[Route]) -> impl Iterator<Item = (usize, usize)> + '_ {
+    (0..routes.len())
+        .flat_map(move |first| (first + 1..routes.len()).map(move |second| (first, second)))
+        .filter(|&(first, second)| routes[first].method == routes[second].method)
+}
+
+fn uri_segments(uri: &str) -> Vec<&str> {
+    uri.split('/').filter(|segment| !segment.is_empty()).collect()
+}
+
+fn is_parameter(segment: &str) -> bool {
+    segment.starts_with('{') && segment.ends_with('}')
+}
+
 async fn clear_route_cache() -> Result<()> {
     CommandUtils::info("Clearing route cache...");
 
-    let cache_path = "bootstrap/cache/routes.json";
+    let existing: Vec<&str> = [ROUTE_CACHE, LEGACY_ROUTE_CACHE]
+        .into_iter()
+        .filter(|path| std::path::Path::new(path).exists())
+        .collect();
 
-    if std::path::Path::new(cache_path).exists() {
-        std::fs::remove_file(cache_path)?;
-        CommandUtils::success("Route cache cleared successfully");
-    } else {
+    if existing.is_empty() {
         CommandUtils::warning("Route cache file not found");
+        return Ok(());
     }
 
+    for path in existing {
+        std::fs::remove_file(path)?;
+    }
+    CommandUtils::success("Route cache cleared successfully");
+
     Ok(())
 }
 
-async fn cache_routes() -> Result<()> {
+pub(crate) async fn cache_routes() -> Result<()> {
     CommandUtils::info("Caching routes...");
 
-    // TODO: Implement route caching logic
     let routes = discover_routes()?;
-    let cache_data = serde_json::to_string_pretty(&routes)?;
+    let cache_data = encode_route_cache(&routes)?;
 
     CommandUtils::ensure_directory(&std::path::Path::new("bootstrap/cache"))?;
-    std::fs::write("bootstrap/cache/routes.json", cache_data)?;
+    std::fs::write(ROUTE_CACHE, &cache_data)?;
+
+    CommandUtils::success(&format!(
+        "Cached {} route(s) in {} ({})",
+        routes.len(),
+        ROUTE_CACHE,
+        super::build::format_size(cache_data.len() as u64)
+    ));
+
+    Ok(())
+}
+
+/// Routes from the binary cache when it was written by this CLI version after the
+/// sources last changed, discovered from the sources otherwise
+fn load_cached_routes() -> Result<Vec<Route>> {
+    let cached = cache_is_fresh(Path::new(ROUTE_CACHE), Path::new("src"))
+        .then(|| std::fs::read(ROUTE_CACHE).ok())
+        .flatten()
+        .and_then(|bytes| decode_route_cache(&bytes));
+    match cached {
+        Some(routes) => Ok(routes),
+        None => discover_routes(),
+    }
+}
+
+/// Whether `cache` exists and is newer than every Rust source under `sources`
+fn cache_is_fresh(cache: &Path, sources: &Path) -> bool {
+    let Ok(cached_at) = std::fs::metadata(cache).and_then(|metadata| metadata.modified()) else {
+        return false;
+    };
+
+    WalkDir::new(sources)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "rs"))
+        .filter_map(|entry| entry.metadata().ok()?.modified().ok())
+        .all(|modified| modified <= cached_at)
+}
+
+/// First four bytes of the SHA-256 of the CLI version, prefixed to the route cache
+fn route_cache_version() -> [u8; 4] {
+    use sha2::{Digest, Sha256};
+
+    let digest = Sha256::digest(env!("CARGO_PKG_VERSION").as_bytes());
+    [digest[0], digest[1], digest[2], digest[3]]
+}
+
+fn encode_route_cache(routes: &[Route]) -> Result<Vec<u8>> {
+    let mut bytes = route_cache_version().to_vec();
+    bytes.extend(bincode::serde::encode_to_vec(routes, bincode::config::standard())?);
+    Ok(bytes)
+}
+
+/// Decode a route cache, or `None` when it is corrupt or from another CLI version
+fn decode_route_cache(bytes: &[u8]) -> Option<Vec<Route>> {
+    let payload = bytes.strip_prefix(route_cache_version().as_slice())?;
+    let (routes, _) = bincode::serde::decode_from_slice(payload, bincode::config::standard()).ok()?;
+    Some(routes)
+}
+
+async fn export_routes(format: String, output: Option<String>) -> Result<()> {
+    if !matches!(format.to_lowercase().as_str(), "openapi" | "swagger") {
+        anyhow::bail!("Unsupported export format: {} (expected 'openapi' or 'swagger')", format);
+    }
+
+    let routes = discover_routes()?;
+    let document = build_openapi_document(&routes, &load_package_info());
+
+    match output {
+        Some(path) => {
+            let content = if path.ends_with(".json") {
+                serde_json::to_string_pretty(&document)?
+            } else {
+                serde_yaml::to_string(&document)?
+            };
+            CommandUtils::write_file(&path, &content)?;
+            CommandUtils::success(&format!("Exported {} route(s) to {}", routes.len(), path));
+        }
+        None => print!("{}", serde_yaml::to_string(&document)?),
+    }
+
+    Ok(())
+}
 
-    CommandUtils::success("Routes cached successfully");
+/// Options of `route test`
+struct RouteTestOptions {
+    base_url: Option<String>,
+    method: Option<String>,
+    auth: Option<String>,
+    all_methods: bool,
+    timeout: u64,
+}
+
+/// Outcome of one request sent by `route test`
+#[derive(Debug, serde::Serialize)]
+struct RouteProbe {
+    method: String,
+    uri: String,
+    url: String,
+    status: Option<u16>,
+    error: Option<String>,
+    elapsed_ms: u128,
+}
+
+impl RouteProbe {
+    fn failed(&self) -> bool {
+        self.status.is_none_or(|status| status >= 500)
+    }
+}
+
+async fn test_routes(options: RouteTestOptions) -> Result<()> {
+    // Only GET routes are safe to call blindly, anything else has to be asked for explicitly
+    let unsafe_method = options.method.as_deref().filter(|method| !method.eq_ignore_ascii_case("GET"));
+    if let Some(method) = unsafe_method.filter(|_| !options.all_methods) {
+        anyhow::bail!("Testing {} routes sends real requests that may change data, pass --all-methods to allow it", method.to_uppercase());
+    }
+
+    let base_url = options
+        .base_url
+        .or_else(|| super::db::load_config().ok().and_then(|config| super::db::get_config_value(&config, "app.url")))
+        .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+
+    let routes: Vec<Route> = filter_routes(load_cached_routes()?, options.method.clone(), None)
+        .into_iter()
+        .filter(|route| options.all_methods || route.method == "GET")
+        .collect();
+
+    if routes.is_empty() {
+        CommandUtils::warning("No routes to test");
+        return Ok(());
+    }
+
+    if !CommandUtils::is_json_output() {
+        CommandUtils::info(&format!("Testing {} route(s) against {}...", routes.len(), base_url));
+    }
+
+    let client = reqwest::Client::builder().timeout(Duration::from_secs(options.timeout)).build()?;
+    let probes = probe_routes(&client, &base_url, &routes, options.auth.as_deref()).await;
+
+    if CommandUtils::is_json_output() {
+        println!("{}", serde_json::to_string_pretty(&probes)?);
+    } else {
+        print_route_probes(&probes);
+    }
+
+    let failed = probes.iter().filter(|probe| probe.failed()).count();
+    if failed > 0 {
+        anyhow::bail!("{} of {} route(s) failed", failed, probes.len());
+    }
+
+    if !CommandUtils::is_json_output() {
+        CommandUtils::success(&format!("All {} route(s) responded", probes.len()));
+    }
 
     Ok(())
 }
 
+/// Send one request per route, in order, with `auth` as the `Authorization` header
+async fn probe_routes(client: &reqwest::Client, base_url: &str, routes: &[Route], auth: Option<&str>) -> Vec<RouteProbe> {
+    let mut probes = Vec::with_capacity(routes.len());
+
+    for route in routes {
+        let url = format!("{}{}", base_url.trim_end_matches('/'), fill_path_parameters(&route.uri));
+        let method = reqwest::Method::from_bytes(route.method.as_bytes()).unwrap_or(reqwest::Method::GET);
+
+        let mut request = client.request(method, &url);
+        if let Some(auth) = auth {
+            request = request.header(reqwest::header::AUTHORIZATION, auth);
+        }
+
+        let started = Instant::now();
+        let response = request.send().await;
+        let elapsed_ms = started.elapsed().as_millis();
+
+        let (status, error) = match response {
+            Ok(response) => (Some(response.status().as_u16()), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+
+        probes.push(RouteProbe {
+            method: route.method.clone(),
+            uri: route.uri.clone(),
+            url,
+            status,
+            error,
+            elapsed_ms,
+        });
+    }
+
+    probes
+}
+
+/// Replace `{param}` placeholders with a plausible value for the parameter name
+fn fill_path_parameters(uri: &str) -> String {
+    uri.split('/')
+        .map(|segment| match segment.strip_prefix('{').and_then(|rest| rest.strip_suffix('}')) {
+            Some(name) => sample_parameter(name.trim_end_matches('?')).to_string(),
+            None => segment.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn sample_parameter(name: &str) -> &'static str {
+    match name {
+        "id" | "page" => "1",
+        "uuid" => "00000000-0000-0000-0000-000000000000",
+        name if name.ends_with("_id") || name.ends_with("Id") => "1",
+        _ => "test",
+    }
+}
+
+fn print_route_probes(probes: &[RouteProbe]) {
+    let rows: Vec<Vec<String>> = probes
+        .iter()
+        .map(|probe| {
+            let status = match probe.status {
+                Some(status) if status >= 500 => status.to_string().red().to_string(),
+                Some(status) if status >= 400 => status.to_string().yellow().to_string(),
+                Some(status) => status.to_string().green().to_string(),
+                None => "error".red().to_string(),
+            };
+            vec![
+                format!("{} {}", probe.method, probe.uri),
+                probe.url.clone(),
+                status,
+                format!("{}ms", probe.elapsed_ms),
+            ]
+        })
+        .collect();
+
+    println!("{}", TextUtils::table_format(&["Route", "URL", "Status", "Time"], &rows, &[40, 50, 8, 10]));
+
+    for probe in probes {
+        if let Some(ref error) = probe.error {
+            CommandUtils::error(&format!("{} {}: {}", probe.method, probe.url, error));
+        }
+    }
+}
+
+/// Package metadata used for the OpenAPI `info` section
+struct PackageInfo {
+    name: String,
+    version: String,
+    description: String,
+}
+
+/// Read package metadata from Cargo.toml
+fn load_package_info() -> PackageInfo {
+    let package = std::fs::read_to_string("Cargo.toml")
+        .ok()
+        .and_then(|content| content.parse::<toml::Value>().ok())
+        .and_then(|manifest| manifest.get("package").cloned());
+    let field = |key: &str| {
+        package
+            .as_ref()
+            .and_then(|package| package.get(key))
+            .and_then(|value| value.as_str())
+            .map(str::to_string)
+    };
+
+    PackageInfo {
+        name: field("name").unwrap_or_else(|| "Rustisan Application".to_string()),
+        version: field("version").unwrap_or_else(|| "0.1.0".to_string()),
+        description: field("description").unwrap_or_default(),
+    }
+}
+
+/// Build an OpenAPI 3.0 document describing the given routes
+fn build_openapi_document(routes: &[Route], info: &PackageInfo) -> serde_json::Value {
+    let mut paths = serde_json::Map::new();
+
+    for route in routes {
+        let parameters: Vec<serde_json::Value> = path_parameters(&route.uri)
+            .into_iter()
+            .map(|parameter| {
+                serde_json::json!({
+                    "name": parameter,
+                    "in": "path",
+                    "required": true,
+                    "schema": { "type": "string" }
+                })
+            })
+            .collect();
+
+        let mut operation = serde_json::json!({
+            "summary": route.action,
+            "responses": {
+                "200": { "description": "Successful response" },
+                "default": {
+                    "description": "Error response",
+                    "content": {
+                        "application/json": {
+                            "schema": { "$ref": "#/components/schemas/Error" }
+                        }
+                    }
+                }
+            }
+        });
+
+        if let Some(ref name) = route.name {
+            operation["operationId"] = serde_json::json!(name);
+        }
+        if !parameters.is_empty() {
+            operation["parameters"] = serde_json::json!(parameters);
+        }
+
+        let path_item = paths
+            .entry(route.uri.clone())
+            .or_insert_with(|| serde_json::json!({}));
+        path_item[route.method.to_lowercase()] = operation;
+    }
+
+    serde_json::json!({
+        "openapi": "3.0.0",
+        "info": {
+            "title": info.name,
+            "version": info.version,
+            "description": info.description
+        },
+        "paths": paths,
+        "components": {
+            "schemas": {
+                "Error": {
+                    "type": "object",
+                    "properties": {
+                        "message": { "type": "string" }
+                    },
+                    "required": ["message"]
+                }
+            }
+        }
+    })
+}
+
+/// Extract `{param}` placeholders from a URI
+fn path_parameters(uri: &str) -> Vec<String> {
+    uri.split('/')
+        .filter_map(|segment| segment.strip_prefix('{')?.strip_suffix('}'))
+        .map(str::to_string)
+        .collect()
+}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 struct Route {
     method: String,
@@ -87,55 +526,191 @@ struct Route {
     name: Option<String>,
     action: String,
     middleware: Vec<String>,
+    file: String,
+    line_number: usize,
 }
 
+/// HTTP methods recognised on a router
+const ROUTE_METHODS: [&str; 6] = ["get", "post", "put", "patch", "delete", "options"];
+
+/// Discover routes by scanning the Rust sources under `src/`
 fn discover_routes() -> Result<Vec<Route>> {
-    // TODO: Implement actual route discovery by parsing route files
-    // For now, return some example routes
-    Ok(vec![
-        Route {
-            method: "GET".to_string(),
-            uri: "/".to_string(),
-            name: Some("home".to_string()),
-            action: "HomeController@index".to_string(),
-            middleware: vec!["web".to_string()],
-        },
-        Route {
-            method: "GET".to_string(),
-            uri: "/api/users".to_string(),
-            name: Some("users.index".to_string()),
-            action: "UserController@index".to_string(),
-            middleware: vec!["api".to_string(), "auth".to_string()],
-        },
-        Route {
-            method: "POST".to_string(),
-            uri: "/api/users".to_string(),
-            name: Some("users.store".to_string()),
-            action: "UserController@store".to_string(),
-            middleware: vec!["api".to_string(), "auth".to_string()],
-        },
-        Route {
-            method: "GET".to_string(),
-            uri: "/api/users/{id}".to_string(),
-            name: Some("users.show".to_string()),
-            action: "UserController@show".to_string(),
-            middleware: vec!["api".to_string(), "auth".to_string()],
-        },
-        Route {
-            method: "PUT".to_string(),
-            uri: "/api/users/{id}".to_string(),
-            name: Some("users.update".to_string()),
-            action: "UserController@update".to_string(),
-            middleware: vec!["api".to_string(), "auth".to_string()],
-        },
-        Route {
-            method: "DELETE".to_string(),
-            uri: "/api/users/{id}".to_string(),
-            name: Some("users.destroy".to_string()),
-            action: "UserController@destroy".to_string(),
-            middleware: vec!["api".to_string(), "auth".to_string()],
-        },
-    ])
+    let mut routes = Vec::new();
+
+    for entry in WalkDir::new("src").into_iter().filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        if !path.is_file() || path.extension().is_none_or(|ext| ext != "rs") {
+            continue;
+        }
+
+        let file = path.display().to_string();
+        let parsed = std::fs::read_to_string(path)
+            .map_err(anyhow::Error::from)
+            .and_then(|source| parse_routes(&source, &file));
+
+        match parsed {
+            Ok(mut file_routes) => routes.append(&mut file_routes),
+            Err(e) => CommandUtils::warning(&format!("Skipping {}: {}", file, e)),
+        }
+    }
+
+    Ok(routes)
+}
+
+/// Parse `router.<method>(...)` and `router.group(...)` calls from a Rust source file
+fn parse_routes(source: &str, file: &str) -> Result<Vec<Route>> {
+    // Byte ranges of group closures and the URI prefix they apply
+    let mut groups: Vec<(usize, usize, String)> = Vec::new();
+    let mut routes = Vec::new();
+
+    for (index, _) in source.match_indices("router") {
+        let mut rest = &source[index + "router".len()..];
+        if rest.starts_with("()") {
+            rest = &rest[2..];
+        }
+        let rest = match rest.strip_prefix('.') {
+            Some(rest) => rest,
+            None => continue,
+        };
+
+        let method: String = rest.chars().take_while(|c| c.is_ascii_alphabetic()).collect();
+        let args_start = source.len() - rest.len() + method.len();
+        if !source[args_start..].starts_with('(') {
+            continue;
+        }
+
+        let args_end = matching_delimiter(source, args_start, '(', ')')
+            .ok_or_else(|| anyhow::anyhow!("unclosed `router.{}(` on line {}", method, line_of(source, index)))?;
+        let args = &source[args_start + 1..args_end];
+
+        let uri = match leading_string_literal(args) {
+            Some(uri) => uri,
+            None => continue,
+        };
+
+        if method == "group" {
+            if let Some(open) = args.find('{') {
+                let open = args_start + 1 + open;
+                let close = matching_delimiter(source, open, '{', '}').unwrap_or(args_end);
+                groups.push((open, close, uri));
+            }
+            continue;
+        }
+
+        if !ROUTE_METHODS.contains(&method.as_str()) {
+            continue;
+        }
+
+        let prefix: String = groups
+            .iter()
+            .filter(|(start, end, _)| *start < index && index < *end)
+            .map(|(_, _, prefix)| prefix.trim_end_matches('/'))
+            .collect();
+
+        let action = args
+            .split_once(',')
+            .map(|(_, handler)| handler.trim())
+            .unwrap_or_default();
+        let action = if action.is_empty() || action.starts_with('|') || action.starts_with("move") {
+            "Closure".to_string()
+        } else {
+            action.split_whitespace().collect::<Vec<_>>().join(" ")
+        };
+
+        let (name, middleware) = parse_route_chain(&source[args_end + 1..]);
+
+        routes.push(Route {
+            method: method.to_uppercase(),
+            uri: join_uri(&prefix, &uri),
+            name,
+            action,
+            middleware,
+            file: file.to_string(),
+            line_number: line_of(source, index),
+        });
+    }
+
+    Ok(routes)
+}
+
+/// Parse chained `.name("...")` and `.middleware("...")` calls following a route definition
+fn parse_route_chain(mut rest: &str) -> (Option<String>, Vec<String>) {
+    let mut name = None;
+    let mut middleware = Vec::new();
+
+    loop {
+        let trimmed = rest.trim_start();
+        let (call, is_name) = if trimmed.starts_with(".name(") {
+            (".name(", true)
+        } else if trimmed.starts_with(".middleware(") {
+            (".middleware(", false)
+        } else {
+            break;
+        };
+
+        let args = &trimmed[call.len()..];
+        let end = match args.find(')') {
+            Some(end) => end,
+            None => break,
+        };
+
+        if let Some(value) = leading_string_literal(&args[..end]) {
+            if is_name {
+                name = Some(value);
+            } else {
+                middleware.push(value);
+            }
+        }
+
+        rest = &args[end + 1..];
+    }
+
+    (name, middleware)
+}
+
+/// Return the string literal at the start of `args`, if the first argument is one
+fn leading_string_literal(args: &str) -> Option<String> {
+    let args = args.trim_start().strip_prefix('"')?;
+    let end = args.find('"')?;
+    Some(args[..end].to_string())
+}
+
+/// Find the index of the delimiter closing the one at `open`
+fn matching_delimiter(source: &str, open: usize, open_char: char, close_char: char) -> Option<usize> {
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut previous = '\0';
+
+    for (index, ch) in source[open..].char_indices() {
+        if ch == '"' && previous != '\\' {
+            in_string = !in_string;
+        } else if !in_string && ch == open_char {
+            depth += 1;
+        } else if !in_string && ch == close_char {
+            depth -= 1;
+            if depth == 0 {
+                return Some(open + index);
+            }
+        }
+        previous = ch;
+    }
+
+    None
+}
+
+/// Join a group prefix and a route URI
+fn join_uri(prefix: &str, uri: &str) -> String {
+    let joined = format!("{}/{}", prefix.trim_end_matches('/'), uri.trim_start_matches('/'));
+    if joined.len() > 1 {
+        joined.trim_end_matches('/').to_string()
+    } else {
+        joined
+    }
+}
+
+/// 1-based line number of a byte offset
+fn line_of(source: &str, index: usize) -> usize {
+    source[..index].matches('\n').count() + 1
 }
 
 fn filter_routes(routes: Vec<Route>, method: Option<String>, name: Option<String>) -> Vec<Route> {
@@ -163,7 +738,7 @@ fn filter_routes(routes: Vec<Route>, method: Option<String>, name: Option<String
         .collect()
 }
 
-fn print_route(route: &Route, show_middleware: bool) {
+fn print_route(route: &Route, show_middleware: bool, show_source: bool) {
     let method_color = match route.method.as_str() {
         "GET" => route.method.green(),
         "POST" => route.method.blue(),
@@ -207,5 +782,221 @@ fn print_route(route: &Route, show_middleware: bool) {
         );
     }
 
+    if show_source {
+        let source = format!("{}:{}", route.file, route.line_number);
+        println!(
+            "│ {} │ {} │",
+            format!("{:11}", "Source:").dimmed(),
+            format!("{:67}", source).dimmed()
+        );
+    }
+
     println!("├─────────────┼─────────────────────────────────────────────────────────────────────┤");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_routes() {
+        let source = r#"
+fn routes(router: &mut Router) {
+    router.get("/", HomeController::index).name("home");
+
+    router.group("/api", |router| {
+        router.get("/users", UserController::index).name("users.index").middleware("auth");
+        router.post("/users", UserController::store);
+        router.delete("/users/{id}", || async { "deleted" });
+    });
+}
+"#;
+
+        let routes = parse_routes(source, "src/routes.rs").unwrap();
+        let summary: Vec<(&str, &str, &str)> = routes
+            .iter()
+            .map(|route| (route.method.as_str(), route.uri.as_str(), route.action.as_str()))
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("GET", "/", "HomeController::index"),
+                ("GET", "/api/users", "UserController::index"),
+                ("POST", "/api/users", "UserController::store"),
+                ("DELETE", "/api/users/{id}", "Closure"),
+            ]
+        );
+        assert_eq!(routes[0].name.as_deref(), Some("home"));
+        assert_eq!(routes[1].middleware, vec!["auth"]);
+        assert_eq!(routes[0].line_number, 3);
+        assert_eq!(routes[3].file, "src/routes.rs");
+    }
+
+    fn route(method: &str, uri: &str, line_number: usize) -> Route {
+        Route {
+            method: method.to_string(),
+            uri: uri.to_string(),
+            name: None,
+            action: "Closure".to_string(),
+            middleware: Vec::new(),
+            file: "src/routes.rs".to_string(),
+            line_number,
+        }
+    }
+
+    #[test]
+    fn test_detect_duplicate_routes() {
+        let routes = vec![
+            route("GET", "/users/{id}", 1),
+            route("GET", "/users", 2),
+            route("POST", "/users/{id}", 3),
+            route("GET", "/users/{user}", 4),
+            route("GET", "/users/", 5),
+        ];
+
+        assert_eq!(detect_duplicate_routes(&routes), vec![(0, 3), (1, 4)]);
+        assert!(detect_duplicate_routes(&routes[..3]).is_empty());
+    }
+
+    #[test]
+    fn test_detect_shadowed_routes() {
+        let routes = vec![
+            route("GET", "/users/{id}", 1),
+            route("GET", "/users/stats", 2),
+            route("POST", "/users/export", 3),
+            route("GET", "/users/{id}/posts", 4),
+            route("GET", "/users/{id}", 5),
+            route("GET", "/posts/latest", 6),
+        ];
+
+        assert_eq!(detect_shadowed_routes(&routes), vec![(0, 1), (1, 4)]);
+    }
+
+    #[test]
+    fn test_route_cache_round_trip() {
+        let source = r#"
+router.get("/", HomeController::index).name("home");
+router.group("/api", |router| {
+    router.delete("/users/{id}", UserController::destroy).middleware("auth");
+});
+"#;
+        let routes = parse_routes(source, "src/routes.rs").unwrap();
+        let bytes = encode_route_cache(&routes).unwrap();
+
+        assert_eq!(bytes[..4], route_cache_version());
+        let decoded = decode_route_cache(&bytes).unwrap();
+        assert_eq!(serde_json::to_value(&decoded).unwrap(), serde_json::to_value(&routes).unwrap());
+
+        // A cache written by another CLI version is ignored
+        let mut stale = bytes.clone();
+        stale[0] ^= 0xff;
+        assert!(decode_route_cache(&stale).is_none());
+        assert!(decode_route_cache(&bytes[..2]).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_non_get_method_requires_all_methods() {
+        let options = |method: &str| RouteTestOptions {
+            base_url: None,
+            method: Some(method.to_string()),
+            auth: None,
+            all_methods: false,
+            timeout: 1,
+        };
+
+        let error = test_routes(options("post")).await.unwrap_err();
+        assert!(error.to_string().contains("--all-methods"));
+        assert!(test_routes(options("DELETE")).await.is_err());
+    }
+
+    #[test]
+    fn test_cache_is_stale_after_sources_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let sources = dir.path().join("src");
+        let cache = dir.path().join("routes.bin");
+        std::fs::create_dir_all(&sources).unwrap();
+        let routes = std::fs::File::create(sources.join("routes.rs")).unwrap();
+        routes.set_modified(std::time::SystemTime::now() - Duration::from_secs(60)).unwrap();
+
+        assert!(!cache_is_fresh(&cache, &sources));
+        std::fs::write(&cache, b"routes").unwrap();
+        assert!(cache_is_fresh(&cache, &sources));
+
+        routes.set_modified(std::time::SystemTime::now() + Duration::from_secs(60)).unwrap();
+        assert!(!cache_is_fresh(&cache, &sources));
+    }
+
+    #[test]
+    fn test_fill_path_parameters() {
+        assert_eq!(fill_path_parameters("/users/{id}"), "/users/1");
+        assert_eq!(fill_path_parameters("/posts/{slug}/comments/{comment_id}"), "/posts/test/comments/1");
+        assert_eq!(fill_path_parameters("/files/{uuid}"), "/files/00000000-0000-0000-0000-000000000000");
+        assert_eq!(fill_path_parameters("/"), "/");
+    }
+
+    #[tokio::test]
+    async fn test_probe_routes_against_mock_server() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let mut requests = Vec::new();
+            for _ in 0..3 {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buffer = [0; 4096];
+                let read = socket.read(&mut buffer).await.unwrap();
+                requests.push(String::from_utf8_lossy(&buffer[..read]).to_string());
+                socket
+                    .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok")
+                    .await
+                    .unwrap();
+            }
+            requests
+        });
+
+        let source = r#"
+router.get("/", HomeController::index);
+router.get("/users/{id}", UserController::show);
+router.get("/posts/{slug}", PostController::show);
+"#;
+        let routes = parse_routes(source, "src/routes.rs").unwrap();
+        let client = reqwest::Client::builder().timeout(Duration::from_secs(5)).build().unwrap();
+
+        let probes = probe_routes(&client, &base_url, &routes, Some("Bearer secret")).await;
+        let requests = server.await.unwrap();
+
+        assert!(probes.iter().all(|probe| probe.status == Some(200)), "{:?}", probes);
+        assert_eq!(probes[1].url, format!("{}/users/1", base_url));
+        assert!(requests[2].starts_with("GET /posts/test HTTP/1.1"));
+        assert!(requests.iter().all(|request| request.to_lowercase().contains("authorization: bearer secret")));
+    }
+
+    #[test]
+    fn test_openapi_export() {
+        let source = r#"
+router.get("/", HomeController::index).name("home");
+router.group("/api", |router| {
+    router.get("/users/{id}", UserController::show);
+    router.put("/users/{id}", UserController::update);
+});
+"#;
+        let routes = parse_routes(source, "src/routes.rs").unwrap();
+        let info = PackageInfo {
+            name: "demo".to_string(),
+            version: "1.0.0".to_string(),
+            description: String::new(),
+        };
+
+        let yaml = serde_yaml::to_string(&build_openapi_document(&routes, &info)).unwrap();
+        let document: serde_json::Value = serde_yaml::from_str(&yaml).unwrap();
+
+        assert_eq!(document["openapi"], "3.0.0");
+        assert_eq!(document["info"]["title"], "demo");
+        for route in &routes {
+            assert!(document["paths"][&route.uri][route.method.to_lowercase()].is_object());
+        }
+        assert_eq!(document["paths"]["/api/users/{id}"]["get"]["parameters"][0]["in"], "path");
+    }
+}
@@ -0,0 +1,441 @@
+//! Schedule command implementations for the Rustisan CLI
+//!
+//! Scheduled tasks are `[[schedule]]` entries in rustisan.toml. `schedule run`
+//! is meant to be invoked every minute by the system cron:
+//!
+//! ```text
+//! * * * * * cd /path/to/app && rustisan schedule run
+//! ```
+//!
+//! `queue schedule` is the queued alternative: due tasks are pushed onto
+//! their queue as jobs and the queue workers run them.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Local, TimeZone, Timelike};
+use colored::*;
+use cron::Schedule;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::str::FromStr;
+use crate::ScheduleCommands;
+use super::CommandUtils;
+use super::queue;
+use crate::utils::{ProcessUtils, TextUtils};
+
+const CONFIG_FILE: &str = "rustisan.toml";
+
+/// Last enqueue time of each task, keeping `queue schedule` from firing a task twice a minute
+const STATE_FILE: &str = "storage/framework/schedule.json";
+
+/// A task from the `[[schedule]]` section of rustisan.toml
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+struct ScheduledTask {
+    cron: String,
+    command: String,
+    /// Name used by `--force-run` and the state file, defaults to the command
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    /// Queue the task is dispatched to by `queue schedule`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    queue: Option<String>,
+}
+
+impl ScheduledTask {
+    fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.command)
+    }
+
+    fn queue(&self) -> &str {
+        self.queue.as_deref().unwrap_or("default")
+    }
+}
+
+#[derive(Debug, Default, serde::Deserialize)]
+struct ScheduleConfig {
+    #[serde(default)]
+    schedule: Vec<ScheduledTask>,
+}
+
+/// Handle schedule command
+pub async fn handle(operation: ScheduleCommands) -> Result<()> {
+    CommandUtils::ensure_rustisan_project()?;
+
+    match operation {
+        ScheduleCommands::Run => run_due_tasks(),
+        ScheduleCommands::List => list_tasks(),
+        ScheduleCommands::Add { command, cron } => add_task(command, cron),
+    }
+}
+
+fn run_due_tasks() -> Result<()> {
+    let now = Local::now();
+    let tasks = load_tasks()?;
+    let mut ran = 0;
+
+    for task in &tasks {
+        if !is_due(&task.cron, &now)? {
+            continue;
+        }
+
+        ran += 1;
+        CommandUtils::info(&format!("Running scheduled command: {}", task.command));
+
+        match ProcessUtils::execute_shell(&task.command) {
+            Ok(true) => CommandUtils::success(&format!("Finished: {}", task.command)),
+            Ok(false) => CommandUtils::error(&format!("Command failed: {}", task.command)),
+            Err(e) => CommandUtils::error(&format!("Could not run '{}': {}", task.command, e)),
+        }
+    }
+
+    if ran == 0 {
+        CommandUtils::info("No scheduled commands are due");
+    }
+
+    Ok(())
+}
+
+/// Enqueue due tasks, or only `force_run` regardless of its cron expression
+pub(crate) async fn enqueue_due_tasks(force_run: Option<String>) -> Result<()> {
+    let now = Local::now();
+    let tasks = load_tasks()?;
+    let mut last_runs = load_last_runs(Path::new(STATE_FILE))?;
+
+    let due: Vec<&ScheduledTask> = match force_run {
+        Some(name) => vec![find_task(&tasks, &name)?],
+        None => {
+            let mut due = Vec::new();
+            for task in &tasks {
+                if should_enqueue(task, &now, last_runs.get(task.name()))? {
+                    due.push(task);
+                }
+            }
+            due
+        }
+    };
+
+    if due.is_empty() {
+        CommandUtils::info("No scheduled tasks are due");
+        return Ok(());
+    }
+
+    let mut redis = queue::connect(&queue::queue_connection_url(None)).await?;
+    for task in due {
+        // Workers resolve the command from rustisan.toml, so the queue never carries shell input
+        let payload = serde_json::json!({ "task": task.name() });
+        let id = queue::dispatch_job(&mut redis, task.queue(), queue::SCHEDULED_COMMAND_JOB, payload).await?;
+        last_runs.insert(task.name().to_string(), now);
+        CommandUtils::success(&format!("Queued '{}' on '{}' as job {}", task.name(), task.queue(), id));
+    }
+
+    save_last_runs(Path::new(STATE_FILE), &last_runs)
+}
+
+/// Command of the `[[schedule]]` task named `name`, run by queue workers for scheduled jobs
+pub(crate) fn scheduled_task_command(name: &str) -> Result<String> {
+    let tasks = load_tasks()?;
+    Ok(find_task(&tasks, name)?.command.clone())
+}
+
+fn find_task<'a>(tasks: &'a [ScheduledTask], name: &str) -> Result<&'a ScheduledTask> {
+    tasks.iter().find(|task| task.name() == name).ok_or_else(|| {
+        let names: Vec<&str> = tasks.iter().map(ScheduledTask::name).collect();
+        anyhow::anyhow!("No scheduled task named '{}' (available: {})", name, names.join(", "))
+    })
+}
+
+/// Whether a task is due and was not already enqueued during this minute
+fn should_enqueue<Tz: TimeZone>(
+    task: &ScheduledTask,
+    now: &DateTime<Tz>,
+    last_run: Option<&DateTime<Local>>,
+) -> Result<bool> {
+    let same_minute = last_run.is_some_and(|last_run| {
+        last_run.timestamp().div_euclid(60) == now.timestamp().div_euclid(60)
+    });
+    Ok(!same_minute && is_due(&task.cron, now)?)
+}
+
+fn load_last_runs(path: &Path) -> Result<BTreeMap<String, DateTime<Local>>> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content).with_context(|| format!("Failed to parse {}", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn save_last_runs(path: &Path, last_runs: &BTreeMap<String, DateTime<Local>>) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        CommandUtils::ensure_directory(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(last_runs)?)?;
+    Ok(())
+}
+
+fn list_tasks() -> Result<()> {
+    let tasks = load_tasks()?;
+
+    if tasks.is_empty() {
+        CommandUtils::warning("No scheduled tasks found in rustisan.toml");
+        return Ok(());
+    }
+
+    let now = Local::now();
+    let mut rows = Vec::with_capacity(tasks.len());
+    for task in &tasks {
+        let next_run = next_run(&task.cron, &now)?
+            .map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_else(|| "never".to_string());
+        rows.push(vec![task.cron.clone(), task.command.clone(), next_run]);
+    }
+
+    if CommandUtils::is_json_output() {
+        let entries: Vec<serde_json::Value> = rows
+            .iter()
+            .map(|row| serde_json::json!({ "cron": row[0], "command": row[1], "next_run": row[2] }))
+            .collect();
+        println!("{}", serde_json::Value::Array(entries));
+    } else {
+        println!("{}", "Scheduled Tasks:".bold());
+        println!("{}", TextUtils::table_format(&["Cron", "Command", "Next Run"], &rows, &[20, 50, 16]));
+    }
+
+    Ok(())
+}
+
+fn add_task(command: String, cron: String) -> Result<()> {
+    parse_schedule(&cron)?;
+
+    let content = std::fs::read_to_string(CONFIG_FILE)?;
+    let mut content = content.trim_end().to_string();
+    content.push_str(&format!(
+        "\n\n[[schedule]]\ncron = {}\ncommand = {}\n",
+        toml::Value::String(cron.clone()),
+        toml::Value::String(command.clone())
+    ));
+    std::fs::write(CONFIG_FILE, content)?;
+
+    CommandUtils::success(&format!("Scheduled '{}' with '{}'", command, cron));
+
+    Ok(())
+}
+
+fn load_tasks() -> Result<Vec<ScheduledTask>> {
+    let content = std::fs::read_to_string(CONFIG_FILE)?;
+    parse_tasks(&content)
+}
+
+fn parse_tasks(content: &str) -> Result<Vec<ScheduledTask>> {
+    let config: ScheduleConfig = toml::from_str(content).context("Failed to parse [[schedule]] in rustisan.toml")?;
+    Ok(config.schedule)
+}
+
+/// Parse a cron expression, accepting the classic five-field form
+///
+/// The `cron` crate expects a leading seconds field, so five-field
+/// expressions are run at second 0. It also numbers days of the week 1-7
+/// from Sunday, so the classic 0-7 (Sunday = 0 or 7) values are remapped.
+pub(crate) fn parse_schedule(expression: &str) -> Result<Schedule> {
+    let fields: Vec<&str> = expression.split_whitespace().collect();
+    let converted = match fields.as_slice() {
+        [minute, hour, day, month, day_of_week] => {
+            format!("0 {} {} {} {} {}", minute, hour, day, month, cron_day_of_week(day_of_week)?)
+        }
+        _ => expression.to_string(),
+    };
+
+    Schedule::from_str(&converted).map_err(|e| anyhow::anyhow!("Invalid cron expression '{}': {}", expression, e))
+}
+
+/// Convert a classic day-of-week field (0-7, Sunday = 0 or 7) to the `cron` crate's 1-7 from Sunday
+fn cron_day_of_week(field: &str) -> Result<String> {
+    let day = |value: &str| -> Result<u32> {
+        match value.parse::<u32>() {
+            Ok(day @ 0..=7) => Ok(day),
+            _ => anyhow::bail!("Invalid day of week '{}' in '{}'", value, field),
+        }
+    };
+    // Sunday is 1 in `cron`, Monday to Saturday move up by one
+    let convert = |day: u32| if day == 7 { 1 } else { day + 1 };
+    let is_numeric = |value: &str| value.chars().all(|c| c.is_ascii_digit());
+
+    let mut parts = Vec::new();
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or_else(|| anyhow::anyhow!("Invalid step in '{}'", field))?;
+                (range, Some(step))
+            }
+            None => (part, None),
+        };
+        let suffix = step.map(|step| format!("/{}", step)).unwrap_or_default();
+
+        match range.split_once('-') {
+            // `*` and day names mean the same in both numberings
+            _ if range == "*" => parts.push(part.to_string()),
+            Some((start, end)) if is_numeric(start) && is_numeric(end) => {
+                let (start, end) = (day(start)?, day(end)?);
+                if end == 7 {
+                    // Sunday closes the range, so it wraps round to 1 in `cron`
+                    parts.push(format!("{}-7{}", convert(start), suffix));
+                    if start > 0 && (7 - start) % step.unwrap_or(1) == 0 {
+                        parts.push("1".to_string());
+                    }
+                } else {
+                    parts.push(format!("{}-{}{}", convert(start), convert(end), suffix));
+                }
+            }
+            None if is_numeric(range) => parts.push(format!("{}{}", convert(day(range)?), suffix)),
+            _ => parts.push(part.to_string()),
+        }
+    }
+
+    Ok(parts.join(","))
+}
+
+/// Whether the task fires during the minute containing `now`
+fn is_due<Tz: TimeZone>(expression: &str, now: &DateTime<Tz>) -> Result<bool> {
+    let schedule = parse_schedule(expression)?;
+    let minute_start = now
+        .with_second(0)
+        .and_then(|time| time.with_nanosecond(0))
+        .unwrap_or_else(|| now.clone());
+
+    Ok(schedule
+        .after(&(minute_start.clone() - Duration::seconds(1)))
+        .next()
+        .is_some_and(|next| next < minute_start + Duration::minutes(1)))
+}
+
+/// Next time the task fires after `now`
+fn next_run<Tz: TimeZone>(expression: &str, now: &DateTime<Tz>) -> Result<Option<DateTime<Tz>>> {
+    Ok(parse_schedule(expression)?.after(now).next())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn test_every_minute_task_is_due() {
+        let now = Utc.with_ymd_and_hms(2024, 5, 17, 10, 42, 31).unwrap();
+        assert!(is_due("* * * * *", &now).unwrap());
+    }
+
+    #[test]
+    fn test_should_enqueue_once_per_minute() {
+        let task = ScheduledTask {
+            cron: "* * * * *".to_string(),
+            command: "rustisan cache clear".to_string(),
+            name: Some("clear-cache".to_string()),
+            queue: None,
+        };
+        let now = Local.with_ymd_and_hms(2024, 5, 17, 10, 42, 31).unwrap();
+
+        assert!(should_enqueue(&task, &now, None).unwrap());
+        assert!(!should_enqueue(&task, &now, Some(&(now - Duration::seconds(20)))).unwrap());
+        assert!(should_enqueue(&task, &now, Some(&(now - Duration::seconds(40)))).unwrap());
+        assert_eq!((task.name(), task.queue()), ("clear-cache", "default"));
+    }
+
+    #[test]
+    fn test_find_task_rejects_unknown_names() {
+        let tasks = parse_tasks(
+            r#"
+[[schedule]]
+cron = "* * * * *"
+command = "rustisan cache clear"
+name = "clear-cache"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(find_task(&tasks, "clear-cache").unwrap().command, "rustisan cache clear");
+        let error = find_task(&tasks, "rustisan cache clear").unwrap_err().to_string();
+        assert_eq!(error, "No scheduled task named 'rustisan cache clear' (available: clear-cache)");
+    }
+
+    #[test]
+    fn test_last_runs_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("framework/schedule.json");
+        assert!(load_last_runs(&path).unwrap().is_empty());
+
+        let now = Local.with_ymd_and_hms(2024, 5, 17, 10, 42, 0).unwrap();
+        let last_runs = BTreeMap::from([("clear-cache".to_string(), now)]);
+        save_last_runs(&path, &last_runs).unwrap();
+
+        assert_eq!(load_last_runs(&path).unwrap(), last_runs);
+    }
+
+    #[test]
+    fn test_is_due() {
+        let now = Utc.with_ymd_and_hms(2024, 5, 17, 10, 0, 45).unwrap();
+        assert!(is_due("0 10 * * *", &now).unwrap());
+        assert!(is_due("*/5 * * * *", &now).unwrap());
+        assert!(!is_due("30 10 * * *", &now).unwrap());
+        assert!(!is_due("0 0 1 1 *", &now).unwrap());
+        assert!(is_due("0 0 10 * * *", &now).unwrap());
+        assert!(is_due("bogus", &now).is_err());
+    }
+
+    #[test]
+    fn test_weekdays_use_the_classic_numbering() {
+        // Friday 2024-05-17 to Monday 2024-05-20
+        let friday = Utc.with_ymd_and_hms(2024, 5, 17, 10, 0, 0).unwrap();
+        let saturday = Utc.with_ymd_and_hms(2024, 5, 18, 10, 0, 0).unwrap();
+        let sunday = Utc.with_ymd_and_hms(2024, 5, 19, 10, 0, 0).unwrap();
+        let monday = Utc.with_ymd_and_hms(2024, 5, 20, 10, 0, 0).unwrap();
+
+        assert!(is_due("* * * * 1-5", &friday).unwrap());
+        assert!(is_due("* * * * 1-5", &monday).unwrap());
+        assert!(!is_due("* * * * 1-5", &saturday).unwrap());
+        assert!(!is_due("* * * * 1-5", &sunday).unwrap());
+
+        assert!(is_due("* * * * 0", &sunday).unwrap());
+        assert!(!is_due("* * * * 0", &monday).unwrap());
+        assert!(is_due("* * * * 7", &sunday).unwrap());
+        assert!(is_due("* * * * 5-7", &sunday).unwrap());
+        assert!(is_due("* * * * 5-7", &saturday).unwrap());
+        assert!(!is_due("* * * * 5-7", &monday).unwrap());
+        assert!(is_due("* * * * 0,6", &saturday).unwrap());
+        assert!(is_due("* * * * MON-FRI", &friday).unwrap());
+        assert!(parse_schedule("* * * * 8").is_err());
+        assert!(parse_schedule("* * * * 1-7/0").is_err());
+    }
+
+    #[test]
+    fn test_parse_tasks_and_next_run() {
+        let tasks = parse_tasks(
+            r#"
+[app]
+name = "Test"
+
+[[schedule]]
+cron = "* * * * *"
+command = "true"
+
+[[schedule]]
+cron = "0 3 * * *"
+command = "rustisan cache clear"
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(
+            tasks[0],
+            ScheduledTask { cron: "* * * * *".to_string(), command: "true".to_string(), name: None, queue: None }
+        );
+
+        let now = Utc.with_ymd_and_hms(2024, 5, 17, 10, 0, 0).unwrap();
+        let next = next_run(&tasks[1].cron, &now).unwrap().unwrap();
+        assert_eq!(next, Utc.with_ymd_and_hms(2024, 5, 18, 3, 0, 0).unwrap());
+
+        assert!(parse_tasks("[app]\nname = \"Test\"\n").unwrap().is_empty());
+    }
+}
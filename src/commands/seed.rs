@@ -1,17 +1,39 @@
 //! Seed command implementations for the Rustisan CLI
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
 use colored::*;
+use std::path::Path;
 use super::CommandUtils;
+use crate::utils::{ProcessUtils, ProgressBar, TextUtils};
+use crate::SeedCommands;
+
+const SEEDERS_DIR: &str = "database/seeders";
+
+/// Seeders that have run, in the order they last ran
+const TRACKING_FILE: &str = "storage/framework/seeders.json";
+
+/// A seeder run recorded in the tracking file
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+struct SeederRecord {
+    name: String,
+    ran_at: DateTime<Local>,
+    /// Records created, as reported on the last output line of the seeder
+    count: usize,
+}
 
 /// Handle seed command
-pub async fn handle(class: Option<String>, force: bool) -> Result<()> {
+pub async fn handle(operation: Option<SeedCommands>, class: Option<String>, force: bool, stop_on_error: bool) -> Result<()> {
     CommandUtils::ensure_rustisan_project()?;
 
-    if let Some(seeder_class) = class {
-        run_specific_seeder(seeder_class, force).await
-    } else {
-        run_all_seeders(force).await
+    match operation {
+        Some(SeedCommands::List) => list_seeders(),
+        Some(SeedCommands::Rollback { class }) => rollback_seeders(class).await,
+        Some(SeedCommands::Status) => seeder_status(),
+        None => match class {
+            Some(seeder_class) => run_specific_seeder(seeder_class, force).await,
+            None => run_all_seeders(force, stop_on_error).await,
+        },
     }
 }
 
@@ -24,13 +46,14 @@ async fn run_specific_seeder(class: String, force: bool) -> Result<()> {
         return Ok(());
     }
 
-    // TODO: Implement specific seeder logic
+    let count = run_seeder(&class)?;
+    record_seeder_run(Path::new(TRACKING_FILE), &class, count)?;
     CommandUtils::success(&format!("Seeder {} completed successfully", class));
 
     Ok(())
 }
 
-async fn run_all_seeders(force: bool) -> Result<()> {
+async fn run_all_seeders(force: bool, stop_on_error: bool) -> Result<()> {
     CommandUtils::info("Running database seeders...");
 
     // Check if we're in production and force is not set
@@ -39,7 +62,6 @@ async fn run_all_seeders(force: bool) -> Result<()> {
         return Ok(());
     }
 
-    // TODO: Implement logic to discover and run all seeders
     let seeders = discover_seeders()?;
 
     if seeders.is_empty() {
@@ -47,18 +69,189 @@ async fn run_all_seeders(force: bool) -> Result<()> {
         return Ok(());
     }
 
-    for seeder in seeders {
-        CommandUtils::info(&format!("Running seeder: {}", seeder));
-        // TODO: Execute seeder
+    let mut progress = ProgressBar::new("Seeding", seeders.len());
+    let mut failed = Vec::new();
+
+    for seeder in &seeders {
+        progress.suspend(|| CommandUtils::info(&format!("Running seeder: {}", seeder)));
+
+        match run_seeder(seeder) {
+            Ok(count) => record_seeder_run(Path::new(TRACKING_FILE), seeder, count)?,
+            Err(e) if stop_on_error => anyhow::bail!("Seeder {} failed, stopping: {}", seeder, e),
+            Err(e) => {
+                progress.suspend(|| CommandUtils::error(&format!("Seeder {} failed: {}", seeder, e)));
+                failed.push(seeder.as_str());
+            }
+        }
+
+        progress.increment();
+    }
+
+    if !failed.is_empty() {
+        progress.finish_and_clear();
+        anyhow::bail!("{} of {} seeder(s) failed: {}", failed.len(), seeders.len(), failed.join(", "));
     }
 
+    progress.finish_and_clear();
     CommandUtils::success("All seeders completed successfully");
 
     Ok(())
 }
 
+/// Print the seeders in the order `seed` runs them
+fn list_seeders() -> Result<()> {
+    let seeders = discover_seeders()?;
+
+    if seeders.is_empty() {
+        CommandUtils::warning("No seeders found");
+        return Ok(());
+    }
+
+    println!("{}", "Seeder execution order:".bold());
+    for (position, seeder) in seeders.iter().enumerate() {
+        println!("  {:>3}. {}", position + 1, seeder.cyan());
+    }
+
+    Ok(())
+}
+
+/// Roll back one seeder, or every tracked seeder in reverse order of execution
+async fn rollback_seeders(class: Option<String>) -> Result<()> {
+    let path = Path::new(TRACKING_FILE);
+    let mut records = load_seeder_records(path)?;
+
+    if records.is_empty() {
+        CommandUtils::warning("No seeders have been run");
+        return Ok(());
+    }
+
+    let result = rollback_tracked(&mut records, class.as_deref(), |name| {
+        CommandUtils::info(&format!("Rolling back seeder: {}", name));
+        seeder_command(name, true).map(|_| ())
+    });
+
+    // Keep the records of the seeders rolled back before a failure removed
+    save_seeder_records(path, &records)?;
+    let rolled_back = result?;
+
+    CommandUtils::success(&format!("Rolled back {} seeder(s)", rolled_back.len()));
+    Ok(())
+}
+
+/// Call `rollback` for `class`, or every record newest first, removing each record that rolls back
+fn rollback_tracked<F>(records: &mut Vec<SeederRecord>, class: Option<&str>, mut rollback: F) -> Result<Vec<String>>
+where
+    F: FnMut(&str) -> Result<()>,
+{
+    let names: Vec<String> = match class {
+        Some(class) => {
+            let record = records
+                .iter()
+                .find(|record| record.name == class)
+                .ok_or_else(|| anyhow::anyhow!("Seeder '{}' has not been run", class))?;
+            vec![record.name.clone()]
+        }
+        None => records.iter().rev().map(|record| record.name.clone()).collect(),
+    };
+
+    for name in &names {
+        rollback(name).with_context(|| format!("Rolling back seeder {} failed", name))?;
+        records.retain(|record| record.name != *name);
+    }
+
+    Ok(names)
+}
+
+/// Show which seeders have run, and when
+fn seeder_status() -> Result<()> {
+    let records = load_seeder_records(Path::new(TRACKING_FILE))?;
+    let mut seeders = discover_seeders()?;
+    for record in &records {
+        if !seeders.contains(&record.name) {
+            seeders.push(record.name.clone());
+        }
+    }
+
+    if seeders.is_empty() {
+        CommandUtils::warning("No seeders found");
+        return Ok(());
+    }
+
+    let rows: Vec<Vec<String>> = seeders
+        .iter()
+        .map(|seeder| match records.iter().find(|record| record.name == *seeder) {
+            Some(record) => vec![
+                seeder.clone(),
+                "Ran".green().to_string(),
+                record.ran_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+                record.count.to_string(),
+            ],
+            None => vec![seeder.clone(), "Pending".yellow().to_string(), "-".to_string(), "-".to_string()],
+        })
+        .collect();
+
+    println!("{}", TextUtils::table_format(&["Seeder", "Status", "Ran At", "Records"], &rows, &[40, 10, 20, 10]));
+    Ok(())
+}
+
+/// Run a seeder class through the application's seeder binary, returning the records it created
+fn run_seeder(class: &str) -> Result<usize> {
+    let stdout = seeder_command(class, false)?;
+    Ok(seeded_count(&stdout))
+}
+
+fn seeder_command(class: &str, rollback: bool) -> Result<String> {
+    let mut args = vec!["run", "--quiet", "--bin", "seed", "--", class];
+    if rollback {
+        args.push("--rollback");
+    }
+
+    let (success, stdout, stderr) = ProcessUtils::execute_with_output("cargo", &args)?;
+
+    if !success {
+        anyhow::bail!("{}", stderr.trim());
+    }
+
+    Ok(stdout)
+}
+
+/// Number on the last output line of a seeder, 0 when it does not report one
+fn seeded_count(stdout: &str) -> usize {
+    stdout
+        .lines()
+        .rev()
+        .find(|line| !line.trim().is_empty())
+        .and_then(|line| line.split_whitespace().find_map(|word| word.parse().ok()))
+        .unwrap_or(0)
+}
+
+fn load_seeder_records(path: &Path) -> Result<Vec<SeederRecord>> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content).with_context(|| format!("Failed to parse {}", path.display())),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn save_seeder_records(path: &Path, records: &[SeederRecord]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        CommandUtils::ensure_directory(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(records)?)?;
+    Ok(())
+}
+
+/// Record a seeder run, moving a seeder that ran before to the end
+fn record_seeder_run(path: &Path, name: &str, count: usize) -> Result<()> {
+    let mut records = load_seeder_records(path)?;
+    records.retain(|record| record.name != name);
+    records.push(SeederRecord { name: name.to_string(), ran_at: Local::now(), count });
+    save_seeder_records(path, &records)
+}
+
+/// Seeder classes under `database/seeders`, in `[seeders] order` followed by the rest alphabetically
 fn discover_seeders() -> Result<Vec<String>> {
-    let seeders_dir = std::path::Path::new("database/seeders");
+    let seeders_dir = std::path::Path::new(SEEDERS_DIR);
 
     if !seeders_dir.exists() {
         return Ok(Vec::new());
@@ -70,17 +263,53 @@ fn discover_seeders() -> Result<Vec<String>> {
         let entry = entry?;
         let path = entry.path();
 
-        if path.is_file() && path.extension().map_or(false, |ext| ext == "rs") {
-            if let Some(name) = path.file_stem() {
-                seeders.push(name.to_string_lossy().to_string());
-            }
+        if path.is_file() && path.extension().is_some_and(|ext| ext == "rs")
+            && let Some(name) = path.file_stem()
+        {
+            seeders.push(CommandUtils::to_pascal_case(&name.to_string_lossy()));
         }
     }
 
     // Sort seeders alphabetically
     seeders.sort();
 
-    Ok(seeders)
+    let (ordered, missing) = order_seeders(seeders, &declared_seeder_order());
+    for seeder in missing {
+        CommandUtils::warning(&format!("Seeder {} listed in [seeders] order was not found in {}", seeder, SEEDERS_DIR));
+    }
+
+    Ok(ordered)
+}
+
+/// `[seeders] order` from rustisan.toml, empty when not configured
+fn declared_seeder_order() -> Vec<String> {
+    let Ok(config) = super::db::load_config() else {
+        return Vec::new();
+    };
+
+    config
+        .get("seeders")
+        .and_then(|seeders| seeders.get("order"))
+        .and_then(|order| order.as_array())
+        .map(|order| order.iter().filter_map(|name| name.as_str()).map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+/// Put `found` seeders in the `declared` order, appending undeclared ones,
+/// and return the declared seeders that were not found
+fn order_seeders(mut found: Vec<String>, declared: &[String]) -> (Vec<String>, Vec<String>) {
+    let mut ordered = Vec::with_capacity(found.len());
+    let mut missing = Vec::new();
+
+    for name in declared {
+        match found.iter().position(|seeder| *seeder == CommandUtils::to_pascal_case(name)) {
+            Some(index) => ordered.push(found.remove(index)),
+            None => missing.push(name.clone()),
+        }
+    }
+
+    ordered.extend(found);
+    (ordered, missing)
 }
 
 fn is_production_environment() -> bool {
@@ -89,3 +318,78 @@ fn is_production_environment() -> bool {
         .map(|env| env.to_lowercase() == "production")
         .unwrap_or(false)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn test_order_seeders_respects_declared_order() {
+        let found = names(&["CommentSeeder", "PostSeeder", "TagSeeder", "UserSeeder"]);
+        let declared = names(&["UserSeeder", "PostSeeder", "CategorySeeder", "CommentSeeder"]);
+
+        let (ordered, missing) = order_seeders(found, &declared);
+
+        assert_eq!(ordered, names(&["UserSeeder", "PostSeeder", "CommentSeeder", "TagSeeder"]));
+        assert_eq!(missing, names(&["CategorySeeder"]));
+    }
+
+    #[test]
+    fn test_rollback_removes_tracking_record() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("storage/framework/seeders.json");
+        record_seeder_run(&path, "UserSeeder", 10).unwrap();
+        record_seeder_run(&path, "PostSeeder", 25).unwrap();
+        record_seeder_run(&path, "CommentSeeder", 0).unwrap();
+
+        let mut records = load_seeder_records(&path).unwrap();
+        let rolled_back = rollback_tracked(&mut records, Some("PostSeeder"), |_| Ok(())).unwrap();
+        save_seeder_records(&path, &records).unwrap();
+
+        assert_eq!(rolled_back, names(&["PostSeeder"]));
+        let remaining: Vec<String> = load_seeder_records(&path).unwrap().into_iter().map(|record| record.name).collect();
+        assert_eq!(remaining, names(&["UserSeeder", "CommentSeeder"]));
+        assert!(rollback_tracked(&mut records, Some("PostSeeder"), |_| Ok(())).is_err());
+    }
+
+    #[test]
+    fn test_rollback_all_runs_in_reverse_order_and_stops_on_failure() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("seeders.json");
+        for name in ["UserSeeder", "PostSeeder", "CommentSeeder"] {
+            record_seeder_run(&path, name, 1).unwrap();
+        }
+        let mut records = load_seeder_records(&path).unwrap();
+
+        let mut called = Vec::new();
+        let result = rollback_tracked(&mut records, None, |name| {
+            called.push(name.to_string());
+            if name == "PostSeeder" {
+                anyhow::bail!("foreign key constraint");
+            }
+            Ok(())
+        });
+
+        assert!(result.is_err());
+        assert_eq!(called, names(&["CommentSeeder", "PostSeeder"]));
+        assert_eq!(records.iter().map(|record| record.name.as_str()).collect::<Vec<_>>(), ["UserSeeder", "PostSeeder"]);
+    }
+
+    #[test]
+    fn test_seeded_count() {
+        assert_eq!(seeded_count("Seeding users...\nSeeded 42 records\n\n"), 42);
+        assert_eq!(seeded_count("Seeding users...\n"), 0);
+        assert_eq!(seeded_count(""), 0);
+    }
+
+    #[test]
+    fn test_order_seeders_without_declared_order_keeps_found_order() {
+        let found = names(&["CommentSeeder", "UserSeeder"]);
+
+        assert_eq!(order_seeders(found.clone(), &[]), (found, Vec::new()));
+    }
+}
@@ -0,0 +1,206 @@
+//! Native hot reload for the serve command
+//!
+//! Watches `src/`, `Cargo.toml` and `rustisan.toml` (plus any `--watch-path`)
+//! with `notify`. After a change the running server is stopped, the
+//! application rebuilt with the build output streamed, and the server started
+//! again. A failed build leaves the server stopped until the next change.
+
+use anyhow::Result;
+use colored::*;
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::future::Future;
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::process::{Child, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+
+use crate::commands::CommandUtils;
+use crate::utils::ProcessUtils;
+use super::{cargo_command, PidFile, SERVER_PID_FILE};
+
+/// Quiet period after the last change before the server is rebuilt
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Paths always watched, relative to the project root
+const WATCHED_PATHS: &[&str] = &["src", "Cargo.toml", "rustisan.toml"];
+
+/// Run the server, rebuilding and restarting it whenever a watched file changes
+pub async fn serve_with_reload(extra_paths: &[String], server_env: Vec<(&'static str, OsString)>) -> Result<()> {
+    let paths: Vec<PathBuf> = WATCHED_PATHS
+        .iter()
+        .map(PathBuf::from)
+        .chain(extra_paths.iter().map(PathBuf::from))
+        .collect();
+
+    let (_watcher, events) = start_watcher(&paths)?;
+    let watched: Vec<String> = paths.iter().filter(|path| path.exists()).map(|path| path.display().to_string()).collect();
+    CommandUtils::info(&format!("Watching {} for changes", watched.join(", ")));
+
+    let server_env = Arc::new(server_env);
+    let first = spawn_server(&server_env)?;
+    let pid_file = Arc::new(PidFile::write(std::path::Path::new(SERVER_PID_FILE), first.id())?);
+    let server = Arc::new(Mutex::new(Some(first)));
+    let restarting = Arc::clone(&server);
+
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+
+    tokio::select! {
+        _ = &mut ctrl_c => {
+            CommandUtils::info("Shutting down development server...");
+        }
+        result = watch_loop(events, |changed| {
+            let server = Arc::clone(&restarting);
+            let pid_file = Arc::clone(&pid_file);
+            let server_env = Arc::clone(&server_env);
+            // The build blocks until cargo exits, so keep it off the runtime that watches for Ctrl+C
+            async move {
+                tokio::task::spawn_blocking(move || restart(&server, &pid_file, &changed, &server_env)).await?
+            }
+        }) => {
+            result?;
+        }
+    }
+
+    if let Ok(mut server) = server.lock() {
+        stop_server(&mut server);
+    }
+
+    CommandUtils::success("Development server stopped");
+    Ok(())
+}
+
+/// Watch the existing paths, sending every created, modified or removed path
+fn start_watcher(paths: &[PathBuf]) -> Result<(RecommendedWatcher, UnboundedReceiver<PathBuf>)> {
+    let (sender, events) = mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+        let Ok(event) = event else {
+            return;
+        };
+        if !(event.kind.is_create() || event.kind.is_modify() || event.kind.is_remove()) {
+            return;
+        }
+        for path in event.paths {
+            let _ = sender.send(path);
+        }
+    })?;
+
+    for path in paths {
+        if path.is_dir() {
+            watcher.watch(path, RecursiveMode::Recursive)?;
+        } else if path.exists() {
+            watcher.watch(path, RecursiveMode::NonRecursive)?;
+        }
+    }
+
+    Ok((watcher, events))
+}
+
+/// Debounce file events and call `on_change` until the channel closes
+async fn watch_loop<F, Fut>(mut events: UnboundedReceiver<PathBuf>, mut on_change: F) -> Result<()>
+where
+    F: FnMut(Vec<PathBuf>) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    while let Some(first) = events.recv().await {
+        let mut changed = vec![first];
+        while let Ok(Some(path)) = tokio::time::timeout(DEBOUNCE, events.recv()).await {
+            if !changed.contains(&path) {
+                changed.push(path);
+            }
+        }
+
+        on_change(changed).await?;
+    }
+
+    Ok(())
+}
+
+/// Stop the server, rebuild and start it again
+fn restart(
+    server: &Mutex<Option<Child>>,
+    pid_file: &PidFile,
+    changed: &[PathBuf],
+    server_env: &[(&'static str, OsString)],
+) -> Result<()> {
+    let root = std::env::current_dir()?;
+    println!("\n{}", "─".repeat(60).dimmed());
+    for path in changed {
+        let relative = path.strip_prefix(&root).unwrap_or(path);
+        CommandUtils::info(&format!("Change detected in {}", relative.display()));
+    }
+
+    let mut server = server.lock().map_err(|_| anyhow::anyhow!("Failed to lock server process"))?;
+    stop_server(&mut server);
+
+    CommandUtils::info("Rebuilding application...");
+    let built = ProcessUtils::execute_command_streaming(
+        &mut cargo_command("build", server_env),
+        |line| println!("{}", line),
+        |line| eprintln!("{}", line),
+    )?;
+
+    if built {
+        CommandUtils::success("Application rebuilt");
+        let child = spawn_server(server_env)?;
+        pid_file.update(child.id())?;
+        *server = Some(child);
+    } else {
+        CommandUtils::error("Build failed, waiting for changes...");
+    }
+
+    Ok(())
+}
+
+fn spawn_server(server_env: &[(&'static str, OsString)]) -> Result<Child> {
+    CommandUtils::info("Starting server...");
+    Ok(cargo_command("run", server_env)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()?)
+}
+
+fn stop_server(server: &mut Option<Child>) {
+    if let Some(mut child) = server.take() {
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_file_change_triggers_rebuild() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        std::fs::create_dir(&src).unwrap();
+        let main = src.join("main.rs");
+        std::fs::write(&main, "fn main() {}").unwrap();
+
+        let paths = vec![src.clone(), dir.path().join("missing")];
+        let (watcher, events) = start_watcher(&paths).unwrap();
+
+        std::fs::write(&main, "fn main() { println!(\"changed\"); }").unwrap();
+
+        // Give the watcher time to deliver the event, then close the channel
+        tokio::time::sleep(Duration::from_millis(1000)).await;
+        drop(watcher);
+
+        let mut rebuilds = Vec::new();
+        watch_loop(events, |changed| {
+            rebuilds.push(changed);
+            async { Ok(()) }
+        })
+        .await
+        .unwrap();
+
+        // Events for one save are debounced into a single rebuild
+        assert_eq!(rebuilds.len(), 1);
+        let main = main.canonicalize().unwrap();
+        assert!(rebuilds[0].iter().any(|path| path.canonicalize().ok().as_ref() == Some(&main)));
+    }
+}
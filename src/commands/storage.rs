@@ -0,0 +1,359 @@
+//! Storage command implementations for the Rustisan CLI
+//!
+//! `storage link` exposes `storage/uploads` to the web server as
+//! `public/storage`. The other subcommands inspect and prune `storage/`.
+
+use anyhow::Result;
+use colored::*;
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use super::CommandUtils;
+use super::build::format_size;
+use crate::StorageCommands;
+use crate::utils::{FileUtils, TextUtils};
+
+const STORAGE_DIR: &str = "storage";
+
+/// Public path of the uploads link
+const LINK_PATH: &str = "public/storage";
+
+/// Directory the link points at
+const LINK_TARGET: &str = "storage/uploads";
+
+/// Directories holding application state rather than data, never cleaned
+const PRESERVED_DIRS: &[&str] = &["framework", "deployments"];
+
+/// Handle storage commands
+pub async fn handle(operation: StorageCommands) -> Result<()> {
+    CommandUtils::ensure_rustisan_project()?;
+    let root = Path::new(".");
+
+    match operation {
+        StorageCommands::Link => {
+            if create_link(root)? {
+                CommandUtils::success(&format!("Linked {} to {}", LINK_PATH.cyan(), LINK_TARGET.cyan()));
+            } else {
+                CommandUtils::warning(&format!("{} already exists", LINK_PATH));
+            }
+            Ok(())
+        }
+        StorageCommands::Unlink => {
+            if remove_link(root)? {
+                CommandUtils::success(&format!("Removed {}", LINK_PATH.cyan()));
+            } else {
+                CommandUtils::warning(&format!("{} does not exist", LINK_PATH));
+            }
+            Ok(())
+        }
+        StorageCommands::Clean { days, dry_run } => {
+            let removed = clean(root, days, dry_run)?;
+            let total: u64 = removed.iter().map(|(_, size)| size).sum();
+
+            for (path, size) in &removed {
+                println!("  {} {} ({})", "-".red(), path.display(), format_size(*size));
+            }
+
+            if dry_run {
+                CommandUtils::info(&format!(
+                    "Dry run: {} file(s) older than {} days would be deleted, freeing {}",
+                    removed.len(),
+                    days,
+                    format_size(total)
+                ));
+            } else {
+                CommandUtils::success(&format!("Deleted {} file(s), freed {}", removed.len(), format_size(total)));
+            }
+            Ok(())
+        }
+        StorageCommands::Stats => {
+            let stats = stats(root)?;
+            let total: u64 = stats.iter().map(|(_, _, size)| size).sum();
+
+            let mut rows: Vec<Vec<String>> = stats
+                .iter()
+                .map(|(name, files, size)| vec![name.clone(), files.to_string(), format_size(*size)])
+                .collect();
+            rows.push(vec!["Total".bold().to_string(), String::new(), format_size(total).bold().to_string()]);
+
+            println!("{}", TextUtils::table_format(&["Directory", "Files", "Size"], &rows, &[30, 10, 12]));
+            Ok(())
+        }
+        StorageCommands::List { path } => {
+            let dir = storage_path(root, path.as_deref())?;
+            let entries = list(&dir)?;
+
+            if entries.is_empty() {
+                CommandUtils::info(&format!("{} is empty", dir.display()));
+                return Ok(());
+            }
+
+            let rows: Vec<Vec<String>> = entries
+                .iter()
+                .map(|(name, size, modified)| {
+                    vec![
+                        name.clone(),
+                        size.map(format_size).unwrap_or_else(|| "-".to_string()),
+                        modified.clone(),
+                    ]
+                })
+                .collect();
+
+            println!("{}", TextUtils::table_format(&["Name", "Size", "Modified"], &rows, &[48, 12, 20]));
+            Ok(())
+        }
+    }
+}
+
+/// Create the `public/storage` link; returns false if it already exists
+fn create_link(root: &Path) -> Result<bool> {
+    let link = root.join(LINK_PATH);
+    if link.symlink_metadata().is_ok() {
+        return Ok(false);
+    }
+
+    FileUtils::ensure_dir(root.join(LINK_TARGET))?;
+    FileUtils::ensure_dir(root.join("public"))?;
+
+    #[cfg(unix)]
+    {
+        // Relative, so the project can be moved without breaking the link
+        std::os::unix::fs::symlink(Path::new("..").join(LINK_TARGET), &link)?;
+    }
+
+    #[cfg(windows)]
+    {
+        // Junctions, unlike directory symlinks, need no administrator rights
+        let target = std::fs::canonicalize(root.join(LINK_TARGET))?;
+        let status = std::process::Command::new("cmd")
+            .args(["/C", "mklink", "/J"])
+            .arg(&link)
+            .arg(&target)
+            .status()?;
+        if !status.success() {
+            anyhow::bail!("Failed to create junction {}", link.display());
+        }
+    }
+
+    Ok(true)
+}
+
+/// Remove the `public/storage` link; returns false if there is none
+fn remove_link(root: &Path) -> Result<bool> {
+    let link = root.join(LINK_PATH);
+    let Ok(metadata) = link.symlink_metadata() else {
+        return Ok(false);
+    };
+
+    if metadata.file_type().is_symlink() {
+        // Directory symlinks and junctions are removed as directories on Windows
+        if cfg!(windows) {
+            std::fs::remove_dir(&link)?;
+        } else {
+            std::fs::remove_file(&link)?;
+        }
+        return Ok(true);
+    }
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::MetadataExt;
+        const FILE_ATTRIBUTE_REPARSE_POINT: u32 = 0x400;
+        if metadata.file_attributes() & FILE_ATTRIBUTE_REPARSE_POINT != 0 {
+            std::fs::remove_dir(&link)?;
+            return Ok(true);
+        }
+    }
+
+    anyhow::bail!("{} is not a link; remove it manually", link.display())
+}
+
+/// Delete files in `storage/` not modified for `days` days
+///
+/// Returns the deleted (or, in a dry run, deletable) files and their sizes.
+fn clean(root: &Path, days: u32, dry_run: bool) -> Result<Vec<(PathBuf, u64)>> {
+    let storage = root.join(STORAGE_DIR);
+    // A cutoff before the earliest representable time means no file is old enough
+    let Some(cutoff) = SystemTime::now().checked_sub(Duration::from_secs(u64::from(days) * 24 * 60 * 60)) else {
+        return Ok(Vec::new());
+    };
+
+    let mut removed = Vec::new();
+    for path in FileUtils::files_recursive(&storage) {
+        let relative = path.strip_prefix(&storage).unwrap_or(&path);
+        let preserved = relative
+            .components()
+            .next()
+            .is_some_and(|first| PRESERVED_DIRS.iter().any(|dir| first.as_os_str() == *dir));
+        if preserved || path.file_name().is_some_and(|name| name == ".gitkeep") {
+            continue;
+        }
+
+        let metadata = path.metadata()?;
+        if metadata.modified()? >= cutoff {
+            continue;
+        }
+
+        if !dry_run {
+            std::fs::remove_file(&path)?;
+        }
+        removed.push((path, metadata.len()));
+    }
+
+    removed.sort();
+    Ok(removed)
+}
+
+/// File count and size of each directory in `storage/`
+fn stats(root: &Path) -> Result<Vec<(String, usize, u64)>> {
+    let storage = root.join(STORAGE_DIR);
+    if !storage.is_dir() {
+        anyhow::bail!("{} directory not found", STORAGE_DIR);
+    }
+
+    let mut stats = Vec::new();
+    for entry in std::fs::read_dir(&storage)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            let name = format!("{}/{}", STORAGE_DIR, path.file_name().unwrap_or_default().to_string_lossy());
+            stats.push((name, FileUtils::files_recursive(&path).len(), FileUtils::dir_size(&path)));
+        }
+    }
+
+    stats.sort();
+    Ok(stats)
+}
+
+/// Resolve a path inside `storage/`, refusing paths that leave it
+fn storage_path(root: &Path, path: Option<&str>) -> Result<PathBuf> {
+    let storage = root.join(STORAGE_DIR);
+    let Some(path) = path else {
+        return Ok(storage);
+    };
+
+    let relative = Path::new(path);
+    let relative = relative.strip_prefix(STORAGE_DIR).unwrap_or(relative);
+    if relative.components().any(|component| !matches!(component, Component::Normal(_) | Component::CurDir)) {
+        anyhow::bail!("Path must be inside {}/: {}", STORAGE_DIR, path);
+    }
+
+    let dir = storage.join(relative);
+    if !dir.is_dir() {
+        anyhow::bail!("Directory not found: {}", dir.display());
+    }
+    Ok(dir)
+}
+
+/// Entries of a directory as (name, size, modified), directories first
+fn list(dir: &Path) -> Result<Vec<(String, Option<u64>, String)>> {
+    let mut entries = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        let modified = metadata
+            .modified()
+            .map(|time| chrono::DateTime::<chrono::Local>::from(time).format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+
+        if metadata.is_dir() {
+            entries.push((format!("{}/", name), None, modified));
+        } else {
+            entries.push((name, Some(metadata.len()), modified));
+        }
+    }
+
+    entries.sort_by(|a, b| (a.1.is_some(), &a.0).cmp(&(b.1.is_some(), &b.0)));
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Create a storage file last modified `days_old` days ago
+    fn fixture_file(root: &Path, path: &str, days_old: u64) {
+        let path = root.join(path);
+        FileUtils::write_file(&path, "fixture").unwrap();
+        let modified = SystemTime::now() - Duration::from_secs(days_old * 24 * 60 * 60);
+        std::fs::File::options().write(true).open(&path).unwrap().set_modified(modified).unwrap();
+    }
+
+    fn fixture() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fixture_file(dir.path(), "storage/logs/rustisan-2024-01-01.log", 90);
+        fixture_file(dir.path(), "storage/logs/rustisan.log", 0);
+        fixture_file(dir.path(), "storage/cache/routes.json", 45);
+        fixture_file(dir.path(), "storage/uploads/avatars/1.png", 10);
+        fixture_file(dir.path(), "storage/uploads/.gitkeep", 400);
+        fixture_file(dir.path(), "storage/framework/maintenance.json", 60);
+        dir
+    }
+
+    #[test]
+    fn test_clean_dry_run_keeps_files() {
+        let dir = fixture();
+        let storage = dir.path().join(STORAGE_DIR);
+
+        let removed = clean(dir.path(), 30, true).unwrap();
+        assert_eq!(
+            removed,
+            [(storage.join("cache/routes.json"), 7), (storage.join("logs/rustisan-2024-01-01.log"), 7)]
+        );
+        assert!(storage.join("cache/routes.json").exists());
+        assert!(storage.join("logs/rustisan-2024-01-01.log").exists());
+
+        assert_eq!(clean(dir.path(), 5, true).unwrap().len(), 3);
+        assert!(clean(dir.path(), u32::MAX, true).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_clean_deletes_old_files() {
+        let dir = fixture();
+        let storage = dir.path().join(STORAGE_DIR);
+
+        assert_eq!(clean(dir.path(), 30, false).unwrap().len(), 2);
+        assert!(!storage.join("cache/routes.json").exists());
+        assert!(storage.join("logs/rustisan.log").exists());
+        assert!(storage.join("uploads/.gitkeep").exists());
+        assert!(storage.join("framework/maintenance.json").exists());
+        assert!(clean(dir.path(), 30, true).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_stats_and_list() {
+        let dir = fixture();
+
+        assert_eq!(
+            stats(dir.path()).unwrap(),
+            [
+                ("storage/cache".to_string(), 1, 7),
+                ("storage/framework".to_string(), 1, 7),
+                ("storage/logs".to_string(), 2, 14),
+                ("storage/uploads".to_string(), 2, 14),
+            ]
+        );
+
+        let uploads = storage_path(dir.path(), Some("storage/uploads")).unwrap();
+        let names: Vec<String> = list(&uploads).unwrap().into_iter().map(|(name, _, _)| name).collect();
+        assert_eq!(names, ["avatars/", ".gitkeep"]);
+
+        assert!(storage_path(dir.path(), Some("../src")).is_err());
+        assert!(storage_path(dir.path(), Some("missing")).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_link_and_unlink() {
+        let dir = tempfile::tempdir().unwrap();
+
+        assert!(create_link(dir.path()).unwrap());
+        assert!(!create_link(dir.path()).unwrap());
+        assert_eq!(std::fs::read_link(dir.path().join(LINK_PATH)).unwrap(), Path::new("../storage/uploads"));
+        assert!(dir.path().join(LINK_PATH).is_dir());
+
+        assert!(remove_link(dir.path()).unwrap());
+        assert!(!remove_link(dir.path()).unwrap());
+        assert!(dir.path().join(LINK_TARGET).is_dir());
+    }
+}
@@ -1,21 +1,52 @@
 //! Test command implementations for the Rustisan CLI
 
+mod junit;
+mod watch;
+
 use anyhow::Result;
 use colored::*;
+use std::cell::RefCell;
 use super::CommandUtils;
+use crate::utils::ProcessUtils;
+
+/// Directory the HTML coverage report is written to
+const COVERAGE_DIR: &str = "storage/coverage";
+
+/// Which tests to run
+#[derive(Debug, Clone, Default)]
+pub struct TestFilter {
+    pub pattern: Option<String>,
+    pub unit: bool,
+    pub integration: bool,
+}
+
+impl TestFilter {
+    /// Cargo arguments selecting the unit or integration test targets
+    fn target_args(&self) -> Vec<&str> {
+        if self.unit {
+            vec!["--lib"]
+        } else if self.integration {
+            vec!["--test", "*"]
+        } else {
+            Vec::new()
+        }
+    }
+}
 
 /// Handle test command
 pub async fn handle(
-    pattern: Option<String>,
-    unit: bool,
-    integration: bool,
+    filter: TestFilter,
     verbose: bool,
+    coverage: bool,
+    min_coverage: Option<f32>,
+    junit_output: Option<String>,
+    watch: bool,
 ) -> Result<()> {
     CommandUtils::ensure_rustisan_project()?;
 
-    let test_type = if unit {
+    let test_type = if filter.unit {
         "unit"
-    } else if integration {
+    } else if filter.integration {
         "integration"
     } else {
         "all"
@@ -23,31 +54,29 @@ pub async fn handle(
 
     CommandUtils::info(&format!("Running {} tests...", test_type));
 
-    if let Some(ref pattern) = pattern {
+    if let Some(ref pattern) = filter.pattern {
         CommandUtils::info(&format!("Test pattern: {}", pattern));
     }
 
-    run_tests(pattern, unit, integration, verbose).await
+    if watch {
+        return watch::watch_tests(&filter, verbose).await;
+    }
+
+    if coverage || min_coverage.is_some() {
+        return run_with_coverage(&filter, min_coverage).await;
+    }
+
+    run_tests(&filter, verbose, junit_output.as_deref()).await
 }
 
-async fn run_tests(
-    pattern: Option<String>,
-    unit: bool,
-    integration: bool,
-    verbose: bool,
-) -> Result<()> {
+async fn run_tests(filter: &TestFilter, verbose: bool, junit_output: Option<&str>) -> Result<()> {
     let mut cargo_args = vec!["test"];
 
     // Add test type filters
-    if unit {
-        cargo_args.push("--lib");
-    } else if integration {
-        cargo_args.push("--test");
-        cargo_args.push("*");
-    }
+    cargo_args.extend(filter.target_args());
 
     // Add pattern filter
-    if let Some(ref pattern) = pattern {
+    if let Some(ref pattern) = filter.pattern {
         cargo_args.push(pattern);
     }
 
@@ -63,6 +92,17 @@ async fn run_tests(
         .args(&cargo_args)
         .output()?;
 
+    if let Some(path) = junit_output {
+        let combined = format!(
+            "{}\n{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let results = parse_test_results(&combined);
+        CommandUtils::write_file(std::path::Path::new(path), &junit::to_xml("cargo test", &results.cases))?;
+        CommandUtils::info(&format!("JUnit report written to {}", path));
+    }
+
     if output.status.success() {
         let stdout = String::from_utf8_lossy(&output.stdout);
         println!("{}", stdout);
@@ -83,12 +123,113 @@ async fn run_tests(
     Ok(())
 }
 
+/// Run the tests under cargo-tarpaulin and report line coverage
+///
+/// Fails when the coverage is below `min_coverage`, so CI can gate on it.
+async fn run_with_coverage(filter: &TestFilter, min_coverage: Option<f32>) -> Result<()> {
+    ensure_tarpaulin_installed()?;
+
+    let mut args = vec!["tarpaulin", "--out", "Html", "--output-dir", COVERAGE_DIR];
+    args.extend(filter.target_args());
+    if let Some(ref pattern) = filter.pattern {
+        args.push("--");
+        args.push(pattern);
+    }
+
+    CommandUtils::info(&format!("Running: cargo {}", args.join(" ")));
+
+    let summary = RefCell::new(None);
+    let success = ProcessUtils::execute_streaming(
+        "cargo",
+        &args,
+        |line| {
+            println!("{}", line);
+            if let Some(parsed) = parse_coverage_summary(line) {
+                *summary.borrow_mut() = Some(parsed);
+            }
+        },
+        |line| eprintln!("{}", line),
+    )?;
+
+    if !success {
+        CommandUtils::error("Tests failed");
+        std::process::exit(1);
+    }
+
+    let summary = summary
+        .into_inner()
+        .ok_or_else(|| anyhow::anyhow!("Could not find the coverage summary in cargo-tarpaulin's output"))?;
+
+    print_coverage_info(&summary);
+    CommandUtils::info(&format!("HTML report written to {}/tarpaulin-report.html", COVERAGE_DIR));
+
+    if let Some(min) = min_coverage
+        && summary.percent < min
+    {
+        return Err(anyhow::anyhow!(
+            "Coverage {:.2}% is below the required minimum of {:.2}%",
+            summary.percent,
+            min
+        ));
+    }
+
+    Ok(())
+}
+
+/// Make sure cargo-tarpaulin is available, offering to install it
+fn ensure_tarpaulin_installed() -> Result<()> {
+    if ProcessUtils::command_exists("cargo-tarpaulin") {
+        return Ok(());
+    }
+
+    CommandUtils::warning("cargo-tarpaulin is not installed");
+
+    let install = std::io::IsTerminal::is_terminal(&std::io::stdin())
+        && dialoguer::Confirm::new()
+            .with_prompt("Install cargo-tarpaulin now?")
+            .default(true)
+            .interact()?;
+
+    if !install {
+        CommandUtils::info("Install it with: cargo install cargo-tarpaulin");
+        return Err(anyhow::anyhow!("cargo-tarpaulin is required for coverage reports"));
+    }
+
+    CommandUtils::info("Installing cargo-tarpaulin...");
+    if !ProcessUtils::execute("cargo", &["install", "cargo-tarpaulin"])? {
+        return Err(anyhow::anyhow!("Failed to install cargo-tarpaulin"));
+    }
+    CommandUtils::success("cargo-tarpaulin installed");
+
+    Ok(())
+}
+
 #[derive(Debug, Default)]
 struct TestResults {
     passed: u32,
     failed: u32,
     ignored: u32,
     total: u32,
+    cases: Vec<TestCase>,
+}
+
+/// Outcome of a single test
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TestStatus {
+    Passed,
+    Failed,
+    Ignored,
+}
+
+/// A single test parsed from `cargo test` output
+#[derive(Debug, Clone, PartialEq)]
+struct TestCase {
+    name: String,
+    status: TestStatus,
+    /// Seconds, only reported by `--report-time`
+    duration: Option<f64>,
+    /// Captured panic output of a failed test
+    error_message: Option<String>,
 }
 
 fn parse_test_results(output: &str) -> TestResults {
@@ -121,9 +262,84 @@ fn parse_test_results(output: &str) -> TestResults {
         }
     }
 
+    results.cases = parse_test_cases(output);
+
     results
 }
 
+/// Collect every `test <name> ... <status>` line, attaching panic output to failures
+fn parse_test_cases(output: &str) -> Vec<TestCase> {
+    let mut cases: Vec<TestCase> = output.lines().filter_map(parse_test_line).collect();
+
+    for (name, message) in parse_failure_output(output) {
+        if let Some(case) = cases
+            .iter_mut()
+            .find(|case| case.name == name && case.status == TestStatus::Failed)
+        {
+            case.error_message = Some(message);
+        }
+    }
+
+    cases
+}
+
+/// Parse a line such as `test tests::it_works ... ok <0.003s>`
+fn parse_test_line(line: &str) -> Option<TestCase> {
+    let (name, outcome) = line.strip_prefix("test ")?.split_once(" ... ")?;
+    let mut words = outcome.split_whitespace();
+
+    let status = match words.next()?.trim_end_matches(',') {
+        "ok" => TestStatus::Passed,
+        "FAILED" => TestStatus::Failed,
+        "ignored" => TestStatus::Ignored,
+        _ => return None,
+    };
+
+    let duration = words
+        .find_map(|word| word.strip_prefix('<')?.strip_suffix("s>"))
+        .and_then(|seconds| seconds.parse().ok());
+
+    Some(TestCase {
+        name: name.to_string(),
+        status,
+        duration,
+        error_message: None,
+    })
+}
+
+/// Parse the `---- <name> stdout ----` blocks printed for failed tests
+fn parse_failure_output(output: &str) -> Vec<(String, String)> {
+    let mut failures = Vec::new();
+    let mut current: Option<(String, Vec<&str>)> = None;
+
+    for line in output.lines() {
+        let header = line
+            .strip_prefix("---- ")
+            .and_then(|rest| rest.strip_suffix(" stdout ----"));
+
+        if (header.is_some() || line == "failures:")
+            && let Some((name, lines)) = current.take()
+        {
+            failures.push((name, lines.join("\n").trim().to_string()));
+        }
+
+        match header {
+            Some(name) => current = Some((name.to_string(), Vec::new())),
+            None => {
+                if let Some((_, lines)) = current.as_mut() {
+                    lines.push(line);
+                }
+            }
+        }
+    }
+
+    if let Some((name, lines)) = current {
+        failures.push((name, lines.join("\n").trim().to_string()));
+    }
+
+    failures
+}
+
 fn print_test_summary(results: &TestResults) {
     println!("\n{}", "Test Summary:".bold());
     println!("┌─────────────────────────────────────────────────────────────────────────────┐");
@@ -150,17 +366,41 @@ fn print_test_summary(results: &TestResults) {
     } else {
         println!("\n{} No tests were run", "ℹ".blue().bold());
     }
+}
 
-    // Print coverage information if available
-    print_coverage_info();
+/// Line coverage reported by cargo-tarpaulin
+#[derive(Debug, Clone, PartialEq)]
+struct CoverageSummary {
+    percent: f32,
+    covered: u32,
+    total: u32,
 }
 
-fn print_coverage_info() {
-    // TODO: Implement test coverage reporting
-    // This would require integration with cargo-tarpaulin or similar tools
+/// Parse tarpaulin's summary line, e.g. `90.32% coverage, 141/156 lines covered`
+fn parse_coverage_summary(line: &str) -> Option<CoverageSummary> {
+    let (percent, rest) = line.trim().split_once("% coverage, ")?;
+    let (lines, _) = rest.split_once(" lines covered")?;
+    let (covered, total) = lines.split_once('/')?;
+
+    Some(CoverageSummary {
+        percent: percent.trim().parse().ok()?,
+        covered: covered.trim().parse().ok()?,
+        total: total.trim().parse().ok()?,
+    })
+}
+
+fn print_coverage_info(summary: &CoverageSummary) {
+    let percent = format!("{:.2}%", summary.percent);
+    let percent = if summary.percent >= 80.0 {
+        percent.green().bold()
+    } else if summary.percent >= 60.0 {
+        percent.yellow().bold()
+    } else {
+        percent.red().bold()
+    };
 
     println!("\n{}", "Coverage Information:".bold());
-    println!("Run `cargo tarpaulin` to generate coverage reports");
+    println!("{} coverage, {}/{} lines covered", percent, summary.covered, summary.total);
 }
 
 /// Run specific test suites
@@ -212,29 +452,66 @@ pub async fn run_benchmark_tests() -> Result<()> {
     Ok(())
 }
 
-/// Watch tests for changes
-pub async fn watch_tests() -> Result<()> {
-    CommandUtils::info("Watching tests for changes...");
-
-    // Check if cargo-watch is installed
-    let watch_check = std::process::Command::new("cargo")
-        .args(&["watch", "--version"])
-        .output();
-
-    if watch_check.is_err() {
-        CommandUtils::error("cargo-watch is not installed");
-        CommandUtils::info("Install it with: cargo install cargo-watch");
-        return Ok(());
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TARPAULIN_OUTPUT: &str = "\
+|| Tested/Total Lines:
+|| src/commands/test.rs: 120/131
+|| src/main.rs: 21/25
+||
+90.32% coverage, 141/156 lines covered
+";
+
+    pub(super) const CARGO_TEST_OUTPUT: &str = "\
+running 3 tests
+test utils::tests::test_pluralize ... ok
+test commands::tests::test_slow ... ignored, requires redis
+test commands::tests::test_parse <escape> & more ... FAILED
+
+failures:
+
+---- commands::tests::test_parse <escape> & more stdout ----
+thread 'commands::tests::test_parse' panicked at src/commands/mod.rs:10:5:
+assertion `left == right` failed
+  left: 1
+ right: 2
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+
+failures:
+    commands::tests::test_parse <escape> & more
+
+test result: FAILED. 1 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out; finished in 0.01s
+";
+
+    #[test]
+    fn test_parse_test_cases() {
+        let results = parse_test_results(CARGO_TEST_OUTPUT);
+        assert_eq!((results.passed, results.failed, results.ignored), (1, 1, 1));
+
+        let statuses: Vec<_> = results.cases.iter().map(|case| case.status).collect();
+        assert_eq!(statuses, [TestStatus::Passed, TestStatus::Ignored, TestStatus::Failed]);
+
+        let failed = &results.cases[2];
+        assert_eq!(failed.name, "commands::tests::test_parse <escape> & more");
+        let message = failed.error_message.as_deref().unwrap();
+        assert!(message.starts_with("thread 'commands::tests::test_parse' panicked"));
+        assert!(message.ends_with("display a backtrace"));
+        assert!(results.cases[0].error_message.is_none());
+
+        let timed = parse_test_line("test a::b ... ok <0.250s>").unwrap();
+        assert_eq!(timed.duration, Some(0.25));
+        assert_eq!(parse_test_line("test result: ok. 1 passed; 0 failed"), None);
     }
 
-    // Start watching
-    let output = std::process::Command::new("cargo")
-        .args(&["watch", "-x", "test"])
-        .status()?;
+    #[test]
+    fn test_parse_coverage_summary() {
+        let summary = TARPAULIN_OUTPUT.lines().find_map(parse_coverage_summary).unwrap();
+        assert_eq!(summary, CoverageSummary { percent: 90.32, covered: 141, total: 156 });
 
-    if !output.success() {
-        CommandUtils::error("Test watcher failed");
+        assert_eq!(parse_coverage_summary("|| src/main.rs: 21/25"), None);
+        assert_eq!(parse_coverage_summary("test result: ok. 3 passed"), None);
     }
-
-    Ok(())
 }
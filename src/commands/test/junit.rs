@@ -0,0 +1,96 @@
+//! JUnit XML report for the test command
+//!
+//! CI servers such as Jenkins and GitLab read this format to display
+//! per-test results. The test path is split so `commands::tests::it_works`
+//! becomes `classname="commands::tests"` and `name="it_works"`.
+
+use super::{TestCase, TestStatus};
+
+/// Render the test cases as a JUnit `<testsuite>` document
+pub fn to_xml(suite: &str, cases: &[TestCase]) -> String {
+    let count = |status| cases.iter().filter(|case| case.status == status).count();
+    let total_time = cases.iter().filter_map(|case| case.duration).fold(0.0, |total, d| total + d);
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\" time=\"{:.3}\">\n",
+        escape(suite),
+        cases.len(),
+        count(TestStatus::Failed),
+        count(TestStatus::Ignored),
+        total_time
+    ));
+
+    for case in cases {
+        let (classname, name) = case.name.rsplit_once("::").unwrap_or(("", case.name.as_str()));
+        let open = format!(
+            "  <testcase classname=\"{}\" name=\"{}\" time=\"{:.3}\"",
+            escape(classname),
+            escape(name),
+            case.duration.unwrap_or(0.0)
+        );
+
+        match case.status {
+            TestStatus::Passed => xml.push_str(&format!("{}/>\n", open)),
+            TestStatus::Ignored => xml.push_str(&format!("{}>\n    <skipped/>\n  </testcase>\n", open)),
+            TestStatus::Failed => {
+                let message = case.error_message.as_deref().unwrap_or("");
+                let summary = message.lines().find(|line| !line.starts_with("thread ")).unwrap_or("test failed");
+                xml.push_str(&format!(
+                    "{}>\n    <failure message=\"{}\">{}</failure>\n  </testcase>\n",
+                    open,
+                    escape(summary.trim()),
+                    escape(message)
+                ));
+            }
+        }
+    }
+
+    xml.push_str("</testsuite>\n");
+    xml
+}
+
+/// Escape text for use in XML attributes and content
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::parse_test_results;
+    use super::super::tests::CARGO_TEST_OUTPUT;
+    use super::*;
+
+    #[test]
+    fn test_junit_xml_from_cargo_output() {
+        let results = parse_test_results(CARGO_TEST_OUTPUT);
+        let xml = to_xml("cargo test", &results.cases);
+
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n"));
+        assert!(xml.contains(
+            "<testsuite name=\"cargo test\" tests=\"3\" failures=\"1\" errors=\"0\" skipped=\"1\" time=\"0.000\">"
+        ));
+        assert!(xml.contains("<testcase classname=\"utils::tests\" name=\"test_pluralize\" time=\"0.000\"/>"));
+        assert!(xml.contains(
+            "<testcase classname=\"commands::tests\" name=\"test_slow\" time=\"0.000\">\n    <skipped/>\n  </testcase>"
+        ));
+        assert!(xml.contains("name=\"test_parse &lt;escape&gt; &amp; more\""));
+        assert!(xml.contains("<failure message=\"assertion `left == right` failed\">thread &apos;commands::tests::test_parse&apos; panicked"));
+        assert!(xml.trim_end().ends_with("</testsuite>"));
+
+        assert_eq!(xml.matches("<testcase ").count(), 3);
+        assert_eq!(xml.matches("</testcase>").count(), 2);
+        assert_eq!(xml.matches("<failure ").count(), xml.matches("</failure>").count());
+    }
+}
@@ -0,0 +1,226 @@
+//! Native watch mode for the test command
+//!
+//! Watches `src/` and `tests/` with `notify` and re-runs only the tests
+//! affected by the changed files: `src/commands/db.rs` re-runs
+//! `cargo test commands::db`, `tests/api.rs` re-runs `cargo test --test api`.
+//! Changes to crate roots such as `src/main.rs` re-run everything.
+
+use anyhow::Result;
+use colored::*;
+use notify::{Event, RecursiveMode, Watcher};
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+
+use super::TestFilter;
+use crate::commands::CommandUtils;
+
+/// Quiet period after the last change before tests are re-run
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Directories watched for changes, relative to the application crate
+const WATCHED_DIRS: &[&str] = &["src", "tests"];
+
+/// Tests to re-run after a change
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Rerun {
+    /// The user's original selection
+    All,
+    /// Unit tests under a module path, e.g. `commands::db`
+    Module(String),
+    /// A single integration test target
+    IntegrationTest(String),
+}
+
+/// Run the tests once, then re-run affected tests whenever a Rust file changes
+pub async fn watch_tests(filter: &TestFilter, verbose: bool) -> Result<()> {
+    let root = std::env::current_dir()?.join(CommandUtils::app_dir());
+
+    let (sender, events) = mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+        let Ok(event) = event else {
+            return;
+        };
+        if !(event.kind.is_create() || event.kind.is_modify() || event.kind.is_remove()) {
+            return;
+        }
+        for path in event.paths {
+            if path.extension().is_some_and(|ext| ext == "rs") {
+                let _ = sender.send(path);
+            }
+        }
+    })?;
+
+    for dir in WATCHED_DIRS {
+        let dir = root.join(dir);
+        if dir.is_dir() {
+            watcher.watch(&dir, RecursiveMode::Recursive)?;
+        }
+    }
+
+    run_cargo(&cargo_args(filter, &Rerun::All, verbose))?;
+    CommandUtils::info("Watching src/ and tests/ for changes (press Ctrl+C to stop)...");
+
+    watch_loop(events, &root, |rerun| run_cargo(&cargo_args(filter, rerun, verbose))).await
+}
+
+/// Debounce file events and re-run the affected tests until the channel closes
+async fn watch_loop<F>(mut events: UnboundedReceiver<PathBuf>, root: &Path, mut run: F) -> Result<()>
+where
+    F: FnMut(&Rerun) -> Result<()>,
+{
+    while let Some(first) = events.recv().await {
+        let mut changed = vec![first];
+        while let Ok(Some(path)) = tokio::time::timeout(DEBOUNCE, events.recv()).await {
+            if !changed.contains(&path) {
+                changed.push(path);
+            }
+        }
+
+        let mut reruns = Vec::new();
+        for path in &changed {
+            let relative = path.strip_prefix(root).unwrap_or(path);
+            println!("\n{}", "─".repeat(60).dimmed());
+            CommandUtils::info(&format!("Change detected in {}", relative.display()));
+
+            if let Some(rerun) = rerun_for(relative)
+                && !reruns.contains(&rerun)
+            {
+                reruns.push(rerun);
+            }
+        }
+
+        if reruns.contains(&Rerun::All) {
+            reruns = vec![Rerun::All];
+        }
+
+        for rerun in &reruns {
+            run(rerun)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Work out which tests cover a changed file, relative to the crate root
+fn rerun_for(path: &Path) -> Option<Rerun> {
+    if path.extension().is_none_or(|ext| ext != "rs") {
+        return None;
+    }
+
+    let mut parts: Vec<String> = path
+        .with_extension("")
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(part.to_string_lossy().to_string()),
+            _ => None,
+        })
+        .collect();
+
+    match parts.first().map(String::as_str) {
+        Some("tests") if parts.len() == 2 => Some(Rerun::IntegrationTest(parts.remove(1))),
+        Some("src") => {
+            parts.remove(0);
+            if parts.last().is_some_and(|last| last == "mod") {
+                parts.pop();
+            }
+
+            match parts.as_slice() {
+                [] => Some(Rerun::All),
+                [root] if root == "main" || root == "lib" => Some(Rerun::All),
+                [bin, ..] if bin == "bin" => Some(Rerun::All),
+                _ => Some(Rerun::Module(parts.join("::"))),
+            }
+        }
+        // Shared helpers such as tests/common/mod.rs can affect every test
+        Some("tests") => Some(Rerun::All),
+        _ => None,
+    }
+}
+
+/// Arguments for `cargo` to run the given tests
+fn cargo_args(filter: &TestFilter, rerun: &Rerun, verbose: bool) -> Vec<String> {
+    let mut args = vec!["test".to_string()];
+
+    match rerun {
+        Rerun::All => {
+            args.extend(filter.target_args().into_iter().map(str::to_string));
+            args.extend(filter.pattern.clone());
+        }
+        Rerun::Module(module) => args.push(module.clone()),
+        Rerun::IntegrationTest(name) => args.extend(["--test".to_string(), name.clone()]),
+    }
+
+    if verbose {
+        args.extend(["--".to_string(), "--nocapture".to_string()]);
+    }
+
+    args
+}
+
+fn run_cargo(args: &[String]) -> Result<()> {
+    CommandUtils::info(&format!("Running: cargo {}", args.join(" ")));
+
+    let status = std::process::Command::new("cargo").args(args).status()?;
+
+    if status.success() {
+        CommandUtils::success("Tests passed");
+    } else {
+        CommandUtils::error("Tests failed");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rerun_for() {
+        assert_eq!(rerun_for(Path::new("src/commands/db.rs")), Some(Rerun::Module("commands::db".to_string())));
+        assert_eq!(rerun_for(Path::new("src/commands/mod.rs")), Some(Rerun::Module("commands".to_string())));
+        assert_eq!(rerun_for(Path::new("src/main.rs")), Some(Rerun::All));
+        assert_eq!(rerun_for(Path::new("src/bin/job.rs")), Some(Rerun::All));
+        assert_eq!(rerun_for(Path::new("tests/api.rs")), Some(Rerun::IntegrationTest("api".to_string())));
+        assert_eq!(rerun_for(Path::new("tests/common/mod.rs")), Some(Rerun::All));
+        assert_eq!(rerun_for(Path::new("src/styles.css")), None);
+    }
+
+    #[test]
+    fn test_cargo_args() {
+        let filter = TestFilter { pattern: Some("users".to_string()), unit: true, integration: false };
+
+        assert_eq!(cargo_args(&filter, &Rerun::All, false), ["test", "--lib", "users"]);
+        assert_eq!(
+            cargo_args(&filter, &Rerun::Module("models::user".to_string()), true),
+            ["test", "models::user", "--", "--nocapture"]
+        );
+        assert_eq!(cargo_args(&filter, &Rerun::IntegrationTest("api".to_string()), false), ["test", "--test", "api"]);
+    }
+
+    #[tokio::test]
+    async fn test_file_event_triggers_rerun() {
+        let root = PathBuf::from("/project");
+        let (sender, events) = mpsc::unbounded_channel();
+
+        // Editors often emit several events for a single save
+        sender.send(root.join("src/models/user.rs")).unwrap();
+        sender.send(root.join("src/models/user.rs")).unwrap();
+        sender.send(root.join("tests/api.rs")).unwrap();
+        drop(sender);
+
+        let mut reruns = Vec::new();
+        watch_loop(events, &root, |rerun| {
+            reruns.push(rerun.clone());
+            Ok(())
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(
+            reruns,
+            [Rerun::Module("models::user".to_string()), Rerun::IntegrationTest("api".to_string())]
+        );
+    }
+}
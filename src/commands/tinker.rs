@@ -0,0 +1,197 @@
+//! Tinker command implementation for the Rustisan CLI
+//!
+//! Starts an `evcxr` REPL with the application's dependencies and, unless
+//! `--no-prelude` is given, its models, services and controllers in scope.
+
+use anyhow::{Context, Result};
+use colored::*;
+use std::path::{Path, PathBuf};
+
+use super::CommandUtils;
+use crate::utils::{FileUtils, ProcessUtils};
+
+/// Prelude written next to the application's Cargo.toml for the session
+const PRELUDE_FILE: &str = "tinker_prelude.rs";
+
+/// evcxr configuration directory used for the session
+const CONFIG_DIR: &str = "target/tinker";
+
+/// Application modules brought into scope by the prelude
+const PRELUDE_MODULES: &[&str] = &["models", "services", "controllers"];
+
+/// Handle tinker command
+pub async fn handle(no_prelude: bool) -> Result<()> {
+    CommandUtils::ensure_rustisan_project()?;
+    ensure_evcxr_installed()?;
+
+    let app_dir = std::fs::canonicalize(Path::new(".").join(CommandUtils::app_dir()))?;
+    let config_dir = app_dir.join(CONFIG_DIR);
+    CommandUtils::ensure_directory(&config_dir)?;
+
+    let mut init = dependency_commands(&app_dir)?;
+    let prelude = if no_prelude {
+        None
+    } else {
+        let path = write_prelude(&app_dir)?;
+        init.push(CommandUtils::read_file(&path)?);
+        print_header(&app_dir);
+        Some(path)
+    };
+    CommandUtils::write_file(config_dir.join("init.evcxr"), &init.join("\n"))?;
+
+    CommandUtils::info("Starting tinker session, type :quit to exit");
+    let status = std::process::Command::new("evcxr")
+        .args(["--edition", "2021", "--crate-type", "lib"])
+        .env("EVCXR_CONFIG_DIR", &config_dir)
+        .status();
+
+    if let Some(prelude) = prelude {
+        let _ = std::fs::remove_file(prelude);
+    }
+    let _ = std::fs::remove_dir_all(&config_dir);
+
+    let status = status.context("Failed to start evcxr")?;
+    if !status.success() {
+        anyhow::bail!("Tinker session exited with {}", status);
+    }
+
+    Ok(())
+}
+
+/// Make sure evcxr is available, offering to install it
+fn ensure_evcxr_installed() -> Result<()> {
+    if ProcessUtils::command_exists("evcxr") {
+        return Ok(());
+    }
+
+    CommandUtils::warning("evcxr is not installed");
+
+    let install = std::io::IsTerminal::is_terminal(&std::io::stdin())
+        && dialoguer::Confirm::new()
+            .with_prompt("Install evcxr_repl now?")
+            .default(true)
+            .interact()?;
+
+    if !install {
+        CommandUtils::info("Install it with: cargo install evcxr_repl");
+        return Err(anyhow::anyhow!("evcxr is required for tinker"));
+    }
+
+    CommandUtils::info("Installing evcxr_repl...");
+    if !ProcessUtils::execute("cargo", &["install", "evcxr_repl"])? {
+        return Err(anyhow::anyhow!("Failed to install evcxr_repl"));
+    }
+    CommandUtils::success("evcxr_repl installed");
+
+    Ok(())
+}
+
+/// `:dep` commands for every dependency of the application, with path dependencies made absolute
+fn dependency_commands(app_dir: &Path) -> Result<Vec<String>> {
+    let manifest: toml::Value = CommandUtils::read_file(app_dir.join("Cargo.toml"))?.parse()?;
+    let Some(dependencies) = manifest.get("dependencies").and_then(|deps| deps.as_table()) else {
+        return Ok(Vec::new());
+    };
+
+    Ok(dependencies
+        .iter()
+        .map(|(name, spec)| {
+            let mut spec = spec.clone();
+            if let Some(path) = spec.get_mut("path")
+                && let Some(relative) = path.as_str()
+            {
+                *path = toml::Value::String(app_dir.join(relative).display().to_string());
+            }
+            format!(":dep {} = {}", name, spec)
+        })
+        .collect())
+}
+
+/// Modules of `PRELUDE_MODULES` present in the application
+fn prelude_modules(app_dir: &Path) -> Vec<&'static str> {
+    PRELUDE_MODULES
+        .iter()
+        .copied()
+        .filter(|module| app_dir.join("src").join(module).join("mod.rs").exists())
+        .collect()
+}
+
+/// Source declaring the application modules and importing their contents
+fn prelude_source(app_dir: &Path) -> String {
+    let mut source = String::from("// Generated by `rustisan tinker`, removed when the session ends\n");
+    for module in prelude_modules(app_dir) {
+        let path = app_dir.join("src").join(module).join("mod.rs");
+        source.push_str(&format!("#[path = {:?}]\nmod {};\nuse {}::*;\n", path.display().to_string(), module, module));
+    }
+    source
+}
+
+/// Write the prelude into the application directory
+fn write_prelude(app_dir: &Path) -> Result<PathBuf> {
+    let path = app_dir.join(PRELUDE_FILE);
+    FileUtils::atomic_write(&path, &prelude_source(app_dir))?;
+    Ok(path)
+}
+
+/// Public structs, enums and traits declared in a module directory
+fn module_types(dir: &Path) -> Vec<String> {
+    let pattern = regex::Regex::new(r"(?m)^pub (?:struct|enum|trait) ([A-Za-z_][A-Za-z0-9_]*)").unwrap();
+    let mut types: Vec<String> = walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "rs"))
+        .filter_map(|entry| std::fs::read_to_string(entry.path()).ok())
+        .flat_map(|source| pattern.captures_iter(&source).map(|c| c[1].to_string()).collect::<Vec<_>>())
+        .collect();
+    types.sort();
+    types
+}
+
+/// Print the types the prelude makes available
+fn print_header(app_dir: &Path) {
+    println!("{}", "Rustisan Tinker".green().bold());
+    for module in prelude_modules(app_dir) {
+        let types = module_types(&app_dir.join("src").join(module));
+        if !types.is_empty() {
+            println!("  {} {}", format!("{}:", module).yellow(), types.join(", "));
+        }
+    }
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_prelude() {
+        let dir = tempfile::tempdir().unwrap();
+        for module in ["models", "controllers"] {
+            std::fs::create_dir_all(dir.path().join("src").join(module)).unwrap();
+            std::fs::write(dir.path().join("src").join(module).join("mod.rs"), "pub struct User;\n").unwrap();
+        }
+
+        let path = write_prelude(dir.path()).unwrap();
+        let prelude = std::fs::read_to_string(&path).unwrap();
+
+        assert_eq!(path, dir.path().join(PRELUDE_FILE));
+        assert!(prelude.contains("mod models;\nuse models::*;"));
+        assert!(prelude.contains("mod controllers;\nuse controllers::*;"));
+        assert!(!prelude.contains("mod services;"));
+        assert_eq!(module_types(&dir.path().join("src/models")), ["User"]);
+    }
+
+    #[test]
+    fn test_dependency_commands() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"shop\"\n\n[dependencies]\nserde = \"1\"\ncore = { path = \"../core\" }\n",
+        )
+        .unwrap();
+
+        let commands = dependency_commands(dir.path()).unwrap();
+        assert!(commands.contains(&":dep serde = \"1\"".to_string()));
+        assert!(commands.iter().any(|command| command.starts_with(":dep core = { path = ") && command.contains(&dir.path().join("../core").display().to_string())));
+    }
+}
@@ -25,6 +25,7 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use crate::commands::CommandUtils;
+use crate::utils::{FileUtils, TextUtils};
 
 /// Base trait for all generators
 pub trait Generator {
@@ -66,6 +67,18 @@ pub struct GeneratorOptions {
     pub modify_table: Option<String>,
 }
 
+impl GeneratorOptions {
+    /// Options for the `create_<table>` migration of a model
+    pub fn for_model_migration(model: &str) -> Self {
+        Self {
+            migration: true,
+            model: Some(model.to_string()),
+            create_table: Some(TextUtils::pluralize(&CommandUtils::to_snake_case(model))),
+            ..Default::default()
+        }
+    }
+}
+
 /// Template manager for handling Handlebars templates
 pub struct TemplateManager {
     handlebars: Handlebars<'static>,
@@ -120,8 +133,8 @@ impl GeneratorUtils {
         vars.insert("camel_case".to_string(), to_camel_case(name));
         vars.insert("kebab_case".to_string(), to_kebab_case(name));
         vars.insert("title_case".to_string(), to_title_case(name));
-        vars.insert("plural".to_string(), pluralize(name));
-        vars.insert("singular".to_string(), singularize(name));
+        vars.insert("plural".to_string(), TextUtils::pluralize(name));
+        vars.insert("singular".to_string(), TextUtils::singularize(name));
 
         vars
     }
@@ -147,9 +160,7 @@ impl GeneratorUtils {
 
     /// Write file with content
     pub fn write_file(path: &Path, content: &str) -> Result<()> {
-        Self::ensure_directory(path)?;
-        fs::write(path, content)?;
-        Ok(())
+        FileUtils::atomic_write(path, content)
     }
 
     /// Update module file to include new component
@@ -161,8 +172,8 @@ impl GeneratorUtils {
             let module_line = format!("pub mod {};", CommandUtils::to_snake_case(component_name));
 
             if !content.contains(&module_line) {
-                let new_content = format!("{}\n{}", content.trim(), module_line);
-                fs::write(&mod_file, new_content)?;
+                let new_content = format!("{}\n{}\n", content.trim(), module_line);
+                FileUtils::atomic_write(&mod_file, &new_content)?;
             }
         }
 
@@ -199,41 +210,3 @@ fn to_title_case(input: &str) -> String {
         .collect::<Vec<String>>()
         .join(" ")
 }
-
-/// Simple pluralize function (basic English rules)
-fn pluralize(input: &str) -> String {
-    let lower = input.to_lowercase();
-
-    if lower.ends_with('y') && !lower.ends_with("ay") && !lower.ends_with("ey") && !lower.ends_with("iy") && !lower.ends_with("oy") && !lower.ends_with("uy") {
-        format!("{}ies", &input[..input.len()-1])
-    } else if lower.ends_with('s') || lower.ends_with("sh") || lower.ends_with("ch") || lower.ends_with('x') || lower.ends_with('z') {
-        format!("{}es", input)
-    } else if lower.ends_with('f') {
-        format!("{}ves", &input[..input.len()-1])
-    } else if lower.ends_with("fe") {
-        format!("{}ves", &input[..input.len()-2])
-    } else {
-        format!("{}s", input)
-    }
-}
-
-/// Simple singularize function (basic English rules)
-fn singularize(input: &str) -> String {
-    let lower = input.to_lowercase();
-
-    if lower.ends_with("ies") && input.len() > 3 {
-        format!("{}y", &input[..input.len()-3])
-    } else if lower.ends_with("ves") && input.len() > 3 {
-        if lower.ends_with("aves") || lower.ends_with("eves") || lower.ends_with("ives") || lower.ends_with("oves") {
-            format!("{}f", &input[..input.len()-3])
-        } else {
-            format!("{}fe", &input[..input.len()-3])
-        }
-    } else if lower.ends_with("ses") || lower.ends_with("shes") || lower.ends_with("ches") || lower.ends_with("xes") || lower.ends_with("zes") {
-        input[..input.len()-2].to_string()
-    } else if lower.ends_with('s') && input.len() > 1 {
-        input[..input.len()-1].to_string()
-    } else {
-        input.to_string()
-    }
-}
@@ -20,7 +20,7 @@ mod generators;
 mod utils;
 
 use commands::*;
-use log::debug;
+use ::log::debug;
 
 /// Rustisan CLI - A Laravel-inspired web framework for Rust
 #[derive(Parser)]
@@ -39,6 +39,10 @@ struct Cli {
     /// Suppress output
     #[arg(short, long, global = true)]
     quiet: bool,
+
+    /// Output format for messages (human or json)
+    #[arg(short = 'F', long, global = true, value_enum, default_value_t = OutputFormat::Human)]
+    output_format: OutputFormat,
 }
 
 #[derive(Subcommand)]
@@ -56,6 +60,18 @@ pub enum Commands {
         /// Initialize git repository
         #[arg(long, default_value = "true")]
         git: bool,
+        /// Skip the interactive questionnaire and use the defaults
+        #[arg(long)]
+        no_interaction: bool,
+        /// Generate a Cargo workspace with app, core and cli crates
+        #[arg(long)]
+        workspace: bool,
+        /// Do not generate Dockerfile and docker-compose.yml
+        #[arg(long)]
+        no_docker: bool,
+        /// Do not generate a GitHub Actions CI workflow
+        #[arg(long)]
+        no_ci: bool,
     },
 
     /// Generate application components
@@ -67,7 +83,7 @@ pub enum Commands {
     /// Serve the application
     Serve {
         /// Host to bind to
-        #[arg(short, long, default_value = "127.0.0.1")]
+        #[arg(short = 'H', long, default_value = "127.0.0.1")]
         host: String,
         /// Port to bind to
         #[arg(short, long, default_value = "3000")]
@@ -78,6 +94,21 @@ pub enum Commands {
         /// Enable hot reload
         #[arg(long)]
         reload: bool,
+        /// Serve over HTTPS (generates a self-signed certificate unless --cert and --key are given)
+        #[arg(long)]
+        tls: bool,
+        /// PEM certificate file
+        #[arg(long, requires_all = ["tls", "key"])]
+        cert: Option<String>,
+        /// PEM private key file
+        #[arg(long, requires_all = ["tls", "cert"])]
+        key: Option<String>,
+        /// Exit instead of picking another port when the port is in use
+        #[arg(long)]
+        strict_port: bool,
+        /// Additional path to watch in reload mode (repeatable)
+        #[arg(long = "watch-path", requires = "reload")]
+        watch_paths: Vec<String>,
     },
 
     /// Database operations
@@ -87,19 +118,28 @@ pub enum Commands {
     },
 
     /// Migration operations
+    #[command(args_conflicts_with_subcommands = true)]
     Migrate {
         #[command(subcommand)]
         operation: Option<MigrateCommands>,
+        /// Print the SQL pending migrations would execute without running them
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Seeder operations
     Seed {
+        #[command(subcommand)]
+        operation: Option<SeedCommands>,
         /// Specific seeder to run
         #[arg(short, long)]
         class: Option<String>,
         /// Force seeding in production
         #[arg(long)]
         force: bool,
+        /// Abort at the first failing seeder instead of running the rest
+        #[arg(long)]
+        stop_on_error: bool,
     },
 
     /// Route operations
@@ -139,6 +179,18 @@ pub enum Commands {
         /// Show test output
         #[arg(long)]
         verbose: bool,
+        /// Generate a coverage report with cargo-tarpaulin
+        #[arg(long)]
+        coverage: bool,
+        /// Fail if line coverage is below this percentage (implies --coverage)
+        #[arg(long, value_name = "PERCENT")]
+        min_coverage: Option<f32>,
+        /// Write a JUnit XML report to this path
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["coverage", "min_coverage"])]
+        junit_output: Option<String>,
+        /// Re-run affected tests when source files change
+        #[arg(long, conflicts_with_all = ["coverage", "min_coverage", "junit_output"])]
+        watch: bool,
     },
 
     /// Build the application for production
@@ -152,6 +204,18 @@ pub enum Commands {
         /// Output directory
         #[arg(short, long)]
         output: Option<String>,
+        /// Embed the Git commit hash and tag in src/generated/version.rs
+        #[arg(long)]
+        git_version: bool,
+        /// Report what contributes to the size of the release binary
+        #[arg(long)]
+        size_report: bool,
+        /// Compilation target
+        #[arg(long, value_enum, default_value_t)]
+        target: commands::build::BuildTarget,
+        /// Bundle CSS and JavaScript without minifying them
+        #[arg(long)]
+        no_minify: bool,
     },
 
     /// Deploy the application
@@ -164,6 +228,21 @@ pub enum Commands {
         /// Dry run (show what would be deployed)
         #[arg(long)]
         dry_run: bool,
+        /// Timeout in seconds for tests and health checks
+        #[arg(long)]
+        timeout: Option<u64>,
+        /// Roll back to the deployment before the latest one
+        #[arg(long)]
+        rollback: bool,
+        /// Deployment version to roll back instead of the latest (requires --rollback)
+        #[arg(long, requires = "rollback")]
+        version: Option<String>,
+        /// Roll back without asking for confirmation
+        #[arg(long, requires = "rollback")]
+        force: bool,
+        /// Host key verification for server deployments
+        #[arg(long, value_enum, default_value_t)]
+        ssh_fingerprint_check: commands::deploy::FingerprintCheck,
     },
 
     /// Show application information
@@ -171,9 +250,60 @@ pub enum Commands {
         /// Show detailed information
         #[arg(long)]
         detailed: bool,
+        /// Warn about source files whose complexity exceeds this value
+        #[arg(long, value_name = "N", requires = "detailed")]
+        complexity_threshold: Option<usize>,
+        /// Show how the project statistics changed since the last `info --detailed`
+        #[arg(long, requires = "detailed")]
+        compare_last: bool,
+        /// Refresh the info with live metrics every N seconds
+        #[arg(long, value_name = "SECONDS", num_args = 0..=1, default_missing_value = "5",
+              value_parser = clap::value_parser!(u64).range(1..), conflicts_with = "detailed")]
+        watch: Option<u64>,
+        /// Print each refresh below the previous one instead of clearing the screen
+        #[arg(long, requires = "watch")]
+        no_clear: bool,
+    },
+
+
+
+    /// Run and manage scheduled tasks
+    Schedule {
+        #[command(subcommand)]
+        operation: ScheduleCommands,
+    },
+
+    /// Put the application into or out of maintenance mode
+    Maintenance {
+        #[command(subcommand)]
+        mode: MaintenanceCommands,
+    },
+
+    /// Inspect and clear application logs
+    Log {
+        #[command(subcommand)]
+        operation: LogCommands,
     },
 
+    /// Manage files in storage/
+    Storage {
+        #[command(subcommand)]
+        operation: StorageCommands,
+    },
+
+    /// Start an interactive Rust REPL with the application loaded
+    Tinker {
+        /// Don't bring models, services and controllers into scope
+        #[arg(long)]
+        no_prelude: bool,
+    },
 
+    /// Cache config and routes and generate autoloads in one step
+    Optimize {
+        /// Exit with an error if any step fails
+        #[arg(long)]
+        strict: bool,
+    },
 
     /// Package management
     Package {
@@ -186,6 +316,19 @@ pub enum Commands {
         #[command(subcommand)]
         tool: DevCommands,
     },
+
+    /// Generate shell completion scripts
+    Completion {
+        /// Shell to generate completions for (bash, zsh, fish, powershell)
+        shell: Option<String>,
+        /// Install the completion script for the detected shell
+        #[arg(long)]
+        install: bool,
+    },
+
+    /// Run a plugin, e.g. `rustisan auth` runs `rustisan-auth`
+    #[command(external_subcommand)]
+    Plugin(Vec<String>),
 }
 
 #[derive(Subcommand)]
@@ -203,6 +346,9 @@ pub enum MakeCommands {
         /// Generate with model
         #[arg(short, long)]
         model: Option<String>,
+        /// Overwrite the file if it already exists
+        #[arg(short, long)]
+        force: bool,
     },
 
     /// Generate a model
@@ -218,6 +364,9 @@ pub enum MakeCommands {
         /// Generate seeder
         #[arg(short, long)]
         seeder: bool,
+        /// Overwrite the file if it already exists
+        #[arg(long)]
+        force: bool,
     },
 
     /// Generate a migration
@@ -225,23 +374,44 @@ pub enum MakeCommands {
         /// Migration name
         name: String,
         /// Create table migration
-        #[arg(long)]
+        #[arg(long, conflicts_with = "table")]
         create: Option<String>,
         /// Modify table migration
         #[arg(long)]
         table: Option<String>,
+        /// Columns as "name:type[:modifier]", e.g. "name:string,email:string:unique,age:integer:nullable"
+        #[arg(long)]
+        columns: Option<String>,
+        /// Generate the changes turning the current database into the schema in this SQL file
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["create", "table", "columns"])]
+        from_schema: Option<String>,
+        /// Overwrite the file if it already exists
+        #[arg(short, long)]
+        force: bool,
     },
 
     /// Generate middleware
     Middleware {
         /// Middleware name
         name: String,
+        /// Generate from a template
+        #[arg(long, value_parser = commands::make::MIDDLEWARE_TEMPLATES.to_vec())]
+        template: Option<String>,
+        /// Overwrite the file if it already exists
+        #[arg(short, long)]
+        force: bool,
     },
 
     /// Generate a request validator
     Request {
         /// Request name
         name: String,
+        /// Fields as "name:type:rules", e.g. "email:string:required|email,age:integer:min:18"
+        #[arg(long)]
+        fields: Option<String>,
+        /// Overwrite the file if it already exists
+        #[arg(short, long)]
+        force: bool,
     },
 
     /// Generate a resource transformer
@@ -251,6 +421,15 @@ pub enum MakeCommands {
         /// Generate collection resource
         #[arg(short, long)]
         collection: bool,
+        /// Fields as "name:type", e.g. "id:integer,name:string"; read from the model when omitted
+        #[arg(long)]
+        fields: Option<String>,
+        /// Model the resource is built from
+        #[arg(short, long)]
+        model: Option<String>,
+        /// Overwrite the file if it already exists
+        #[arg(short, long)]
+        force: bool,
     },
 
     /// Generate a seeder
@@ -260,6 +439,15 @@ pub enum MakeCommands {
         /// Associated model
         #[arg(short, long)]
         model: Option<String>,
+        /// Seed the rows of a CSV file with a header row
+        #[arg(long, conflicts_with = "from_json")]
+        from_csv: Option<String>,
+        /// Seed the objects of a JSON array
+        #[arg(long)]
+        from_json: Option<String>,
+        /// Overwrite the file if it already exists
+        #[arg(short, long)]
+        force: bool,
     },
 
     /// Generate a factory
@@ -269,12 +457,27 @@ pub enum MakeCommands {
         /// Associated model
         #[arg(short, long)]
         model: Option<String>,
+        /// Overwrite the file if it already exists
+        #[arg(short, long)]
+        force: bool,
     },
 
     /// Generate a command
     Command {
         /// Command name
         name: String,
+        /// Positional arguments as "name:required|optional", e.g. "name:required,env:optional"
+        #[arg(long)]
+        arguments: Option<String>,
+        /// Flags as "name:type", e.g. "verbose:bool,count:usize"
+        #[arg(long)]
+        options: Option<String>,
+        /// Cron expression to suggest for `rustisan schedule add`, e.g. "0 * * * *"
+        #[arg(long)]
+        schedule: Option<String>,
+        /// Overwrite the file if it already exists
+        #[arg(short, long)]
+        force: bool,
     },
 
     /// Generate a job
@@ -284,12 +487,27 @@ pub enum MakeCommands {
         /// Synchronous job
         #[arg(long)]
         sync: bool,
+        /// Queue the job is pushed onto [default: default]
+        #[arg(long)]
+        queue: Option<String>,
+        /// Attempts before the job is marked as failed [default: 3]
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+        tries: Option<u32>,
+        /// Seconds a single attempt may run [default: 60]
+        #[arg(long)]
+        timeout: Option<u32>,
+        /// Overwrite the file if it already exists
+        #[arg(short, long)]
+        force: bool,
     },
 
     /// Generate an event
     Event {
         /// Event name
         name: String,
+        /// Overwrite the file if it already exists
+        #[arg(short, long)]
+        force: bool,
     },
 
     /// Generate a listener
@@ -299,21 +517,207 @@ pub enum MakeCommands {
         /// Associated event
         #[arg(short, long)]
         event: Option<String>,
+        /// Overwrite the file if it already exists
+        #[arg(short, long)]
+        force: bool,
     },
 
     /// Generate a policy
     Policy {
         /// Policy name
         name: String,
-        /// Associated model
-        #[arg(short, long)]
+        /// Model the policy authorizes, required unless --no-model is given
+        #[arg(short, long, required_unless_present = "no_model")]
         model: Option<String>,
+        /// Authorize plain JSON values instead of a model
+        #[arg(long, conflicts_with = "model")]
+        no_model: bool,
+        /// Overwrite the file if it already exists
+        #[arg(short, long)]
+        force: bool,
     },
 
     /// Generate a trait
     Trait {
         /// Trait name
         name: String,
+        /// Methods as "name:ReturnType", e.g. "find:Result<Option<Self>>,save:Result<()>"
+        #[arg(long)]
+        methods: Vec<String>,
+        /// Generate a synchronous trait instead of an async_trait
+        #[arg(long)]
+        no_async: bool,
+        /// Overwrite the file if it already exists
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Generate a service
+    Service {
+        /// Service name
+        name: String,
+        /// Associated model
+        #[arg(short, long)]
+        model: Option<String>,
+        /// Also generate a unit test with a mock implementation
+        #[arg(long)]
+        test: bool,
+        /// Overwrite the file if it already exists
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Generate a repository
+    Repository {
+        /// Repository name
+        name: String,
+        /// Associated model
+        #[arg(short, long)]
+        model: Option<String>,
+        /// Overwrite the file if it already exists
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Generate a model observer
+    Observer {
+        /// Observer name
+        name: String,
+        /// Observed model
+        #[arg(short, long)]
+        model: Option<String>,
+        /// Overwrite the file if it already exists
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Generate a notification
+    Notification {
+        /// Notification name
+        name: String,
+        /// Delivery channels (e.g. mail,database,slack)
+        #[arg(long, value_delimiter = ',')]
+        channels: Vec<String>,
+        /// Overwrite the file if it already exists
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Generate a custom error type
+    Exception {
+        /// Exception name
+        name: String,
+        /// Kind of error (validation, auth, not_found or custom)
+        #[arg(short, long)]
+        kind: Option<String>,
+        /// Overwrite the file if it already exists
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Generate an enum with string conversions
+    Enum {
+        /// Enum name
+        name: String,
+        /// Variants with optional discriminants or integer type, e.g. "Active,Inactive,Pending:u8"
+        #[arg(long, value_delimiter = ',', required = true)]
+        variants: Vec<String>,
+        /// Derive serde Serialize and Deserialize with snake_case names
+        #[arg(long)]
+        serde: bool,
+        /// Implement Display with a readable label per variant
+        #[arg(long)]
+        display: bool,
+        /// Overwrite the file if it already exists
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Generate a query scope
+    Scope {
+        /// Scope name
+        name: String,
+        /// Model to generate a scoped query for
+        #[arg(short, long)]
+        model: Option<String>,
+        /// Overwrite the file if it already exists
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Generate a service provider
+    Provider {
+        /// Provider name
+        name: String,
+        /// Register the provider's services only when they are first needed
+        #[arg(long)]
+        deferred: bool,
+        /// Overwrite the file if it already exists
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Generate a model attribute cast
+    Cast {
+        /// Cast name
+        name: String,
+        /// How the attribute is stored
+        #[arg(long, value_parser = commands::make::CAST_TYPES.to_vec())]
+        cast_type: String,
+        /// Overwrite the file if it already exists
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Generate a single-purpose action
+    Action {
+        /// Action name
+        name: String,
+        /// Model the action works on
+        #[arg(short, long)]
+        model: Option<String>,
+        /// Overwrite the file if it already exists
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Generate a mailable
+    Mail {
+        /// Mail name
+        name: String,
+        /// Render the mail from a Markdown template in resources/views/mail
+        #[arg(long)]
+        markdown: bool,
+        /// Overwrite the file if it already exists
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Generate a typed config module and its example file in config/
+    Config {
+        /// Config name
+        name: String,
+        /// Table of rustisan.toml to read, defaults to the name
+        #[arg(short, long)]
+        section: Option<String>,
+        /// Overwrite the file if it already exists
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Generate Kubernetes manifests in k8s/
+    K8s {
+        /// Application name used for the Kubernetes resources
+        name: String,
+        /// Number of pod replicas
+        #[arg(long, default_value = "2")]
+        replicas: u32,
+        /// Container image (defaults to docker_image in deploy/production.toml)
+        #[arg(long)]
+        image: Option<String>,
+        /// Overwrite the file if it already exists
+        #[arg(short, long)]
+        force: bool,
     },
 
     /// Generate a test
@@ -326,16 +730,26 @@ pub enum MakeCommands {
         /// Integration test
         #[arg(long)]
         integration: bool,
+        /// Generate tests for a feature (http)
+        #[arg(long, requires = "integration", value_parser = ["http"])]
+        feature: Option<String>,
+        /// Overwrite the file if it already exists
+        #[arg(short, long)]
+        force: bool,
     },
 }
 
 #[derive(Subcommand)]
 pub enum DbCommands {
     /// Show database status
-    Status,
-    /// Create database
+    Status {
+        /// Refresh the status every N seconds
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        interval: Option<u64>,
+    },
+    /// Create database (mysql, postgres or sqlite)
     Create,
-    /// Drop database
+    /// Drop database (mysql, postgres or sqlite)
     Drop {
         #[arg(long)]
         force: bool,
@@ -347,18 +761,57 @@ pub enum DbCommands {
     },
     /// Seed database
     Seed,
+    /// Back up the database to a SQL dump
+    Backup {
+        /// Output file (defaults to storage/backups/{app}_{timestamp}.sql)
+        #[arg(short, long)]
+        output: Option<String>,
+        /// Compress the dump with gzip
+        #[arg(long)]
+        gzip: bool,
+    },
+    /// Restore the database from a SQL dump
+    Restore {
+        /// Dump file to restore (.sql or .sql.gz)
+        file: String,
+        #[arg(long)]
+        force: bool,
+    },
+    /// Show the database schema (read-only)
+    Schema {
+        /// Show the columns and indexes of this table
+        #[arg(short, long)]
+        table: Option<String>,
+        /// Output format
+        #[arg(long, value_enum, default_value_t)]
+        format: commands::db::SchemaFormat,
+    },
 }
 
 #[derive(Subcommand)]
 pub enum MigrateCommands {
     /// Run pending migrations
-    Up,
+    Up {
+        /// Print the SQL pending migrations would execute without running them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Show the SQL pending migrations would execute without running them
+    DryRun,
     /// Rollback migrations
     Down {
-        /// Number of migrations to rollback
+        /// Number of batches to rollback
         #[arg(short, long, default_value = "1")]
         steps: u32,
     },
+    /// Rollback a single migration by file name
+    Rollback {
+        /// Migration file name, e.g. 2024_01_01_000000_create_users_table
+        name: String,
+        /// Also roll back the migrations that ran after it
+        #[arg(long)]
+        cascade: bool,
+    },
     /// Reset all migrations
     Reset,
     /// Rollback and re-run migrations
@@ -369,6 +822,29 @@ pub enum MigrateCommands {
     Make {
         name: String,
     },
+    /// Squash all ran migrations into a single baseline migration
+    Squash {
+        /// Replace the squashed migrations' records with the baseline and remove their files
+        #[arg(long)]
+        delete_old: bool,
+        /// Keep the original migration files and their records when using --delete-old
+        #[arg(long)]
+        keep: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum SeedCommands {
+    /// Show the order seeders run in without running them
+    List,
+    /// Delete the records created by a seeder, or by every seeder in reverse order
+    Rollback {
+        /// Seeder to roll back
+        #[arg(short, long)]
+        class: Option<String>,
+    },
+    /// Show which seeders have run
+    Status,
 }
 
 #[derive(Subcommand)]
@@ -384,23 +860,80 @@ pub enum RouteCommands {
         /// Show middleware
         #[arg(long)]
         middleware: bool,
+        /// Show the file and line where each route is defined
+        #[arg(long)]
+        source: bool,
     },
     /// Clear route cache
     Clear,
+    /// Fail when two routes share a method and URI pattern
+    Check,
     /// Cache routes
     Cache,
+    /// Export routes as an API specification
+    Export {
+        /// Export format (openapi or swagger)
+        #[arg(short, long, default_value = "openapi")]
+        format: String,
+        /// Output file (JSON when it ends with .json, YAML otherwise)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Send a request to each route and show the status codes
+    Test {
+        /// Base URL of the running application (defaults to app.url)
+        #[arg(long)]
+        base_url: Option<String>,
+        /// Only test routes with this method
+        #[arg(short, long)]
+        method: Option<String>,
+        /// Authorization header value, e.g. "Bearer <token>"
+        #[arg(long)]
+        auth: Option<String>,
+        /// Also send non-GET requests, required when --method is not GET
+        #[arg(long)]
+        all_methods: bool,
+        /// Seconds before a request times out
+        #[arg(long, default_value = "5")]
+        timeout: u64,
+    },
 }
 
 #[derive(Subcommand)]
 pub enum CacheCommands {
     /// Clear all cache
     Clear,
-    /// Clear specific cache store
+    /// Remove a single key from the default cache store
     Forget {
         key: String,
     },
     /// Cache configuration
     Config,
+    /// Cache the discovered routes
+    Routes,
+    /// List the keys in the default cache store
+    List {
+        /// Only list keys starting with this prefix
+        #[arg(long)]
+        prefix: Option<String>,
+    },
+    /// Show hit/miss ratio and memory usage of the default cache store
+    Stats,
+    /// Pre-populate the caches after a deploy (everything when no step is selected)
+    Warm {
+        /// Cache the configuration
+        #[arg(long)]
+        config: bool,
+        /// Cache the routes
+        #[arg(long)]
+        routes: bool,
+        /// Cache config and routes, then run the commands in `[cache.warm_up] commands`
+        #[arg(long)]
+        all: bool,
+        /// Seconds before a warm-up command is killed
+        #[arg(long, default_value_t = 30)]
+        timeout: u64,
+    },
 }
 
 #[derive(Subcommand)]
@@ -408,7 +941,7 @@ pub enum QueueCommands {
     /// Start queue worker
     Work {
         /// Queue name
-        #[arg(short, long)]
+        #[arg(long)]
         queue: Option<String>,
         /// Number of jobs to process
         #[arg(long)]
@@ -419,16 +952,44 @@ pub enum QueueCommands {
         /// Sleep time when no jobs
         #[arg(long, default_value = "3")]
         sleep: u64,
+        /// Redis connection URL, overriding `[queue] connection`
+        #[arg(long)]
+        connection: Option<String>,
+    },
+    /// Show queue depth and throughput
+    Stats {
+        /// Queue name (defaults to the queues listed in rustisan.toml)
+        #[arg(long)]
+        queue: Option<String>,
+        /// Refresh the statistics every 2 seconds
+        #[arg(long)]
+        watch: bool,
     },
     /// Restart queue workers
     Restart,
     /// Show failed jobs
-    Failed,
+    Failed {
+        /// Keep polling and print failed jobs as they are added
+        #[arg(long)]
+        tail: bool,
+        /// Only show jobs that failed at or after this time, e.g. "2024-05-17 10:00:00"
+        #[arg(long)]
+        since: Option<String>,
+        /// Flush the failed jobs when tailing is stopped with Ctrl+C
+        #[arg(long, requires = "tail")]
+        clear_on_exit: bool,
+    },
     /// Retry failed jobs
     Retry {
         /// Job ID to retry
         id: Option<String>,
     },
+    /// Enqueue the due tasks of `[[schedule]]` as jobs for the workers to run
+    Schedule {
+        /// Enqueue this task now regardless of its cron expression
+        #[arg(long, value_name = "TASK_NAME")]
+        force_run: Option<String>,
+    },
     /// Flush failed jobs
     Flush,
 }
@@ -450,14 +1011,124 @@ pub enum ConfigCommands {
         value: String,
     },
     /// Generate application key
-    GenerateKey,
+    GenerateKey {
+        /// Re-encrypt the .enc files of storage/ with the new key before replacing the old one
+        #[arg(long)]
+        rotate: bool,
+        /// List the files --rotate would re-encrypt without changing anything
+        #[arg(long, requires = "rotate")]
+        dry_run: bool,
+    },
     /// Validate configuration
-    Validate,
+    Validate {
+        /// TOML file with additional [[rules]] to validate against
+        #[arg(long)]
+        schema: Option<String>,
+    },
     /// Reset configuration to defaults
     Reset,
+    /// Export configuration in another format
+    Export {
+        /// Export format (env)
+        #[arg(short, long, default_value = "env")]
+        format: String,
+        /// Output file (defaults to stdout)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Import configuration values from a .env file
+    Import {
+        /// Path to the .env file
+        file: String,
+    },
+    /// Compare rustisan.toml with rustisan.{env}.toml
+    Diff {
+        /// Environment name, e.g. production
+        #[arg(long)]
+        env: String,
+    },
+    /// Keep only the keys in rustisan.{env}.toml that differ from rustisan.toml
+    Override {
+        /// Environment name, e.g. production
+        #[arg(long)]
+        env: String,
+    },
+}
+
+
+
+#[derive(Subcommand)]
+pub enum ScheduleCommands {
+    /// Run the tasks that are due this minute
+    Run,
+    /// List scheduled tasks and their next run times
+    List,
+    /// Add a scheduled task to rustisan.toml
+    Add {
+        /// Shell command to run
+        command: String,
+        /// Cron expression, e.g. "0 3 * * *"
+        #[arg(long)]
+        cron: String,
+    },
 }
 
+#[derive(Subcommand)]
+pub enum MaintenanceCommands {
+    /// Enable maintenance mode
+    On {
+        /// Message shown to visitors
+        #[arg(long)]
+        message: Option<String>,
+        /// IP addresses that can still access the application
+        #[arg(long, value_delimiter = ',')]
+        allow: Vec<String>,
+    },
+    /// Disable maintenance mode
+    Off,
+}
+
+#[derive(Subcommand)]
+pub enum LogCommands {
+    /// Show the latest log file
+    Show {
+        /// Number of lines to show from the end of the log
+        #[arg(short = 'n', long, default_value = "50")]
+        lines: usize,
+        /// Only show entries at this level or more severe (error, warn, info, debug)
+        #[arg(long)]
+        level: Option<String>,
+        /// Keep printing new lines as they are written
+        #[arg(short, long)]
+        follow: bool,
+    },
+    /// Delete all log files
+    Clear,
+}
 
+#[derive(Subcommand)]
+pub enum StorageCommands {
+    /// Link public/storage to storage/uploads
+    Link,
+    /// Remove the public/storage link
+    Unlink,
+    /// Delete files in storage/ older than a number of days
+    Clean {
+        /// Minimum age in days of the files to delete
+        #[arg(long, default_value = "30")]
+        days: u32,
+        /// List the files that would be deleted without deleting them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Show the size of each storage directory
+    Stats,
+    /// List files in a storage directory
+    List {
+        /// Directory inside storage/ (defaults to storage/ itself)
+        path: Option<String>,
+    },
+}
 
 #[derive(Subcommand)]
 pub enum PackageCommands {
@@ -465,7 +1136,7 @@ pub enum PackageCommands {
     Install {
         name: String,
         /// Package version
-        #[arg(short, long)]
+        #[arg(long)]
         version: Option<String>,
     },
     /// Remove a package
@@ -474,6 +1145,34 @@ pub enum PackageCommands {
     },
     /// List installed packages
     List,
+    /// Audit dependencies for known security vulnerabilities
+    Audit {
+        /// Upgrade vulnerable dependencies with `cargo audit fix` first
+        #[arg(long)]
+        fix: bool,
+    },
+    /// Show the dependency graph
+    Graph {
+        /// Levels of dependencies to show
+        #[arg(long)]
+        depth: Option<usize>,
+        /// Only show the dependencies of this package
+        #[arg(short, long)]
+        package: Option<String>,
+        /// Write the graph to a Graphviz (.dot) or Mermaid (.mmd) file
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+    /// Search crates.io for packages
+    Search {
+        query: String,
+        /// Result order
+        #[arg(long, value_enum, default_value_t)]
+        sort: commands::package::SearchSort,
+        /// Page of results to show
+        #[arg(long, default_value = "1", value_parser = clap::value_parser!(u64).range(1..))]
+        page: u64,
+    },
     /// Update packages
     Update,
 }
@@ -482,7 +1181,7 @@ pub enum PackageCommands {
 pub enum DevCommands {
     /// Start development server with hot reload
     Server {
-        #[arg(short, long, default_value = "127.0.0.1")]
+        #[arg(short = 'H', long, default_value = "127.0.0.1")]
         host: String,
         #[arg(short, long, default_value = "3000")]
         port: u16,
@@ -492,45 +1191,110 @@ pub enum DevCommands {
     /// Format code
     Format,
     /// Check code with clippy
-    Check,
+    Check {
+        /// Also warn on the pedantic, nursery and cargo lint groups
+        #[arg(long)]
+        pedantic: bool,
+        /// Apply clippy's suggested fixes
+        #[arg(long)]
+        fix: bool,
+    },
     /// Generate documentation
     Docs {
         #[arg(long)]
         open: bool,
     },
+    /// Capture a CPU profile of the application with pprof
+    Profile {
+        /// Seconds to profile for
+        #[arg(short, long, default_value = "30")]
+        duration: u64,
+        /// Profile file, .pb for protobuf or .svg for a flamegraph (default: storage/profiles/{timestamp}.pb)
+        #[arg(short, long, conflicts_with = "flamegraph")]
+        output: Option<String>,
+        /// Write a flamegraph SVG instead of a protobuf profile
+        #[arg(long)]
+        flamegraph: bool,
+    },
+    /// Run criterion benchmarks (runs them when no option is given)
+    Benchmark {
+        /// Create benches/benchmarks.rs with a stub for each public function in src/services/
+        #[arg(long)]
+        generate: bool,
+        /// Run `cargo bench` and save a summary to storage/benchmarks/
+        #[arg(long)]
+        run: bool,
+        /// Compare the latest summary against this older summary
+        #[arg(long, value_name = "OLD_JSON")]
+        compare: Option<String>,
+    },
+    /// Check and bootstrap the development environment
+    Setup {
+        /// Report the status without installing or creating anything
+        #[arg(long)]
+        check_only: bool,
+    },
 }
 
-#[tokio::main]
-async fn main() {
-    let cli = Cli::parse();
+fn main() {
+    // Environment variables can only be set safely before the runtime starts its worker threads
+    let env_file = utils::env::load_env_file_if_exists(".env");
+
+    let args = utils::alias::expand_aliases(std::env::args_os().collect(), &utils::alias::load_aliases());
+    let cli = Cli::parse_from(args);
+    CommandUtils::set_output_format(cli.output_format);
+
     // Initialize logging based on verbosity
     if !cli.quiet {
         init_logging();
     }
 
-    // Print banner unless quiet
-    if !cli.quiet {
+    match env_file {
+        Ok(true) => {}
+        Ok(false) => ::log::warn!("No .env file found"),
+        Err(e) => CommandUtils::warning(&format!("Failed to load .env: {}", e)),
+    }
+
+    let runtime = match tokio::runtime::Builder::new_multi_thread().enable_all().build() {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("{} Failed to start the async runtime: {}", "Error:".red().bold(), e);
+            process::exit(1);
+        }
+    };
+    runtime.block_on(run(cli));
+}
+
+async fn run(cli: Cli) {
+    // Print banner unless quiet, generating completions or emitting JSON
+    if !cli.quiet
+        && cli.output_format == OutputFormat::Human
+        && !matches!(cli.command, Commands::Completion { .. } | Commands::Plugin(_))
+    {
         print_banner();
     }
 
     let result = match cli.command {
-        Commands::New { name, path, template, git } => {
-            commands::new::handle(name, path, template, git).await
+        Commands::New { name, path, template, git, no_interaction, workspace, no_docker, no_ci } => {
+            let layout = commands::new::ProjectLayout { workspace, docker: !no_docker, ci: !no_ci };
+            commands::new::handle(name, path, template, git, no_interaction, layout).await
         }
         Commands::Make { component } => {
             commands::make::handle(component).await
         }
-        Commands::Serve { host, port, env, reload } => {
-            commands::serve::handle(host, port, env, reload).await
+        Commands::Serve { host, port, env, reload, tls, cert, key, strict_port, watch_paths } => {
+            let tls = commands::serve::TlsOptions { enabled: tls, cert, key };
+            let options = commands::serve::ServeOptions { host, port, env, reload, tls, strict_port, watch_paths };
+            commands::serve::handle(options).await
         }
         Commands::Db { operation } => {
             commands::db::handle(operation).await
         }
-        Commands::Migrate { operation } => {
-            commands::migrate::handle(operation).await
+        Commands::Migrate { operation, dry_run } => {
+            commands::migrate::handle(operation, dry_run).await
         }
-        Commands::Seed { class, force } => {
-            commands::seed::handle(class, force).await
+        Commands::Seed { operation, class, force, stop_on_error } => {
+            commands::seed::handle(operation, class, force, stop_on_error).await
         }
         Commands::Route { operation } => {
             commands::route::handle(operation).await
@@ -544,29 +1308,69 @@ async fn main() {
         Commands::Config { operation } => {
             commands::config::handle(operation).await
         }
-        Commands::Test { pattern, unit, integration, verbose } => {
-            commands::test::handle(pattern, unit, integration, verbose).await
+        Commands::Test { pattern, unit, integration, verbose, coverage, min_coverage, junit_output, watch } => {
+            let filter = commands::test::TestFilter { pattern, unit, integration };
+            commands::test::handle(filter, verbose, coverage, min_coverage, junit_output, watch).await
         }
-        Commands::Build { env, optimize, output } => {
-            commands::build::handle(env, optimize, output).await
+        Commands::Build { env, optimize, output, git_version, size_report, target, no_minify } => {
+            let options = commands::build::BuildOptions { optimize, output, git_version, size_report, target, no_minify };
+            commands::build::handle(env, options).await
         }
-        Commands::Deploy { target, skip_build, dry_run } => {
-            commands::deploy::handle(target, skip_build, dry_run).await
+        Commands::Deploy { target, skip_build, dry_run, timeout, rollback, version, force, ssh_fingerprint_check } => {
+            let options = commands::deploy::DeployOptions {
+                skip_build,
+                dry_run,
+                timeout,
+                rollback,
+                version,
+                force,
+                ssh_fingerprint_check,
+            };
+            commands::deploy::handle(target, options).await
         }
-        Commands::Info { detailed } => {
-            commands::info::handle(detailed).await
+        Commands::Info { detailed, complexity_threshold, compare_last, watch, no_clear } => {
+            let options = commands::info::InfoOptions { detailed, complexity_threshold, compare_last, watch, no_clear };
+            commands::info::handle(options, cli.output_format).await
         }
 
+        Commands::Schedule { operation } => {
+            commands::schedule::handle(operation).await
+        }
+        Commands::Maintenance { mode } => {
+            commands::maintenance::handle(mode).await
+        }
+        Commands::Log { operation } => {
+            commands::log::handle(operation).await
+        }
+        Commands::Storage { operation } => {
+            commands::storage::handle(operation).await
+        }
+        Commands::Tinker { no_prelude } => {
+            commands::tinker::handle(no_prelude).await
+        }
+        Commands::Optimize { strict } => {
+            commands::optimize::handle(strict).await
+        }
         Commands::Package { operation } => {
             commands::package::handle(operation).await
         }
         Commands::Dev { tool } => {
             commands::dev::handle(tool).await
         }
+        Commands::Completion { shell, install } => {
+            commands::completion::handle(shell, install).await
+        }
+        Commands::Plugin(command) => {
+            commands::plugin::handle(command).await
+        }
     };
 
     if let Err(e) = result {
-        eprintln!("{} {}", "Error:".red().bold(), e);
+        if cli.output_format == OutputFormat::Json {
+            CommandUtils::error(&e.to_string());
+        } else {
+            eprintln!("{} {}", "Error:".red().bold(), e);
+        }
         process::exit(1);
     }
 }
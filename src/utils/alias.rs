@@ -0,0 +1,133 @@
+//! Command aliases for the Rustisan CLI
+//!
+//! This module expands the aliases defined in the `[aliases]` table of rustisan.toml.
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+
+/// Global options taking a value, skipped when looking for the subcommand
+const VALUE_OPTIONS: &[&str] = &["-F", "--output-format"];
+
+/// Read the `[aliases]` table of rustisan.toml in the current directory
+pub fn load_aliases() -> HashMap<String, String> {
+    std::fs::read_to_string("rustisan.toml")
+        .map(|content| parse_aliases(&content))
+        .unwrap_or_default()
+}
+
+fn parse_aliases(content: &str) -> HashMap<String, String> {
+    let Ok(config) = content.parse::<toml::Value>() else {
+        return HashMap::new();
+    };
+
+    config
+        .get("aliases")
+        .and_then(|aliases| aliases.as_table())
+        .map(|aliases| {
+            aliases
+                .iter()
+                .filter_map(|(name, expansion)| Some((name.clone(), expansion.as_str()?.to_string())))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Replace an alias in the subcommand position with its whitespace-separated expansion
+///
+/// Aliases may expand to other aliases. A cycle such as `a = "b"`, `b = "a"`
+/// is reported with a warning and the arguments are left unchanged.
+pub fn expand_aliases(args: Vec<OsString>, aliases: &HashMap<String, String>) -> Vec<OsString> {
+    let Some(position) = subcommand_position(&args) else {
+        return args;
+    };
+    // An argument that is not valid UTF-8 cannot name an alias
+    let Some(subcommand) = args[position].to_str() else {
+        return args;
+    };
+
+    let mut expanded = vec![subcommand.to_string()];
+    let mut seen = Vec::new();
+    while let Some(expansion) = aliases.get(&expanded[0]) {
+        seen.push(expanded.remove(0));
+        if expansion.split_whitespace().next().is_some_and(|head| seen.iter().any(|name| name == head)) {
+            crate::commands::CommandUtils::warning(&format!(
+                "Alias cycle detected: {} -> {}",
+                seen.join(" -> "),
+                expansion.split_whitespace().next().unwrap_or_default()
+            ));
+            return args;
+        }
+
+        let mut next: Vec<String> = expansion.split_whitespace().map(str::to_string).collect();
+        if next.is_empty() {
+            return args;
+        }
+        next.append(&mut expanded);
+        expanded = next;
+    }
+
+    let mut result = args[..position].to_vec();
+    result.extend(expanded.into_iter().map(OsString::from));
+    result.extend_from_slice(&args[position + 1..]);
+    result
+}
+
+/// Index of the first positional argument after the program name
+fn subcommand_position(args: &[OsString]) -> Option<usize> {
+    let mut index = 1;
+    while index < args.len() {
+        let arg = args[index].to_string_lossy();
+        if VALUE_OPTIONS.contains(&arg.as_ref()) {
+            index += 2;
+        } else if arg.starts_with('-') {
+            index += 1;
+        } else {
+            return Some(index);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<OsString> {
+        values.iter().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn test_expand_aliases() {
+        let aliases = parse_aliases("[aliases]\nm = \"migrate up\"\ns = \"serve --port 3000\"\nmf = \"m --force\"\n");
+
+        assert_eq!(expand_aliases(args(&["rustisan", "m"]), &aliases), args(&["rustisan", "migrate", "up"]));
+        assert_eq!(
+            expand_aliases(args(&["rustisan", "-F", "json", "s", "--reload"]), &aliases),
+            args(&["rustisan", "-F", "json", "serve", "--port", "3000", "--reload"])
+        );
+        assert_eq!(expand_aliases(args(&["rustisan", "mf"]), &aliases), args(&["rustisan", "migrate", "up", "--force"]));
+        assert_eq!(expand_aliases(args(&["rustisan", "migrate"]), &aliases), args(&["rustisan", "migrate"]));
+    }
+
+    #[test]
+    fn test_alias_cycle_is_not_expanded() {
+        let aliases = parse_aliases("[aliases]\na = \"b --x\"\nb = \"a\"\nc = \"c\"\n");
+
+        assert_eq!(expand_aliases(args(&["rustisan", "a"]), &aliases), args(&["rustisan", "a"]));
+        assert_eq!(expand_aliases(args(&["rustisan", "c"]), &aliases), args(&["rustisan", "c"]));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_non_utf8_arguments_are_kept() {
+        use std::os::unix::ffi::OsStringExt;
+
+        let aliases = parse_aliases("[aliases]\nm = \"migrate up\"\n");
+        let path = OsString::from_vec(vec![b'f', 0xff]);
+
+        let mut expected = args(&["rustisan", "migrate", "up"]);
+        expected.push(path.clone());
+        assert_eq!(expand_aliases(vec!["rustisan".into(), "m".into(), path.clone()], &aliases), expected);
+        assert_eq!(expand_aliases(vec!["rustisan".into(), path.clone()], &aliases), vec![OsString::from("rustisan"), path]);
+    }
+}
@@ -1,3 +1,5 @@
+use anyhow::Result;
+use std::collections::HashMap;
 use std::ffi::OsStr;
 
 /// Safe wrapper around unsafe set_var in custom std
@@ -7,4 +9,205 @@ pub fn set_var<K: AsRef<OsStr>, V: AsRef<OsStr>>(key: K, value: V) {
     unsafe {
         std::env::set_var(key, value);
     }
-}
\ No newline at end of file
+}
+
+/// Load a `.env` file, setting each variable that isn't already in the environment
+///
+/// Returns every variable of the file, including the ones left untouched
+/// because the environment already defines them.
+///
+/// A key defined more than once takes its last value, both in the
+/// environment and in the returned map.
+pub fn load_env_file(path: &str) -> Result<HashMap<String, String>> {
+    let content = std::fs::read_to_string(path)?;
+    let variables: HashMap<String, String> = parse_env_file(&content)?.into_iter().collect();
+
+    for (key, value) in &variables {
+        if std::env::var_os(key).is_none() {
+            set_var(key, value);
+        }
+    }
+
+    Ok(variables)
+}
+
+/// Load a `.env` file when present, returning whether it existed
+///
+/// Must run before the async runtime starts its worker threads, see [`set_var`].
+pub fn load_env_file_if_exists(path: &str) -> Result<bool> {
+    match load_env_file(path) {
+        Ok(_) => Ok(true),
+        Err(e) if e.downcast_ref::<std::io::Error>().is_some_and(|e| e.kind() == std::io::ErrorKind::NotFound) => {
+            Ok(false)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Parse `KEY=VALUE` lines, with `#` comments and quoted values that may span lines
+///
+/// Double-quoted values support `\n`, `\"` and `\\` escapes, single-quoted
+/// values are taken literally.
+fn parse_env_file(content: &str) -> Result<Vec<(String, String)>> {
+    let mut variables = Vec::new();
+    let mut lines = content.lines().enumerate();
+
+    while let Some((number, line)) = lines.next() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            anyhow::bail!("Line {}: expected KEY=VALUE", number + 1);
+        };
+        let key = key.trim().to_string();
+        let value = value.trim_start();
+
+        let value = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let mut raw = value[1..].to_string();
+                while closing_quote(&raw, quote).is_none() {
+                    let Some((_, next)) = lines.next() else {
+                        anyhow::bail!("Line {}: unterminated quoted value for {}", number + 1, key);
+                    };
+                    raw.push('\n');
+                    raw.push_str(next);
+                }
+                let raw = &raw[..closing_quote(&raw, quote).unwrap_or(raw.len())];
+                if quote == '"' { unescape(raw) } else { raw.to_string() }
+            }
+            _ => value.split(" #").next().unwrap_or_default().trim().to_string(),
+        };
+
+        variables.push((key, value));
+    }
+
+    Ok(variables)
+}
+
+/// Byte index of the first unescaped `quote` in a value
+fn closing_quote(value: &str, quote: char) -> Option<usize> {
+    let mut escaped = false;
+    for (index, c) in value.char_indices() {
+        match c {
+            '\\' if quote == '"' && !escaped => escaped = true,
+            c if c == quote && !escaped => return Some(index),
+            _ => escaped = false,
+        }
+    }
+    None
+}
+
+/// Resolve the escapes of a double-quoted value
+fn unescape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+/// Replace `${NAME}` placeholders with the values of environment variables
+///
+/// Unset variables are reported with a warning and left as placeholders.
+pub fn expand_env_vars(value: &str) -> String {
+    static PLACEHOLDER: std::sync::LazyLock<regex::Regex> =
+        std::sync::LazyLock::new(|| regex::Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)\}").unwrap());
+
+    PLACEHOLDER
+        .replace_all(value, |captures: &regex::Captures| match std::env::var(&captures[1]) {
+            Ok(expanded) => expanded,
+            Err(_) => {
+                crate::commands::CommandUtils::warning(&format!(
+                    "Environment variable {} is not set; leaving {} in place",
+                    &captures[1], &captures[0]
+                ));
+                captures[0].to_string()
+            }
+        })
+        .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_env_vars() {
+        set_var("RUSTISAN_TEST_EXPAND_HOST", "db.internal");
+
+        assert_eq!(expand_env_vars("postgres://${RUSTISAN_TEST_EXPAND_HOST}:5432"), "postgres://db.internal:5432");
+        assert_eq!(expand_env_vars("${RUSTISAN_TEST_EXPAND_UNSET}"), "${RUSTISAN_TEST_EXPAND_UNSET}");
+        assert_eq!(expand_env_vars("$HOME and ${not valid}"), "$HOME and ${not valid}");
+    }
+
+    #[test]
+    fn test_parse_env_file() {
+        let content = r#"
+# Application
+APP_NAME=Shop # inline comment
+export APP_URL=http://localhost:3000
+GREETING="Hello \"world\"\nBye"
+LITERAL='no \n escapes'
+EMPTY=
+"#;
+
+        let variables: HashMap<String, String> = parse_env_file(content).unwrap().into_iter().collect();
+        assert_eq!(variables.len(), 5);
+        assert_eq!(variables["APP_NAME"], "Shop");
+        assert_eq!(variables["APP_URL"], "http://localhost:3000");
+        assert_eq!(variables["GREETING"], "Hello \"world\"\nBye");
+        assert_eq!(variables["LITERAL"], "no \\n escapes");
+        assert_eq!(variables["EMPTY"], "");
+        assert!(parse_env_file("NOT A VARIABLE").is_err());
+    }
+
+    #[test]
+    fn test_parse_multiline_value() {
+        let content = "KEY=\"-----BEGIN KEY-----\nabc\n-----END KEY-----\"\nNEXT=1\n";
+
+        let variables = parse_env_file(content).unwrap();
+        assert_eq!(variables[0], ("KEY".to_string(), "-----BEGIN KEY-----\nabc\n-----END KEY-----".to_string()));
+        assert_eq!(variables[1], ("NEXT".to_string(), "1".to_string()));
+        assert!(parse_env_file("KEY=\"never closed\nNEXT=1\n").is_err());
+    }
+
+    #[test]
+    fn test_load_env_file_keeps_existing_variables() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".env");
+        std::fs::write(&path, "RUSTISAN_TEST_ENV_FILE_SET=from-file\nRUSTISAN_TEST_ENV_FILE_NEW=from-file\n").unwrap();
+        set_var("RUSTISAN_TEST_ENV_FILE_SET", "from-env");
+
+        let variables = load_env_file(path.to_str().unwrap()).unwrap();
+
+        assert_eq!(variables["RUSTISAN_TEST_ENV_FILE_SET"], "from-file");
+        assert_eq!(std::env::var("RUSTISAN_TEST_ENV_FILE_SET").unwrap(), "from-env");
+        assert_eq!(std::env::var("RUSTISAN_TEST_ENV_FILE_NEW").unwrap(), "from-file");
+        assert!(load_env_file(dir.path().join("missing").to_str().unwrap()).is_err());
+        assert!(!load_env_file_if_exists(dir.path().join("missing").to_str().unwrap()).unwrap());
+    }
+
+    #[test]
+    fn test_load_env_file_duplicate_keys_take_the_last_value() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".env");
+        std::fs::write(&path, "RUSTISAN_TEST_ENV_FILE_DUPLICATE=first\nRUSTISAN_TEST_ENV_FILE_DUPLICATE=last\n").unwrap();
+
+        let variables = load_env_file(path.to_str().unwrap()).unwrap();
+
+        assert_eq!(variables["RUSTISAN_TEST_ENV_FILE_DUPLICATE"], "last");
+        assert_eq!(std::env::var("RUSTISAN_TEST_ENV_FILE_DUPLICATE").unwrap(), "last");
+    }
+}
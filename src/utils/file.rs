@@ -44,6 +44,42 @@ impl FileUtils {
         Ok(())
     }
 
+    /// Write content to a file through a temporary file renamed over it,
+    /// so readers and interrupted writes never leave a partial file behind
+    pub fn atomic_write<P: AsRef<Path>>(path: P, content: &str) -> Result<()> {
+        use std::io::Write;
+
+        let path = path.as_ref();
+        let dir = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        Self::ensure_dir(dir)?;
+
+        let mut temp = tempfile::NamedTempFile::new_in(dir)?;
+        temp.write_all(content.as_bytes())?;
+        temp.as_file().sync_all()?;
+
+        // Temporary files are private, keep the permissions of the file being replaced
+        let permissions = match fs::metadata(path) {
+            Ok(metadata) => Some(metadata.permissions()),
+            #[cfg(unix)]
+            Err(_) => Some(std::os::unix::fs::PermissionsExt::from_mode(0o644)),
+            #[cfg(not(unix))]
+            Err(_) => None,
+        };
+        if let Some(permissions) = permissions {
+            temp.as_file().set_permissions(permissions)?;
+        }
+
+        // Dropping the path on error removes the temporary file
+        let temp_path = temp.into_temp_path();
+        fs::rename(&temp_path, path)?;
+        let _ = temp_path.keep();
+
+        Ok(())
+    }
+
     /// Read file content as string
     pub fn read_file<P: AsRef<Path>>(path: P) -> Result<String> {
         Ok(fs::read_to_string(path)?)
@@ -125,4 +161,79 @@ impl FileUtils {
     pub fn is_dir<P: AsRef<Path>>(path: P) -> bool {
         path.as_ref().is_dir()
     }
+
+    /// List all files under a directory recursively, without following symlinks
+    pub fn files_recursive<P: AsRef<Path>>(dir: P) -> Vec<PathBuf> {
+        walkdir::WalkDir::new(dir)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| entry.into_path())
+            .collect()
+    }
+
+    /// Total size in bytes of the files under a directory
+    pub fn dir_size<P: AsRef<Path>>(dir: P) -> u64 {
+        Self::files_recursive(dir)
+            .iter()
+            .filter_map(|path| path.metadata().ok())
+            .map(|metadata| metadata.len())
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    #[test]
+    fn test_atomic_write_never_exposes_partial_content() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("src/models/user.rs");
+        let old = "// old\n".repeat(100_000);
+        let new = "// new\n".repeat(150_000);
+        FileUtils::atomic_write(&path, &old).unwrap();
+
+        let done = Arc::new(AtomicBool::new(false));
+        let reader = {
+            let (path, old, new, done) = (path.clone(), old.clone(), new.clone(), done.clone());
+            std::thread::spawn(move || {
+                let mut reads = 0;
+                while !done.load(Ordering::SeqCst) || reads == 0 {
+                    let content = fs::read_to_string(&path).unwrap();
+                    assert!(content == old || content == new, "read a partial file of {} bytes", content.len());
+                    reads += 1;
+                }
+                reads
+            })
+        };
+
+        for round in 0..20 {
+            FileUtils::atomic_write(&path, if round % 2 == 0 { &new } else { &old }).unwrap();
+        }
+        done.store(true, Ordering::SeqCst);
+
+        assert!(reader.join().unwrap() > 0);
+        assert_eq!(fs::read_to_string(&path).unwrap(), old);
+        // Only the target is left, no temporary files
+        assert_eq!(fs::read_dir(path.parent().unwrap()).unwrap().count(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_atomic_write_keeps_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("deploy.sh");
+
+        FileUtils::atomic_write(&path, "#!/bin/sh\n").unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o644);
+
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        FileUtils::atomic_write(&path, "#!/bin/sh\necho deployed\n").unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o755);
+    }
 }
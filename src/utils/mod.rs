@@ -1,8 +1,11 @@
+pub mod alias;
 pub mod env;
 pub mod file;
 pub mod process;
+pub mod progress;
 pub mod text;
 
 pub use file::FileUtils;
 pub use process::ProcessUtils;
+pub use progress::ProgressBar;
 pub use text::TextUtils;
@@ -3,7 +3,11 @@
 //! This module provides common process and command execution utilities.
 
 use anyhow::Result;
-use std::process::Command;
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
 
 /// Process utilities
 pub struct ProcessUtils;
@@ -39,6 +43,130 @@ impl ProcessUtils {
         Ok((output.status.success(), stdout, stderr))
     }
 
+    /// Execute a command, passing each line of output to a callback as it is produced
+    ///
+    /// Stdout and stderr are read concurrently, so a chatty stderr cannot block
+    /// stdout. The callbacks run on the calling thread. Returns the success status
+    /// once the child exits.
+    pub fn execute_streaming(
+        command: &str,
+        args: &[&str],
+        on_stdout: impl Fn(&str),
+        on_stderr: impl Fn(&str),
+    ) -> Result<bool> {
+        Self::execute_command_streaming(Command::new(command).args(args), on_stdout, on_stderr)
+    }
+
+    /// Like [`Self::execute_streaming`], for a command prepared with its own environment
+    pub fn execute_command_streaming(
+        command: &mut Command,
+        on_stdout: impl Fn(&str),
+        on_stderr: impl Fn(&str),
+    ) -> Result<bool> {
+        let mut child = command
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        let (sender, receiver) = mpsc::channel();
+        let readers = [
+            child.stdout.take().map(|out| Self::forward_lines(out, false, sender.clone())),
+            child.stderr.take().map(|err| Self::forward_lines(err, true, sender.clone())),
+        ];
+        drop(sender);
+
+        for (is_stderr, line) in receiver {
+            if is_stderr {
+                on_stderr(&line);
+            } else {
+                on_stdout(&line);
+            }
+        }
+
+        for reader in readers.into_iter().flatten() {
+            let _ = reader.join();
+        }
+
+        Ok(child.wait()?.success())
+    }
+
+    /// Read lines from a child pipe on a separate thread and forward them to a channel
+    fn forward_lines<R: Read + Send + 'static>(
+        pipe: R,
+        is_stderr: bool,
+        sender: mpsc::Sender<(bool, String)>,
+    ) -> thread::JoinHandle<()> {
+        thread::spawn(move || {
+            for line in BufReader::new(pipe).lines().map_while(Result::ok) {
+                if sender.send((is_stderr, line)).is_err() {
+                    break;
+                }
+            }
+        })
+    }
+
+    /// Execute a command, killing it if it runs longer than `timeout`
+    ///
+    /// Stdin is closed so the child cannot block waiting for input. On timeout
+    /// the child receives SIGTERM, followed by SIGKILL if it is still running
+    /// five seconds later (on other platforms it is killed immediately).
+    pub fn execute_with_timeout(command: &str, args: &[&str], timeout: Duration) -> Result<()> {
+        let mut child = Command::new(command)
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        let stderr = child.stderr.take().map(|mut pipe| {
+            thread::spawn(move || {
+                let mut output = String::new();
+                let _ = pipe.read_to_string(&mut output);
+                output
+            })
+        });
+
+        let deadline = Instant::now() + timeout;
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            if Instant::now() >= deadline {
+                Self::terminate(&mut child);
+                return Err(anyhow::anyhow!("Command timed out after {:?}", timeout));
+            }
+            thread::sleep(Duration::from_millis(50));
+        };
+
+        if !status.success() {
+            let stderr = stderr.and_then(|reader| reader.join().ok()).unwrap_or_default();
+            anyhow::bail!("Command '{}' failed: {}", command, stderr);
+        }
+
+        Ok(())
+    }
+
+    /// Stop a child process, giving it a chance to exit cleanly on Unix
+    fn terminate(child: &mut Child) {
+        #[cfg(unix)]
+        {
+            let _ = Command::new("kill")
+                .args(["-TERM", &child.id().to_string()])
+                .status();
+
+            let grace_period = Instant::now() + Duration::from_secs(5);
+            while Instant::now() < grace_period {
+                if let Ok(Some(_)) = child.try_wait() {
+                    return;
+                }
+                thread::sleep(Duration::from_millis(50));
+            }
+        }
+
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+
     /// Execute a command and return Result based on success
     pub fn execute_or_fail(command: &str, args: &[&str]) -> Result<()> {
         let output = Command::new(command)
@@ -105,4 +233,101 @@ impl ProcessUtils {
         let (shell, flag) = Self::get_shell_command();
         Self::execute(shell, &[flag, command])
     }
+
+    /// Check whether a TCP port can be bound on all interfaces and loopback
+    pub fn is_port_available(port: u16) -> bool {
+        ["0.0.0.0", "127.0.0.1"]
+            .iter()
+            .all(|host| std::net::TcpListener::bind((*host, port)).is_ok())
+    }
+
+    /// Find the first free port in `start..start + range`
+    pub fn find_available_port(start: u16, range: u16) -> Option<u16> {
+        (0..range)
+            .filter_map(|offset| start.checked_add(offset))
+            .find(|&port| Self::is_port_available(port))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_find_available_port_skips_busy_port() {
+        // Retry in case the port after the one the OS picked is taken too
+        for _ in 0..10 {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            let busy = listener.local_addr().unwrap().port();
+            if busy == u16::MAX || !ProcessUtils::is_port_available(busy + 1) {
+                continue;
+            }
+
+            assert!(!ProcessUtils::is_port_available(busy));
+            assert_eq!(ProcessUtils::find_available_port(busy, 20), Some(busy + 1));
+            assert_eq!(ProcessUtils::find_available_port(busy, 1), None);
+            return;
+        }
+        panic!("no pair of adjacent free ports found");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_execute_streaming_receives_lines_in_order() {
+        let stdout = RefCell::new(Vec::new());
+        let stderr = RefCell::new(Vec::new());
+
+        let success = ProcessUtils::execute_streaming(
+            "sh",
+            &["-c", "printf 'one\\ntwo\\nthree\\n'; echo oops >&2"],
+            |line| stdout.borrow_mut().push(line.to_string()),
+            |line| stderr.borrow_mut().push(line.to_string()),
+        )
+        .unwrap();
+
+        assert!(success);
+        assert_eq!(stdout.into_inner(), ["one", "two", "three"]);
+        assert_eq!(stderr.into_inner(), ["oops"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_execute_command_streaming_passes_environment() {
+        let stdout = RefCell::new(Vec::new());
+
+        let success = ProcessUtils::execute_command_streaming(
+            Command::new("sh").args(["-c", "echo $RUSTISAN_TEST_STREAM_ENV"]).env("RUSTISAN_TEST_STREAM_ENV", "staging"),
+            |line| stdout.borrow_mut().push(line.to_string()),
+            |_| {},
+        )
+        .unwrap();
+
+        assert!(success);
+        assert_eq!(stdout.into_inner(), ["staging"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_execute_with_timeout_kills_hung_command() {
+        let started = Instant::now();
+        let error = ProcessUtils::execute_with_timeout("sleep", &["60"], Duration::from_secs(1)).unwrap_err();
+
+        assert_eq!(error.to_string(), "Command timed out after 1s");
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_execute_with_timeout_completes_in_time() {
+        assert!(ProcessUtils::execute_with_timeout("true", &[], Duration::from_secs(5)).is_ok());
+        assert!(ProcessUtils::execute_with_timeout("false", &[], Duration::from_secs(5)).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_execute_streaming_reports_failure() {
+        let success = ProcessUtils::execute_streaming("sh", &["-c", "exit 3"], |_| {}, |_| {}).unwrap();
+        assert!(!success);
+    }
 }
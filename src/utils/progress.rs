@@ -0,0 +1,158 @@
+//! Progress bar utilities for the Rustisan CLI
+//!
+//! This module provides a small progress bar for long-running operations.
+//! The bar is drawn on stderr so machine-readable output on stdout stays clean.
+
+use colored::*;
+use std::io::{self, IsTerminal, Write};
+
+/// Width of the bar in characters
+const BAR_WIDTH: usize = 30;
+
+/// Frames used when the total is unknown
+const SPINNER_FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// ANSI sequence returning the cursor to the start of the line and clearing it
+const CLEAR_LINE: &str = "\r\x1b[2K";
+
+/// Progress bar rendered on stderr
+///
+/// A `total` of 0 renders a spinner instead of a percentage bar.
+pub struct ProgressBar {
+    total: usize,
+    current: usize,
+    label: String,
+    visible: bool,
+}
+
+impl ProgressBar {
+    /// Create a progress bar and draw it
+    ///
+    /// The bar is only drawn when stderr is a terminal.
+    pub fn new(label: &str, total: usize) -> Self {
+        let bar = Self {
+            total,
+            current: 0,
+            label: label.to_string(),
+            visible: io::stderr().is_terminal(),
+        };
+        bar.draw();
+        bar
+    }
+
+    /// Advance the bar by one step
+    pub fn increment(&mut self) {
+        self.current += 1;
+        self.draw();
+    }
+
+    /// Remove the bar, e.g. before printing the final message
+    pub fn finish_and_clear(&self) {
+        let _ = self.write_clear(&mut io::stderr());
+    }
+
+    /// Remove the bar while `f` prints, then draw it again
+    pub fn suspend<R>(&self, f: impl FnOnce() -> R) -> R {
+        self.finish_and_clear();
+        let result = f();
+        self.draw();
+        result
+    }
+
+    /// Print a line above the bar without disrupting it
+    pub fn println_above(&self, msg: &str) {
+        let mut stderr = io::stderr();
+        if self.visible {
+            let _ = write!(stderr, "{}", CLEAR_LINE);
+        }
+        let _ = writeln!(stderr, "{}", msg);
+        self.draw();
+    }
+
+    /// Render the current state of the bar
+    fn render(&self) -> String {
+        if self.total == 0 {
+            let frame = SPINNER_FRAMES[self.current % SPINNER_FRAMES.len()];
+            return format!("{} {}", frame.cyan(), self.label);
+        }
+
+        let current = self.current.min(self.total);
+        let filled = current * BAR_WIDTH / self.total;
+        let percent = current * 100 / self.total;
+
+        format!(
+            "{} [{}{}] {}/{} {:>3}%",
+            self.label,
+            "█".repeat(filled).cyan(),
+            "░".repeat(BAR_WIDTH - filled).dimmed(),
+            current,
+            self.total,
+            percent
+        )
+    }
+
+    /// Clear the line the bar is drawn on, if it is drawn
+    fn write_clear<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        if self.visible {
+            write!(out, "{}", CLEAR_LINE)?;
+        }
+        out.flush()
+    }
+
+    /// Redraw the bar in place
+    fn draw(&self) {
+        if !self.visible {
+            return;
+        }
+
+        let mut stderr = io::stderr();
+        let _ = write!(stderr, "{}{}", CLEAR_LINE, self.render());
+        let _ = stderr.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hidden_bar(label: &str, total: usize) -> ProgressBar {
+        ProgressBar {
+            total,
+            current: 0,
+            label: label.to_string(),
+            visible: false,
+        }
+    }
+
+    #[test]
+    fn test_clear_only_when_visible() {
+        let mut bar = hidden_bar("Migrating", 3);
+        let mut out = Vec::new();
+        bar.write_clear(&mut out).unwrap();
+        assert!(out.is_empty());
+
+        bar.visible = true;
+        bar.write_clear(&mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "\r\x1b[2K");
+    }
+
+    #[test]
+    fn test_suspend_returns_result() {
+        let bar = hidden_bar("Seeding", 2);
+        assert_eq!(bar.suspend(|| 42), 42);
+    }
+
+    #[test]
+    fn test_render() {
+        colored::control::set_override(false);
+
+        let mut bar = hidden_bar("Build", 4);
+        bar.increment();
+        assert_eq!(bar.render(), format!("Build [{}{}] 1/4  25%", "█".repeat(7), "░".repeat(23)));
+
+        let mut spinner = hidden_bar("Compiling", 0);
+        assert_eq!(spinner.render(), "⠋ Compiling");
+        spinner.increment();
+        assert_eq!(spinner.render(), "⠙ Compiling");
+    }
+}
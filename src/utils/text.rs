@@ -2,6 +2,24 @@
 //!
 //! This module provides common text manipulation and formatting utilities.
 
+/// Irregular English nouns as `(singular, plural)` pairs
+pub const IRREGULAR_WORDS: &[(&str, &str)] = &[
+    ("person", "people"),
+    ("man", "men"),
+    ("woman", "women"),
+    ("child", "children"),
+    ("ox", "oxen"),
+    ("mouse", "mice"),
+    ("foot", "feet"),
+    ("tooth", "teeth"),
+    ("goose", "geese"),
+    ("datum", "data"),
+    ("criterion", "criteria"),
+    ("analysis", "analyses"),
+    ("status", "statuses"),
+    ("bus", "buses"),
+];
+
 /// Text utilities
 pub struct TextUtils;
 
@@ -16,13 +34,34 @@ impl TextUtils {
     }
 
     /// Convert string to snake_case
+    ///
+    /// A run of uppercase letters is kept together as one word, so acronyms
+    /// convert naturally: `HTTPSConnection` becomes `https_connection`.
     pub fn to_snake_case(input: &str) -> String {
-        let mut result = String::new();
-        for (i, ch) in input.chars().enumerate() {
-            if i > 0 && ch.is_uppercase() {
+        let chars: Vec<char> = input.chars().collect();
+
+        // First pass: find the indices where a new word starts
+        let mut boundaries = Vec::new();
+        for i in 1..chars.len() {
+            let (prev, ch) = (chars[i - 1], chars[i]);
+            if !ch.is_uppercase() {
+                continue;
+            }
+
+            let after_lower = prev.is_lowercase() || prev.is_ascii_digit();
+            let ends_acronym = prev.is_uppercase() && chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if after_lower || ends_acronym {
+                boundaries.push(i);
+            }
+        }
+
+        // Second pass: lowercase the words and join them with underscores
+        let mut result = String::with_capacity(input.len() + boundaries.len());
+        for (i, ch) in chars.iter().enumerate() {
+            if boundaries.contains(&i) && !result.ends_with('_') {
                 result.push('_');
             }
-            result.push(ch.to_lowercase().next().unwrap_or(ch));
+            result.extend(ch.to_lowercase());
         }
         result
     }
@@ -56,12 +95,61 @@ impl TextUtils {
         Self::to_snake_case(input).replace('_', "-")
     }
 
-    /// Pluralize a word (simple English rules)
+    /// Pluralize a word (simple English rules plus irregular nouns)
+    ///
+    /// Words that are already plural are returned unchanged.
     pub fn pluralize(word: &str) -> String {
         if word.is_empty() {
             return word.to_string();
         }
 
+        if let Some(irregular) = Self::irregular_form(word, true) {
+            return irregular;
+        }
+
+        let singular = Self::singularize(word);
+        if singular != word && Self::apply_plural_rules(&singular) == word {
+            return word.to_string();
+        }
+
+        Self::apply_plural_rules(word)
+    }
+
+    /// Singularize a word (simple English rules plus irregular nouns)
+    ///
+    /// Words that are already singular are returned unchanged.
+    pub fn singularize(word: &str) -> String {
+        if word.is_empty() {
+            return word.to_string();
+        }
+
+        if let Some(irregular) = Self::irregular_form(word, false) {
+            return irregular;
+        }
+
+        let lower = word.to_lowercase();
+        if lower.ends_with("ies") && word.len() > 3 {
+            format!("{}y", &word[..word.len()-3])
+        } else if lower.ends_with("ves") && word.len() > 3 {
+            if lower.ends_with("ives") {
+                format!("{}fe", &word[..word.len()-3])
+            } else {
+                format!("{}f", &word[..word.len()-3])
+            }
+        } else if lower.ends_with("sses") || lower.ends_with("shes") || lower.ends_with("ches")
+                  || lower.ends_with("xes") || lower.ends_with("zes") {
+            word[..word.len()-2].to_string()
+        } else if lower.ends_with("ss") || lower.ends_with("us") || lower.ends_with("is") {
+            word.to_string()
+        } else if lower.ends_with('s') && word.len() > 1 {
+            word[..word.len()-1].to_string()
+        } else {
+            word.to_string()
+        }
+    }
+
+    /// Apply the regular suffix rules to a singular word
+    fn apply_plural_rules(word: &str) -> String {
         let lower = word.to_lowercase();
         if lower.ends_with('s') || lower.ends_with("sh") || lower.ends_with("ch")
            || lower.ends_with('x') || lower.ends_with('z') {
@@ -78,35 +166,36 @@ impl TextUtils {
         }
     }
 
-    /// Singularize a word (simple English rules)
-    pub fn singularize(word: &str) -> String {
-        if word.is_empty() {
-            return word.to_string();
-        }
-
+    /// Look up the plural (or singular) form of an irregular noun
+    ///
+    /// Only the last `_`-separated segment is matched, so `blog_person`
+    /// pluralizes to `blog_people`.
+    fn irregular_form(word: &str, plural: bool) -> Option<String> {
         let lower = word.to_lowercase();
-        if lower.ends_with("ies") {
-            format!("{}y", &word[..word.len()-3])
-        } else if lower.ends_with("ves") {
-            if word.len() > 4 && &lower[word.len()-4..word.len()-3] == "l" {
-                format!("{}f", &word[..word.len()-3])
-            } else {
-                format!("{}fe", &word[..word.len()-3])
+        let ends_with_word = |form: &str| {
+            lower.ends_with(form)
+                && (lower.len() == form.len() || lower.as_bytes()[lower.len() - form.len() - 1] == b'_')
+        };
+
+        for &(singular, plural_form) in IRREGULAR_WORDS {
+            let (from, to) = if plural { (singular, plural_form) } else { (plural_form, singular) };
+
+            if ends_with_word(to) {
+                return Some(word.to_string());
             }
-        } else if lower.ends_with("es") && word.len() > 2 {
-            let before_es = &lower[word.len()-3..word.len()-2];
-            if before_es == "s" || before_es == "x" || before_es == "z" {
-                word[..word.len()-2].to_string()
-            } else if word.len() > 3 && (&lower[word.len()-4..word.len()-2] == "sh" || &lower[word.len()-4..word.len()-2] == "ch") {
-                word[..word.len()-2].to_string()
-            } else {
-                word[..word.len()-1].to_string()
+
+            if ends_with_word(from) {
+                let prefix = &word[..word.len() - from.len()];
+                let replaced = if word[prefix.len()..].starts_with(|c: char| c.is_uppercase()) {
+                    Self::capitalize(to)
+                } else {
+                    to.to_string()
+                };
+                return Some(format!("{}{}", prefix, replaced));
             }
-        } else if lower.ends_with('s') && word.len() > 1 {
-            word[..word.len()-1].to_string()
-        } else {
-            word.to_string()
         }
+
+        None
     }
 
     /// Truncate text to a specified length with ellipsis
@@ -140,6 +229,95 @@ impl TextUtils {
         text.split_whitespace().collect::<Vec<&str>>().join(" ")
     }
 
+    /// Visible width of text, ignoring ANSI escape codes
+    pub fn visible_width(text: &str) -> usize {
+        console::measure_text_width(text)
+    }
+
+    /// Wrap text at word boundaries so no line exceeds `width` visible columns
+    ///
+    /// Words longer than `width` are kept whole on their own line. Existing
+    /// line breaks are preserved.
+    pub fn word_wrap(text: &str, width: usize) -> String {
+        let width = width.max(1);
+
+        text.lines()
+            .map(|paragraph| {
+                let mut lines: Vec<String> = Vec::new();
+                let mut line = String::new();
+                let mut line_width = 0;
+
+                for word in paragraph.split_whitespace() {
+                    let word_width = Self::visible_width(word);
+                    if line_width > 0 && line_width + 1 + word_width > width {
+                        lines.push(std::mem::take(&mut line));
+                        line_width = 0;
+                    }
+                    if line_width > 0 {
+                        line.push(' ');
+                        line_width += 1;
+                    }
+                    line.push_str(word);
+                    line_width += word_width;
+                }
+
+                lines.push(line);
+                lines.join("\n")
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Format rows as a box-drawn table
+    ///
+    /// Each column is as wide as its widest cell, capped at the matching
+    /// entry of `widths`; longer cells are truncated with `…`. Columns
+    /// without an entry in `widths` are not capped.
+    pub fn table_format(headers: &[&str], rows: &[Vec<String>], widths: &[usize]) -> String {
+        let column_widths: Vec<usize> = headers
+            .iter()
+            .enumerate()
+            .map(|(i, header)| {
+                let widest = rows
+                    .iter()
+                    .filter_map(|row| row.get(i))
+                    .map(|cell| Self::visible_width(cell))
+                    .fold(Self::visible_width(header), usize::max);
+                widest.min(widths.get(i).copied().unwrap_or(usize::MAX)).max(1)
+            })
+            .collect();
+
+        let border = |left: &str, middle: &str, right: &str| {
+            let segments: Vec<String> = column_widths.iter().map(|width| "─".repeat(width + 2)).collect();
+            format!("{}{}{}", left, segments.join(middle), right)
+        };
+
+        let format_row = |cells: Vec<&str>| {
+            let cells: Vec<String> = column_widths
+                .iter()
+                .enumerate()
+                .map(|(i, &width)| {
+                    let cell = cells.get(i).copied().unwrap_or("");
+                    let cell = if Self::visible_width(cell) > width {
+                        console::truncate_str(cell, width, "…")
+                    } else {
+                        cell.into()
+                    };
+                    format!(" {} ", console::pad_str(&cell, width, console::Alignment::Left, None))
+                })
+                .collect();
+            format!("│{}│", cells.join("│"))
+        };
+
+        let mut lines = vec![border("┌", "┬", "┐"), format_row(headers.to_vec()), border("├", "┼", "┤")];
+        for row in rows {
+            lines.push(format_row(row.iter().map(String::as_str).collect()));
+        }
+        lines.push(border("└", "┴", "┘"));
+
+        lines.join("\n")
+    }
+
     /// Check if a string is a valid identifier (starts with letter/underscore, contains only alphanumeric/underscore)
     pub fn is_valid_identifier(s: &str) -> bool {
         if s.is_empty() {
@@ -172,7 +350,37 @@ mod tests {
     fn test_to_snake_case() {
         assert_eq!(TextUtils::to_snake_case("HelloWorld"), "hello_world");
         assert_eq!(TextUtils::to_snake_case("hello"), "hello");
-        assert_eq!(TextUtils::to_snake_case("HTTPSConnection"), "h_t_t_p_s_connection");
+        assert_eq!(TextUtils::to_snake_case("HTTPSConnection"), "https_connection");
+    }
+
+    #[test]
+    fn test_to_snake_case_table() {
+        let cases = [
+            ("", ""),
+            ("a", "a"),
+            ("A", "a"),
+            ("hello", "hello"),
+            ("HelloWorld", "hello_world"),
+            ("helloWorld", "hello_world"),
+            ("already_snake", "already_snake"),
+            ("Mixed_Case", "mixed_case"),
+            ("HTTPSConnection", "https_connection"),
+            ("UserID", "user_id"),
+            ("userId", "user_id"),
+            ("XMLParser", "xml_parser"),
+            ("getHTMLContent", "get_html_content"),
+            ("URL", "url"),
+            ("IOError", "io_error"),
+            ("HTML5Parser", "html5_parser"),
+            ("user2Factor", "user2_factor"),
+            ("Version10", "version10"),
+            ("OAuth2Token", "o_auth2_token"),
+            ("ABCDef", "abc_def"),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(TextUtils::to_snake_case(input), expected, "to_snake_case({:?})", input);
+        }
     }
 
     #[test]
@@ -195,7 +403,75 @@ mod tests {
         assert_eq!(TextUtils::singularize("cats"), "cat");
         assert_eq!(TextUtils::singularize("boxes"), "box");
         assert_eq!(TextUtils::singularize("cities"), "city");
-        assert_eq!(TextUtils::singularize("leaves"), "leave");
+        assert_eq!(TextUtils::singularize("leaves"), "leaf");
+        assert_eq!(TextUtils::singularize("knives"), "knife");
+        assert_eq!(TextUtils::singularize("classes"), "class");
+        assert_eq!(TextUtils::singularize("user"), "user");
+        assert_eq!(TextUtils::singularize("People"), "Person");
+    }
+
+    #[test]
+    fn test_pluralize_already_plural() {
+        assert_eq!(TextUtils::pluralize("users"), "users");
+        assert_eq!(TextUtils::pluralize("cities"), "cities");
+        assert_eq!(TextUtils::pluralize("boxes"), "boxes");
+        assert_eq!(TextUtils::pluralize("people"), "people");
+        assert_eq!(TextUtils::pluralize("class"), "classes");
+        assert_eq!(TextUtils::pluralize("blog_person"), "blog_people");
+    }
+
+    #[test]
+    fn test_irregular_words_round_trip() {
+        for &(singular, plural) in IRREGULAR_WORDS {
+            assert_eq!(TextUtils::pluralize(singular), plural);
+            assert_eq!(TextUtils::singularize(&TextUtils::pluralize(singular)), singular);
+            assert_eq!(TextUtils::singularize(plural), singular);
+        }
+    }
+
+    #[test]
+    fn test_word_wrap() {
+        assert_eq!(
+            TextUtils::word_wrap("the quick brown fox jumps over the lazy dog", 10),
+            "the quick\nbrown fox\njumps over\nthe lazy\ndog"
+        );
+        assert_eq!(TextUtils::word_wrap("short", 10), "short");
+        assert_eq!(TextUtils::word_wrap("a verylongword b", 4), "a\nverylongword\nb");
+        assert_eq!(TextUtils::word_wrap("one\ntwo three", 20), "one\ntwo three");
+    }
+
+    #[test]
+    fn test_word_wrap_ignores_ansi_codes() {
+        let red = "\x1b[31mred\x1b[0m";
+        let text = format!("{} {} {}", red, red, red);
+        assert_eq!(TextUtils::word_wrap(&text, 7), format!("{} {}\n{}", red, red, red));
+    }
+
+    #[test]
+    fn test_table_format() {
+        let rows = vec![
+            vec!["1".to_string(), "SendEmail".to_string()],
+            vec!["2".to_string(), "ProcessPaymentForOrder".to_string()],
+        ];
+        let table = TextUtils::table_format(&["ID", "Job"], &rows, &[5, 10]);
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(lines[0], "┌────┬────────────┐");
+        assert_eq!(lines[1], "│ ID │ Job        │");
+        assert_eq!(lines[2], "├────┼────────────┤");
+        assert_eq!(lines[3], "│ 1  │ SendEmail  │");
+        assert_eq!(lines[4], "│ 2  │ ProcessPa… │");
+        assert_eq!(lines[5], "└────┴────────────┘");
+    }
+
+    #[test]
+    fn test_table_format_ignores_ansi_codes() {
+        let rows = vec![vec!["\x1b[1mbold\x1b[0m".to_string()]];
+        let table = TextUtils::table_format(&["Name"], &rows, &[20]);
+        let lines: Vec<&str> = table.lines().collect();
+
+        assert_eq!(lines[0], "┌──────┐");
+        assert_eq!(TextUtils::visible_width(lines[3]), TextUtils::visible_width(lines[0]));
     }
 
     #[test]
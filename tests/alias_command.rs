@@ -0,0 +1,25 @@
+use assert_cmd::Command;
+use tempfile::TempDir;
+
+#[test]
+fn test_alias_expands_to_subcommand() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("rustisan.toml"), "[aliases]\nm = \"migrate up\"\nh = \"migrate --help\"\n").unwrap();
+
+    Command::cargo_bin("rustisan")
+        .unwrap()
+        .current_dir(dir.path())
+        .args(["--quiet", "h"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Usage: rustisan migrate"));
+
+    // `migrate up` needs a project, proving the alias reached the migrate command
+    Command::cargo_bin("rustisan")
+        .unwrap()
+        .current_dir(dir.path())
+        .args(["--quiet", "m"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("must be run from within a Rustisan project"));
+}
@@ -0,0 +1,30 @@
+#![cfg(unix)]
+
+use assert_cmd::Command;
+use std::os::unix::fs::PermissionsExt;
+use tempfile::TempDir;
+
+#[test]
+fn test_dev_profile_writes_profile() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"shop\"\nversion = \"0.1.0\"\n").unwrap();
+    std::fs::write(dir.path().join("rustisan.toml"), "[app]\nname = \"shop\"\n").unwrap();
+
+    // A prebuilt release binary keeps the command from running cargo build
+    let release = dir.path().join("target/release");
+    std::fs::create_dir_all(&release).unwrap();
+    let server = release.join("shop");
+    std::fs::write(&server, "#!/bin/sh\nexec sleep 30\n").unwrap();
+    std::fs::set_permissions(&server, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    Command::cargo_bin("rustisan")
+        .unwrap()
+        .current_dir(dir.path())
+        .args(["dev", "profile", "--duration", "1", "--output", "storage/profiles/cpu.pb"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("storage/profiles/cpu.pb"));
+
+    let profile = std::fs::metadata(dir.path().join("storage/profiles/cpu.pb")).unwrap();
+    assert!(profile.len() > 0);
+}
@@ -0,0 +1,46 @@
+//! PublishPost action
+
+use anyhow::Result;
+
+/// Input of [`PublishPostAction`]
+#[derive(Debug, Clone, Default)]
+pub struct PublishPostInput {
+    // Add input fields here
+}
+
+/// Output of [`PublishPostAction`]
+#[derive(Debug, Clone)]
+pub struct PublishPostOutput {
+    // Add output fields here
+}
+
+/// Performs a single task: publish post
+#[derive(Debug, Default)]
+pub struct PublishPostAction {
+    // Add injected dependencies here, e.g. a repository
+}
+
+impl PublishPostAction {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn execute(&self, input: PublishPostInput) -> Result<PublishPostOutput> {
+        // Publish post here
+        let _ = input;
+
+        Ok(PublishPostOutput {})
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_execute() {
+        let action = PublishPostAction::new();
+
+        assert!(action.execute(PublishPostInput::default()).await.is_ok());
+    }
+}
@@ -0,0 +1,10 @@
+-- Rustisan test fixture: small database dump
+
+CREATE TABLE users (
+    id INTEGER PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    email VARCHAR(255) NOT NULL UNIQUE
+);
+
+INSERT INTO users (id, name, email) VALUES (1, 'Ada Lovelace', 'ada@example.com');
+INSERT INTO users (id, name, email) VALUES (2, 'Alan Turing', 'alan@example.com');
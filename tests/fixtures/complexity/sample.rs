@@ -0,0 +1,44 @@
+//! Fixture for the complexity count of `info --detailed`
+
+pub struct Order {
+    pub total: u32,
+    pub items: Vec<u32>,
+}
+
+impl Clone for Order {
+    fn clone(&self) -> Self {
+        Self { total: self.total, items: self.items.clone() }
+    }
+}
+
+// TODO: support discounts
+pub fn shipping(order: &Order) -> u32 {
+    // if this comment were code it would count
+    if order.total > 100 && order.items.len() > 1 {
+        0
+    } else if order.total > 50 || order.items.is_empty() {
+        5
+    } else {
+        10
+    }
+}
+
+pub fn total(order: &Order) -> u32 {
+    let label = "if && || while";
+    let mut sum = 0;
+    for item in &order.items {
+        match item {
+            0 => continue,
+            _ => sum += item,
+        }
+    }
+    let check = || sum > 0;
+    while !check() {
+        loop {
+            break;
+        }
+    }
+    // FIXME: label is unused
+    let _ = label;
+    sum
+}
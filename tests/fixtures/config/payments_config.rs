@@ -0,0 +1,67 @@
+//! Payments configuration, read from the `[payments]` table of rustisan.toml
+//!
+//! Requires the following dependencies in Cargo.toml:
+//!
+//! ```toml
+//! anyhow = "1.0"
+//! serde = { version = "1.0", features = ["derive"] }
+//! toml = "0.8"
+//! ```
+
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct PaymentsConfig {
+    pub enabled: bool,
+    pub url: String,
+    pub timeout: u64,
+}
+
+/// rustisan.toml, keeping only the `[payments]` table
+#[derive(Deserialize)]
+struct ConfigFile {
+    #[serde(rename = "payments", default)]
+    section: PaymentsConfig,
+}
+
+impl PaymentsConfig {
+    /// Load the `[payments]` table from rustisan.toml
+    pub fn load() -> anyhow::Result<Self> {
+        Self::load_from("rustisan.toml")
+    }
+
+    /// Load the `[payments]` table from a TOML file
+    pub fn load_from(path: impl AsRef<std::path::Path>) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        let file: ConfigFile = toml::from_str(&content)?;
+        Ok(file.section)
+    }
+
+    /// Check the loaded values, e.g. that required keys are set
+    pub fn validate(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_section() {
+        let content = "[payments]\nenabled = true\ntimeout = 10\n";
+        let file: ConfigFile = toml::from_str(content).unwrap();
+
+        assert!(file.section.enabled);
+        assert_eq!(file.section.timeout, 10);
+        assert!(file.section.validate().is_ok());
+    }
+
+    #[test]
+    fn test_missing_section_uses_defaults() {
+        let file: ConfigFile = toml::from_str("[app]\nname = \"app\"\n").unwrap();
+
+        assert_eq!(file.section, PaymentsConfig::default());
+    }
+}
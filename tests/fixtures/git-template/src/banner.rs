@@ -0,0 +1 @@
+pub const APP_NAME: &str = "{{app_name}}";
@@ -0,0 +1,24 @@
+-- Desired schema for the shop application
+
+CREATE TABLE `users` (
+    `id` BIGINT UNSIGNED NOT NULL AUTO_INCREMENT,
+    `email` VARCHAR(255) NOT NULL,
+    `name` VARCHAR(100) NOT NULL,
+    `avatar` VARCHAR(255) NULL DEFAULT 'default.png',
+    PRIMARY KEY (`id`),
+    UNIQUE KEY `users_email_unique` (`email`)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE INDEX users_name_index ON users (name);
+
+/* Posts belong to users */
+CREATE TABLE posts (
+    id BIGINT UNSIGNED NOT NULL AUTO_INCREMENT,
+    user_id BIGINT UNSIGNED NOT NULL,
+    title VARCHAR(255) NOT NULL,
+    price DECIMAL(8,2) NOT NULL DEFAULT 0,
+    PRIMARY KEY (id),
+    CONSTRAINT posts_user_id_foreign FOREIGN KEY (user_id) REFERENCES users (id)
+);
+
+CREATE VIEW active_users AS SELECT * FROM users;
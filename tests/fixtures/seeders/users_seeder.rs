@@ -0,0 +1,87 @@
+//! UsersSeeder
+//!
+//! Seeds User records from `data/users.csv`, embedded at compile time.
+//!
+//! Requires the following dependencies in Cargo.toml:
+//!
+//! ```toml
+//! csv = "1.3"
+//! ```
+
+use std::future::Future;
+
+use anyhow::Result;
+use serde_json::{Map, Value};
+
+/// Seeded rows
+const DATA: &str = include_str!("data/users.csv");
+
+/// Columns of each row
+pub const COLUMNS: &[&str] = &["name", "email", "age", "active"];
+
+/// Column identifying the seeded records on rollback
+pub const KEY_COLUMN: Option<&str> = Some("email");
+
+/// Persistence the seeder needs, implemented by the model being seeded
+pub trait Seedable {
+    fn create(row: Map<String, Value>) -> impl Future<Output = Result<()>> + Send;
+
+    /// Delete the records whose `column` equals `value`
+    fn delete_where(column: &str, value: &Value) -> impl Future<Output = Result<()>> + Send;
+}
+
+pub struct UsersSeeder {}
+
+impl UsersSeeder {
+    /// Rows keyed by column name
+    pub fn rows() -> Result<Vec<Map<String, Value>>> {
+        let mut reader = csv::Reader::from_reader(DATA.as_bytes());
+        reader
+            .records()
+            .map(|record| {
+                let record = record?;
+                Ok(COLUMNS
+                    .iter()
+                    .zip(record.iter())
+                    .map(|(column, value)| (column.to_string(), parse_value(value)))
+                    .collect())
+            })
+            .collect()
+    }
+
+    /// Create a record for every row, e.g. `UsersSeeder::run::<User>()`
+    pub async fn run<M: Seedable>() -> Result<()> {
+        for row in Self::rows()? {
+            M::create(row).await?;
+        }
+        Ok(())
+    }
+
+    /// Delete the records created by `run`, matched on `KEY_COLUMN`
+    pub async fn rollback<M: Seedable>() -> Result<()> {
+        let Some(key_column) = KEY_COLUMN else {
+            anyhow::bail!("UsersSeeder cannot be rolled back: no id, email, slug column identifies its records");
+        };
+        for row in Self::rows()? {
+            if let Some(key) = row.get(key_column) {
+                M::delete_where(key_column, key).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Type a CSV value: empty cells become null, numbers and booleans are parsed
+fn parse_value(value: &str) -> Value {
+    if value.is_empty() {
+        Value::Null
+    } else if let Ok(number) = value.parse::<i64>() {
+        Value::from(number)
+    } else if let Ok(number) = value.parse::<f64>() {
+        Value::from(number)
+    } else if let Ok(flag) = value.parse::<bool>() {
+        Value::from(flag)
+    } else {
+        Value::from(value)
+    }
+}
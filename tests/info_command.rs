@@ -0,0 +1,30 @@
+use assert_cmd::Command;
+use tempfile::TempDir;
+
+#[test]
+fn test_info_json_output() {
+    let dir = TempDir::new().unwrap();
+
+    Command::cargo_bin("rustisan")
+        .unwrap()
+        .args(["--quiet", "new", "shop", "--no-interaction", "--path"])
+        .arg(dir.path())
+        .assert()
+        .success();
+
+    let output = Command::cargo_bin("rustisan")
+        .unwrap()
+        .current_dir(dir.path().join("shop"))
+        .args(["--output-format", "json", "info"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    // stdout holds nothing but the JSON document
+    let info: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(info["app"]["name"], "shop");
+    assert_eq!(info["app"]["version"], "0.1.0");
+    assert_eq!(info["app"]["license"], "MIT");
+    assert!(info["system"]["os"].is_string());
+    assert!(info.get("dependencies").is_none());
+}
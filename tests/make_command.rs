@@ -0,0 +1,93 @@
+use assert_cmd::Command;
+use tempfile::TempDir;
+
+fn project() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"shop\"\nversion = \"0.1.0\"\n").unwrap();
+    std::fs::write(dir.path().join("rustisan.toml"), "[app]\nname = \"shop\"\n").unwrap();
+    dir
+}
+
+fn rustisan(dir: &TempDir) -> Command {
+    let mut command = Command::cargo_bin("rustisan").unwrap();
+    command.current_dir(dir.path()).arg("--quiet");
+    command
+}
+
+#[test]
+fn test_make_refuses_to_overwrite_without_force() {
+    let dir = project();
+    let job = dir.path().join("src/jobs/send_email.rs");
+
+    rustisan(&dir).args(["make", "job", "SendEmail"]).assert().success();
+    std::fs::write(&job, "// edited").unwrap();
+
+    rustisan(&dir)
+        .args(["make", "job", "SendEmail"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("already exists. Use --force to overwrite."));
+    assert_eq!(std::fs::read_to_string(&job).unwrap(), "// edited");
+
+    rustisan(&dir)
+        .args(["make", "job", "SendEmail", "--force"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Overwriting existing file"));
+    assert!(std::fs::read_to_string(&job).unwrap().contains("pub struct SendEmailJob"));
+}
+
+#[test]
+fn test_make_job_rejects_zero_tries() {
+    let dir = project();
+
+    rustisan(&dir)
+        .args(["make", "job", "SendEmail", "--tries", "0"])
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("--tries"));
+    assert!(!dir.path().join("src/jobs/send_email.rs").exists());
+
+    rustisan(&dir).args(["make", "job", "SendEmail", "--tries", "1"]).assert().success();
+}
+
+#[test]
+fn test_make_event_in_new_project_defines_event_trait() {
+    let dir = TempDir::new().unwrap();
+
+    Command::cargo_bin("rustisan")
+        .unwrap()
+        .args(["--quiet", "new", "shop", "--no-interaction", "--path"])
+        .arg(dir.path())
+        .write_stdin("")
+        .assert()
+        .success();
+
+    let project = dir.path().join("shop");
+    let mod_file = project.join("src/events/mod.rs");
+    assert!(!std::fs::read_to_string(&mod_file).unwrap().contains("trait Event"));
+
+    Command::cargo_bin("rustisan")
+        .unwrap()
+        .current_dir(&project)
+        .args(["--quiet", "make", "event", "OrderShipped"])
+        .assert()
+        .success();
+
+    let module = std::fs::read_to_string(&mod_file).unwrap();
+    assert!(module.starts_with("//! Application events\n//!\n"));
+    assert!(module.contains("pub trait Event: Any + Send + Sync {"));
+    assert!(module.contains("pub struct EventDispatcher {"));
+    assert!(module.contains("pub mod order_shipped;"));
+
+    Command::cargo_bin("rustisan")
+        .unwrap()
+        .current_dir(&project)
+        .args(["--quiet", "make", "event", "OrderPaid"])
+        .assert()
+        .success();
+
+    let module = std::fs::read_to_string(&mod_file).unwrap();
+    assert_eq!(module.matches("pub trait Event").count(), 1);
+    assert!(module.contains("pub mod order_paid;"));
+}
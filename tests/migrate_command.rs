@@ -0,0 +1,35 @@
+use assert_cmd::Command;
+use tempfile::TempDir;
+
+fn project() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"shop\"\nversion = \"0.1.0\"\n").unwrap();
+    std::fs::write(dir.path().join("rustisan.toml"), "[app]\nname = \"shop\"\n").unwrap();
+    dir
+}
+
+fn rustisan(dir: &TempDir) -> Command {
+    let mut command = Command::cargo_bin("rustisan").unwrap();
+    command.current_dir(dir.path());
+    command
+}
+
+#[test]
+fn test_migrate_dry_run_is_rejected_for_destructive_subcommands() {
+    let dir = project();
+
+    for args in [
+        &["migrate", "down", "--dry-run"][..],
+        &["migrate", "--dry-run", "down"],
+        &["migrate", "reset", "--dry-run"],
+        &["migrate", "refresh", "--dry-run"],
+        &["migrate", "rollback", "2024_01_01_000000_create_users_table", "--dry-run"],
+        &["migrate", "squash", "--delete-old", "--dry-run"],
+    ] {
+        rustisan(&dir)
+            .args(args)
+            .assert()
+            .failure()
+            .stderr(predicates::str::contains("--dry-run"));
+    }
+}
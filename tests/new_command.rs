@@ -0,0 +1,213 @@
+use assert_cmd::Command;
+use tempfile::TempDir;
+
+#[test]
+fn test_new_without_interaction_writes_valid_toml() {
+    let dir = TempDir::new().unwrap();
+
+    Command::cargo_bin("rustisan")
+        .unwrap()
+        .args(["--quiet", "new", "demo", "--no-interaction", "--path"])
+        .arg(dir.path())
+        .write_stdin("")
+        .assert()
+        .success();
+
+    let project = dir.path().join("demo");
+
+    let cargo: toml::Value = std::fs::read_to_string(project.join("Cargo.toml")).unwrap().parse().unwrap();
+    assert_eq!(cargo["package"]["name"].as_str(), Some("demo"));
+    assert_eq!(cargo["package"]["license"].as_str(), Some("MIT"));
+    assert_eq!(
+        cargo["package"]["authors"][0].as_str(),
+        Some("Your Name <your.email@example.com>")
+    );
+
+    let config: toml::Value = std::fs::read_to_string(project.join("rustisan.toml")).unwrap().parse().unwrap();
+    let connection = &config["database"]["connections"]["default"];
+    assert_eq!(connection["driver"].as_str(), Some("mysql"));
+    assert_eq!(connection["port"].as_integer(), Some(3306));
+    assert_eq!(connection["charset"].as_str(), Some("utf8mb4"));
+    assert!(config.get("session").is_some());
+    assert!(config.get("cache").is_some());
+    assert!(config.get("queue").is_none());
+}
+
+#[test]
+fn test_new_with_piped_stdin_skips_questionnaire() {
+    let dir = TempDir::new().unwrap();
+
+    Command::cargo_bin("rustisan")
+        .unwrap()
+        .args(["--quiet", "new", "piped", "--path"])
+        .arg(dir.path())
+        .write_stdin("\n\n\n")
+        .assert()
+        .success();
+
+    let config = std::fs::read_to_string(dir.path().join("piped/rustisan.toml")).unwrap();
+    assert!(config.parse::<toml::Value>().is_ok());
+}
+
+#[test]
+fn test_new_from_git_template() {
+    let fixture = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/git-template");
+    let repo = TempDir::new().unwrap();
+    copy_dir(&fixture, repo.path());
+
+    for args in [
+        vec!["init", "--quiet"],
+        vec!["add", "."],
+        vec!["-c", "user.name=Fixture", "-c", "user.email=fixture@example.com", "commit", "--quiet", "-m", "Template"],
+    ] {
+        let status = std::process::Command::new("git")
+            .args(&args)
+            .current_dir(repo.path())
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    let dir = TempDir::new().unwrap();
+    let url = format!("file://{}", repo.path().display());
+
+    Command::cargo_bin("rustisan")
+        .unwrap()
+        .args(["--quiet", "new", "blog", "--no-interaction", "--template", &url, "--path"])
+        .arg(dir.path())
+        .assert()
+        .success();
+
+    let project = dir.path().join("blog");
+    let readme = std::fs::read_to_string(project.join("README.md")).unwrap();
+    assert!(readme.starts_with("# blog"));
+    assert!(readme.contains("Maintained by Your Name <your.email@example.com>."));
+
+    let banner = std::fs::read_to_string(project.join("src/banner.rs")).unwrap();
+    assert!(banner.contains("\"blog\""));
+
+    let raw = std::fs::read_to_string(project.join("assets/raw.txt")).unwrap();
+    assert_eq!(raw.trim(), "{{app_name}}");
+
+    assert!(!project.join("rustisan-template.toml").exists());
+    assert!(project.join("Cargo.toml").exists());
+}
+
+#[test]
+fn test_new_from_missing_git_template_leaves_no_project() {
+    let dir = TempDir::new().unwrap();
+    let url = format!("file://{}", dir.path().join("missing").display());
+
+    Command::cargo_bin("rustisan")
+        .unwrap()
+        .args(["--quiet", "new", "broken", "--no-interaction", "--template", &url, "--path"])
+        .arg(dir.path())
+        .assert()
+        .failure();
+
+    assert!(!dir.path().join("broken").exists());
+}
+
+fn copy_dir(from: &std::path::Path, to: &std::path::Path) {
+    for entry in std::fs::read_dir(from).unwrap() {
+        let entry = entry.unwrap();
+        let destination = to.join(entry.file_name());
+        if entry.path().is_dir() {
+            std::fs::create_dir_all(&destination).unwrap();
+            copy_dir(&entry.path(), &destination);
+        } else {
+            std::fs::copy(entry.path(), destination).unwrap();
+        }
+    }
+}
+
+#[test]
+fn test_new_workspace_layout() {
+    let dir = TempDir::new().unwrap();
+
+    Command::cargo_bin("rustisan")
+        .unwrap()
+        .args(["--quiet", "new", "shop", "--no-interaction", "--workspace", "--path"])
+        .arg(dir.path())
+        .assert()
+        .success();
+
+    let project = dir.path().join("shop");
+
+    let cargo: toml::Value = std::fs::read_to_string(project.join("Cargo.toml")).unwrap().parse().unwrap();
+    let members: Vec<&str> = cargo["workspace"]["members"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|member| member.as_str().unwrap())
+        .collect();
+    assert_eq!(members, ["app", "core", "cli"]);
+
+    for manifest in ["app/Cargo.toml", "core/Cargo.toml", "cli/Cargo.toml"] {
+        let member: toml::Value = std::fs::read_to_string(project.join(manifest)).unwrap().parse().unwrap();
+        assert!(member.get("package").is_some(), "{manifest} has no package");
+    }
+    assert!(project.join("app/src/main.rs").exists());
+    assert!(project.join("core/src/lib.rs").exists());
+    assert!(project.join("cli/src/main.rs").exists());
+
+    let config: toml::Value = std::fs::read_to_string(project.join("rustisan.toml")).unwrap().parse().unwrap();
+    assert_eq!(config["workspace"].as_bool(), Some(true));
+
+    Command::cargo_bin("rustisan")
+        .unwrap()
+        .args(["--quiet", "make", "service", "Billing"])
+        .current_dir(&project)
+        .assert()
+        .success();
+
+    assert!(project.join("app/src/services/billing_service.rs").exists());
+    assert!(!project.join("src").exists());
+}
+
+#[test]
+fn test_new_generates_docker_and_ci_files() {
+    let dir = TempDir::new().unwrap();
+
+    Command::cargo_bin("rustisan")
+        .unwrap()
+        .args(["--quiet", "new", "api", "--no-interaction", "--path"])
+        .arg(dir.path())
+        .assert()
+        .success();
+
+    let project = dir.path().join("api");
+
+    let dockerfile = std::fs::read_to_string(project.join("Dockerfile")).unwrap();
+    assert!(dockerfile.contains("FROM rust:latest AS builder"));
+    assert!(dockerfile.contains("EXPOSE 3000"));
+    assert!(dockerfile.contains("target/release/api"));
+
+    let compose: serde_yaml::Value =
+        serde_yaml::from_str(&std::fs::read_to_string(project.join("docker-compose.yml")).unwrap()).unwrap();
+    assert_eq!(compose["services"]["db"]["image"].as_str(), Some("mysql:8"));
+    assert!(compose["services"].get("redis").is_none());
+
+    let workflow: serde_yaml::Value =
+        serde_yaml::from_str(&std::fs::read_to_string(project.join(".github/workflows/ci.yml")).unwrap()).unwrap();
+    for job in ["check", "test", "clippy"] {
+        assert!(workflow["jobs"].get(job).is_some(), "missing {job} job");
+    }
+}
+
+#[test]
+fn test_new_without_docker_and_ci() {
+    let dir = TempDir::new().unwrap();
+
+    Command::cargo_bin("rustisan")
+        .unwrap()
+        .args(["--quiet", "new", "bare", "--no-interaction", "--no-docker", "--no-ci", "--path"])
+        .arg(dir.path())
+        .assert()
+        .success();
+
+    let project = dir.path().join("bare");
+    assert!(!project.join("Dockerfile").exists());
+    assert!(!project.join("docker-compose.yml").exists());
+    assert!(!project.join(".github").exists());
+}